@@ -54,7 +54,9 @@ fn benchmark_files_no_partitions(c: &mut Criterion) {
                     sinks::file::FileSinkConfig {
                         path: output.try_into().unwrap(),
                         idle_timeout_secs: None,
-                        encoding: sinks::file::Encoding::Text.into(),
+                        encoding: Some(sinks::file::Encoding::Text.into()),
+                        framing: None,
+                        serializer: None,
                         compression: sinks::file::Compression::None,
                     },
                 );
@@ -52,7 +52,9 @@ fn benchmark_http(c: &mut Criterion) {
                                     max_bytes: Some(num_lines * line_size),
                                     ..Default::default()
                                 },
-                                encoding: sinks::http::Encoding::Text.into(),
+                                encoding: Some(sinks::http::Encoding::Text.into()),
+                                framing: None,
+                                serializer: None,
                                 request: Default::default(),
                                 tls: Default::default(),
                             },
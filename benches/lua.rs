@@ -8,7 +8,7 @@ use vector::{
     config::{TransformConfig, TransformContext},
     event::Event,
     test_util::{collect_ready, runtime},
-    transforms::{self, Transform},
+    transforms::{self, Transform, TransformOutputsBuf},
 };
 
 fn bench_add_fields(c: &mut Criterion) {
@@ -73,6 +73,14 @@ fn bench_add_fields(c: &mut Criterion) {
                     stream::iter(buf.into_iter())
                 }))
             }
+            Transform::Synchronous(t) => {
+                let mut t = t.clone();
+                Box::pin(rx.flat_map(move |v| {
+                    let mut buf = TransformOutputsBuf::default();
+                    t.transform(v, &mut buf);
+                    stream::iter(buf.take_primary().into_iter())
+                }))
+            }
             Transform::Task(t) => t.transform(Box::pin(rx)),
         };
 
@@ -168,6 +176,14 @@ fn bench_field_filter(c: &mut Criterion) {
                     stream::iter(buf.into_iter())
                 }))
             }
+            Transform::Synchronous(t) => {
+                let mut t = t.clone();
+                Box::pin(rx.flat_map(move |v| {
+                    let mut buf = TransformOutputsBuf::default();
+                    t.transform(v, &mut buf);
+                    stream::iter(buf.take_primary().into_iter())
+                }))
+            }
             Transform::Task(t) => t.transform(Box::pin(rx)),
         };
 
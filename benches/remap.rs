@@ -56,6 +56,8 @@ fn benchmark_remap(c: &mut Criterion) {
                     timezone: TimeZone::default(),
                     drop_on_error: true,
                     drop_on_abort: true,
+                    reroute_dropped: false,
+                    import_paths: Vec::new(),
                 },
                 &Default::default(),
             )
@@ -122,6 +124,8 @@ fn benchmark_remap(c: &mut Criterion) {
                     timezone: TimeZone::default(),
                     drop_on_error: true,
                     drop_on_abort: true,
+                    reroute_dropped: false,
+                    import_paths: Vec::new(),
                 },
                 &Default::default(),
             )
@@ -150,6 +154,8 @@ fn benchmark_remap(c: &mut Criterion) {
             drop_field: false,
             drop_invalid: false,
             overwrite_target: None,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         }));
 
         let event = {
@@ -196,6 +202,8 @@ fn benchmark_remap(c: &mut Criterion) {
                 timezone: TimeZone::default(),
                 drop_on_error: true,
                 drop_on_abort: true,
+                reroute_dropped: false,
+                import_paths: Vec::new(),
             }, &Default::default())
             .unwrap(),
         );
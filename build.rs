@@ -122,6 +122,25 @@ fn main() {
             .unwrap();
     }
 
+    #[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+    {
+        println!("cargo:rerun-if-changed=proto/opentelemetry");
+
+        let mut prost_build = prost_build::Config::new();
+        prost_build.btree_map(&["."]);
+
+        tonic_build::configure()
+            .compile_with_config(
+                prost_build,
+                &[
+                    "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+                ],
+                &["proto/"],
+            )
+            .unwrap();
+    }
+
     // We keep track of which environment variables we slurp in, and then emit stanzas at the end to
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
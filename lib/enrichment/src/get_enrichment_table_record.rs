@@ -166,6 +166,7 @@ mod tests {
     use crate::test_util::{
         get_table_registry, get_table_registry_with_tables, DummyEnrichmentTable,
     };
+    use crate::IndexKind;
     use chrono::{TimeZone as _, Utc};
     use shared::{btreemap, TimeZone};
     use std::sync::{Arc, Mutex};
@@ -250,4 +251,120 @@ mod tests {
         let indexes = indexes.lock().unwrap();
         assert_eq!(vec![vec!["field1".to_string()]], *indexes);
     }
+
+    #[test]
+    fn add_field_indexes() {
+        let indexes = Arc::new(Mutex::new(Vec::new()));
+        let field_indexes = Arc::new(Mutex::new(Vec::new()));
+        let dummy =
+            DummyEnrichmentTable::new_with_field_index(indexes.clone(), field_indexes.clone());
+
+        let registry = get_table_registry_with_tables(vec![("dummy1".to_string(), dummy)]);
+
+        let mut func = GetEnrichmentTableRecordFn {
+            table: "dummy1".to_string(),
+            condition: btreemap! {
+                "field1" =>  expression::Literal::from("value"),
+                "network" => expression::Container::new(expression::Variant::Object(btreemap! {
+                    "cidr_contains" => expression::Literal::from("192.168.0.1"),
+                }.into())),
+                "path" => expression::Container::new(expression::Variant::Object(btreemap! {
+                    "longest_prefix_of" => expression::Literal::from("/api/v1/users"),
+                }.into())),
+                "size" => expression::Container::new(expression::Variant::Object(btreemap! {
+                    "from" => expression::Literal::from(10),
+                    "to" => expression::Literal::from(20),
+                }.into())),
+            },
+            index: None,
+            select: None,
+            case_sensitive: Case::Sensitive,
+            enrichment_tables: registry.as_readonly(),
+        };
+
+        let mut compiler = state::Compiler::new();
+        compiler.set_external_context(Some(Box::new(registry)));
+
+        assert_eq!(Ok(()), func.update_state(&mut compiler));
+        assert_eq!(Some(IndexHandle(0)), func.index);
+
+        // Only the exact match is part of the index, the other fields get their own index.
+        let indexes = indexes.lock().unwrap();
+        assert_eq!(vec![vec!["field1".to_string()]], *indexes);
+
+        let field_indexes = field_indexes.lock().unwrap();
+        assert_eq!(
+            vec![
+                ("network".to_string(), IndexKind::Cidr),
+                ("path".to_string(), IndexKind::Prefix),
+                ("size".to_string(), IndexKind::Range),
+            ],
+            *field_indexes
+        );
+    }
+
+    #[test]
+    fn evaluate_conditions() {
+        let tz = TimeZone::default();
+        let mut object: Value = BTreeMap::new().into();
+        let mut runtime_state = vrl_core::state::Runtime::default();
+        let mut ctx = Context::new(&mut object, &mut runtime_state, &tz);
+
+        let condition = |value: BTreeMap<String, expression::Expr>| {
+            expression::Container::new(expression::Variant::Object(value.into())).into()
+        };
+
+        assert_eq!(
+            Ok(Condition::CidrContains {
+                field: "network",
+                ip: "192.168.0.1".parse().unwrap(),
+            }),
+            evaluate_condition(
+                &mut ctx,
+                "network",
+                &condition(btreemap! {
+                    "cidr_contains" => expression::Literal::from("192.168.0.1"),
+                })
+            )
+        );
+
+        assert_eq!(
+            Ok(Condition::LongestPrefix {
+                field: "path",
+                value: "/api/v1/users".to_string(),
+            }),
+            evaluate_condition(
+                &mut ctx,
+                "path",
+                &condition(btreemap! {
+                    "longest_prefix_of" => expression::Literal::from("/api/v1/users"),
+                })
+            )
+        );
+
+        assert_eq!(
+            Ok(Condition::BetweenNumbers {
+                field: "size",
+                from: 10.0,
+                to: 20.0,
+            }),
+            evaluate_condition(
+                &mut ctx,
+                "size",
+                &condition(btreemap! {
+                    "from" => expression::Literal::from(10),
+                    "to" => expression::Literal::from(20),
+                })
+            )
+        );
+
+        assert!(evaluate_condition(
+            &mut ctx,
+            "network",
+            &condition(btreemap! {
+                "cidr_contains" => expression::Literal::from("not an ip"),
+            })
+        )
+        .is_err());
+    }
 }
@@ -7,6 +7,7 @@ mod test_util;
 mod vrl_util;
 use dyn_clone::DynClone;
 use std::collections::BTreeMap;
+use std::net::IpAddr;
 use vrl_core::Value;
 
 pub use tables::{TableRegistry, TableSearch};
@@ -24,6 +25,47 @@ pub enum Condition<'a> {
         from: chrono::DateTime<chrono::Utc>,
         to: chrono::DateTime<chrono::Utc>,
     },
+    /// The field holds a CIDR network range that contains the IP address.
+    CidrContains { field: &'a str, ip: IpAddr },
+    /// The field value is the longest prefix of the value found in the table.
+    LongestPrefix { field: &'a str, value: String },
+    /// The number in the field is between from and to (inclusive).
+    BetweenNumbers { field: &'a str, from: f64, to: f64 },
+}
+
+impl<'a> Condition<'a> {
+    /// The kind of index that can speed up searching with this condition, if any.
+    pub fn index_kind(&self) -> Option<IndexKind> {
+        match self {
+            Condition::Equals { .. } | Condition::BetweenDates { .. } => None,
+            Condition::CidrContains { .. } => Some(IndexKind::Cidr),
+            Condition::LongestPrefix { .. } => Some(IndexKind::Prefix),
+            Condition::BetweenNumbers { .. } => Some(IndexKind::Range),
+        }
+    }
+
+    /// The field the condition applies to.
+    pub fn field(&self) -> &'a str {
+        match self {
+            Condition::Equals { field, .. }
+            | Condition::BetweenDates { field, .. }
+            | Condition::CidrContains { field, .. }
+            | Condition::LongestPrefix { field, .. }
+            | Condition::BetweenNumbers { field, .. } => field,
+        }
+    }
+}
+
+/// An index built over a single field, to search it with conditions other than
+/// `Equals`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IndexKind {
+    /// Indexes the CIDR network ranges of the field, for `CidrContains`.
+    Cidr,
+    /// Indexes the field by its value, for `LongestPrefix`.
+    Prefix,
+    /// Keeps the numbers of the field sorted, for `BetweenNumbers`.
+    Range,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -69,6 +111,22 @@ pub trait Table: DynClone {
     /// Returns a list of the field names that are in each index
     fn index_fields(&self) -> Vec<(Case, Vec<String>)>;
 
+    /// Hints to the enrichment table that the field is going to be searched with conditions
+    /// that need an index of the given kind, to allow it to build the index in advance.
+    ///
+    /// Tables that don't support these indexes can ignore the hint.
+    ///
+    /// # Errors
+    /// Errors if the field is not in the table.
+    fn add_field_index(&mut self, _field: &str, _kind: IndexKind) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Returns a list of the fields that have been indexed with `add_field_index`.
+    fn field_indexes(&self) -> Vec<(String, IndexKind)> {
+        Vec::new()
+    }
+
     /// Returns true if the underlying data has changed and the table needs reloading.
     fn needs_reload(&self) -> bool;
 }
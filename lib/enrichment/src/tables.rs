@@ -31,7 +31,7 @@
 
 use crate::Case;
 
-use super::{Condition, IndexHandle, Table};
+use super::{Condition, IndexHandle, IndexKind, Table};
 use arc_swap::ArcSwap;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
@@ -147,6 +147,30 @@ impl TableRegistry {
         }
     }
 
+    /// Adds an index of the given kind over a single field of the given Enrichment Table.
+    ///
+    /// If we are in the reading stage, this function will error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex is poisoned.
+    pub fn add_field_index(
+        &mut self,
+        table: &str,
+        field: &str,
+        kind: IndexKind,
+    ) -> Result<(), String> {
+        let mut locked = self.loading.lock().unwrap();
+
+        match *locked {
+            None => Err("finish_load has been called".to_string()),
+            Some(ref mut tables) => match tables.get_mut(table) {
+                None => Err(format!("table '{}' not loaded", table)),
+                Some(table) => table.add_field_index(field, kind),
+            },
+        }
+    }
+
     /// Returns a cheaply clonable struct through that provides lock free read
     /// access to the enrichment tables.
     pub fn as_readonly(&self) -> TableSearch {
@@ -165,6 +189,18 @@ impl TableRegistry {
         }
     }
 
+    /// Returns the field indexes that have been applied to the given table.
+    /// If the table is reloaded we need these to reapply them to the new reloaded tables.
+    pub fn field_indexes(&self, table: &str) -> Vec<(String, IndexKind)> {
+        match &**self.tables.load() {
+            Some(tables) => tables
+                .get(table)
+                .map(|table| table.field_indexes())
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+
     /// Checks if the table needs reloading.
     /// If in doubt (the table isn't in our list) we return true.
     pub fn needs_reload(&self, table: &str) -> bool {
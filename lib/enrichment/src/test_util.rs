@@ -1,4 +1,4 @@
-use crate::{Case, Condition, IndexHandle, Table, TableRegistry};
+use crate::{Case, Condition, IndexHandle, IndexKind, Table, TableRegistry};
 use shared::btreemap;
 use std::{
     collections::{BTreeMap, HashMap},
@@ -10,6 +10,7 @@ use vrl_core::Value;
 pub(crate) struct DummyEnrichmentTable {
     data: BTreeMap<String, Value>,
     indexes: Arc<Mutex<Vec<Vec<String>>>>,
+    field_indexes: Arc<Mutex<Vec<(String, IndexKind)>>>,
 }
 
 impl DummyEnrichmentTable {
@@ -23,6 +24,17 @@ impl DummyEnrichmentTable {
                 "field".to_string() => Value::from("result"),
             },
             indexes,
+            field_indexes: Default::default(),
+        }
+    }
+
+    pub(crate) fn new_with_field_index(
+        indexes: Arc<Mutex<Vec<Vec<String>>>>,
+        field_indexes: Arc<Mutex<Vec<(String, IndexKind)>>>,
+    ) -> Self {
+        Self {
+            field_indexes,
+            ..Self::new_with_index(indexes)
         }
     }
 
@@ -30,6 +42,7 @@ impl DummyEnrichmentTable {
         Self {
             data,
             indexes: Default::default(),
+            field_indexes: Default::default(),
         }
     }
 }
@@ -65,6 +78,12 @@ impl Table for DummyEnrichmentTable {
         Vec::new()
     }
 
+    fn add_field_index(&mut self, field: &str, kind: IndexKind) -> Result<(), String> {
+        let mut field_indexes = self.field_indexes.lock().unwrap();
+        field_indexes.push((field.to_string(), kind));
+        Ok(())
+    }
+
     fn needs_reload(&self) -> bool {
         false
     }
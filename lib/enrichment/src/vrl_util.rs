@@ -6,7 +6,7 @@ use vrl_core::{
     prelude::*,
 };
 
-use crate::{Case, Condition, IndexHandle, TableRegistry};
+use crate::{Case, Condition, IndexHandle, IndexKind, TableRegistry};
 
 #[derive(Debug)]
 pub enum Error {
@@ -50,24 +50,101 @@ pub(crate) fn evaluate_condition<'a>(
 
     Ok(match value {
         Value::Object(map) if map.contains_key("from") && map.contains_key("to") => {
-            Condition::BetweenDates {
+            let from = map.get("from").expect("should contain from");
+            let to = map.get("to").expect("should contain to");
+
+            match (from, to) {
+                (Value::Timestamp(from), Value::Timestamp(to)) => Condition::BetweenDates {
+                    field: key,
+                    from: *from,
+                    to: *to,
+                },
+                (from, to) => Condition::BetweenNumbers {
+                    field: key,
+                    from: as_number(from)
+                        .ok_or("from in condition must be a timestamp or a number")?,
+                    to: as_number(to).ok_or("to in condition must be a timestamp or a number")?,
+                },
+            }
+        }
+        Value::Object(map) if map.len() == 1 && map.contains_key("cidr_contains") => {
+            Condition::CidrContains {
+                field: key,
+                ip: map
+                    .get("cidr_contains")
+                    .expect("should contain cidr_contains")
+                    .try_bytes_utf8_lossy()?
+                    .parse()
+                    .map_err(|_| "cidr_contains in condition must be an IP address")?,
+            }
+        }
+        Value::Object(map) if map.len() == 1 && map.contains_key("longest_prefix_of") => {
+            Condition::LongestPrefix {
                 field: key,
-                from: *map
-                    .get("from")
-                    .expect("should contain from")
-                    .as_timestamp()
-                    .ok_or("from in condition must be a timestamp")?,
-                to: *map
-                    .get("to")
-                    .expect("should contain to")
-                    .as_timestamp()
-                    .ok_or("to in condition must be a timestamp")?,
+                value: map
+                    .get("longest_prefix_of")
+                    .expect("should contain longest_prefix_of")
+                    .try_bytes_utf8_lossy()?
+                    .into_owned(),
             }
         }
         _ => Condition::Equals { field: key, value },
     })
 }
 
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(int) => Some(*int as f64),
+        Value::Float(float) => Some(float.into_inner()),
+        _ => None,
+    }
+}
+
+/// The kind of field index that speeds up searching with the given condition
+/// expression, if any.
+///
+/// This mirrors `evaluate_condition`, but is determined at compile time.
+fn index_kind(state: &state::Compiler, value: &expression::Expr) -> Option<IndexKind> {
+    match value {
+        expression::Expr::Container(expression::Container {
+            variant: expression::Variant::Object(map),
+        }) => {
+            if map.contains_key("from") && map.contains_key("to") {
+                let from = map.get("from").expect("should contain from");
+                let numeric = Kind::Integer | Kind::Float;
+
+                if from.type_def(state).kind().intersects(numeric) {
+                    Some(IndexKind::Range)
+                } else {
+                    None
+                }
+            } else if map.len() == 1 && map.contains_key("cidr_contains") {
+                Some(IndexKind::Cidr)
+            } else if map.len() == 1 && map.contains_key("longest_prefix_of") {
+                Some(IndexKind::Prefix)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether the condition expression is searched with anything other than an
+/// equality condition.
+fn is_equality(value: &expression::Expr) -> bool {
+    match value {
+        expression::Expr::Container(expression::Container {
+            variant: expression::Variant::Object(map),
+        }) => {
+            !(map.contains_key("from") && map.contains_key("to")
+                || map.len() == 1
+                    && (map.contains_key("cidr_contains") || map.contains_key("longest_prefix_of")))
+        }
+        _ => true,
+    }
+}
+
 /// Add an index for the given condition to the given enrichment table.
 pub(crate) fn add_index(
     state: &mut state::Compiler,
@@ -75,21 +152,26 @@ pub(crate) fn add_index(
     case: Case,
     condition: &BTreeMap<String, expression::Expr>,
 ) -> std::result::Result<IndexHandle, ExpressionError> {
+    let field_indexes = condition
+        .iter()
+        .filter_map(|(field, value)| Some((field.as_str(), index_kind(state, value)?)))
+        .collect::<Vec<_>>();
+
     let mut registry = state.get_external_context_mut::<TableRegistry>();
 
     match registry {
         Some(ref mut table) => {
             let fields = condition
                 .iter()
-                .filter_map(|(field, value)| match value {
-                    expression::Expr::Container(expression::Container {
-                        variant: expression::Variant::Object(map),
-                    }) if map.contains_key("from") && map.contains_key("to") => None,
-                    _ => Some(field.as_ref()),
-                })
+                .filter(|(_, value)| is_equality(value))
+                .map(|(field, _)| field.as_ref())
                 .collect::<Vec<_>>();
             let index = table.add_index(tablename, case, &fields)?;
 
+            for (field, kind) in field_indexes {
+                table.add_field_index(tablename, field, kind)?;
+            }
+
             Ok(index)
         }
         // We shouldn't reach this point since the type checker will ensure the table exists before this function is called.
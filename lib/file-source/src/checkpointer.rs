@@ -178,6 +178,12 @@ impl CheckpointsView {
             self.update(fng, pos);
         }
 
+        if let Ok(Some(old_checksum)) =
+            fingerprinter.get_compressed_checksum(path, fingerprint_buffer)
+        {
+            self.update_key(old_checksum, fng)
+        }
+
         if self.checkpoints.get(&fng).is_none() {
             if let Ok(Some(fingerprint)) =
                 fingerprinter.get_legacy_checksum(path, fingerprint_buffer)
@@ -601,6 +607,51 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checkpointer_fingerprint_upgrades_compressed_checksum() {
+        use flate2::{Compression, GzBuilder};
+        use std::io::Write;
+
+        let log_dir = tempdir().unwrap();
+        let path = log_dir.path().join("test.log.gz");
+        // Compressed files were checksummed up to the first newline byte of their compressed
+        // content, which is found in the comment of the header here.
+        let mut encoder = GzBuilder::new()
+            .comment("rotated\n")
+            .write(std::fs::File::create(&path).unwrap(), Compression::fast());
+        encoder.write_all(b"hello\n").unwrap();
+        encoder.finish().unwrap();
+        let position: FilePosition = 6;
+
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: 102400,
+            ignore_not_found: false,
+        };
+
+        let mut buf = Vec::new();
+        let old_fingerprint = fingerprinter
+            .get_compressed_checksum(&path, &mut buf)
+            .unwrap()
+            .unwrap();
+        let new_fingerprint = fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        assert_ne!(old_fingerprint, new_fingerprint);
+
+        let data_dir = tempdir().unwrap();
+        let mut chkptr = Checkpointer::new(data_dir.path());
+        chkptr.update_checkpoint(old_fingerprint, position);
+
+        chkptr.maybe_upgrade(&path, new_fingerprint, &fingerprinter, &mut buf);
+
+        assert_eq!(chkptr.get_checkpoint(new_fingerprint), Some(position));
+        assert_eq!(chkptr.get_checkpoint(old_fingerprint), None);
+    }
+
     #[test]
     fn test_checkpointer_file_upgrades() {
         let fingerprint = FileFingerprint::DevInode(1, 2);
@@ -0,0 +1,112 @@
+use flate2::bufread::MultiGzDecoder;
+use std::io::{self, BufRead, BufReader, Read};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// The compression of a file, as detected from the magic bytes at its start. Files are
+/// decompressed as they're read, so positions in compressed files are offsets into their
+/// decompressed content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detects the compression of the data in the reader, without consuming any of it.
+    pub fn detect(reader: &mut impl BufRead) -> io::Result<Option<Self>> {
+        let header_bytes = reader.fill_buf()?;
+        // WARN: The paired `BufRead::consume` is not called intentionally. If we
+        // do we'll chop a decent part of the potential compressed stream off.
+        Ok(if header_bytes.starts_with(GZIP_MAGIC) {
+            Some(Self::Gzip)
+        } else if header_bytes.starts_with(ZSTD_MAGIC) {
+            Some(Self::Zstd)
+        } else {
+            None
+        })
+    }
+
+    /// Wraps the reader in a decoder reading the decompressed data. Concatenated streams, as
+    /// produced by appending to a compressed file, are read one after the other.
+    pub fn decoder<R: BufRead + 'static>(self, reader: R) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Self::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+            Self::Zstd => Box::new(BufReader::new(zstd::stream::read::Decoder::with_buffer(
+                reader,
+            )?)),
+        })
+    }
+}
+
+/// Wraps the reader in a decoder if its data is compressed.
+pub fn decompress<R: BufRead + 'static>(mut reader: R) -> io::Result<Box<dyn BufRead>> {
+    match Compression::detect(&mut reader)? {
+        Some(compression) => compression.decoder(reader),
+        None => Ok(Box::new(reader)),
+    }
+}
+
+/// Reads and discards up to `bytes` bytes, returning how many were skipped. This is how
+/// positions are reached in compressed files, which can't be seeked into.
+pub fn skip(reader: &mut impl Read, bytes: u64) -> io::Result<u64> {
+    io::copy(&mut reader.take(bytes), &mut io::sink())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression as GzCompression};
+    use std::io::{Cursor, Write};
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), GzCompression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn read_all(reader: &mut impl Read) -> Vec<u8> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn detects_compression() {
+        let plain = b"hello\n".to_vec();
+        let gzipped = gzip(&plain);
+        let zstd = zstd::encode_all(&plain[..], 0).unwrap();
+
+        for (data, expected) in vec![
+            (plain.clone(), None),
+            (gzipped, Some(Compression::Gzip)),
+            (zstd, Some(Compression::Zstd)),
+            (Vec::new(), None),
+        ] {
+            let mut reader = Cursor::new(data.clone());
+            assert_eq!(Compression::detect(&mut reader).unwrap(), expected);
+            // Nothing was consumed.
+            assert_eq!(read_all(&mut reader), data);
+
+            if !data.is_empty() {
+                let mut decompressed = decompress(Cursor::new(data)).unwrap();
+                assert_eq!(read_all(&mut decompressed), plain);
+            }
+        }
+    }
+
+    #[test]
+    fn decompresses_concatenated_streams() {
+        let mut gzipped = gzip(b"first\n");
+        gzipped.extend(gzip(b"second\n"));
+        let mut zstd = zstd::encode_all(&b"first\n"[..], 0).unwrap();
+        zstd.extend(zstd::encode_all(&b"second\n"[..], 0).unwrap());
+
+        for data in vec![gzipped, zstd] {
+            let mut reader = decompress(Cursor::new(data)).unwrap();
+            assert_eq!(skip(&mut reader, 3).unwrap(), 3);
+            assert_eq!(read_all(&mut reader), b"st\nsecond\n".to_vec());
+        }
+    }
+}
@@ -1,9 +1,9 @@
 use crate::buffer::read_until_with_max_size;
+use crate::compression::{self, Compression};
 use crate::metadata_ext::PortableFileExt;
 use crate::{FilePosition, ReadFrom};
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
-use flate2::bufread::MultiGzDecoder;
 use std::{
     fs::{self, File},
     io::{self, BufRead, Seek},
@@ -14,6 +14,11 @@ use tracing::debug;
 #[cfg(test)]
 mod tests;
 
+/// The position of compressed files read from their end. Compressed files aren't written to
+/// anymore, so they're considered complete without being decompressed, and are neither read when
+/// resumed from this position.
+const COMPRESSED_END: FilePosition = FilePosition::MAX;
+
 /// The `FileWatcher` struct defines the polling based state machine which reads
 /// from a file path, transparently updating the underlying file descriptor when
 /// the file has been rolled over, as is common for logs.
@@ -63,53 +68,63 @@ impl FileWatcher {
             false
         };
 
-        let gzipped = is_gzipped(&mut reader)?;
+        let compression = Compression::detect(&mut reader)?;
 
-        // Determine the actual position at which we should start reading
+        // Determine the actual position at which we should start reading. Compressed files
+        // can't be seeked into, so positions in them are reached by decompressing and skipping
+        // over their content.
         let (reader, file_position): (Box<dyn BufRead>, FilePosition) =
-            match (gzipped, too_old, read_from) {
-                (true, true, _) => {
+            match (compression, too_old, read_from) {
+                (Some(_), true, _) => {
                     debug!(
-                        message = "Not reading gzipped file older than `ignore_older`.",
+                        message = "Not reading compressed file older than `ignore_older`.",
                         ?path,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, _, ReadFrom::Checkpoint(file_position)) => {
+                (Some(_), false, ReadFrom::Checkpoint(COMPRESSED_END)) => {
+                    (Box::new(null_reader()), COMPRESSED_END)
+                }
+                (Some(compression), false, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
-                        message = "Not re-reading gzipped file with existing stored offset.",
+                        message = "Resuming compressed file from stored offset.",
                         ?path,
+                        ?compression,
                         %file_position
                     );
-                    (Box::new(null_reader()), file_position)
+                    let mut reader = compression.decoder(reader)?;
+                    let pos = compression::skip(&mut reader, file_position)?;
+                    (reader, pos)
                 }
-                // TODO: This may become the default, leading us to stop reading gzipped files that
-                // we were reading before. Should we merge this and the next branch to read
-                // compressed file from the beginning even when `read_from = "end"` (implicitly via
-                // default or explicitly via config)?
-                (true, _, ReadFrom::End) => {
+                (Some(compression), false, ReadFrom::End) => {
                     debug!(
-                        message = "Can't read from the end of already-compressed file.",
+                        message = "Skipping to the end of compressed file.",
                         ?path,
+                        ?compression,
                     );
-                    (Box::new(null_reader()), 0)
+                    (Box::new(null_reader()), COMPRESSED_END)
                 }
-                (true, false, ReadFrom::Beginning) => {
-                    (Box::new(io::BufReader::new(MultiGzDecoder::new(reader))), 0)
+                (Some(compression), false, ReadFrom::Beginning) => {
+                    (compression.decoder(reader)?, 0)
+                }
+                (None, true, _) => {
+                    let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
+                    (Box::new(reader), pos)
                 }
-                (false, true, _) => {
+                // The file may have been decompressed since it was read from its end.
+                (None, false, ReadFrom::Checkpoint(COMPRESSED_END)) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Checkpoint(file_position)) => {
+                (None, false, ReadFrom::Checkpoint(file_position)) => {
                     let pos = reader.seek(io::SeekFrom::Start(file_position)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Beginning) => {
+                (None, false, ReadFrom::Beginning) => {
                     let pos = reader.seek(io::SeekFrom::Start(0)).unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::End) => {
+                (None, false, ReadFrom::End) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
                     (Box::new(reader), pos)
                 }
@@ -142,16 +157,24 @@ impl FileWatcher {
         let file_handle = File::open(&path)?;
         if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
             let mut reader = io::BufReader::new(fs::File::open(&path)?);
-            let gzipped = is_gzipped(&mut reader)?;
-            let new_reader: Box<dyn BufRead> = if gzipped {
-                if self.file_position != 0 {
-                    Box::new(null_reader())
-                } else {
-                    Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
+            // A file with the same fingerprint but a different inode may have been compressed
+            // by log rotation, in which case reading continues from the same position in its
+            // decompressed content.
+            let new_reader: Box<dyn BufRead> = match Compression::detect(&mut reader)? {
+                Some(_) if self.file_position == COMPRESSED_END => Box::new(null_reader()),
+                Some(compression) => {
+                    let mut reader = compression.decoder(reader)?;
+                    compression::skip(&mut reader, self.file_position)?;
+                    reader
+                }
+                None if self.file_position == COMPRESSED_END => {
+                    self.file_position = reader.seek(io::SeekFrom::End(0))?;
+                    Box::new(reader)
+                }
+                None => {
+                    reader.seek(io::SeekFrom::Start(self.file_position))?;
+                    Box::new(reader)
                 }
-            } else {
-                reader.seek(io::SeekFrom::Start(self.file_position))?;
-                Box::new(reader)
             };
             self.reader = new_reader;
             self.devno = file_handle.portable_dev()?;
@@ -250,13 +273,6 @@ impl FileWatcher {
     }
 }
 
-fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
-    let header_bytes = r.fill_buf()?;
-    // WARN: The paired `BufReader::consume` is not called intentionally. If we
-    // do we'll chop a decent part of the potential gzip stream off.
-    Ok(header_bytes.starts_with(&[0x1f, 0x8b]))
-}
-
 fn null_reader() -> impl BufRead {
     io::Cursor::new(Vec::new())
 }
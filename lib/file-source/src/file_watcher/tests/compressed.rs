@@ -0,0 +1,89 @@
+use crate::file_watcher::FileWatcher;
+use crate::ReadFrom;
+use bytes::Bytes;
+use flate2::{write::GzEncoder, Compression};
+use std::{fs, io::Write, path::Path};
+
+const CONTENTS: &[u8] = b"first\nsecond\nthird\n";
+
+fn write_gzipped(path: &Path, contents: &[u8]) {
+    let mut encoder = GzEncoder::new(fs::File::create(path).unwrap(), Compression::fast());
+    encoder.write_all(contents).unwrap();
+    encoder.finish().unwrap();
+}
+
+fn watcher(path: &Path, read_from: ReadFrom) -> FileWatcher {
+    FileWatcher::new(
+        path.to_path_buf(),
+        read_from,
+        None,
+        100_000,
+        Bytes::from("\n"),
+    )
+    .expect("must be able to create")
+}
+
+fn read_lines(fw: &mut FileWatcher) -> Vec<Bytes> {
+    let mut lines = Vec::new();
+    while let Some(line) = fw.read_line().unwrap() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[test]
+fn reads_compressed_files() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let gzipped = dir.path().join("a_file.log.gz");
+    let zstd = dir.path().join("a_file.log.zst");
+    write_gzipped(&gzipped, CONTENTS);
+    fs::write(&zstd, zstd::encode_all(CONTENTS, 0).unwrap()).unwrap();
+
+    for path in &[gzipped, zstd] {
+        let mut fw = watcher(path, ReadFrom::Beginning);
+        assert_eq!(read_lines(&mut fw), vec!["first", "second", "third"]);
+        assert_eq!(fw.get_file_position(), CONTENTS.len() as u64);
+    }
+}
+
+#[test]
+fn resumes_compressed_file_from_checkpoint() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("a_file.log.gz");
+    write_gzipped(&path, CONTENTS);
+
+    let mut fw = watcher(&path, ReadFrom::Checkpoint(6));
+    assert_eq!(fw.get_file_position(), 6);
+    assert_eq!(read_lines(&mut fw), vec!["second", "third"]);
+
+    // Reading from the end considers the file complete, and it isn't read again when resuming.
+    let mut fw = watcher(&path, ReadFrom::End);
+    assert!(read_lines(&mut fw).is_empty());
+    let position = fw.get_file_position();
+    let mut fw = watcher(&path, ReadFrom::Checkpoint(position));
+    assert!(read_lines(&mut fw).is_empty());
+
+    // Nor if the file is decompressed in the meantime.
+    fs::write(&path, CONTENTS).unwrap();
+    let mut fw = watcher(&path, ReadFrom::Checkpoint(position));
+    assert!(read_lines(&mut fw).is_empty());
+    assert_eq!(fw.get_file_position(), CONTENTS.len() as u64);
+}
+
+#[test]
+fn continues_reading_file_compressed_by_rotation() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("a_file.log.1");
+    let compressed_path = dir.path().join("a_file.log.1.gz");
+    fs::write(&path, b"first\nsecond\n").unwrap();
+
+    let mut fw = watcher(&path, ReadFrom::Beginning);
+    assert_eq!(read_lines(&mut fw), vec!["first", "second"]);
+
+    // The tail written after the last read is compressed along with the rest of the file.
+    write_gzipped(&compressed_path, CONTENTS);
+    fs::remove_file(&path).unwrap();
+    fw.update_path(compressed_path).unwrap();
+
+    assert_eq!(read_lines(&mut fw), vec!["third"]);
+}
@@ -1,3 +1,4 @@
+mod compressed;
 mod experiment;
 mod experiment_no_truncations;
 
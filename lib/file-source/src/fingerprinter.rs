@@ -1,4 +1,4 @@
-use crate::{metadata_ext::PortableFileExt, FileSourceInternalEvents};
+use crate::{compression, metadata_ext::PortableFileExt, FileSourceInternalEvents};
 use crc::Crc;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -92,8 +92,10 @@ impl Fingerprinter {
                 lines,
             } => {
                 buffer.resize(self.max_line_length, 0u8);
-                let mut fp = fs::File::open(path)?;
-                fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
+                // Compressed files are fingerprinted on their decompressed content, so that a
+                // file compressed by log rotation is recognized as the one it was before.
+                let mut fp = compression::decompress(io::BufReader::new(fs::File::open(path)?))?;
+                compression::skip(&mut fp, ignored_header_bytes as u64)?;
                 fingerprinter_read_until(fp, b'\n', lines, buffer)?;
                 let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..]);
                 Ok(FirstLinesChecksum(fingerprint))
@@ -158,6 +160,37 @@ impl Fingerprinter {
         }
     }
 
+    /// Calculates the checksum of a compressed file on its compressed bytes, as compressed files
+    /// were fingerprinted before being fingerprinted on their decompressed content.
+    pub fn get_compressed_checksum(
+        &self,
+        path: &Path,
+        buffer: &mut Vec<u8>,
+    ) -> Result<Option<FileFingerprint>, io::Error> {
+        match self.strategy {
+            FingerprintStrategy::Checksum {
+                ignored_header_bytes,
+                bytes: _,
+                lines,
+            }
+            | FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes,
+                lines,
+            } => {
+                let mut fp = io::BufReader::new(fs::File::open(path)?);
+                if compression::Compression::detect(&mut fp)?.is_none() {
+                    return Ok(None);
+                }
+                buffer.resize(self.max_line_length, 0u8);
+                fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
+                fingerprinter_read_until(fp, b'\n', lines, buffer)?;
+                let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..]);
+                Ok(Some(FileFingerprint::FirstLinesChecksum(fingerprint)))
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Calculates checksums using strategy pre-0.14.0
     /// <https://github.com/timberio/vector/issues/8182>
     pub fn get_legacy_checksum(
@@ -222,7 +255,14 @@ fn fingerprinter_read_until(
 #[cfg(test)]
 mod test {
     use super::{FileSourceInternalEvents, FingerprintStrategy, Fingerprinter};
-    use std::{collections::HashSet, fs, io::Error, path::Path, time::Duration};
+    use flate2::{write::GzEncoder, Compression};
+    use std::{
+        collections::HashSet,
+        fs,
+        io::{Error, Write},
+        path::Path,
+        time::Duration,
+    };
     use tempfile::tempdir;
 
     #[test]
@@ -344,6 +384,46 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_compressed_file_fingerprint() {
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: 64,
+            ignore_not_found: false,
+        };
+
+        let target_dir = tempdir().unwrap();
+        let contents = b"hello world\nthe next line\n";
+        let plain = target_dir.path().join("plain.log");
+        let gzipped = target_dir.path().join("plain.log.1.gz");
+        let zstd = target_dir.path().join("plain.log.1.zst");
+        fs::write(&plain, contents).unwrap();
+        let mut encoder = GzEncoder::new(fs::File::create(&gzipped).unwrap(), Compression::fast());
+        encoder.write_all(contents).unwrap();
+        encoder.finish().unwrap();
+        fs::write(&zstd, zstd::encode_all(&contents[..], 0).unwrap()).unwrap();
+
+        let mut buf = Vec::new();
+        let plain = fingerprinter
+            .get_fingerprint_of_file(&plain, &mut buf)
+            .unwrap();
+        assert_eq!(
+            plain,
+            fingerprinter
+                .get_fingerprint_of_file(&gzipped, &mut buf)
+                .unwrap()
+        );
+        assert_eq!(
+            plain,
+            fingerprinter
+                .get_fingerprint_of_file(&zstd, &mut buf)
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_first_two_lines_checksum_fingerprint() {
         let max_line_length = 64;
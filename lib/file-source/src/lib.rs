@@ -5,6 +5,7 @@ extern crate scan_fmt;
 
 pub mod buffer;
 mod checkpointer;
+mod compression;
 mod file_server;
 mod file_watcher;
 mod fingerprinter;
@@ -16,6 +16,16 @@ type DateTime = chrono::DateTime<chrono::Utc>;
 )]
 pub struct OutputEventsByComponentIdPatternsSubscription;
 
+/// TransformInvocationsByComponentIdPatternsSubscription allows observability into the events
+/// received by transform(s), paired with the events emitted in return.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/transform_invocations_by_component_id_patterns.graphql",
+    response_derives = "Debug"
+)]
+pub struct TransformInvocationsByComponentIdPatternsSubscription;
+
 /// Tap encoding format type that is more convenient to use for public clients than the
 /// generated `output_events_by_component_id_patterns_subscription::EventEncodingType`.
 #[derive(Debug, Clone, Copy)]
@@ -49,6 +59,18 @@ impl From<TapEncodingFormat>
     }
 }
 
+/// Map the public-facing `TapEncodingFormat` to the internal `EventEncodingType`.
+impl From<TapEncodingFormat>
+    for transform_invocations_by_component_id_patterns_subscription::EventEncodingType
+{
+    fn from(encoding: TapEncodingFormat) -> Self {
+        match encoding {
+            TapEncodingFormat::Json => Self::JSON,
+            TapEncodingFormat::Yaml => Self::YAML,
+        }
+    }
+}
+
 impl output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns {
     pub fn as_log(
         &self,
@@ -60,15 +82,40 @@ impl output_events_by_component_id_patterns_subscription::OutputEventsByComponen
     }
 }
 
+impl transform_invocations_by_component_id_patterns_subscription::TransformInvocationsByComponentIdPatternsSubscriptionTransformInvocationsByComponentIdPatterns {
+    pub fn as_invocation(
+        &self,
+    ) -> Option<&transform_invocations_by_component_id_patterns_subscription::TransformInvocationsByComponentIdPatternsSubscriptionTransformInvocationsByComponentIdPatternsOnTransformInvocation>{
+        match self {
+            transform_invocations_by_component_id_patterns_subscription::TransformInvocationsByComponentIdPatternsSubscriptionTransformInvocationsByComponentIdPatterns::TransformInvocation(ev) => Some(ev),
+            _ => None,
+        }
+    }
+}
+
 pub trait TapSubscriptionExt {
     /// Executes an output events subscription.
     fn output_events_by_component_id_patterns_subscription(
         &self,
         component_patterns: Vec<String>,
+        input_patterns: Option<Vec<String>>,
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        filter: Option<String>,
+        sample_rate: i64,
     ) -> crate::BoxedSubscription<OutputEventsByComponentIdPatternsSubscription>;
+
+    /// Executes a transform invocations subscription.
+    fn transform_invocations_by_component_id_patterns_subscription(
+        &self,
+        component_patterns: Vec<String>,
+        encoding: TapEncodingFormat,
+        limit: i64,
+        interval: i64,
+        filter: Option<String>,
+        sample_rate: i64,
+    ) -> crate::BoxedSubscription<TransformInvocationsByComponentIdPatternsSubscription>;
 }
 
 impl TapSubscriptionExt for crate::SubscriptionClient {
@@ -76,19 +123,49 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
     fn output_events_by_component_id_patterns_subscription(
         &self,
         patterns: Vec<String>,
+        input_patterns: Option<Vec<String>>,
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        filter: Option<String>,
+        sample_rate: i64,
     ) -> BoxedSubscription<OutputEventsByComponentIdPatternsSubscription> {
         let request_body = OutputEventsByComponentIdPatternsSubscription::build_query(
             output_events_by_component_id_patterns_subscription::Variables {
                 patterns,
+                input_patterns,
                 limit,
                 interval,
                 encoding: encoding.into(),
+                filter,
+                sample_rate,
             },
         );
 
         self.start::<OutputEventsByComponentIdPatternsSubscription>(&request_body)
     }
+
+    /// Executes a transform invocations subscription.
+    fn transform_invocations_by_component_id_patterns_subscription(
+        &self,
+        patterns: Vec<String>,
+        encoding: TapEncodingFormat,
+        limit: i64,
+        interval: i64,
+        filter: Option<String>,
+        sample_rate: i64,
+    ) -> BoxedSubscription<TransformInvocationsByComponentIdPatternsSubscription> {
+        let request_body = TransformInvocationsByComponentIdPatternsSubscription::build_query(
+            transform_invocations_by_component_id_patterns_subscription::Variables {
+                patterns,
+                limit,
+                interval,
+                encoding: encoding.into(),
+                filter,
+                sample_rate,
+            },
+        );
+
+        self.start::<TransformInvocationsByComponentIdPatternsSubscription>(&request_body)
+    }
 }
@@ -1,5 +1,7 @@
+use crate::overflow::OverflowAcker;
 use futures::task::AtomicWaker;
 use metrics::counter;
+use std::fmt::Debug;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
@@ -13,9 +15,23 @@ pub trait Ackable {
     fn ack_size(&self) -> usize;
 }
 
+/// A buffer that applies acknowledgements as soon as they are made.
+pub trait Acknowledge: Debug + Send + Sync {
+    /// Acknowledges the next `num` events read from the buffer.
+    fn acknowledge(&self, num: usize);
+}
+
 #[derive(Debug, Clone)]
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicWaker>),
+    /// Unlike `Disk`, which defers the bookkeeping of acknowledged events
+    /// until the buffer's reader is polled again, this frees up space in the
+    /// buffer right away, so that blocked writers can make progress without
+    /// waiting on the reader.
+    Segmented(Arc<dyn Acknowledge>),
+    /// Acknowledges events read from a pair of buffers chained through
+    /// `WhenFull::Overflow`.
+    Overflow(Arc<OverflowAcker>),
     Null,
 }
 
@@ -29,13 +45,7 @@ impl Acker {
     pub fn ack(&self, num: usize) {
         // Only ack items if the amount to ack is larger than zero.
         if num > 0 {
-            match self {
-                Acker::Null => {}
-                Acker::Disk(counter, notifier) => {
-                    counter.fetch_add(num, Ordering::Relaxed);
-                    notifier.wake();
-                }
-            }
+            self.acknowledge(num);
 
             // WARN this string "events_out_total" is a duplicate of the metric
             // name in `ROOT/src/internal_events/topology.rs`. `Acker` had a
@@ -49,6 +59,20 @@ impl Acker {
         }
     }
 
+    /// Acknowledges events with the underlying buffer, without recording them
+    /// as sent. `num` must be larger than zero.
+    pub(crate) fn acknowledge(&self, num: usize) {
+        match self {
+            Acker::Null => {}
+            Acker::Disk(counter, notifier) => {
+                counter.fetch_add(num, Ordering::Relaxed);
+                notifier.wake();
+            }
+            Acker::Segmented(ledger) => ledger.acknowledge(num),
+            Acker::Overflow(acker) => acker.acknowledge(num),
+        }
+    }
+
     #[must_use]
     pub fn new_for_testing() -> (Self, Arc<AtomicUsize>) {
         let ack_counter = Arc::new(AtomicUsize::new(0));
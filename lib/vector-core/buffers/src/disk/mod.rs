@@ -13,7 +13,9 @@ use std::{
     task::{Context, Poll},
 };
 
+#[cfg(feature = "disk-buffer")]
 pub mod leveldb_buffer;
+pub mod segmented_buffer;
 
 #[derive(Debug, Snafu)]
 pub enum DataDirError {
@@ -26,11 +28,21 @@ pub enum DataDirError {
         data_dir: PathBuf,
         source: std::io::Error,
     },
+    #[cfg(feature = "disk-buffer")]
     #[snafu(display("Unable to open data_dir {:?}: {:?}", data_dir, source))]
     Open {
         data_dir: PathBuf,
         source: leveldb::database::error::Error,
     },
+    #[snafu(display(
+        "Unable to recover disk buffer in data_dir {:?}: {:?}",
+        data_dir,
+        source
+    ))]
+    Recover {
+        data_dir: PathBuf,
+        source: std::io::Error,
+    },
 }
 
 #[pin_project]
@@ -42,7 +54,20 @@ where
     <T as DecodeBytes<T>>::Error: Debug,
 {
     #[pin]
-    inner: leveldb_buffer::Writer<T>,
+    inner: WriterInner<T>,
+}
+
+#[pin_project(project = WriterInnerProj)]
+#[derive(Clone)]
+enum WriterInner<T>
+where
+    T: Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    #[cfg(feature = "disk-buffer")]
+    LevelDb(#[pin] leveldb_buffer::Writer<T>),
+    Segmented(#[pin] segmented_buffer::Writer<T>),
 }
 
 impl<T> Sink<T> for Writer<T>
@@ -53,28 +78,70 @@ where
 {
     type Error = ();
     fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.project().inner.poll_ready(cx)
+        match self.project().inner.project() {
+            #[cfg(feature = "disk-buffer")]
+            WriterInnerProj::LevelDb(writer) => writer.poll_ready(cx),
+            WriterInnerProj::Segmented(writer) => writer.poll_ready(cx),
+        }
     }
 
     fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
-        self.project().inner.start_send(item)
+        match self.project().inner.project() {
+            #[cfg(feature = "disk-buffer")]
+            WriterInnerProj::LevelDb(writer) => writer.start_send(item),
+            WriterInnerProj::Segmented(writer) => writer.start_send(item),
+        }
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.project().inner.poll_flush(cx)
+        match self.project().inner.project() {
+            #[cfg(feature = "disk-buffer")]
+            WriterInnerProj::LevelDb(writer) => writer.poll_flush(cx),
+            WriterInnerProj::Segmented(writer) => writer.poll_flush(cx),
+        }
     }
 
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.project().inner.poll_close(cx)
+        match self.project().inner.project() {
+            #[cfg(feature = "disk-buffer")]
+            WriterInnerProj::LevelDb(writer) => writer.poll_close(cx),
+            WriterInnerProj::Segmented(writer) => writer.poll_close(cx),
+        }
     }
 }
 
+fn check_data_dir(data_dir: &Path) -> Result<(), DataDirError> {
+    std::fs::metadata(&data_dir)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::PermissionDenied => DataDirError::NotWritable {
+                data_dir: data_dir.into(),
+            },
+            io::ErrorKind::NotFound => DataDirError::NotFound {
+                data_dir: data_dir.into(),
+            },
+            _ => DataDirError::Metadata {
+                data_dir: data_dir.into(),
+                source: e,
+            },
+        })
+        .and_then(|m| {
+            if m.permissions().readonly() {
+                Err(DataDirError::NotWritable {
+                    data_dir: data_dir.into(),
+                })
+            } else {
+                Ok(())
+            }
+        })
+}
+
 /// Open a [`leveldb_buffer::Buffer`]
 ///
 /// # Errors
 ///
 /// This function will fail with [`DataDirError`] if the directory does not exist at
 /// `data_dir`, if permissions are not sufficient etc.
+#[cfg(feature = "disk-buffer")]
 pub fn open<'a, T>(
     data_dir: &Path,
     name: &str,
@@ -95,31 +162,55 @@ where
 {
     let path = data_dir.join(name);
 
-    // Check data dir
-    std::fs::metadata(&data_dir)
-        .map_err(|e| match e.kind() {
-            io::ErrorKind::PermissionDenied => DataDirError::NotWritable {
-                data_dir: data_dir.into(),
-            },
-            io::ErrorKind::NotFound => DataDirError::NotFound {
-                data_dir: data_dir.into(),
-            },
-            _ => DataDirError::Metadata {
-                data_dir: data_dir.into(),
-                source: e,
-            },
-        })
-        .and_then(|m| {
-            if m.permissions().readonly() {
-                Err(DataDirError::NotWritable {
-                    data_dir: data_dir.into(),
-                })
-            } else {
-                Ok(())
-            }
-        })?;
+    check_data_dir(data_dir)?;
 
     let (writer, reader, acker) =
         leveldb_buffer::Buffer::build(&path, max_size, buffer_usage_data)?;
-    Ok((Writer { inner: writer }, Box::new(reader), acker))
+    Ok((
+        Writer {
+            inner: WriterInner::LevelDb(writer),
+        },
+        Box::new(reader),
+        acker,
+    ))
+}
+
+/// Open a [`segmented_buffer::Buffer`]
+///
+/// # Errors
+///
+/// This function will fail with [`DataDirError`] if the directory does not exist at
+/// `data_dir`, if permissions are not sufficient, if the buffer left behind by
+/// a previous run can't be recovered etc.
+pub fn open_segmented<'a, T>(
+    data_dir: &Path,
+    name: &str,
+    max_size: usize,
+    buffer_usage_data: Arc<BufferUsageData>,
+) -> Result<
+    (
+        Writer<T>,
+        Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+        super::Acker,
+    ),
+    DataDirError,
+>
+where
+    T: 'a + Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug + Display,
+{
+    let path = data_dir.join(name);
+
+    check_data_dir(data_dir)?;
+
+    let (writer, reader, acker) =
+        segmented_buffer::Buffer::build(&path, max_size, buffer_usage_data)?;
+    Ok((
+        Writer {
+            inner: WriterInner::Segmented(writer),
+        },
+        Box::new(reader),
+        acker,
+    ))
 }
@@ -0,0 +1,184 @@
+use super::record::checksum;
+use crate::acker::Acknowledge;
+use futures::task::AtomicWaker;
+use std::{
+    collections::VecDeque,
+    convert::TryInto,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    task::Waker,
+};
+
+const LEDGER_FILE: &str = "buffer.ledger";
+const LEDGER_TMP_FILE: &str = "buffer.ledger.tmp";
+const LEDGER_LEN: usize = 20;
+
+/// A location in the buffer, given by the id of a segment and an offset in
+/// bytes into it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct Position {
+    pub(super) segment: u64,
+    pub(super) offset: u64,
+}
+
+/// A record that has been read, but not acknowledged yet.
+#[derive(Debug)]
+struct Unacked {
+    /// Position right after the record.
+    end: Position,
+    /// Size of the record on disk.
+    size: usize,
+}
+
+#[derive(Debug, Default)]
+struct Acks {
+    /// Records handed out by the reader, oldest first.
+    unacked: VecDeque<Unacked>,
+    /// Position up to which records have been acknowledged, along with the
+    /// number of records, since the reader last took it.
+    acked: Option<(Position, usize)>,
+}
+
+/// State shared between the writers, the reader and the acker of a segmented
+/// buffer.
+#[derive(Debug)]
+pub(super) struct Ledger {
+    dir: PathBuf,
+    /// Size in bytes of the records that have been written, but not
+    /// acknowledged yet.
+    pub(super) current_size: AtomicUsize,
+    /// Number of writers still alive.
+    pub(super) writers: AtomicUsize,
+    /// Position up to which complete records have been flushed to the active
+    /// segment. Segments preceding it are complete.
+    flushed: Mutex<Position>,
+    acks: Mutex<Acks>,
+    /// Writers notify the reader through this waker.
+    pub(super) reader_waker: AtomicWaker,
+    /// Writers blocked by the buffer being full.
+    blocked_writers: Mutex<Vec<Waker>>,
+}
+
+impl Ledger {
+    pub(super) fn new(dir: PathBuf, current_size: usize, flushed: Position) -> Self {
+        Self {
+            dir,
+            current_size: AtomicUsize::new(current_size),
+            writers: AtomicUsize::new(0),
+            flushed: Mutex::new(flushed),
+            acks: Mutex::new(Acks::default()),
+            reader_waker: AtomicWaker::new(),
+            blocked_writers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(super) fn flushed(&self) -> Position {
+        *self.flushed.lock().unwrap()
+    }
+
+    pub(super) fn set_flushed(&self, position: Position) {
+        *self.flushed.lock().unwrap() = position;
+        self.reader_waker.wake();
+    }
+
+    /// Registers a record handed out by the reader, to be acknowledged later.
+    pub(super) fn track(&self, end: Position, size: usize) {
+        self.acks
+            .lock()
+            .unwrap()
+            .unacked
+            .push_back(Unacked { end, size });
+    }
+
+    /// Takes the position up to which records have been acknowledged since
+    /// the last call, along with their number.
+    pub(super) fn take_acked(&self) -> Option<(Position, usize)> {
+        self.acks.lock().unwrap().acked.take()
+    }
+
+    /// Frees up `size` bytes of the buffer, waking up any writers blocked by
+    /// it being full.
+    pub(super) fn release(&self, size: usize) {
+        self.current_size.fetch_sub(size, Ordering::Release);
+        for waker in self.blocked_writers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub(super) fn block_writer(&self, waker: Waker) {
+        self.blocked_writers.lock().unwrap().push(waker);
+    }
+
+    /// Durably records that everything preceding `position` has been
+    /// acknowledged.
+    ///
+    /// The ledger is written to a temporary file first and then moved in
+    /// place, so that it is never observed half written.
+    pub(super) fn persist(&self, position: Position) -> io::Result<()> {
+        let mut contents = [0; LEDGER_LEN];
+        contents[..8].copy_from_slice(&position.segment.to_le_bytes());
+        contents[8..16].copy_from_slice(&position.offset.to_le_bytes());
+        let checksum = checksum(&contents[..16]);
+        contents[16..].copy_from_slice(&checksum.to_le_bytes());
+
+        let tmp_path = self.dir.join(LEDGER_TMP_FILE);
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&contents)?;
+        file.sync_data()?;
+        fs::rename(tmp_path, self.dir.join(LEDGER_FILE))
+    }
+}
+
+impl Acknowledge for Ledger {
+    fn acknowledge(&self, num: usize) {
+        let size = {
+            let mut acks = self.acks.lock().unwrap();
+            assert!(num <= acks.unacked.len(), "Tried to ack beyond read offset");
+
+            let mut size = 0;
+            let mut end = None;
+            for unacked in acks.unacked.drain(..num) {
+                size += unacked.size;
+                end = Some(unacked.end);
+            }
+            if let Some(end) = end {
+                let count = acks.acked.map_or(0, |(_, count)| count);
+                acks.acked = Some((end, count + num));
+            }
+            size
+        };
+
+        self.release(size);
+        self.reader_waker.wake();
+    }
+}
+
+/// Loads the position up to which records have been acknowledged by a previous
+/// run of the buffer rooted at `dir`, if any.
+pub(super) fn load(dir: &Path) -> io::Result<Option<Position>> {
+    let contents = match fs::read(dir.join(LEDGER_FILE)) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    let valid = contents.len() == LEDGER_LEN
+        && checksum(&contents[..16]) == u32::from_le_bytes(contents[16..].try_into().unwrap());
+    if !valid {
+        warn!(
+            message = "Disk buffer ledger is corrupted, replaying all buffered events.",
+            path = ?dir.join(LEDGER_FILE)
+        );
+        return Ok(None);
+    }
+
+    Ok(Some(Position {
+        segment: u64::from_le_bytes(contents[..8].try_into().unwrap()),
+        offset: u64::from_le_bytes(contents[8..16].try_into().unwrap()),
+    }))
+}
@@ -0,0 +1,217 @@
+//! A disk buffer storing events in a series of append-only segment files.
+//!
+//! Writers append checksummed records to the active segment, starting a new
+//! one once it grows too large, while the reader follows along from the oldest
+//! segment. The position up to which events have been acknowledged is kept in
+//! a small ledger file, and segments are deleted once all of their events have
+//! been acknowledged. On startup, events that haven't been acknowledged by the
+//! previous run are replayed, while records that have been torn by a crash are
+//! detected through their checksum and dropped.
+mod ledger;
+mod reader;
+mod record;
+mod writer;
+
+use super::{DataDirError, Recover};
+use crate::buffer_usage_data::BufferUsageData;
+use crate::bytes::{DecodeBytes, EncodeBytes};
+use crate::Acker;
+use ledger::{Ledger, Position};
+pub use reader::Reader;
+use reader::SegmentReader;
+use record::{read_record, segment_id, segment_path, Record, HEADER_LEN};
+use snafu::ResultExt;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Seek, SeekFrom},
+    marker::PhantomData,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use writer::SegmentWriter;
+pub use writer::Writer;
+
+/// Segments are sized relative to the buffer, so that acknowledged events are
+/// deleted in reasonably small steps.
+const SEGMENT_SIZE_DENOMINATOR: usize = 8;
+
+const MIN_SEGMENT_SIZE: usize = 64 * 1024;
+
+const MAX_SEGMENT_SIZE: usize = 128 * 1024 * 1024;
+
+#[derive(Default)]
+pub struct Buffer<T> {
+    phantom: PhantomData<T>,
+}
+
+/// The contents of the buffer left behind by a previous run.
+struct Recovered {
+    /// Position of the first unacknowledged record.
+    start: Position,
+    /// Id of the segment following the last existing one.
+    next_segment: u64,
+    event_count: u64,
+    byte_size: usize,
+}
+
+/// Scans the segments in `dir` for events that haven't been acknowledged yet.
+///
+/// Segments that have been read and acknowledged completely are deleted, and
+/// segments ending in a corrupted record are truncated right before it.
+fn recover(dir: &Path) -> io::Result<Recovered> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        if let Some(segment) = entry?.file_name().to_str().and_then(segment_id) {
+            segments.push(segment);
+        }
+    }
+    segments.sort_unstable();
+
+    let mut start = ledger::load(dir)?.unwrap_or_default();
+    if !segments.contains(&start.segment) {
+        // The acknowledged segment is gone, so reading resumes from the next
+        // one available.
+        start = Position {
+            segment: segments
+                .iter()
+                .copied()
+                .find(|&segment| segment > start.segment)
+                .unwrap_or(start.segment),
+            offset: 0,
+        };
+    }
+
+    let mut recovered = Recovered {
+        start,
+        next_segment: segments.last().map_or(start.segment, |&last| last + 1),
+        event_count: 0,
+        byte_size: 0,
+    };
+    for segment in segments {
+        let path = segment_path(dir, segment);
+        if segment < start.segment {
+            fs::remove_file(&path)?;
+            continue;
+        }
+
+        let offset = if segment == start.segment {
+            start.offset
+        } else {
+            0
+        };
+        let mut file = BufReader::new(File::open(&path)?);
+        file.seek(SeekFrom::Start(offset))?;
+
+        let mut valid_len = offset;
+        loop {
+            match read_record(&mut file)? {
+                Record::Valid(payload) => {
+                    let size = HEADER_LEN + payload.len();
+                    valid_len += size as u64;
+                    recovered.event_count += 1;
+                    recovered.byte_size += size;
+                }
+                Record::Corrupted => {
+                    warn!(
+                        message = "Truncating corrupted disk buffer segment.",
+                        ?path,
+                        len = valid_len
+                    );
+                    OpenOptions::new()
+                        .write(true)
+                        .open(&path)?
+                        .set_len(valid_len)?;
+                    break;
+                }
+                Record::End => break,
+            }
+        }
+
+        drop(file);
+        if valid_len == offset && segment == recovered.start.segment {
+            // Nothing is left to be read from this segment, and it won't be
+            // written to anymore either.
+            fs::remove_file(&path)?;
+            recovered.start = Position {
+                segment: segment + 1,
+                offset: 0,
+            };
+        }
+    }
+
+    Ok(recovered)
+}
+
+impl<T> Buffer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    /// Build a new segmented disk buffer rooted at `path`
+    ///
+    /// # Errors
+    ///
+    /// Function will fail if the permissions of `path` are not correct, if
+    /// there is no space available on disk etc.
+    pub fn build(
+        path: &Path,
+        max_size: usize,
+        buffer_usage_data: Arc<BufferUsageData>,
+    ) -> Result<(Writer<T>, Reader<T>, Acker), DataDirError> {
+        let context = || Recover {
+            data_dir: path.parent().unwrap_or(path),
+        };
+        fs::create_dir_all(path).with_context(context)?;
+        let recovered = recover(path).with_context(context)?;
+        buffer_usage_data.increment_received_event_count_and_byte_size(
+            recovered.event_count,
+            recovered.byte_size,
+        );
+
+        // Writing always starts out with a fresh segment, so that segments
+        // left behind by a previous run are never appended to.
+        let max_segment_size =
+            (max_size / SEGMENT_SIZE_DENOMINATOR).clamp(MIN_SEGMENT_SIZE, MAX_SEGMENT_SIZE);
+        let segment_writer = SegmentWriter::create(
+            path.to_path_buf(),
+            recovered.next_segment,
+            max_segment_size as u64,
+        )
+        .with_context(context)?;
+
+        let ledger = Arc::new(Ledger::new(
+            path.to_path_buf(),
+            recovered.byte_size,
+            Position {
+                segment: recovered.next_segment,
+                offset: 0,
+            },
+        ));
+        let acker = Acker::Segmented(Arc::clone(&ledger) as _);
+
+        let writer = Writer::new(
+            Arc::new(Mutex::new(segment_writer)),
+            Arc::clone(&ledger),
+            max_size,
+            buffer_usage_data.clone(),
+        );
+
+        let reader = Reader {
+            dir: path.to_path_buf(),
+            ledger,
+            segment_reader: Some(SegmentReader::new(path.to_path_buf(), recovered.start)),
+            pending_read: None,
+            buffer: VecDeque::new(),
+            acked: recovered.start,
+            unpersisted_acks: 0,
+            persisted_segment: recovered.start.segment,
+            phantom: PhantomData,
+            buffer_usage_data,
+        };
+
+        Ok((writer, reader, acker))
+    }
+}
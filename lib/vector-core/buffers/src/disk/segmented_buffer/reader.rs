@@ -0,0 +1,339 @@
+use super::{
+    ledger::{Ledger, Position},
+    record::{read_record, segment_path, Record, HEADER_LEN},
+};
+use crate::{buffer_usage_data::BufferUsageData, bytes::DecodeBytes};
+use bytes::Bytes;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{atomic::Ordering, Arc};
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+
+/// Maximum number of records read from disk at once.
+const MAX_BATCH_SIZE: usize = 1000;
+
+/// Number of acknowledged records after which the ledger is persisted.
+const MAX_UNPERSISTED_ACKS: usize = 100;
+
+/// A record read from disk, along with the position right after it.
+pub(super) struct ReadRecord {
+    payload: Vec<u8>,
+    end: Position,
+}
+
+/// The outcome of reading a batch of records on a blocking thread.
+pub(super) struct ReadBatch {
+    segment_reader: SegmentReader,
+    records: Vec<ReadRecord>,
+    /// Size in bytes of the corrupted records that have been skipped.
+    skipped: usize,
+    /// Whether reading failed before making any progress.
+    stalled: bool,
+}
+
+/// Reads records from the segment files, one segment after another.
+pub(super) struct SegmentReader {
+    dir: PathBuf,
+    /// Position of the next record to read.
+    position: Position,
+    file: Option<BufReader<File>>,
+    /// Size in bytes of the corrupted records skipped since the last batch.
+    skipped: usize,
+}
+
+impl SegmentReader {
+    pub(super) const fn new(dir: PathBuf, position: Position) -> Self {
+        Self {
+            dir,
+            position,
+            file: None,
+            skipped: 0,
+        }
+    }
+
+    /// Reads up to `MAX_BATCH_SIZE` records that have been flushed up to
+    /// `flushed`.
+    fn read_batch(mut self, flushed: Position) -> ReadBatch {
+        let start = self.position;
+        let mut records = Vec::new();
+        while records.len() < MAX_BATCH_SIZE && self.position < flushed {
+            match self.read_next(flushed) {
+                Ok(Some(record)) => records.push(record),
+                Ok(None) => {}
+                Err(error) => {
+                    error!(message = "Error reading from disk buffer.", %error);
+                    break;
+                }
+            }
+        }
+        ReadBatch {
+            stalled: records.is_empty() && self.position == start,
+            skipped: std::mem::take(&mut self.skipped),
+            segment_reader: self,
+            records,
+        }
+    }
+
+    /// Reads the next record, or moves on to the next segment if the current
+    /// one has been read completely.
+    fn read_next(&mut self, flushed: Position) -> io::Result<Option<ReadRecord>> {
+        let active = self.position.segment == flushed.segment;
+        if self.file.is_none() {
+            match File::open(segment_path(&self.dir, self.position.segment)) {
+                Ok(mut file) => {
+                    file.seek(SeekFrom::Start(self.position.offset))?;
+                    self.file = Some(BufReader::new(file));
+                }
+                Err(error) if error.kind() == io::ErrorKind::NotFound && !active => {
+                    self.next_segment();
+                    return Ok(None);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        let file = self.file.as_mut().expect("segment file was just opened");
+
+        // Only complete records are read from the active segment, while the
+        // preceding ones are read to their end.
+        let available = if active {
+            flushed.offset - self.position.offset
+        } else {
+            u64::MAX
+        };
+        match read_record(&mut file.by_ref().take(available))? {
+            Record::Valid(payload) => {
+                self.position.offset += (HEADER_LEN + payload.len()) as u64;
+                Ok(Some(ReadRecord {
+                    payload,
+                    end: self.position,
+                }))
+            }
+            Record::Corrupted => {
+                error!(
+                    message = "Disk buffer segment is corrupted, skipping the rest of it.",
+                    segment = self.position.segment
+                );
+                self.skip_segment(active, flushed)?;
+                Ok(None)
+            }
+            Record::End => {
+                if active {
+                    error!(
+                        message = "Disk buffer segment is truncated, skipping the rest of it.",
+                        segment = self.position.segment
+                    );
+                    self.skip_segment(active, flushed)?;
+                } else {
+                    self.next_segment();
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Skips the rest of the current segment, keeping track of the size of
+    /// the skipped records, which won't ever be acknowledged.
+    fn skip_segment(&mut self, active: bool, flushed: Position) -> io::Result<()> {
+        let skipped = if active {
+            // The writer only ever appends complete records, so the flushed
+            // position is the start of the next one.
+            let skipped = flushed.offset - self.position.offset;
+            self.position = flushed;
+            self.file = None;
+            skipped
+        } else {
+            let len = fs::metadata(segment_path(&self.dir, self.position.segment))?.len();
+            let skipped = len.saturating_sub(self.position.offset);
+            self.next_segment();
+            skipped
+        };
+        self.skipped += usize::try_from(skipped).unwrap_or(usize::MAX);
+        Ok(())
+    }
+
+    fn next_segment(&mut self) {
+        self.position = Position {
+            segment: self.position.segment + 1,
+            offset: 0,
+        };
+        self.file = None;
+    }
+}
+
+/// The reader side of N to 1 channel through segment files.
+pub struct Reader<T>
+where
+    T: Send + Sync + Unpin,
+{
+    pub(super) dir: PathBuf,
+    /// Shared with Writers and Acker.
+    pub(super) ledger: Arc<Ledger>,
+    /// Reads from disk, unless a read is in flight.
+    pub(super) segment_reader: Option<SegmentReader>,
+    /// Pending read from disk.
+    pub(super) pending_read: Option<JoinHandle<ReadBatch>>,
+    /// Buffer for internal use.
+    pub(super) buffer: VecDeque<ReadRecord>,
+    /// Position up to which records have been acknowledged.
+    pub(super) acked: Position,
+    /// Number of acknowledged records that haven't been persisted yet.
+    pub(super) unpersisted_acks: usize,
+    /// Segment of the last persisted acknowledgement. Preceding segments
+    /// have been deleted.
+    pub(super) persisted_segment: u64,
+    pub(super) phantom: PhantomData<T>,
+    /// Atomic structure for recording buffer metadata
+    pub(super) buffer_usage_data: Arc<BufferUsageData>,
+}
+
+impl<T> Stream for Reader<T>
+where
+    T: Send + Sync + Unpin + DecodeBytes<T>,
+    <T as DecodeBytes<T>>::Error: Display,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // If there's nothing left to read, we return Pending and rely on
+        // Writers and the Acker using the waker to wake this task up.
+        this.ledger.reader_waker.register(cx.waker());
+
+        this.take_acked();
+        if this.unpersisted_acks >= MAX_UNPERSISTED_ACKS
+            || this.acked.segment > this.persisted_segment
+        {
+            this.persist_acked();
+        }
+
+        loop {
+            while this.buffer.is_empty() {
+                match this.pending_read.take() {
+                    None => {
+                        // We have no pending read in-flight, so queue one up.
+                        let writers_gone = this.ledger.writers.load(Ordering::Acquire) == 0;
+                        let flushed = this.ledger.flushed();
+                        let segment_reader = this
+                            .segment_reader
+                            .as_ref()
+                            .expect("segment reader is only taken by pending reads");
+                        if segment_reader.position >= flushed {
+                            // Everything that has been written has been read, and
+                            // there may be no writers left to write anything else.
+                            return if writers_gone {
+                                Poll::Ready(None)
+                            } else {
+                                Poll::Pending
+                            };
+                        }
+
+                        let segment_reader = this.segment_reader.take().unwrap();
+                        this.pending_read = Some(tokio::task::spawn_blocking(move || {
+                            segment_reader.read_batch(flushed)
+                        }));
+                    }
+                    Some(mut handle) => match Pin::new(&mut handle).poll(cx) {
+                        Poll::Ready(Ok(batch)) => {
+                            this.segment_reader = Some(batch.segment_reader);
+                            if batch.skipped > 0 {
+                                // Skipped records will never be acknowledged, so
+                                // their space is freed up right away.
+                                this.ledger.release(batch.skipped);
+                            }
+                            if batch.stalled {
+                                // Retry once woken up by the next write or ack.
+                                return Poll::Pending;
+                            }
+                            // Nothing may have been read if a corrupted segment was
+                            // skipped, in which case the loop comes back around.
+                            this.buffer.extend(batch.records);
+                        }
+                        Poll::Ready(Err(error)) => {
+                            // The segment reader is lost along with the task, so
+                            // there is no way to continue reading.
+                            error!(message = "Error during read.", %error);
+                            return Poll::Ready(None);
+                        }
+                        Poll::Pending => {
+                            this.pending_read = Some(handle);
+                            return Poll::Pending;
+                        }
+                    },
+                }
+            }
+
+            let record = this.buffer.pop_front().expect("buffer is not empty");
+            let byte_size = HEADER_LEN + record.payload.len();
+            match T::decode(Bytes::from(record.payload)) {
+                Ok(event) => {
+                    this.ledger.track(record.end, byte_size);
+                    this.buffer_usage_data
+                        .increment_sent_event_count_and_byte_size(1, byte_size);
+                    return Poll::Ready(Some(event));
+                }
+                Err(error) => {
+                    error!(message = "Error deserializing event.", %error);
+                    // The event will never be acknowledged, so its space is
+                    // freed up right away.
+                    this.ledger.release(byte_size);
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for Reader<T>
+where
+    T: Send + Sync + Unpin,
+{
+    fn drop(&mut self) {
+        self.take_acked();
+        self.persist_acked();
+    }
+}
+
+impl<T> Reader<T>
+where
+    T: Send + Sync + Unpin,
+{
+    fn take_acked(&mut self) {
+        if let Some((position, count)) = self.ledger.take_acked() {
+            self.acked = position;
+            self.unpersisted_acks += count;
+        }
+    }
+
+    /// Persists the acknowledged position, and deletes the segments which
+    /// have been acknowledged completely.
+    fn persist_acked(&mut self) {
+        if self.unpersisted_acks == 0 {
+            return;
+        }
+
+        if let Err(error) = self.ledger.persist(self.acked) {
+            error!(message = "Error persisting disk buffer ledger.", %error);
+            return;
+        }
+        self.unpersisted_acks = 0;
+
+        for segment in self.persisted_segment..self.acked.segment {
+            let path = segment_path(&self.dir, segment);
+            if let Err(error) = fs::remove_file(&path) {
+                if error.kind() != io::ErrorKind::NotFound {
+                    error!(message = "Error deleting disk buffer segment.", ?path, %error);
+                }
+            }
+        }
+        self.persisted_segment = self.acked.segment;
+    }
+}
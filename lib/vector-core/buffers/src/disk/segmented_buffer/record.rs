@@ -0,0 +1,117 @@
+//! On-disk layout of the segment files.
+//!
+//! A segment file is a sequence of records, each of which is laid out as
+//!
+//! ```text
+//! |--length: u32--|--checksum: u32--|--payload: [u8; length]--|
+//! ```
+//!
+//! Both integers are little-endian, and the checksum is the CRC32 of the
+//! payload. Records are only ever appended to a segment, so a record that was
+//! torn by a crash can only ever be found at the very end of a segment.
+use crc32fast::Hasher;
+use std::{
+    convert::TryInto,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// Size of the header preceding the payload of every record.
+pub(super) const HEADER_LEN: usize = 8;
+
+/// Upper bound for the length of a single record, used to avoid allocating
+/// huge buffers when a corrupted length is read.
+const MAX_RECORD_LEN: usize = 128 * 1024 * 1024;
+
+const SEGMENT_PREFIX: &str = "buffer-data-";
+const SEGMENT_SUFFIX: &str = ".dat";
+
+/// The outcome of reading a single record.
+pub(super) enum Record {
+    /// The payload of an intact record.
+    Valid(Vec<u8>),
+    /// The record is either truncated or its checksum doesn't match.
+    Corrupted,
+    /// There are no more records to be read.
+    End,
+}
+
+pub(super) fn checksum(bytes: &[u8]) -> u32 {
+    let mut hasher = Hasher::new();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// Writes a record holding `payload`, returning its size on disk.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn write_record(writer: &mut impl Write, payload: &[u8]) -> io::Result<usize> {
+    let mut header = [0; HEADER_LEN];
+    header[..4].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+    header[4..].copy_from_slice(&checksum(payload).to_le_bytes());
+    writer.write_all(&header)?;
+    writer.write_all(payload)?;
+    Ok(HEADER_LEN + payload.len())
+}
+
+/// Reads the next record from `reader`.
+///
+/// # Errors
+///
+/// Fails if reading from `reader` fails for any other reason than the record
+/// being truncated.
+pub(super) fn read_record(reader: &mut impl Read) -> io::Result<Record> {
+    let mut header = [0; HEADER_LEN];
+    match read_full(reader, &mut header)? {
+        0 => return Ok(Record::End),
+        HEADER_LEN => {}
+        _ => return Ok(Record::Corrupted),
+    }
+
+    let length = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+    let expected = u32::from_le_bytes(header[4..].try_into().unwrap());
+
+    if length > MAX_RECORD_LEN {
+        return Ok(Record::Corrupted);
+    }
+
+    let mut payload = vec![0; length];
+    if read_full(reader, &mut payload)? != length || checksum(&payload) != expected {
+        return Ok(Record::Corrupted);
+    }
+
+    Ok(Record::Valid(payload))
+}
+
+/// Like `Read::read_exact`, but returns how much has been read when hitting
+/// the end of `reader` early.
+fn read_full(reader: &mut impl Read, mut buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while !buf.is_empty() {
+        match reader.read(buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                read += n;
+                buf = &mut buf[n..];
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(read)
+}
+
+pub(super) fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!(
+        "{}{:020}{}",
+        SEGMENT_PREFIX, segment, SEGMENT_SUFFIX
+    ))
+}
+
+/// Extracts the id of a segment from its file name.
+pub(super) fn segment_id(file_name: &str) -> Option<u64> {
+    file_name
+        .strip_prefix(SEGMENT_PREFIX)?
+        .strip_suffix(SEGMENT_SUFFIX)?
+        .parse()
+        .ok()
+}
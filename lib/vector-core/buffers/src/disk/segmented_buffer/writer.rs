@@ -0,0 +1,273 @@
+use super::{
+    ledger::{Ledger, Position},
+    record::{segment_path, write_record, HEADER_LEN},
+};
+use crate::{
+    buffer_usage_data::BufferUsageData,
+    bytes::{DecodeBytes, EncodeBytes},
+};
+use bytes::BytesMut;
+use futures::Sink;
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{atomic::Ordering, Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// Number of records after which written records are flushed.
+const MAX_UNFLUSHED_RECORDS: usize = 100;
+
+/// The active segment, which is shared by all writers.
+pub(super) struct SegmentWriter {
+    dir: PathBuf,
+    file: BufWriter<File>,
+    /// Position right after the last written record.
+    position: Position,
+    /// Records written since the last flush.
+    unflushed: usize,
+    /// Size in bytes after which a new segment is started.
+    max_segment_size: u64,
+}
+
+impl SegmentWriter {
+    pub(super) fn create(dir: PathBuf, segment: u64, max_segment_size: u64) -> io::Result<Self> {
+        let file = create_segment(&dir, segment)?;
+        Ok(Self {
+            dir,
+            file,
+            position: Position { segment, offset: 0 },
+            unflushed: 0,
+            max_segment_size,
+        })
+    }
+
+    fn write(&mut self, payload: &[u8], ledger: &Ledger) -> io::Result<()> {
+        let size = (HEADER_LEN + payload.len()) as u64;
+        if self.position.offset > 0 && self.position.offset + size > self.max_segment_size {
+            // The current segment must be complete before the reader learns
+            // about the next one, as it reads preceding segments to their end.
+            self.flush(ledger)?;
+            let segment = self.position.segment + 1;
+            self.file = create_segment(&self.dir, segment)?;
+            self.position = Position { segment, offset: 0 };
+            ledger.set_flushed(self.position);
+        }
+
+        write_record(&mut self.file, payload)?;
+        self.position.offset += size;
+        self.unflushed += 1;
+
+        if self.unflushed >= MAX_UNFLUSHED_RECORDS {
+            self.flush(ledger)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, ledger: &Ledger) -> io::Result<()> {
+        if self.unflushed > 0 {
+            self.file.flush()?;
+            // The records are synced before the reader learns about them, so
+            // that flushed events survive a crash of the machine, and the
+            // ledger never points past the durable end of a segment.
+            self.file.get_ref().sync_data()?;
+            self.unflushed = 0;
+            ledger.set_flushed(self.position);
+        }
+        Ok(())
+    }
+}
+
+fn create_segment(dir: &Path, segment: u64) -> io::Result<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(segment_path(dir, segment))?;
+    Ok(BufWriter::new(file))
+}
+
+/// The writer side of N to 1 channel through segment files.
+pub struct Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    /// Active segment.
+    /// Shared with other Writers.
+    pub(super) segment: Arc<Mutex<SegmentWriter>>,
+    /// Shared with Reader and Acker.
+    pub(super) ledger: Arc<Ledger>,
+    /// Max size of unacknowledged events in bytes.
+    pub(super) max_size: usize,
+    /// Buffer for internal use.
+    pub(super) slot: Option<T>,
+    /// Atomic structure for recording buffer metadata
+    pub(super) buffer_usage_data: Arc<BufferUsageData>,
+}
+
+impl<T> Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    pub(super) fn new(
+        segment: Arc<Mutex<SegmentWriter>>,
+        ledger: Arc<Ledger>,
+        max_size: usize,
+        buffer_usage_data: Arc<BufferUsageData>,
+    ) -> Self {
+        ledger.writers.fetch_add(1, Ordering::AcqRel);
+        Self {
+            segment,
+            ledger,
+            max_size,
+            slot: None,
+            buffer_usage_data,
+        }
+    }
+
+    /// Writes `event` unless the buffer is full, in which case it is handed
+    /// back.
+    fn try_send(&mut self, event: T) -> io::Result<Option<T>> {
+        let mut buffer: BytesMut = BytesMut::with_capacity(64);
+        T::encode(event, &mut buffer).unwrap();
+        let event_size = HEADER_LEN + buffer.len();
+
+        // An event larger than the whole buffer is let through once the buffer
+        // has been drained, as it would block forever otherwise.
+        let current_size = &self.ledger.current_size;
+        let previous_size = current_size.fetch_add(event_size, Ordering::Relaxed);
+        if previous_size > 0 && previous_size + event_size > self.max_size {
+            current_size.fetch_sub(event_size, Ordering::Relaxed);
+            self.flush()?;
+
+            return Ok(Some(T::decode(buffer).unwrap()));
+        }
+
+        if let Err(error) = self.segment.lock().unwrap().write(&buffer, &self.ledger) {
+            current_size.fetch_sub(event_size, Ordering::Relaxed);
+            return Err(error);
+        }
+        self.buffer_usage_data
+            .increment_received_event_count_and_byte_size(1, event_size);
+
+        Ok(None)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.segment.lock().unwrap().flush(&self.ledger)
+    }
+}
+
+impl<T> Clone for Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    fn clone(&self) -> Self {
+        Self::new(
+            Arc::clone(&self.segment),
+            Arc::clone(&self.ledger),
+            self.max_size,
+            Arc::clone(&self.buffer_usage_data),
+        )
+    }
+}
+
+impl<T> Sink<T> for Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.slot.is_none() {
+            Poll::Ready(Ok(()))
+        } else {
+            // Assumes that flush will only succeed if it has also emptied the
+            // slot, hence we don't need to recheck if the slot is empty.
+            self.poll_flush(cx)
+        }
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        match self.try_send(item) {
+            Ok(Some(event)) => {
+                debug_assert!(self.slot.is_none());
+                self.slot = Some(event);
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(error) => {
+                error!(message = "Error writing to disk buffer.", %error);
+                Err(())
+            }
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(event) = self.slot.take() {
+            // Register before retrying, so that space freed up in between
+            // isn't missed.
+            self.ledger.block_writer(cx.waker().clone());
+            match self.try_send(event) {
+                Ok(Some(event)) => {
+                    self.slot = Some(event);
+                    return Poll::Pending;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    error!(message = "Error writing to disk buffer.", %error);
+                    return Poll::Ready(Err(()));
+                }
+            }
+        }
+
+        if let Err(error) = self.flush() {
+            error!(message = "Error flushing disk buffer.", %error);
+            return Poll::Ready(Err(()));
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<T> Drop for Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    fn drop(&mut self) {
+        if let Some(event) = self.slot.take() {
+            // This can happen if poll_close wasn't called which is a bug
+            // or we are unwinding the stack.
+            //
+            // We can't be picky at the moment so we will allow
+            // for the buffer to exceed configured limit.
+            self.max_size = usize::MAX;
+            if let Err(error) = self.try_send(event) {
+                error!(message = "Error writing to disk buffer.", %error);
+            }
+        }
+
+        if let Err(error) = self.flush() {
+            error!(message = "Error flushing disk buffer.", %error);
+        }
+
+        // We need to wake up the reader so it can return None if there are no
+        // more writers
+        self.ledger.writers.fetch_sub(1, Ordering::AcqRel);
+        self.ledger.reader_waker.wake();
+    }
+}
@@ -14,9 +14,10 @@ extern crate tracing;
 mod acker;
 mod buffer_usage_data;
 pub mod bytes;
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 pub mod disk;
 mod internal_events;
+mod overflow;
 #[cfg(test)]
 mod test;
 mod variant;
@@ -27,6 +28,7 @@ pub use acker::{Ackable, Acker};
 use core_common::byte_size_of::ByteSizeOf;
 use futures::StreamExt;
 use futures::{channel::mpsc, Sink, SinkExt, Stream};
+pub use overflow::{OverflowAcker, OverflowBufferInput, OverflowReader};
 use pin_project::pin_project;
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen};
@@ -78,6 +80,22 @@ where
             let tx = BufferInputCloner::Disk(tx, when_full, buffer_usage_data);
             Ok((tx, rx, acker))
         }
+        #[cfg(feature = "disk-buffer-v2")]
+        Variant::DiskV2 {
+            max_size,
+            when_full,
+            data_dir,
+            id,
+            ..
+        } => {
+            let buffer_dir = format!("{}_buffer_v2", id);
+            let buffer_usage_data = BufferUsageData::new(when_full, span, Some(max_size), None);
+            let (tx, rx, acker) =
+                disk::open_segmented(&data_dir, &buffer_dir, max_size, buffer_usage_data.clone())
+                    .map_err(|error| error.to_string())?;
+            let tx = BufferInputCloner::Disk(tx, when_full, buffer_usage_data);
+            Ok((tx, rx, acker))
+        }
         Variant::Memory {
             max_events,
             when_full,
@@ -100,6 +118,13 @@ where
                 Ok((tx, Box::new(rx), Acker::Null))
             }
         }
+        Variant::Overflow { base, overflow } => {
+            let (base_tx, base_rx, base_acker) = build(*base, span.clone())?;
+            let (overflow_tx, overflow_rx, overflow_acker) = build(*overflow, span)?;
+            let tx = BufferInputCloner::Overflow(Box::new(base_tx), Box::new(overflow_tx));
+            let (rx, acker) = overflow::combine(base_rx, overflow_rx, base_acker, overflow_acker);
+            Ok((tx, Box::new(rx), acker))
+        }
     }
 }
 
@@ -108,6 +133,8 @@ where
 pub enum WhenFull {
     Block,
     DropNewest,
+    /// Writes events to the next buffer in the chain, see `Variant::Overflow`.
+    Overflow,
 }
 
 impl Default for WhenFull {
@@ -139,8 +166,9 @@ where
     <T as DecodeBytes<T>>::Error: Debug,
 {
     Memory(mpsc::Sender<T>, WhenFull, Option<Arc<BufferUsageData>>),
-    #[cfg(feature = "disk-buffer")]
+    #[cfg(feature = "disk-buffer-v2")]
     Disk(disk::Writer<T>, WhenFull, Arc<BufferUsageData>),
+    Overflow(Box<BufferInputCloner<T>>, Box<BufferInputCloner<T>>),
 }
 
 impl<'a, T> BufferInputCloner<T>
@@ -164,7 +192,7 @@ where
                 ))
             }
 
-            #[cfg(feature = "disk-buffer")]
+            #[cfg(feature = "disk-buffer-v2")]
             BufferInputCloner::Disk(writer, when_full, buffer_usage_data) => {
                 let inner: disk::Writer<T> = (*writer).clone();
                 if when_full == &WhenFull::DropNewest {
@@ -173,6 +201,10 @@ where
                     Box::new(inner)
                 }
             }
+
+            BufferInputCloner::Overflow(base, overflow) => {
+                Box::new(OverflowBufferInput::new(base.get(), overflow.get()))
+            }
         }
     }
 }
@@ -192,7 +224,7 @@ impl<S> MemoryBufferInput<S> {
         buffer_usage_data: Option<Arc<BufferUsageData>>,
     ) -> Self {
         let drop = match when_full {
-            WhenFull::Block => None,
+            WhenFull::Block | WhenFull::Overflow => None,
             WhenFull::DropNewest => Some(false),
         };
 
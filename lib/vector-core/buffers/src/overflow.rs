@@ -0,0 +1,229 @@
+//! Chaining of buffers, where events spill over from one buffer into the next
+//! once it is full.
+//!
+//! This allows a small and fast in-memory buffer to take the regular load,
+//! while a disk buffer only comes into play during sink outages or load
+//! spikes.
+use crate::Acker;
+use futures::stream::Fuse;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// One of the two buffers of an overflow pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Base,
+    Overflow,
+}
+
+/// Writes events to the base buffer, or to the overflow buffer while the base
+/// one is full.
+pub struct OverflowBufferInput<'a, T> {
+    base: Box<dyn Sink<T, Error = ()> + 'a + Send + Unpin>,
+    overflow: Box<dyn Sink<T, Error = ()> + 'a + Send + Unpin>,
+    /// The buffer found ready by the last call to `poll_ready`.
+    ready: Stage,
+}
+
+impl<'a, T> OverflowBufferInput<'a, T> {
+    #[must_use]
+    pub fn new(
+        base: Box<dyn Sink<T, Error = ()> + 'a + Send + Unpin>,
+        overflow: Box<dyn Sink<T, Error = ()> + 'a + Send + Unpin>,
+    ) -> Self {
+        Self {
+            base,
+            overflow,
+            ready: Stage::Base,
+        }
+    }
+}
+
+impl<T> Sink<T> for OverflowBufferInput<'_, T> {
+    type Error = ();
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.base.poll_ready_unpin(cx) {
+            Poll::Ready(Ok(())) => {
+                self.ready = Stage::Base;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(())) => Poll::Ready(Err(())),
+            Poll::Pending => {
+                // The base buffer will wake us up once it has room again, so
+                // it is fine to also wait on the overflow buffer.
+                let ready = self.overflow.poll_ready_unpin(cx);
+                if let Poll::Ready(Ok(())) = ready {
+                    self.ready = Stage::Overflow;
+                }
+                ready
+            }
+        }
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        match self.ready {
+            Stage::Base => self.base.start_send_unpin(item),
+            Stage::Overflow => {
+                trace!(message = "Buffer is full; writing event to overflow buffer.");
+                self.ready = Stage::Base;
+                self.overflow.start_send_unpin(item)
+            }
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Flushing a full buffer only completes once it has room again, which
+        // is exactly what the overflow buffer takes care of, so only the
+        // latter is waited on.
+        if let Poll::Ready(Err(())) = self.base.poll_flush_unpin(cx) {
+            return Poll::Ready(Err(()));
+        }
+        self.overflow.poll_flush_unpin(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let base = self.base.poll_close_unpin(cx)?;
+        let overflow = self.overflow.poll_close_unpin(cx)?;
+        if base.is_ready() && overflow.is_ready() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Forwards acknowledgements to the buffers the acknowledged events have been
+/// read from.
+#[derive(Debug)]
+pub struct OverflowAcker {
+    base: Acker,
+    overflow: Acker,
+    /// The buffers that the events handed out but not acknowledged yet have
+    /// been read from, oldest first. Consecutive events read from the same
+    /// buffer share a single entry.
+    unacked: Mutex<VecDeque<(Stage, usize)>>,
+}
+
+impl OverflowAcker {
+    fn read(&self, stage: Stage) {
+        let mut unacked = self.unacked.lock().unwrap();
+        match unacked.back_mut() {
+            Some((last, count)) if *last == stage => *count += 1,
+            _ => unacked.push_back((stage, 1)),
+        }
+    }
+
+    pub(crate) fn acknowledge(&self, mut num: usize) {
+        let mut base = 0;
+        let mut overflow = 0;
+        {
+            let mut unacked = self.unacked.lock().unwrap();
+            while num > 0 {
+                let (stage, count) = unacked
+                    .front_mut()
+                    .expect("Tried to ack beyond read offset");
+                let acked = num.min(*count);
+                match stage {
+                    Stage::Base => base += acked,
+                    Stage::Overflow => overflow += acked,
+                }
+                *count -= acked;
+                num -= acked;
+                if *count == 0 {
+                    unacked.pop_front();
+                }
+            }
+        }
+
+        if base > 0 {
+            self.base.acknowledge(base);
+        }
+        if overflow > 0 {
+            self.overflow.acknowledge(overflow);
+        }
+    }
+}
+
+/// Reads events from both buffers of an overflow pair, taking turns between
+/// them so that neither is starved.
+///
+/// Both readers are kept around until this one is dropped, as disk buffers
+/// still need their reader to persist acknowledgements made after the last
+/// event has been read.
+pub struct OverflowReader<'a, T> {
+    base: Fuse<Box<dyn Stream<Item = T> + 'a + Unpin + Send>>,
+    overflow: Fuse<Box<dyn Stream<Item = T> + 'a + Unpin + Send>>,
+    /// The buffer to be polled first next time.
+    next: Stage,
+    /// Only present if either buffer needs to be acknowledged.
+    acker: Option<Arc<OverflowAcker>>,
+}
+
+impl<T> OverflowReader<'_, T> {
+    fn poll_stage(&mut self, stage: Stage, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let stream = match stage {
+            Stage::Base => &mut self.base,
+            Stage::Overflow => &mut self.overflow,
+        };
+        let item = stream.poll_next_unpin(cx);
+        if let (Poll::Ready(Some(_)), Some(acker)) = (&item, &self.acker) {
+            acker.read(stage);
+        }
+        item
+    }
+}
+
+impl<T> Stream for OverflowReader<'_, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (first, second) = match self.next {
+            Stage::Base => (Stage::Base, Stage::Overflow),
+            Stage::Overflow => (Stage::Overflow, Stage::Base),
+        };
+        self.next = second;
+
+        let first = self.poll_stage(first, cx);
+        if let Poll::Ready(Some(item)) = first {
+            return Poll::Ready(Some(item));
+        }
+        match self.poll_stage(second, cx) {
+            Poll::Ready(None) if first.is_pending() => Poll::Pending,
+            poll => poll,
+        }
+    }
+}
+
+/// Combines the outputs of the base and overflow buffers into a single reader
+/// and acker.
+pub(crate) fn combine<'a, T>(
+    base: Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+    overflow: Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+    base_acker: Acker,
+    overflow_acker: Acker,
+) -> (OverflowReader<'a, T>, Acker) {
+    let acker = if matches!((&base_acker, &overflow_acker), (Acker::Null, Acker::Null)) {
+        // Memory buffers don't need to be acknowledged, so there is nothing
+        // to keep track of.
+        None
+    } else {
+        Some(Arc::new(OverflowAcker {
+            base: base_acker,
+            overflow: overflow_acker,
+            unacked: Mutex::new(VecDeque::new()),
+        }))
+    };
+
+    let reader = OverflowReader {
+        base: base.fuse(),
+        overflow: overflow.fuse(),
+        next: Stage::Base,
+        acker: acker.clone(),
+    };
+    let acker = acker.map_or(Acker::Null, Acker::Overflow);
+    (reader, acker)
+}
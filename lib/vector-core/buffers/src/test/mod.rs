@@ -1,5 +1,8 @@
 mod common;
 mod model;
+mod overflow;
+#[cfg(feature = "disk-buffer-v2")]
+mod segmented_buffer;
 
 use crate::buffer_usage_data::BufferUsageData;
 use crate::{Acker, DropWhenFull, WhenFull};
@@ -27,6 +27,9 @@ impl InMemory {
             },
             #[cfg(feature = "disk-buffer")]
             Variant::Disk { .. } => unreachable!(),
+            #[cfg(feature = "disk-buffer-v2")]
+            Variant::DiskV2 { .. } => unreachable!(),
+            Variant::Overflow { .. } => unreachable!(),
         }
     }
 }
@@ -43,7 +46,7 @@ impl Model for InMemory {
                 }
                 Progress::Advanced
             }
-            WhenFull::Block => {
+            WhenFull::Block | WhenFull::Overflow => {
                 if self.inner.len() >= (self.capacity + self.num_senders) {
                     Progress::Blocked(item)
                 } else {
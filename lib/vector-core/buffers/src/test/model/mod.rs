@@ -1,10 +1,10 @@
 mod in_memory;
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 mod on_disk;
 
 use crate::test::common::{Action, Message};
 use crate::test::model::in_memory::InMemory;
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 use crate::test::model::on_disk::OnDisk;
 use crate::Variant;
 use futures::task::{noop_waker, Context, Poll};
@@ -38,18 +38,25 @@ fn check(variant: &Variant) -> bool {
             // nothing to check
             true
         }
+        // chained buffers are not modeled
+        Variant::Overflow { .. } => false,
         #[cfg(feature = "disk-buffer")]
-        Variant::Disk { id, data_dir, .. } => {
-            // determine if data_dir is in temp_dir/id
-            let mut prefix = std::path::PathBuf::new();
-            prefix.push(std::env::temp_dir());
-            prefix.push(id);
-
-            data_dir.starts_with(prefix)
-        }
+        Variant::Disk { id, data_dir, .. } => in_temp_dir(id, data_dir),
+        #[cfg(feature = "disk-buffer-v2")]
+        Variant::DiskV2 { id, data_dir, .. } => in_temp_dir(id, data_dir),
     }
 }
 
+/// Determines if `data_dir` is in `temp_dir/id`.
+#[cfg(feature = "disk-buffer-v2")]
+fn in_temp_dir(id: &str, data_dir: &std::path::Path) -> bool {
+    let mut prefix = std::path::PathBuf::new();
+    prefix.push(std::env::temp_dir());
+    prefix.push(id);
+
+    data_dir.starts_with(prefix)
+}
+
 /// `VariantGuard` wraps a `Variant`, allowing a convenient Drop implementation
 struct VariantGuard {
     inner: Variant,
@@ -58,7 +65,7 @@ struct VariantGuard {
 impl VariantGuard {
     fn new(variant: Variant) -> Self {
         match variant {
-            Variant::Memory { .. } => VariantGuard { inner: variant },
+            Variant::Memory { .. } | Variant::Overflow { .. } => VariantGuard { inner: variant },
             #[cfg(feature = "disk-buffer")]
             Variant::Disk {
                 max_size,
@@ -81,6 +88,26 @@ impl VariantGuard {
                     },
                 }
             }
+            #[cfg(feature = "disk-buffer-v2")]
+            Variant::DiskV2 {
+                max_size,
+                when_full,
+                id,
+                ..
+            } => {
+                // SAFETY: See the note for `Variant::Disk` above.
+                let data_dir = tempdir::TempDir::new_in(std::env::temp_dir(), &id)
+                    .unwrap()
+                    .into_path();
+                VariantGuard {
+                    inner: Variant::DiskV2 {
+                        max_size,
+                        when_full,
+                        data_dir,
+                        id,
+                    },
+                }
+            }
         }
     }
 }
@@ -94,13 +121,18 @@ impl AsRef<Variant> for VariantGuard {
 impl Drop for VariantGuard {
     fn drop(&mut self) {
         match &self.inner {
-            Variant::Memory { .. } => { /* nothing to clean up */ }
+            Variant::Memory { .. } | Variant::Overflow { .. } => { /* nothing to clean up */ }
             #[cfg(feature = "disk-buffer")]
             Variant::Disk { data_dir, .. } => {
                 // SAFETY: Here we clean up the data_dir of the inner `Variant`,
                 // see note in the constructor for this type.
                 std::fs::remove_dir_all(data_dir).unwrap();
             }
+            #[cfg(feature = "disk-buffer-v2")]
+            Variant::DiskV2 { data_dir, .. } => {
+                // SAFETY: See the note for `Variant::Disk` above.
+                std::fs::remove_dir_all(data_dir).unwrap();
+            }
         }
     }
 }
@@ -123,8 +155,11 @@ fn model_check() {
         let guard = VariantGuard::new(variant);
         let mut model: Box<dyn Model> = match guard.as_ref() {
             Variant::Memory { .. } => Box::new(InMemory::new(guard.as_ref(), 1)),
+            Variant::Overflow { .. } => unreachable!(),
             #[cfg(feature = "disk-buffer")]
             Variant::Disk { .. } => Box::new(OnDisk::new(guard.as_ref())),
+            #[cfg(feature = "disk-buffer-v2")]
+            Variant::DiskV2 { .. } => Box::new(OnDisk::new(guard.as_ref())),
         };
 
         let rcv_waker = noop_waker();
@@ -5,7 +5,7 @@ use std::collections::VecDeque;
 use super::Progress;
 
 /// `OnDisk` is the `Model` for on-disk buffer
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 pub(crate) struct OnDisk {
     inner: VecDeque<Message>,
     when_full: WhenFull,
@@ -13,27 +13,36 @@ pub(crate) struct OnDisk {
     capacity: usize,
 }
 
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 impl OnDisk {
     pub(crate) fn new(variant: &Variant) -> Self {
         match variant {
-            Variant::Memory { .. } => unreachable!(),
+            Variant::Memory { .. } | Variant::Overflow { .. } => unreachable!(),
             #[cfg(feature = "disk-buffer")]
             Variant::Disk {
                 max_size,
                 when_full,
                 ..
-            } => OnDisk {
-                inner: VecDeque::with_capacity(*max_size),
-                current_bytes: 0,
-                capacity: *max_size,
-                when_full: *when_full,
-            },
+            } => OnDisk::with_capacity(*max_size, *when_full),
+            Variant::DiskV2 {
+                max_size,
+                when_full,
+                ..
+            } => OnDisk::with_capacity(*max_size, *when_full),
+        }
+    }
+
+    fn with_capacity(capacity: usize, when_full: WhenFull) -> Self {
+        OnDisk {
+            inner: VecDeque::with_capacity(capacity),
+            current_bytes: 0,
+            capacity,
+            when_full,
         }
     }
 }
 
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 impl Model for OnDisk {
     fn send(&mut self, item: Message) -> Progress {
         let byte_size = EncodeBytes::encoded_size(&item).unwrap();
@@ -48,7 +57,7 @@ impl Model for OnDisk {
                 }
                 Progress::Advanced
             }
-            WhenFull::Block => {
+            WhenFull::Block | WhenFull::Overflow => {
                 if self.is_full() {
                     Progress::Blocked(item)
                 } else {
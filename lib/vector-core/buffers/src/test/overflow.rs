@@ -0,0 +1,90 @@
+use crate::test::common::Message;
+use crate::{build, Variant, WhenFull};
+use futures::{FutureExt, SinkExt, StreamExt};
+use tracing::Span;
+
+fn memory(max_events: usize, when_full: WhenFull) -> Variant {
+    Variant::Memory {
+        max_events,
+        when_full,
+        instrument: false,
+    }
+}
+
+#[tokio::test]
+async fn spills_into_overflow_when_full() {
+    let variant = Variant::Overflow {
+        base: Box::new(memory(2, WhenFull::Overflow)),
+        overflow: Box::new(memory(100, WhenFull::Block)),
+    };
+    let (tx, rx, _acker) = build::<Message>(variant, Span::none()).unwrap();
+
+    let mut sink = tx.get();
+    drop(tx);
+    for id in 0..20 {
+        sink.send(Message::new(id))
+            .now_or_never()
+            .expect("overflow buffer should have room")
+            .unwrap();
+    }
+    drop(sink);
+
+    let mut received = rx.collect::<Vec<_>>().await;
+    received.sort_by_key(Message::id);
+    assert_eq!(received, (0..20).map(Message::new).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn blocks_when_overflow_is_full() {
+    let variant = Variant::Overflow {
+        base: Box::new(memory(2, WhenFull::Overflow)),
+        overflow: Box::new(memory(2, WhenFull::Block)),
+    };
+    let (tx, _rx, _acker) = build::<Message>(variant, Span::none()).unwrap();
+
+    let mut sink = tx.get();
+    let mut sent = 0;
+    while sink.send(Message::new(sent)).now_or_never().is_some() {
+        sent += 1;
+        assert!(sent < 100, "buffer never blocked");
+    }
+    // Both channels have room for one more event per sender.
+    assert!(sent >= 4);
+}
+
+#[cfg(feature = "disk-buffer-v2")]
+#[tokio::test]
+async fn acks_are_forwarded_to_overflow() {
+    let dir = tempdir::TempDir::new("overflow").unwrap();
+    let variant = || Variant::Overflow {
+        base: Box::new(memory(2, WhenFull::Overflow)),
+        overflow: Box::new(Variant::DiskV2 {
+            max_size: 1024 * 1024,
+            when_full: WhenFull::Block,
+            data_dir: dir.path().to_path_buf(),
+            id: "overflow".into(),
+        }),
+    };
+
+    {
+        let (tx, mut rx, acker) = build::<Message>(variant(), Span::none()).unwrap();
+        let mut sink = tx.get();
+        drop(tx);
+        for id in 0..10 {
+            sink.send(Message::new(id)).await.unwrap();
+        }
+        drop(sink);
+
+        let mut received = 0;
+        while rx.next().await.is_some() {
+            received += 1;
+        }
+        assert_eq!(received, 10);
+        acker.ack(10);
+    }
+
+    // Everything has been acknowledged, so nothing is replayed from disk.
+    let (tx, rx, _acker) = build::<Message>(variant(), Span::none()).unwrap();
+    drop(tx);
+    assert_eq!(rx.collect::<Vec<_>>().await, Vec::new());
+}
@@ -0,0 +1,156 @@
+use crate::buffer_usage_data::BufferUsageData;
+use crate::disk::{open_segmented, Writer};
+use crate::test::common::Message;
+use crate::{Acker, WhenFull};
+use futures::{SinkExt, Stream, StreamExt};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempdir::TempDir;
+use tokio_test::{assert_pending, assert_ready, task::spawn};
+use tracing::Span;
+
+type Reader = Box<dyn Stream<Item = Message> + Unpin + Send>;
+
+fn open(data_dir: &Path, max_size: usize) -> (Writer<Message>, Reader, Acker) {
+    let buffer_usage_data = BufferUsageData::new(WhenFull::Block, Span::none(), None, None);
+    open_segmented(data_dir, "buffer", max_size, buffer_usage_data).unwrap()
+}
+
+async fn send_all(writer: &mut Writer<Message>, ids: std::ops::Range<u64>) {
+    for id in ids {
+        writer.send(Message::new(id)).await.unwrap();
+    }
+}
+
+fn segments(data_dir: &Path) -> Vec<PathBuf> {
+    let mut segments = fs::read_dir(data_dir.join("buffer"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("dat".as_ref()))
+        .collect::<Vec<_>>();
+    segments.sort();
+    segments
+}
+
+#[tokio::test]
+async fn roundtrip() {
+    let dir = TempDir::new("segmented_buffer").unwrap();
+    let (mut writer, reader, _acker) = open(dir.path(), 1024 * 1024);
+
+    send_all(&mut writer, 0..100).await;
+    drop(writer);
+
+    let received = reader.collect::<Vec<_>>().await;
+    assert_eq!(received, (0..100).map(Message::new).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn acked_events_are_not_replayed() {
+    let dir = TempDir::new("segmented_buffer").unwrap();
+    {
+        let (mut writer, mut reader, acker) = open(dir.path(), 1024 * 1024);
+        send_all(&mut writer, 0..10).await;
+        for id in 0..5 {
+            assert_eq!(reader.next().await, Some(Message::new(id)));
+        }
+        acker.ack(5);
+    }
+
+    let (writer, reader, _acker) = open(dir.path(), 1024 * 1024);
+    drop(writer);
+    let received = reader.collect::<Vec<_>>().await;
+    assert_eq!(received, (5..10).map(Message::new).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn acked_segments_are_deleted() {
+    let dir = TempDir::new("segmented_buffer").unwrap();
+    {
+        let (mut writer, mut reader, acker) = open(dir.path(), 1024 * 1024);
+        send_all(&mut writer, 0..10).await;
+        drop(writer);
+        while reader.next().await.is_some() {}
+        acker.ack(10);
+    }
+    assert_eq!(segments(dir.path()).len(), 1);
+
+    // Reopening leaves a new segment behind, while the acknowledged one is
+    // removed.
+    drop(open(dir.path(), 1024 * 1024));
+    let remaining = segments(dir.path());
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining[0].ends_with("buffer-data-00000000000000000001.dat"));
+}
+
+#[tokio::test]
+async fn torn_record_is_truncated() {
+    let dir = TempDir::new("segmented_buffer").unwrap();
+    {
+        let (mut writer, _reader, _acker) = open(dir.path(), 1024 * 1024);
+        send_all(&mut writer, 0..3).await;
+    }
+
+    // Simulate a crash in the middle of writing a record.
+    let segment = segments(dir.path()).pop().unwrap();
+    let len = fs::metadata(&segment).unwrap().len();
+    OpenOptions::new()
+        .append(true)
+        .open(&segment)
+        .unwrap()
+        .write_all(&[16, 0, 0, 0, 1, 2])
+        .unwrap();
+
+    let (mut writer, reader, _acker) = open(dir.path(), 1024 * 1024);
+    assert_eq!(fs::metadata(&segment).unwrap().len(), len);
+
+    send_all(&mut writer, 3..5).await;
+    drop(writer);
+    let received = reader.collect::<Vec<_>>().await;
+    assert_eq!(received, (0..5).map(Message::new).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn corrupted_record_is_dropped() {
+    let dir = TempDir::new("segmented_buffer").unwrap();
+    {
+        let (mut writer, _reader, _acker) = open(dir.path(), 1024 * 1024);
+        send_all(&mut writer, 0..3).await;
+    }
+
+    // Flip a bit in the payload of the last record.
+    let segment = segments(dir.path()).pop().unwrap();
+    let mut contents = fs::read(&segment).unwrap();
+    *contents.last_mut().unwrap() ^= 1;
+    fs::write(&segment, contents).unwrap();
+
+    let (writer, reader, _acker) = open(dir.path(), 1024 * 1024);
+    drop(writer);
+    let received = reader.collect::<Vec<_>>().await;
+    assert_eq!(received, (0..2).map(Message::new).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn blocks_when_full() {
+    let dir = TempDir::new("segmented_buffer").unwrap();
+    // Every record takes up 16 bytes on disk, so two of them fit.
+    let (mut writer, mut reader, acker) = open(dir.path(), 32);
+    send_all(&mut writer, 0..2).await;
+
+    let mut blocked = writer.clone();
+    let mut send = spawn(blocked.send(Message::new(2)));
+    assert_pending!(send.poll());
+
+    assert_eq!(reader.next().await, Some(Message::new(0)));
+    assert_pending!(send.poll());
+
+    acker.ack(1);
+    assert!(send.is_woken());
+    assert_ready!(send.poll()).unwrap();
+    drop(send);
+    drop(blocked);
+    drop(writer);
+
+    let received = reader.collect::<Vec<_>>().await;
+    assert_eq!(received, (1..3).map(Message::new).collect::<Vec<_>>());
+}
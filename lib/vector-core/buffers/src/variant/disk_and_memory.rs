@@ -21,12 +21,25 @@ pub enum Variant {
         when_full: WhenFull,
         instrument: bool,
     },
+    #[cfg(feature = "disk-buffer")]
     Disk {
         max_size: usize,
         when_full: WhenFull,
         data_dir: PathBuf,
         id: String,
     },
+    DiskV2 {
+        max_size: usize,
+        when_full: WhenFull,
+        data_dir: PathBuf,
+        id: String,
+    },
+    /// Chains two buffers, where events are written to `overflow` while
+    /// `base`, whose `when_full` is `WhenFull::Overflow`, is full.
+    Overflow {
+        base: Box<Variant>,
+        overflow: Box<Variant>,
+    },
 }
 
 #[cfg(test)]
@@ -51,19 +64,25 @@ impl Arbitrary for Id {
 #[cfg(test)]
 impl Arbitrary for Variant {
     fn arbitrary(g: &mut Gen) -> Self {
-        if bool::arbitrary(g) {
-            Variant::Memory {
+        match u8::arbitrary(g) % 3 {
+            0 => Variant::Memory {
                 max_events: u16::arbitrary(g) as usize, // u16 avoids allocation failures
                 when_full: WhenFull::arbitrary(g),
                 instrument: false,
-            }
-        } else {
-            Variant::Disk {
+            },
+            #[cfg(feature = "disk-buffer")]
+            1 => Variant::Disk {
                 max_size: u16::arbitrary(g) as usize, // u16 avoids allocation failures
                 when_full: WhenFull::arbitrary(g),
                 id: Id::arbitrary(g).inner,
                 data_dir: PathBuf::arbitrary(g),
-            }
+            },
+            _ => Variant::DiskV2 {
+                max_size: u16::arbitrary(g) as usize, // u16 avoids allocation failures
+                when_full: WhenFull::arbitrary(g),
+                id: Id::arbitrary(g).inner,
+                data_dir: PathBuf::arbitrary(g),
+            },
         }
     }
 
@@ -83,6 +102,7 @@ impl Arbitrary for Variant {
                     instrument,
                 }))
             }
+            #[cfg(feature = "disk-buffer")]
             Variant::Disk {
                 max_size,
                 when_full,
@@ -101,6 +121,25 @@ impl Arbitrary for Variant {
                     data_dir: data_dir.clone(),
                 }))
             }
+            Variant::DiskV2 {
+                max_size,
+                when_full,
+                id,
+                data_dir,
+                ..
+            } => {
+                let max_size = *max_size;
+                let when_full = *when_full;
+                let id = id.clone();
+                let data_dir = data_dir.clone();
+                Box::new(max_size.shrink().map(move |ms| Variant::DiskV2 {
+                    max_size: ms,
+                    when_full,
+                    id: id.clone(),
+                    data_dir: data_dir.clone(),
+                }))
+            }
+            Variant::Overflow { .. } => Box::new(std::iter::empty()),
         }
     }
 }
@@ -9,6 +9,12 @@ pub enum Variant {
         when_full: WhenFull,
         instrument: bool,
     },
+    /// Chains two buffers, where events are written to `overflow` while
+    /// `base`, whose `when_full` is `WhenFull::Overflow`, is full.
+    Overflow {
+        base: Box<Variant>,
+        overflow: Box<Variant>,
+    },
 }
 
 #[cfg(test)]
@@ -32,6 +38,7 @@ impl Arbitrary for Variant {
                 when_full,
                 instrument,
             })),
+            Variant::Overflow { .. } => Box::new(std::iter::empty()),
         }
     }
 }
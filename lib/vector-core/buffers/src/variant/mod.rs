@@ -1,12 +1,12 @@
 // NOTE unfortunately because we can't edit out a lifetime based on a feature
 // flag we need two copies of `Variant` else the liftime being unused when
-// 'disk-buffers' is flagged off will ding the build.
-#[cfg(not(feature = "disk-buffer"))]
+// 'disk-buffer-v2' is flagged off will ding the build.
+#[cfg(not(feature = "disk-buffer-v2"))]
 mod memory_only;
-#[cfg(not(feature = "disk-buffer"))]
+#[cfg(not(feature = "disk-buffer-v2"))]
 pub use memory_only::*;
 
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 mod disk_and_memory;
-#[cfg(feature = "disk-buffer")]
+#[cfg(feature = "disk-buffer-v2")]
 pub use disk_and_memory::*;
@@ -214,6 +214,18 @@ impl BatchNotifier {
         (Arc::new(notifier), BatchStatusReceiver(receiver))
     }
 
+    /// Optionally call `new_with_receiver`, for sources acknowledging only when enabled.
+    pub fn maybe_new_with_receiver(
+        enabled: bool,
+    ) -> (Option<Arc<Self>>, Option<BatchStatusReceiver>) {
+        if enabled {
+            let (batch, receiver) = Self::new_with_receiver();
+            (Some(batch), Some(receiver))
+        } else {
+            (None, None)
+        }
+    }
+
     /// Update this notifier's status from the status of a finalized event.
     #[allow(clippy::missing_panics_doc)] // Panic is unreachable
     fn update_status(&self, status: EventStatus) {
@@ -1,4 +1,4 @@
-use crate::event::{Event, LogEvent, Metric};
+use crate::event::{Event, LogEvent, Metric, TraceEvent};
 use mlua::prelude::*;
 
 impl<'a> ToLua<'a> for Event {
@@ -8,6 +8,7 @@ impl<'a> ToLua<'a> for Event {
         match self {
             Event::Log(log) => table.raw_set("log", log.to_lua(lua)?)?,
             Event::Metric(metric) => table.raw_set("metric", metric.to_lua(lua)?)?,
+            Event::Trace(trace) => table.raw_set("trace", LogEvent::from(trace).to_lua(lua)?)?,
         }
         Ok(LuaValue::Table(table))
     }
@@ -25,19 +26,25 @@ impl<'a> FromLua<'a> for Event {
                 })
             }
         };
-        match (table.raw_get("log")?, table.raw_get("metric")?) {
-            (LuaValue::Table(log), LuaValue::Nil) => {
+        match (
+            table.raw_get("log")?,
+            table.raw_get("metric")?,
+            table.raw_get("trace")?,
+        ) {
+            (LuaValue::Table(log), LuaValue::Nil, LuaValue::Nil) => {
                 Ok(Event::Log(LogEvent::from_lua(LuaValue::Table(log), lua)?))
             }
-            (LuaValue::Nil, LuaValue::Table(metric)) => Ok(Event::Metric(Metric::from_lua(
-                LuaValue::Table(metric),
-                lua,
-            )?)),
+            (LuaValue::Nil, LuaValue::Table(metric), LuaValue::Nil) => Ok(Event::Metric(
+                Metric::from_lua(LuaValue::Table(metric), lua)?,
+            )),
+            (LuaValue::Nil, LuaValue::Nil, LuaValue::Table(trace)) => Ok(Event::Trace(
+                TraceEvent::from(LogEvent::from_lua(LuaValue::Table(trace), lua)?),
+            )),
             _ => Err(LuaError::FromLuaConversionError {
                 from: value.type_name(),
                 to: "Event",
                 message: Some(
-                    "Event should contain either \"log\" or \"metric\" key at the top level"
+                    "Event should contain exactly one of \"log\", \"metric\" or \"trace\" keys at the top level"
                         .to_string(),
                 ),
             }),
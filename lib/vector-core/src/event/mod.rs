@@ -16,6 +16,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
 use std::sync::Arc;
+pub use trace::TraceEvent;
 pub use util::log::PathComponent;
 pub use util::log::PathIter;
 pub use value::Value;
@@ -35,6 +36,7 @@ pub mod metric;
 pub mod proto;
 #[cfg(test)]
 mod test;
+pub mod trace;
 pub mod util;
 mod value;
 #[cfg(feature = "vrl")]
@@ -46,6 +48,7 @@ pub const PARTIAL: &str = "_partial";
 pub enum Event {
     Log(LogEvent),
     Metric(Metric),
+    Trace(TraceEvent),
 }
 
 impl ByteSizeOf for Event {
@@ -53,6 +56,7 @@ impl ByteSizeOf for Event {
         match self {
             Event::Log(log_event) => log_event.allocated_bytes(),
             Event::Metric(metric_event) => metric_event.allocated_bytes(),
+            Event::Trace(trace_event) => trace_event.allocated_bytes(),
         }
     }
 }
@@ -62,6 +66,7 @@ impl Finalizable for Event {
         match self {
             Event::Log(log) => log.metadata_mut().take_finalizers(),
             Event::Metric(metric) => metric.metadata_mut().take_finalizers(),
+            Event::Trace(trace) => trace.metadata_mut().take_finalizers(),
         }
     }
 }
@@ -154,10 +159,47 @@ impl Event {
         }
     }
 
+    /// Return self as a `TraceEvent`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if self is anything other than an `Event::Trace`.
+    pub fn as_trace(&self) -> &TraceEvent {
+        match self {
+            Event::Trace(trace) => trace,
+            _ => panic!("Failed type coercion, {:?} is not a trace event", self),
+        }
+    }
+
+    /// Return self as a mutable `TraceEvent`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if self is anything other than an `Event::Trace`.
+    pub fn as_mut_trace(&mut self) -> &mut TraceEvent {
+        match self {
+            Event::Trace(trace) => trace,
+            _ => panic!("Failed type coercion, {:?} is not a trace event", self),
+        }
+    }
+
+    /// Coerces self into `TraceEvent`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if self is anything other than an `Event::Trace`.
+    pub fn into_trace(self) -> TraceEvent {
+        match self {
+            Event::Trace(trace) => trace,
+            _ => panic!("Failed type coercion, {:?} is not a trace event", self),
+        }
+    }
+
     pub fn metadata(&self) -> &EventMetadata {
         match self {
             Self::Log(log) => log.metadata(),
             Self::Metric(metric) => metric.metadata(),
+            Self::Trace(trace) => trace.metadata(),
         }
     }
 
@@ -165,6 +207,7 @@ impl Event {
         match self {
             Self::Log(log) => log.metadata_mut(),
             Self::Metric(metric) => metric.metadata_mut(),
+            Self::Trace(trace) => trace.metadata_mut(),
         }
     }
 
@@ -173,6 +216,7 @@ impl Event {
         match self {
             Self::Log(log) => log.into_parts().1,
             Self::Metric(metric) => metric.into_parts().2,
+            Self::Trace(trace) => trace.into_parts().1,
         }
     }
 
@@ -181,6 +225,7 @@ impl Event {
         match self {
             Self::Log(log) => log.add_finalizer(finalizer),
             Self::Metric(metric) => metric.add_finalizer(finalizer),
+            Self::Trace(trace) => trace.add_finalizer(finalizer),
         }
     }
 
@@ -188,6 +233,7 @@ impl Event {
         match self {
             Self::Log(log) => log.with_batch_notifier(batch).into(),
             Self::Metric(metric) => metric.with_batch_notifier(batch).into(),
+            Self::Trace(trace) => trace.with_batch_notifier(batch).into(),
         }
     }
 }
@@ -197,6 +243,7 @@ impl EventDataEq for Event {
         match (self, other) {
             (Self::Log(a), Self::Log(b)) => a.event_data_eq(b),
             (Self::Metric(a), Self::Metric(b)) => a.event_data_eq(b),
+            (Self::Trace(a), Self::Trace(b)) => a.event_data_eq(b),
             _ => false,
         }
     }
@@ -243,6 +290,7 @@ impl TryInto<serde_json::Value> for Event {
         match self {
             Event::Log(fields) => serde_json::to_value(fields),
             Event::Metric(metric) => serde_json::to_value(metric),
+            Event::Trace(trace) => serde_json::to_value(trace),
         }
     }
 }
@@ -340,6 +388,12 @@ impl From<Metric> for Event {
     }
 }
 
+impl From<TraceEvent> for Event {
+    fn from(trace: TraceEvent) -> Self {
+        Event::Trace(trace)
+    }
+}
+
 pub trait MaybeAsLogMut {
     fn maybe_as_log_mut(&mut self) -> Option<&mut LogEvent>;
 }
@@ -349,6 +403,7 @@ impl MaybeAsLogMut for Event {
         match self {
             Event::Log(log) => Some(log),
             Event::Metric(_) => None,
+            Event::Trace(trace) => Some(trace.as_mut()),
         }
     }
 }
@@ -359,6 +414,7 @@ impl MaybeAsLogMut for Event {
 pub enum EventRef<'a> {
     Log(&'a LogEvent),
     Metric(&'a Metric),
+    Trace(&'a TraceEvent),
 }
 
 impl<'a> From<&'a Event> for EventRef<'a> {
@@ -366,6 +422,7 @@ impl<'a> From<&'a Event> for EventRef<'a> {
         match event {
             Event::Log(log) => log.into(),
             Event::Metric(metric) => metric.into(),
+            Event::Trace(trace) => trace.into(),
         }
     }
 }
@@ -382,6 +439,12 @@ impl<'a> From<&'a Metric> for EventRef<'a> {
     }
 }
 
+impl<'a> From<&'a TraceEvent> for EventRef<'a> {
+    fn from(trace: &'a TraceEvent) -> Self {
+        Self::Trace(trace)
+    }
+}
+
 impl EncodeBytes<Event> for Event {
     type Error = EncodeError;
 
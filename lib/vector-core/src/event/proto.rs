@@ -23,15 +23,21 @@ impl From<Metric> for Event {
     }
 }
 
+impl From<Trace> for Event {
+    fn from(trace: Trace) -> Self {
+        Self::Trace(trace)
+    }
+}
+
 impl From<Log> for event::LogEvent {
     fn from(log: Log) -> Self {
-        let fields = log
-            .fields
-            .into_iter()
-            .filter_map(|(k, v)| decode_value(v).map(|value| (k, value)))
-            .collect::<BTreeMap<_, _>>();
+        Self::from(decode_fields(log.fields))
+    }
+}
 
-        Self::from(fields)
+impl From<Trace> for event::TraceEvent {
+    fn from(trace: Trace) -> Self {
+        Self::from(decode_fields(trace.fields))
     }
 }
 
@@ -112,6 +118,7 @@ impl From<EventWrapper> for event::Event {
         match event {
             Event::Log(proto) => Self::Log(proto.into()),
             Event::Metric(proto) => Self::Metric(proto.into()),
+            Event::Trace(proto) => Self::Trace(proto.into()),
         }
     }
 }
@@ -125,12 +132,25 @@ impl From<event::LogEvent> for Log {
 impl From<event::LogEvent> for WithMetadata<Log> {
     fn from(log_event: event::LogEvent) -> Self {
         let (fields, metadata) = log_event.into_parts();
-        let fields = fields
-            .into_iter()
-            .map(|(k, v)| (k, encode_value(v)))
-            .collect::<BTreeMap<_, _>>();
+        let data = Log {
+            fields: encode_fields(fields),
+        };
+        Self { data, metadata }
+    }
+}
 
-        let data = Log { fields };
+impl From<event::TraceEvent> for Trace {
+    fn from(trace: event::TraceEvent) -> Self {
+        WithMetadata::<Self>::from(trace).data
+    }
+}
+
+impl From<event::TraceEvent> for WithMetadata<Trace> {
+    fn from(trace: event::TraceEvent) -> Self {
+        let (fields, metadata) = trace.into_parts();
+        let data = Trace {
+            fields: encode_fields(fields),
+        };
         Self { data, metadata }
     }
 }
@@ -219,6 +239,7 @@ impl From<event::Event> for WithMetadata<Event> {
         match event {
             event::Event::Log(log_event) => WithMetadata::<Log>::from(log_event).into(),
             event::Event::Metric(metric) => WithMetadata::<Metric>::from(metric).into(),
+            event::Event::Trace(trace) => WithMetadata::<Trace>::from(trace).into(),
         }
     }
 }
@@ -235,6 +256,20 @@ impl From<event::Event> for WithMetadata<EventWrapper> {
     }
 }
 
+fn decode_fields(fields: BTreeMap<String, Value>) -> BTreeMap<String, event::Value> {
+    fields
+        .into_iter()
+        .filter_map(|(k, v)| decode_value(v).map(|value| (k, value)))
+        .collect()
+}
+
+fn encode_fields(fields: BTreeMap<String, event::Value>) -> BTreeMap<String, Value> {
+    fields
+        .into_iter()
+        .map(|(k, v)| (k, encode_value(v)))
+        .collect()
+}
+
 fn decode_value(input: Value) -> Option<event::Value> {
     match input.kind {
         Some(value::Kind::RawBytes(data)) => Some(event::Value::Bytes(data)),
@@ -1,6 +1,7 @@
 use crate::event::{
     metric::{Bucket, MetricData, MetricName, MetricSeries, Quantile, Sample},
-    Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, Value,
+    Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, TraceEvent,
+    Value,
 };
 use bytes::Bytes;
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -53,10 +54,10 @@ impl Arbitrary for Event {
         let choice: u8 = u8::arbitrary(g);
         // Quickcheck can't derive Arbitrary for enums, see
         // https://github.com/BurntSushi/quickcheck/issues/98
-        if choice % 2 == 0 {
-            Event::Log(LogEvent::arbitrary(g))
-        } else {
-            Event::Metric(Metric::arbitrary(g))
+        match choice % 3 {
+            0 => Event::Log(LogEvent::arbitrary(g)),
+            1 => Event::Metric(Metric::arbitrary(g)),
+            _ => Event::Trace(TraceEvent::arbitrary(g)),
         }
     }
 
@@ -64,10 +65,22 @@ impl Arbitrary for Event {
         match self {
             Event::Log(log_event) => Box::new(log_event.shrink().map(Event::Log)),
             Event::Metric(metric) => Box::new(metric.shrink().map(Event::Metric)),
+            Event::Trace(trace) => Box::new(trace.shrink().map(Event::Trace)),
         }
     }
 }
 
+impl Arbitrary for TraceEvent {
+    fn arbitrary(g: &mut Gen) -> Self {
+        TraceEvent::from(LogEvent::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let log_event: &LogEvent = self.as_ref();
+        Box::new(log_event.shrink().map(TraceEvent::from))
+    }
+}
+
 impl Arbitrary for LogEvent {
     fn arbitrary(g: &mut Gen) -> Self {
         let mut gen = Gen::new(MAX_MAP_SIZE);
@@ -0,0 +1,254 @@
+use super::{
+    finalization::{BatchNotifier, EventFinalizer},
+    metadata::EventMetadata,
+    LogEvent, Value,
+};
+use crate::ByteSizeOf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::EventDataEq;
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
+
+/// Field holding the identifier of the trace a span belongs to.
+pub const TRACE_ID_KEY: &str = "trace_id";
+/// Field holding the identifier of the span itself.
+pub const SPAN_ID_KEY: &str = "span_id";
+/// Field holding the identifier of the parent span, absent for root spans.
+pub const PARENT_SPAN_ID_KEY: &str = "parent_span_id";
+/// Field holding the human readable name of the span.
+pub const NAME_KEY: &str = "name";
+/// Field holding the time at which the span started.
+pub const START_TIMESTAMP_KEY: &str = "start_timestamp";
+/// Field holding the time at which the span ended.
+pub const END_TIMESTAMP_KEY: &str = "end_timestamp";
+/// Field holding the free-form attributes attached to the span.
+pub const ATTRIBUTES_KEY: &str = "attributes";
+
+/// A single span of a distributed trace.
+///
+/// Spans are stored as a map of fields so that they can be manipulated with
+/// the same tooling as logs (VRL, templates, field paths), while the
+/// well-known span properties are exposed through typed accessors.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct TraceEvent(LogEvent);
+
+impl TraceEvent {
+    /// Create a new span belonging to `trace_id`, identified by `span_id`.
+    pub fn new(
+        trace_id: impl Into<String>,
+        span_id: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        let (trace_id, span_id, name): (String, String, String) =
+            (trace_id.into(), span_id.into(), name.into());
+
+        let mut trace = Self::default();
+        trace.0.insert_flat(TRACE_ID_KEY, trace_id);
+        trace.0.insert_flat(SPAN_ID_KEY, span_id);
+        trace.0.insert_flat(NAME_KEY, name);
+        trace
+    }
+
+    /// Create a `TraceEvent` from a tuple of its components
+    pub fn from_parts(map: BTreeMap<String, Value>, metadata: EventMetadata) -> Self {
+        Self(LogEvent::from_parts(map, metadata))
+    }
+
+    /// Convert a `TraceEvent` into a tuple of its components
+    pub fn into_parts(self) -> (BTreeMap<String, Value>, EventMetadata) {
+        self.0.into_parts()
+    }
+
+    #[must_use]
+    pub fn with_parent_span_id(mut self, parent_span_id: Option<String>) -> Self {
+        match parent_span_id {
+            Some(parent_span_id) => self.0.insert_flat(PARENT_SPAN_ID_KEY, parent_span_id),
+            None => self.0.as_map_mut().remove(PARENT_SPAN_ID_KEY),
+        };
+        self
+    }
+
+    #[must_use]
+    pub fn with_start_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.0.insert_flat(START_TIMESTAMP_KEY, timestamp);
+        self
+    }
+
+    #[must_use]
+    pub fn with_end_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.0.insert_flat(END_TIMESTAMP_KEY, timestamp);
+        self
+    }
+
+    #[must_use]
+    pub fn with_attributes(mut self, attributes: BTreeMap<String, Value>) -> Self {
+        self.0.insert_flat(ATTRIBUTES_KEY, attributes);
+        self
+    }
+
+    #[must_use]
+    pub fn with_batch_notifier(self, batch: &Arc<BatchNotifier>) -> Self {
+        Self(self.0.with_batch_notifier(batch))
+    }
+
+    pub fn add_finalizer(&mut self, finalizer: EventFinalizer) {
+        self.0.add_finalizer(finalizer);
+    }
+
+    pub fn metadata(&self) -> &EventMetadata {
+        self.0.metadata()
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        self.0.metadata_mut()
+    }
+
+    pub fn trace_id(&self) -> Option<&Value> {
+        self.0.get_flat(TRACE_ID_KEY)
+    }
+
+    pub fn span_id(&self) -> Option<&Value> {
+        self.0.get_flat(SPAN_ID_KEY)
+    }
+
+    pub fn parent_span_id(&self) -> Option<&Value> {
+        self.0.get_flat(PARENT_SPAN_ID_KEY)
+    }
+
+    pub fn name(&self) -> Option<&Value> {
+        self.0.get_flat(NAME_KEY)
+    }
+
+    pub fn start_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.0
+            .get_flat(START_TIMESTAMP_KEY)
+            .and_then(Value::as_timestamp)
+    }
+
+    pub fn end_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.0
+            .get_flat(END_TIMESTAMP_KEY)
+            .and_then(Value::as_timestamp)
+    }
+
+    pub fn attributes(&self) -> Option<&BTreeMap<String, Value>> {
+        match self.0.get_flat(ATTRIBUTES_KEY) {
+            Some(Value::Map(attributes)) => Some(attributes),
+            _ => None,
+        }
+    }
+
+    /// A span without a parent is the root of its trace.
+    pub fn is_root(&self) -> bool {
+        self.parent_span_id().is_none()
+    }
+
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: impl AsRef<str>) -> Option<&mut Value> {
+        self.0.get_mut(key)
+    }
+
+    pub fn insert(
+        &mut self,
+        key: impl AsRef<str>,
+        value: impl Into<Value> + Debug,
+    ) -> Option<Value> {
+        self.0.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: impl AsRef<str>) -> Option<Value> {
+        self.0.remove(key)
+    }
+
+    pub fn as_map(&self) -> &BTreeMap<String, Value> {
+        self.0.as_map()
+    }
+}
+
+impl ByteSizeOf for TraceEvent {
+    fn allocated_bytes(&self) -> usize {
+        self.0.allocated_bytes()
+    }
+}
+
+impl EventDataEq for TraceEvent {
+    fn event_data_eq(&self, other: &Self) -> bool {
+        self.0.event_data_eq(&other.0)
+    }
+}
+
+impl AsRef<LogEvent> for TraceEvent {
+    fn as_ref(&self) -> &LogEvent {
+        &self.0
+    }
+}
+
+impl AsMut<LogEvent> for TraceEvent {
+    fn as_mut(&mut self) -> &mut LogEvent {
+        &mut self.0
+    }
+}
+
+impl From<LogEvent> for TraceEvent {
+    fn from(log: LogEvent) -> Self {
+        Self(log)
+    }
+}
+
+impl From<BTreeMap<String, Value>> for TraceEvent {
+    fn from(map: BTreeMap<String, Value>) -> Self {
+        Self(map.into())
+    }
+}
+
+impl From<TraceEvent> for LogEvent {
+    fn from(trace: TraceEvent) -> Self {
+        trace.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn span_accessors() {
+        let start = Utc.ymd(2021, 10, 1).and_hms(12, 0, 0);
+        let end = Utc.ymd(2021, 10, 1).and_hms(12, 0, 1);
+        let mut attributes = BTreeMap::new();
+        attributes.insert("http.method".to_string(), Value::from("GET"));
+
+        let span = TraceEvent::new("4bf92f3577b34da6", "00f067aa0ba902b7", "GET /users")
+            .with_parent_span_id(Some("53995c3f42cd8ad8".into()))
+            .with_start_timestamp(start)
+            .with_end_timestamp(end)
+            .with_attributes(attributes.clone());
+
+        assert_eq!(span.trace_id(), Some(&Value::from("4bf92f3577b34da6")));
+        assert_eq!(span.span_id(), Some(&Value::from("00f067aa0ba902b7")));
+        assert_eq!(
+            span.parent_span_id(),
+            Some(&Value::from("53995c3f42cd8ad8"))
+        );
+        assert_eq!(span.name(), Some(&Value::from("GET /users")));
+        assert_eq!(span.start_timestamp(), Some(&start));
+        assert_eq!(span.end_timestamp(), Some(&end));
+        assert_eq!(span.attributes(), Some(&attributes));
+        assert!(!span.is_root());
+    }
+
+    #[test]
+    fn root_span_has_no_parent() {
+        let span = TraceEvent::new("4bf92f3577b34da6", "00f067aa0ba902b7", "root")
+            .with_parent_span_id(Some("53995c3f42cd8ad8".into()))
+            .with_parent_span_id(None);
+
+        assert!(span.is_root());
+        assert_eq!(span.parent_span_id(), None);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, Value};
+use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, TraceEvent, Value};
 use crate::config::log_schema;
 use lookup::LookupBuf;
 use snafu::Snafu;
@@ -22,6 +22,8 @@ pub enum VrlTarget {
     // that `fields` must always be a `Map` variant.
     LogEvent(Value, EventMetadata),
     Metric(Metric),
+    // Traces are accessed in VRL exactly like logs, only the conversion back into events differs.
+    Trace(Value, EventMetadata),
 }
 
 impl VrlTarget {
@@ -32,6 +34,10 @@ impl VrlTarget {
                 VrlTarget::LogEvent(Value::Map(fields), metadata)
             }
             Event::Metric(event) => VrlTarget::Metric(event),
+            Event::Trace(event) => {
+                let (fields, metadata) = event.into_parts();
+                VrlTarget::Trace(Value::Map(fields), metadata)
+            }
         }
     }
 
@@ -47,6 +53,8 @@ impl VrlTarget {
             VrlTarget::Metric(metric) => {
                 Box::new(std::iter::once(Event::Metric(metric))) as Box<dyn Iterator<Item = Event>>
             }
+            VrlTarget::Trace(value, metadata) => Box::new(value_into_trace_events(value, metadata))
+                as Box<dyn Iterator<Item = Event>>,
         }
     }
 }
@@ -54,7 +62,7 @@ impl VrlTarget {
 impl vrl_core::Target for VrlTarget {
     fn insert(&mut self, path: &LookupBuf, value: vrl_core::Value) -> Result<(), String> {
         match self {
-            VrlTarget::LogEvent(ref mut log, _) => log
+            VrlTarget::LogEvent(ref mut log, _) | VrlTarget::Trace(ref mut log, _) => log
                 .insert(path.clone(), value)
                 .map(|_| ())
                 .map_err(|err| err.to_string()),
@@ -127,7 +135,7 @@ impl vrl_core::Target for VrlTarget {
 
     fn get(&self, path: &LookupBuf) -> std::result::Result<Option<vrl_core::Value>, String> {
         match self {
-            VrlTarget::LogEvent(log, _) => log
+            VrlTarget::LogEvent(log, _) | VrlTarget::Trace(log, _) => log
                 .get(path)
                 .map(|val| val.map(|val| val.clone().into()))
                 .map_err(|err| err.to_string()),
@@ -202,7 +210,7 @@ impl vrl_core::Target for VrlTarget {
         compact: bool,
     ) -> Result<Option<vrl_core::Value>, String> {
         match self {
-            VrlTarget::LogEvent(ref mut log, _) => {
+            VrlTarget::LogEvent(ref mut log, _) | VrlTarget::Trace(ref mut log, _) => {
                 if path.is_root() {
                     Ok(Some({
                         let mut map = Value::Map(BTreeMap::new());
@@ -283,6 +291,23 @@ fn value_into_log_events(value: Value, metadata: EventMetadata) -> impl Iterator
     }
 }
 
+// Turn a `Value` back into `TraceEvents`. Spans only make sense as objects, so unlike logs, any
+// non-object value (or non-object array element) assigned to `.` is discarded.
+fn value_into_trace_events(value: Value, metadata: EventMetadata) -> impl Iterator<Item = Event> {
+    let values = match value {
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+
+    values.into_iter().filter_map(move |value| match value {
+        Value::Map(object) => Some(Event::from(TraceEvent::from_parts(
+            object,
+            metadata.clone(),
+        ))),
+        _ => None,
+    })
+}
+
 #[derive(Debug, Snafu)]
 enum MetricPathError<'a> {
     #[snafu(display("cannot set root path"))]
@@ -609,6 +634,45 @@ mod test {
         }
     }
 
+    #[test]
+    fn trace_into_events() {
+        use shared::btreemap;
+
+        let cases = vec![
+            (
+                vrl_core::Value::from(btreemap! {"span_id" => "a"}),
+                vec![btreemap! {"span_id" => "a"}],
+            ),
+            (vrl_core::Value::from(1), vec![]),
+            (
+                vrl_core::Value::from(vec![
+                    vrl_core::Value::from(btreemap! {"span_id" => "a"}),
+                    vrl_core::Value::from("2"),
+                    vrl_core::Value::from(btreemap! {"span_id" => "b"}),
+                ]),
+                vec![btreemap! {"span_id" => "a"}, btreemap! {"span_id" => "b"}],
+            ),
+        ];
+
+        for (value, expect) in cases {
+            let metadata = EventMetadata::default();
+            let mut target = VrlTarget::new(Event::Trace(TraceEvent::from_parts(
+                BTreeMap::new(),
+                metadata.clone(),
+            )));
+
+            vrl_core::Target::insert(&mut target, &LookupBuf::root(), value).unwrap();
+
+            assert_eq!(
+                target.into_events().collect::<Vec<_>>(),
+                expect
+                    .into_iter()
+                    .map(|v| Event::Trace(TraceEvent::from_parts(v, metadata.clone())))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
     #[test]
     fn metric_all_fields() {
         let metric = Metric::new(
@@ -7,6 +7,7 @@ pub enum DataType {
     Any,
     Log,
     Metric,
+    Trace,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
@@ -54,6 +55,14 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
         Vec::new()
     }
 
+    /// Whether the transform sends events to its primary output, the one
+    /// referred to by the transform's key alone. Transforms only sending events
+    /// to their named outputs don't declare it, so that it can't be used as an
+    /// input.
+    fn has_primary_output(&self) -> bool {
+        true
+    }
+
     fn transform_type(&self) -> &'static str;
 
     /// Allows a transform configuration to expand itself into multiple "child"
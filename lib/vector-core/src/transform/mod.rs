@@ -1,6 +1,7 @@
 use crate::event::Event;
+use crate::ByteSizeOf;
 use futures::Stream;
-use std::pin::Pin;
+use std::{collections::HashMap, pin::Pin};
 #[cfg(any(feature = "lua"))]
 pub mod runtime_transform;
 pub use config::{DataType, ExpandType, TransformConfig, TransformContext};
@@ -14,6 +15,7 @@ mod config;
 pub enum Transform {
     Function(Box<dyn FunctionTransform>),
     FallibleFunction(Box<dyn FallibleFunctionTransform>),
+    Synchronous(Box<dyn SyncTransform>),
     Task(Box<dyn TaskTransform>),
 }
 
@@ -93,6 +95,42 @@ impl Transform {
         }
     }
 
+    /// Create a new synchronous transform.
+    ///
+    /// These are similar to `FunctionTransform`, but can send each event to
+    /// any of their named outputs, as declared by their configuration.
+    pub fn synchronous(v: impl SyncTransform + 'static) -> Self {
+        Transform::Synchronous(Box::new(v))
+    }
+
+    /// Mutably borrow the inner transform as a synchronous transform.
+    ///
+    /// # Panics
+    ///
+    /// If the transform is not a [`SyncTransform`] this will panic.
+    pub fn as_synchronous(&mut self) -> &mut Box<dyn SyncTransform> {
+        match self {
+            Transform::Synchronous(t) => t,
+            _ => panic!(
+                "Called `Transform::as_synchronous` on something that was not a synchronous variant."
+            ),
+        }
+    }
+
+    /// Transmute the inner transform into a synchronous transform.
+    ///
+    /// # Panics
+    ///
+    /// If the transform is not a [`SyncTransform`] this will panic.
+    pub fn into_synchronous(self) -> Box<dyn SyncTransform> {
+        match self {
+            Transform::Synchronous(t) => t,
+            _ => panic!(
+                "Called `Transform::into_synchronous` on something that was not a synchronous variant."
+            ),
+        }
+    }
+
     /// Create a new task transform.
     ///
     /// These tasks are coordinated, and map a stream of some `U` to some other
@@ -154,6 +192,79 @@ pub trait FallibleFunctionTransform: Send + dyn_clone::DynClone + Sync {
 
 dyn_clone::clone_trait_object!(FallibleFunctionTransform);
 
+/// Similar to `FunctionTransform`, but with any number of named outputs, which
+/// each event can be sent to.
+pub trait SyncTransform: Send + dyn_clone::DynClone + Sync {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf);
+}
+
+dyn_clone::clone_trait_object!(SyncTransform);
+
+/// Buffers the events a `SyncTransform` sends to its default output and to
+/// each of its named outputs.
+#[derive(Debug, Default)]
+pub struct TransformOutputsBuf {
+    primary: Vec<Event>,
+    named: HashMap<String, Vec<Event>>,
+}
+
+impl TransformOutputsBuf {
+    pub fn new_with_capacity(named_outputs: Vec<String>, capacity: usize) -> Self {
+        Self {
+            primary: Vec::with_capacity(capacity),
+            named: named_outputs
+                .into_iter()
+                .map(|name| (name, Vec::with_capacity(capacity)))
+                .collect(),
+        }
+    }
+
+    /// Sends `event` to the default output.
+    pub fn push(&mut self, event: Event) {
+        self.primary.push(event);
+    }
+
+    /// Sends `event` to the named output `name`.
+    ///
+    /// # Panics
+    ///
+    /// If the transform hasn't declared an output with that name.
+    pub fn push_named(&mut self, name: &str, event: Event) {
+        self.named
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("Unknown transform output {:?}.", name))
+            .push(event);
+    }
+
+    /// Takes the events sent to the default output so far.
+    pub fn take_primary(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.primary)
+    }
+
+    /// Takes the events sent to the named output `name` so far.
+    pub fn take_named(&mut self, name: &str) -> Vec<Event> {
+        self.named
+            .get_mut(name)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// The number of events sent to all outputs so far.
+    pub fn len(&self) -> usize {
+        self.primary.len() + self.named.values().map(Vec::len).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ByteSizeOf for TransformOutputsBuf {
+    fn allocated_bytes(&self) -> usize {
+        self.primary.size_of() + self.named.values().map(ByteSizeOf::size_of).sum::<usize>()
+    }
+}
+
 /// Transforms that tend to be more complicated runtime style components.
 ///
 /// These require coordination and map a stream of some `T` to some `U`.
@@ -34,6 +34,11 @@ pub struct Opts {
     /// The timezone used to parse dates.
     #[structopt(short = "tz", long)]
     timezone: Option<String>,
+
+    /// A directory searched for the files imported by the program. Can be given multiple times,
+    /// directories are searched in the given order.
+    #[structopt(short = "I", long = "import-path", parse(from_os_str))]
+    import_paths: Vec<PathBuf>,
 }
 
 impl Opts {
@@ -98,13 +103,17 @@ fn run(opts: &Opts) -> Result<(), Error> {
             default_objects()
         };
 
-        repl(repl_objects, &tz)
+        repl(repl_objects, &tz, &opts.import_paths)
     } else {
         let objects = opts.read_into_objects()?;
         let source = opts.read_program()?;
-        let program = vrl::compile(&source, &stdlib::all(), None).map_err(|diagnostics| {
-            Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
-        })?;
+
+        let mut state = state::Compiler::new();
+        state.set_import_paths(opts.import_paths.clone());
+
+        let program = vrl::compile_with_state(&source, &stdlib::all(), &mut state).map_err(
+            |diagnostics| Error::Parse(Formatter::new(&source, diagnostics).colored().to_string()),
+        )?;
 
         for mut object in objects {
             let result = execute(&mut object, &program, &tz).map(|v| {
@@ -125,9 +134,9 @@ fn run(opts: &Opts) -> Result<(), Error> {
     }
 }
 
-fn repl(objects: Vec<Value>, timezone: &TimeZone) -> Result<(), Error> {
+fn repl(objects: Vec<Value>, timezone: &TimeZone, import_paths: &[PathBuf]) -> Result<(), Error> {
     if cfg!(feature = "repl") {
-        repl::run(objects, timezone);
+        repl::run(objects, timezone, import_paths);
         Ok(())
     } else {
         Err(Error::ReplFeature)
@@ -10,6 +10,7 @@ use rustyline::validate::{self, ValidationResult, Validator};
 use rustyline::{Context, Editor, Helper};
 use shared::TimeZone;
 use std::borrow::Cow::{self, Borrowed, Owned};
+use std::path::PathBuf;
 use vrl::{diagnostic::Formatter, state, value, Runtime, Target, Value};
 
 // Create a list of all possible error values for potential docs lookup
@@ -38,12 +39,13 @@ const RESERVED_TERMS: &[&str] = &[
     "help docs",
 ];
 
-pub(crate) fn run(mut objects: Vec<Value>, timezone: &TimeZone) {
+pub(crate) fn run(mut objects: Vec<Value>, timezone: &TimeZone, import_paths: &[PathBuf]) {
     let mut index = 0;
     let func_docs_regex = Regex::new(r"^help\sdocs\s(\w{1,})$").unwrap();
     let error_docs_regex = Regex::new(r"^help\serror\s(\w{1,})$").unwrap();
 
     let mut compiler_state = state::Compiler::default();
+    compiler_state.set_import_paths(import_paths.to_vec());
     let mut rt = Runtime::new(state::Runtime::default());
     let mut rl = Editor::<Repl>::new();
     rl.set_helper(Some(Repl::new()));
@@ -1,10 +1,12 @@
 use crate::expression::*;
-use crate::{Function, Program, State, Value};
+use crate::{import, Function, Program, State, TypeDef, Value};
 use chrono::{TimeZone, Utc};
-use diagnostic::DiagnosticError;
+use diagnostic::{DiagnosticError, File};
 use ordered_float::NotNan;
 use parser::ast::{self, AssignmentOp, Node};
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::path::PathBuf;
 
 pub type Errors = Vec<Box<dyn DiagnosticError>>;
 
@@ -14,6 +16,9 @@ pub struct Compiler<'a> {
     errors: Errors,
     fallible: bool,
     abortable: bool,
+
+    // The files currently being imported, used to detect cyclic imports.
+    importing: Vec<PathBuf>,
 }
 
 impl<'a> Compiler<'a> {
@@ -24,6 +29,7 @@ impl<'a> Compiler<'a> {
             errors: vec![],
             fallible: false,
             abortable: false,
+            importing: vec![],
         }
     }
 
@@ -67,6 +73,14 @@ impl<'a> Compiler<'a> {
 
                         Some(expr)
                     }
+                    FunctionDefinition(node) => {
+                        self.compile_function_definition(node);
+                        None
+                    }
+                    Import(node) => {
+                        self.compile_import(node);
+                        None
+                    }
                     Error(err) => {
                         self.handle_parser_error(err);
                         None
@@ -76,6 +90,148 @@ impl<'a> Compiler<'a> {
             .collect()
     }
 
+    fn compile_function_definition(&mut self, node: Node<ast::FunctionDefinition>) {
+        use user_function::Error;
+
+        let ast::FunctionDefinition {
+            ident,
+            parameters,
+            block,
+        } = node.into_inner();
+
+        let (ident_span, ident) = ident.take();
+
+        let builtin = self.fns.iter().any(|f| f.identifier() == ident.as_ref());
+        if builtin || self.state.function(&ident).is_some() {
+            let err = Error::AlreadyDefined {
+                ident_span,
+                builtin,
+            };
+            self.errors.push(Box::new(err));
+            return;
+        }
+
+        let mut variables = HashMap::with_capacity(parameters.len());
+        for parameter in &parameters {
+            let details = assignment::Details {
+                type_def: TypeDef::new().unknown().infallible(),
+                value: None,
+            };
+
+            if variables
+                .insert(parameter.inner().clone(), details)
+                .is_some()
+            {
+                let err = Error::DuplicateParameter {
+                    parameter_span: parameter.span(),
+                };
+                self.errors.push(Box::new(err));
+                return;
+            }
+        }
+
+        // The block is compiled with only the parameters defined as variables,
+        // and against the target as it is known at this point. Any changes the
+        // block makes to the target are applied where the function is called.
+        let target = self.state.target().cloned();
+        let variables = self.state.swap_variables(variables);
+
+        let block = self.compile_block(block);
+        let type_def = block.type_def(self.state);
+
+        self.state.swap_variables(variables);
+        let target_type_def = target.as_ref().map(|details| details.type_def.clone());
+        let target = self
+            .state
+            .swap_target(target)
+            .map(|details| details.type_def)
+            .filter(|type_def| Some(type_def) != target_type_def.as_ref());
+
+        let parameters = parameters.into_iter().map(Node::into_inner).collect();
+        let function = UserFunction::new(ident, parameters, block, type_def, target);
+
+        self.state.insert_function(function);
+    }
+
+    /// Compiles the function definitions of an imported file.
+    ///
+    /// Errors raised while compiling the file point into that file.
+    fn compile_import(&mut self, node: Node<ast::Import>) {
+        use import::Error;
+
+        let (path_span, path) = node.into_inner().path.take();
+
+        let resolved = match import::resolve(&path, self.state.import_paths()) {
+            Some(resolved) => resolved.canonicalize().unwrap_or(resolved),
+            None => {
+                let err = Error::NotFound { path_span, path };
+                self.errors.push(Box::new(err));
+                return;
+            }
+        };
+
+        if self.importing.contains(&resolved) {
+            let err = Error::Cyclic { path_span, path };
+            self.errors.push(Box::new(err));
+            return;
+        }
+
+        // Importing the same file more than once is a no-op.
+        if !self.state.insert_imported(resolved.clone()) {
+            return;
+        }
+
+        let source = match std::fs::read_to_string(&resolved) {
+            Ok(source) => source,
+            Err(error) => {
+                let err = Error::Read {
+                    path_span,
+                    path,
+                    error,
+                };
+                self.errors.push(Box::new(err));
+                return;
+            }
+        };
+
+        let file = File::new(&path, &source);
+        let errors = std::mem::take(&mut self.errors);
+        self.importing.push(resolved);
+
+        match parser::parse(&source) {
+            Ok(ast) => self.compile_module(ast),
+            Err(err) => self.handle_parser_error(err),
+        }
+
+        self.importing.pop();
+        let module_errors = std::mem::replace(&mut self.errors, errors);
+        self.errors.extend(
+            module_errors
+                .into_iter()
+                .map(|err| import::Imported::wrap(err, &file)),
+        );
+    }
+
+    /// Compiles an imported file, which can only contain function definitions
+    /// and other imports.
+    fn compile_module(&mut self, ast: parser::Program) {
+        use ast::RootExpr::*;
+
+        for node in ast {
+            let span = node.span();
+
+            match node.into_inner() {
+                FunctionDefinition(node) => self.compile_function_definition(node),
+                Import(node) => self.compile_import(node),
+                Expr(_) => {
+                    let err = import::Error::UnexpectedExpression { span };
+                    self.errors.push(Box::new(err));
+                }
+                Error(err) => self.handle_parser_error(err),
+            }
+        }
+    }
+
     fn compile_exprs(&mut self, nodes: impl IntoIterator<Item = Node<ast::Expr>>) -> Vec<Expr> {
         nodes
             .into_iter()
@@ -93,6 +249,9 @@ impl<'a> Compiler<'a> {
             Op(node) => self.compile_op(node).into(),
             Assignment(node) => self.compile_assignment(node).into(),
             Query(node) => self.compile_query(node).into(),
+            FunctionCall(node) if self.state.function(&node.ident).is_some() => {
+                self.compile_user_function_call(node).into()
+            }
             FunctionCall(node) => self.compile_function_call(node).into(),
             Variable(node) => self.compile_variable(node).into(),
             Unary(node) => self.compile_unary(node).into(),
@@ -316,6 +475,10 @@ impl<'a> Compiler<'a> {
                 let container = self.compile_container(Node::new(span, container));
                 Target::Container(container)
             }
+            FunctionCall(call) if self.state.function(&call.ident).is_some() => {
+                let call = self.compile_user_function_call(Node::new(span, call));
+                Target::UserFunctionCall(call)
+            }
             FunctionCall(call) => {
                 let call = self.compile_function_call(Node::new(span, call));
                 Target::FunctionCall(call)
@@ -329,6 +492,7 @@ impl<'a> Compiler<'a> {
             ident,
             abort_on_error,
             arguments,
+            closure,
         } = node.into_inner();
 
         let arguments = arguments
@@ -340,20 +504,129 @@ impl<'a> Compiler<'a> {
             self.fallible = true;
         }
 
-        FunctionCall::new(
+        let (closure_variables, closure_block) = match closure {
+            Some(closure) => {
+                let (span, ast::FunctionClosure { variables, block }) = closure.take();
+                (Some(Node::new(span, variables)), Some(block))
+            }
+            None => (None, None),
+        };
+
+        let builder = match FunctionCall::builder(
             call_span,
             ident,
             abort_on_error,
             arguments,
             self.fns,
             self.state,
+            closure_variables,
+        ) {
+            Ok(builder) => builder,
+            Err(err) => {
+                self.errors.push(Box::new(err));
+                return FunctionCall::noop();
+            }
+        };
+
+        let closure_block = closure_block.map(|block| self.compile_closure_block(block, &builder));
+
+        builder
+            .compile(self.state, closure_block)
+            .unwrap_or_else(|err| {
+                self.errors.push(Box::new(err));
+                FunctionCall::noop()
+            })
+    }
+
+    /// Compiles a call to a function defined by the program, or by one of the
+    /// files it imports.
+    fn compile_user_function_call(&mut self, node: Node<ast::FunctionCall>) -> UserFunctionCall {
+        let call_span = node.span();
+        let ast::FunctionCall {
+            ident,
+            abort_on_error,
+            arguments,
+            closure,
+        } = node.into_inner();
+
+        let (ident_span, ident) = ident.take();
+        let function = match self.state.function(&ident) {
+            Some(function) => function.clone(),
+            None => return UserFunctionCall::noop(),
+        };
+
+        if let Some(closure) = closure {
+            let err = user_function::Error::UnexpectedClosure {
+                call_span,
+                closure_span: closure.span(),
+            };
+            self.errors.push(Box::new(err));
+            return UserFunctionCall::noop();
+        }
+
+        let arguments = arguments
+            .into_iter()
+            .map(|node| {
+                let ast::FunctionArgument { ident, expr } = node.into_inner();
+                (ident, Node::new(expr.span(), self.compile_expr(expr)))
+            })
+            .collect();
+
+        if abort_on_error {
+            self.fallible = true;
+        }
+
+        UserFunctionCall::new(
+            call_span,
+            ident_span,
+            function,
+            abort_on_error,
+            arguments,
+            self.state,
         )
         .unwrap_or_else(|err| {
             self.errors.push(Box::new(err));
-            FunctionCall::noop()
+            UserFunctionCall::noop()
         })
     }
 
+    /// Compiles the block of a closure, with the closure variables defined.
+    ///
+    /// Variables shadowed by those of the closure are restored afterwards,
+    /// closure variables are not available outside of the closure.
+    fn compile_closure_block(
+        &mut self,
+        node: Node<ast::Block>,
+        builder: &function_call::Builder,
+    ) -> (Node<Block>, TypeDef) {
+        let span = node.span();
+
+        let shadowed = builder
+            .closure_variables()
+            .iter()
+            .map(|(ident, type_def)| {
+                let details = assignment::Details {
+                    type_def: type_def.clone(),
+                    value: None,
+                };
+
+                (ident, self.state.insert_variable(ident.clone(), details))
+            })
+            .collect::<Vec<_>>();
+
+        let block = self.compile_block(node);
+        let type_def = block.type_def(self.state);
+
+        for (ident, details) in shadowed {
+            match details {
+                Some(details) => self.state.insert_variable(ident.clone(), details),
+                None => self.state.remove_variable(ident),
+            };
+        }
+
+        (Node::new(span, block), type_def)
+    }
+
     fn compile_function_argument(&mut self, node: Node<ast::FunctionArgument>) -> FunctionArgument {
         let ast::FunctionArgument { ident, expr } = node.into_inner();
         let expr = Node::new(expr.span(), self.compile_expr(expr));
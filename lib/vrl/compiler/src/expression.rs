@@ -23,6 +23,7 @@ pub(crate) mod function_call;
 pub(crate) mod literal;
 pub(crate) mod predicate;
 pub(crate) mod query;
+pub(crate) mod user_function;
 
 pub use abort::Abort;
 pub use array::Array;
@@ -43,6 +44,7 @@ pub use predicate::Predicate;
 pub use query::Query;
 pub use query::Target;
 pub use unary::Unary;
+pub use user_function::{UserFunction, UserFunctionCall};
 pub use variable::Variable;
 
 pub type Resolved = Result<Value, ExpressionError>;
@@ -94,6 +96,7 @@ pub enum Expr {
     Assignment(Assignment),
     Query(Query),
     FunctionCall(FunctionCall),
+    UserFunctionCall(UserFunctionCall),
     Variable(Variable),
     Noop(Noop),
     Unary(Unary),
@@ -117,7 +120,7 @@ impl Expr {
             Op(..) => "operation",
             Assignment(..) => "assignment",
             Query(..) => "query",
-            FunctionCall(..) => "function call",
+            FunctionCall(..) | UserFunctionCall(..) => "function call",
             Variable(..) => "variable call",
             Noop(..) => "noop",
             Unary(..) => "unary operation",
@@ -138,6 +141,7 @@ impl Expression for Expr {
             Assignment(v) => v.resolve(ctx),
             Query(v) => v.resolve(ctx),
             FunctionCall(v) => v.resolve(ctx),
+            UserFunctionCall(v) => v.resolve(ctx),
             Variable(v) => v.resolve(ctx),
             Noop(v) => v.resolve(ctx),
             Unary(v) => v.resolve(ctx),
@@ -156,6 +160,7 @@ impl Expression for Expr {
             Assignment(v) => Expression::as_value(v),
             Query(v) => Expression::as_value(v),
             FunctionCall(v) => Expression::as_value(v),
+            UserFunctionCall(v) => Expression::as_value(v),
             Variable(v) => Expression::as_value(v),
             Noop(v) => Expression::as_value(v),
             Unary(v) => Expression::as_value(v),
@@ -174,6 +179,7 @@ impl Expression for Expr {
             Assignment(v) => v.type_def(state),
             Query(v) => v.type_def(state),
             FunctionCall(v) => v.type_def(state),
+            UserFunctionCall(v) => v.type_def(state),
             Variable(v) => v.type_def(state),
             Noop(v) => v.type_def(state),
             Unary(v) => v.type_def(state),
@@ -194,6 +200,7 @@ impl fmt::Display for Expr {
             Assignment(v) => v.fmt(f),
             Query(v) => v.fmt(f),
             FunctionCall(v) => v.fmt(f),
+            UserFunctionCall(v) => v.fmt(f),
             Variable(v) => v.fmt(f),
             Noop(v) => v.fmt(f),
             Unary(v) => v.fmt(f),
@@ -246,6 +253,12 @@ impl From<FunctionCall> for Expr {
     }
 }
 
+impl From<UserFunctionCall> for Expr {
+    fn from(user_function_call: UserFunctionCall) -> Self {
+        Expr::UserFunctionCall(user_function_call)
+    }
+}
+
 impl From<Variable> for Expr {
     fn from(variable: Variable) -> Self {
         Expr::Variable(variable)
@@ -251,16 +251,20 @@ impl Target {
                 // without any path appended and return early.
                 let path = match path {
                     Some(path) => path,
-                    None => return ctx.state_mut().insert_variable(ident.clone(), value),
+                    None => {
+                        ctx.state_mut().insert_variable(ident.clone(), value);
+                        return;
+                    }
                 };
 
                 // Update existing variable using the provided path, or create a
                 // new value in the store.
                 match ctx.state_mut().variable_mut(ident) {
                     Some(stored) => stored.insert_by_path(path, value),
-                    None => ctx
-                        .state_mut()
-                        .insert_variable(ident.clone(), value.at_path(path)),
+                    None => {
+                        ctx.state_mut()
+                            .insert_variable(ident.clone(), value.at_path(path));
+                    }
                 }
             }
 
@@ -1,4 +1,5 @@
-use crate::expression::{levenstein, ExpressionError, FunctionArgument, Noop};
+use crate::expression::{levenstein, Block, ExpressionError, FunctionArgument, Noop};
+use crate::function::closure::{self, FunctionClosure};
 use crate::function::{ArgumentList, FunctionCompileContext, Parameter};
 use crate::parser::{Ident, Node};
 use crate::{value::Kind, Context, Expression, Function, Resolved, Span, State, TypeDef};
@@ -12,6 +13,9 @@ pub struct FunctionCall {
     expr: Box<dyn Expression>,
     maybe_fallible_arguments: bool,
 
+    // Whether the block of the closure passed to the function can fail.
+    closure_fallible: bool,
+
     // used for enhancing runtime error messages (using abort-instruction).
     //
     // TODO: have span store line/col details to further improve this.
@@ -28,14 +32,21 @@ pub struct FunctionCall {
 }
 
 impl FunctionCall {
-    pub fn new(
+    /// Prepares a call to the function with the given `ident`.
+    ///
+    /// Function calls are compiled in two steps, as a closure attached to the
+    /// call can only be compiled once the types of its variables are known,
+    /// which in turn depend on the arguments passed to the function. See
+    /// [`Builder::compile`] for the second step.
+    pub fn builder<'a>(
         call_span: Span,
         ident: Node<Ident>,
         abort_on_error: bool,
         arguments: Vec<Node<FunctionArgument>>,
-        funcs: &[Box<dyn Function>],
-        state: &mut State,
-    ) -> Result<Self, Error> {
+        funcs: &'a [Box<dyn Function>],
+        state: &State,
+        closure_variables: Option<Node<Vec<Node<Ident>>>>,
+    ) -> Result<Builder<'a>, Error> {
         let (ident_span, ident) = ident.take();
 
         // Check if function exists.
@@ -162,36 +173,94 @@ impl FunctionCall {
                 })
             })?;
 
-        let compile_ctx = FunctionCompileContext { span: call_span };
+        // Check the closure against the one the function accepts, and infer the
+        // types of its variables.
+        let closure = match (function.closure(), closure_variables) {
+            (None, None) => None,
+            (None, Some(variables)) => {
+                return Err(Error::UnexpectedClosure {
+                    call_span,
+                    closure_span: variables.span(),
+                })
+            }
+            (Some(definition), None) => {
+                let example = definition.inputs.first().map(|input| input.example);
 
-        let mut expr = function
-            .compile(state, &compile_ctx, list)
-            .map_err(|error| Error::Compilation { call_span, error })?;
+                return Err(Error::MissingClosure { call_span, example });
+            }
+            (Some(definition), Some(variables)) => {
+                let (variables_span, variables) = variables.take();
+
+                // Only inputs matching the kind of the argument they apply to
+                // are relevant, e.g. iterating over something that can be an
+                // object or an array yields either keys or indices.
+                let mut inputs = definition
+                    .inputs
+                    .iter()
+                    .filter_map(|input| {
+                        let kind = list
+                            .get(input.parameter_keyword)
+                            .map(|expr| expr.type_def(state).kind() & input.kind)
+                            .filter(|kind| !kind.is_empty())?;
 
-        // Asking for an infallible function to abort on error makes no sense.
-        // We consider this an error at compile-time, because it makes the
-        // resulting program incorrectly convey this function call might fail.
-        if abort_on_error && !maybe_fallible_arguments && !expr.type_def(state).is_fallible() {
-            return Err(Error::AbortInfallible {
-                ident_span,
-                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
-            });
-        }
+                        Some((input, kind))
+                    })
+                    .peekable();
 
-        // Update the state if necessary.
-        expr.update_state(state).map_err(|err| Error::UpdateState {
-            call_span,
-            error: err.to_string(),
-        })?;
+                let (first, _) = *inputs.peek().ok_or_else(|| Error::MissingClosure {
+                    call_span,
+                    example: definition.inputs.first().map(|input| input.example),
+                })?;
+
+                let expected = first.variables.len();
+                let output = first.output.clone();
+
+                if variables.len() != expected {
+                    return Err(Error::ClosureArityMismatch {
+                        ident_span,
+                        closure_arguments_span: variables_span,
+                        expected,
+                        supplied: variables.len(),
+                    });
+                }
+
+                let mut type_defs: Vec<Option<TypeDef>> = vec![None; expected];
+                for (input, kind) in inputs {
+                    let target = list
+                        .get(input.parameter_keyword)
+                        .expect("exists")
+                        .type_def(state);
+
+                    for (type_def, variable) in type_defs.iter_mut().zip(&input.variables) {
+                        let variable = variable.kind.type_def(kind, &target);
+
+                        *type_def = Some(match type_def.take() {
+                            Some(type_def) => type_def.merge(variable),
+                            None => variable,
+                        });
+                    }
+                }
 
-        Ok(Self {
+                let variables = variables
+                    .into_iter()
+                    .map(Node::into_inner)
+                    .zip(type_defs.into_iter().map(Option::unwrap_or_default))
+                    .collect();
+
+                Some((variables, output))
+            }
+        };
+
+        Ok(Builder {
             abort_on_error,
-            expr,
-            maybe_fallible_arguments,
-            span: call_span,
             arguments_fmt,
             arguments_dbg,
-            ident: function.identifier(),
+            call_span,
+            closure,
+            function: function.as_ref(),
+            ident_span,
+            list,
+            maybe_fallible_arguments,
         })
     }
 
@@ -202,6 +271,7 @@ impl FunctionCall {
             abort_on_error: false,
             expr,
             maybe_fallible_arguments: false,
+            closure_fallible: false,
             span: Span::default(),
             arguments_fmt: vec![],
             arguments_dbg: vec![],
@@ -210,12 +280,112 @@ impl FunctionCall {
     }
 }
 
+/// A function call for which all arguments have been checked, but which
+/// still needs to be compiled.
+pub struct Builder<'a> {
+    abort_on_error: bool,
+    arguments_fmt: Vec<String>,
+    arguments_dbg: Vec<String>,
+    call_span: Span,
+    closure: Option<(Vec<(Ident, TypeDef)>, closure::Output)>,
+    function: &'a dyn Function,
+    ident_span: Span,
+    list: ArgumentList,
+    maybe_fallible_arguments: bool,
+}
+
+impl<'a> Builder<'a> {
+    /// The variables of the closure attached to the function call, with their
+    /// inferred type definitions.
+    ///
+    /// These need to be defined while compiling the closure block.
+    pub fn closure_variables(&self) -> &[(Ident, TypeDef)] {
+        self.closure
+            .as_ref()
+            .map(|(variables, _)| variables.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Compiles the function call, along with the block of its closure, and
+    /// the type definition the block resolves to.
+    pub fn compile(
+        mut self,
+        state: &mut State,
+        closure_block: Option<(Node<Block>, TypeDef)>,
+    ) -> Result<FunctionCall, Error> {
+        let call_span = self.call_span;
+        let ident_span = self.ident_span;
+
+        let mut closure_fallible = false;
+        if let (Some((variables, output)), Some((block, type_def))) =
+            (self.closure.take(), closure_block)
+        {
+            let (block_span, block) = block.take();
+
+            // Check if the closure resolves to the expected type.
+            if let closure::Output::Kind(expected) = output {
+                if !expected.contains(type_def.kind()) {
+                    return Err(Error::ReturnTypeMismatch {
+                        block_span,
+                        found_kind: type_def.kind(),
+                        expected_kind: expected,
+                    });
+                }
+            }
+
+            closure_fallible = type_def.is_fallible();
+
+            let variables = variables.into_iter().map(|(ident, _)| ident).collect();
+            self.list
+                .set_closure(FunctionClosure::new(variables, block, type_def));
+        }
+
+        let compile_ctx = FunctionCompileContext { span: call_span };
+
+        let mut expr = self
+            .function
+            .compile(state, &compile_ctx, self.list)
+            .map_err(|error| Error::Compilation { call_span, error })?;
+
+        // Asking for an infallible function to abort on error makes no sense.
+        // We consider this an error at compile-time, because it makes the
+        // resulting program incorrectly convey this function call might fail.
+        if self.abort_on_error
+            && !self.maybe_fallible_arguments
+            && !closure_fallible
+            && !expr.type_def(state).is_fallible()
+        {
+            return Err(Error::AbortInfallible {
+                ident_span,
+                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+            });
+        }
+
+        // Update the state if necessary.
+        expr.update_state(state).map_err(|err| Error::UpdateState {
+            call_span,
+            error: err.to_string(),
+        })?;
+
+        Ok(FunctionCall {
+            abort_on_error: self.abort_on_error,
+            expr,
+            maybe_fallible_arguments: self.maybe_fallible_arguments,
+            closure_fallible,
+            span: call_span,
+            arguments_fmt: self.arguments_fmt,
+            arguments_dbg: self.arguments_dbg,
+            ident: self.function.identifier(),
+        })
+    }
+}
+
 impl Expression for FunctionCall {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         self.expr.resolve(ctx).map_err(|err| match err {
-            ExpressionError::Abort { .. } => {
-                panic!("abort errors must only be defined by `abort` statement")
-            }
+            // A closure can contain an `abort` statement, which aborts the
+            // program as a whole.
+            err @ ExpressionError::Abort { .. } => err,
             ExpressionError::Error {
                 message,
                 mut labels,
@@ -301,6 +471,11 @@ impl Expression for FunctionCall {
             type_def.fallible = true;
         }
 
+        // Errors returned by the closure are returned by the function.
+        if self.closure_fallible {
+            type_def.fallible = true;
+        }
+
         if self.abort_on_error {
             type_def.fallible = false;
         }
@@ -407,6 +582,30 @@ pub enum Error {
 
     #[error("error updating state {}", error)]
     UpdateState { call_span: Span, error: String },
+
+    #[error("unexpected closure")]
+    UnexpectedClosure { call_span: Span, closure_span: Span },
+
+    #[error("missing closure")]
+    MissingClosure {
+        call_span: Span,
+        example: Option<&'static str>,
+    },
+
+    #[error("invalid closure arguments")]
+    ClosureArityMismatch {
+        ident_span: Span,
+        closure_arguments_span: Span,
+        expected: usize,
+        supplied: usize,
+    },
+
+    #[error("return type mismatch")]
+    ReturnTypeMismatch {
+        block_span: Span,
+        found_kind: Kind,
+        expected_kind: Kind,
+    },
 }
 
 impl DiagnosticError for Error {
@@ -423,6 +622,10 @@ impl DiagnosticError for Error {
             InvalidArgumentKind { .. } => 110,
             FallibleArgument { .. } => 630,
             UpdateState { .. } => 640,
+            UnexpectedClosure { .. } => 109,
+            MissingClosure { .. } => 111,
+            ClosureArityMismatch { .. } => 120,
+            ReturnTypeMismatch { .. } => 122,
         }
     }
 
@@ -576,6 +779,57 @@ impl DiagnosticError for Error {
                 format!("an error occurred updating the compiler state: {}", error),
                 call_span,
             )],
+
+            UnexpectedClosure {
+                call_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("this function does not accept a closure", call_span),
+            ],
+
+            MissingClosure { call_span, .. } => {
+                vec![Label::primary("this function expects a closure", call_span)]
+            }
+
+            ClosureArityMismatch {
+                ident_span,
+                closure_arguments_span,
+                expected,
+                supplied,
+            } => {
+                let variable = |n: usize| if n == 1 { "variable" } else { "variables" };
+
+                vec![
+                    Label::primary(
+                        format!("this closure defines {} {}", supplied, variable(*supplied)),
+                        closure_arguments_span,
+                    ),
+                    Label::context(
+                        format!(
+                            "this function expects a closure defining {} {}",
+                            expected,
+                            variable(*expected)
+                        ),
+                        ident_span,
+                    ),
+                ]
+            }
+
+            ReturnTypeMismatch {
+                block_span,
+                found_kind,
+                expected_kind,
+            } => vec![
+                Label::primary(format!("this block resolves to {}", found_kind), block_span),
+                Label::context(
+                    format!(
+                        "but the closure is expected to resolve to {}",
+                        expected_kind
+                    ),
+                    block_span,
+                ),
+            ],
         }
     }
 
@@ -655,6 +909,11 @@ impl DiagnosticError for Error {
 
             Compilation { error, .. } => error.notes(),
 
+            MissingClosure {
+                example: Some(example),
+                ..
+            } => Note::solution("adding a closure to the function call", vec![*example]),
+
             _ => vec![],
         }
     }
@@ -1,4 +1,6 @@
-use crate::expression::{assignment, Container, FunctionCall, Resolved, Variable};
+use crate::expression::{
+    assignment, Container, FunctionCall, Resolved, UserFunctionCall, Variable,
+};
 use crate::parser::ast::Ident;
 use crate::{Context, Expression, State, TypeDef, Value};
 use lookup::LookupBuf;
@@ -41,6 +43,7 @@ impl Query {
     pub fn expression_target(&self) -> Option<&dyn Expression> {
         match &self.target {
             Target::FunctionCall(expr) => Some(expr),
+            Target::UserFunctionCall(expr) => Some(expr),
             Target::Container(expr) => Some(expr),
             _ => None,
         }
@@ -73,6 +76,7 @@ impl Expression for Query {
             }
             Internal(variable) => variable.resolve(ctx)?,
             FunctionCall(call) => call.resolve(ctx)?,
+            UserFunctionCall(call) => call.resolve(ctx)?,
             Container(container) => container.resolve(ctx)?,
         };
 
@@ -114,6 +118,7 @@ impl Expression for Query {
 
             Internal(variable) => variable.type_def(state).at_path(self.path.clone()),
             FunctionCall(call) => call.type_def(state).at_path(self.path.clone()),
+            UserFunctionCall(call) => call.type_def(state).at_path(self.path.clone()),
             Container(container) => container.type_def(state).at_path(self.path.clone()),
         }
     }
@@ -136,6 +141,7 @@ pub enum Target {
     Internal(Variable),
     External,
     FunctionCall(FunctionCall),
+    UserFunctionCall(UserFunctionCall),
     Container(Container),
 }
 
@@ -147,6 +153,7 @@ impl fmt::Display for Target {
             Internal(v) => v.fmt(f),
             External => write!(f, "."),
             FunctionCall(v) => v.fmt(f),
+            UserFunctionCall(v) => v.fmt(f),
             Container(v) => v.fmt(f),
         }
     }
@@ -160,6 +167,7 @@ impl fmt::Debug for Target {
             Internal(v) => write!(f, "Internal({:?})", v),
             External => f.write_str("External"),
             FunctionCall(v) => v.fmt(f),
+            UserFunctionCall(v) => v.fmt(f),
             Container(v) => v.fmt(f),
         }
     }
@@ -0,0 +1,425 @@
+use crate::expression::{Block, Expr, ExpressionError, Resolved};
+use crate::parser::{Ident, Node};
+use crate::{Context, Expression, Span, State, TypeDef};
+use diagnostic::{DiagnosticError, Label, Note, Urls};
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// A function defined by the program itself, or by one of the files it
+/// imports.
+///
+/// A function can only be called after it has been defined, which means it
+/// can't call itself, and a program still runs to completion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction {
+    ident: Ident,
+    parameters: Vec<Ident>,
+    block: Block,
+    type_def: TypeDef,
+
+    // The type definition of the target after the function has been called,
+    // if the function changes the target.
+    target: Option<TypeDef>,
+}
+
+impl UserFunction {
+    pub(crate) fn new(
+        ident: Ident,
+        parameters: Vec<Ident>,
+        block: Block,
+        type_def: TypeDef,
+        target: Option<TypeDef>,
+    ) -> Self {
+        Self {
+            ident,
+            parameters,
+            block,
+            type_def,
+            target,
+        }
+    }
+
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    pub fn parameters(&self) -> &[Ident] {
+        &self.parameters
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunctionCall {
+    function: Arc<UserFunction>,
+    arguments: Vec<Expr>,
+    abort_on_error: bool,
+
+    // used for enhancing runtime error messages (using abort-instruction).
+    span: Span,
+}
+
+impl UserFunctionCall {
+    pub(crate) fn new(
+        call_span: Span,
+        ident_span: Span,
+        function: Arc<UserFunction>,
+        abort_on_error: bool,
+        arguments: Vec<(Option<Node<Ident>>, Node<Expr>)>,
+        state: &mut State,
+    ) -> Result<Self, Error> {
+        let max = function.parameters.len();
+        let mut list: Vec<Option<Expr>> = vec![None; max];
+
+        for (keyword, expr) in arguments {
+            let (expr_span, expr) = expr.take();
+
+            let position = match keyword {
+                Some(keyword) => {
+                    let (keyword_span, keyword) = keyword.take();
+
+                    match function.parameters.iter().position(|p| p == &keyword) {
+                        Some(position) => position,
+                        None => {
+                            return Err(Error::UnknownKeyword {
+                                keyword_span,
+                                ident_span,
+                                keywords: function.parameters.clone(),
+                            })
+                        }
+                    }
+                }
+                None => match list.iter().position(Option::is_none) {
+                    Some(position) => position,
+                    None => {
+                        return Err(Error::WrongNumberOfArgs {
+                            arguments_span: expr_span,
+                            max,
+                        })
+                    }
+                },
+            };
+
+            if list[position].is_some() {
+                return Err(Error::DuplicateArgument {
+                    expr_span,
+                    keyword: function.parameters[position].clone(),
+                });
+            }
+
+            if expr.type_def(state).is_fallible() {
+                return Err(Error::FallibleArgument { expr_span });
+            }
+
+            list[position] = Some(expr);
+        }
+
+        let arguments = list
+            .into_iter()
+            .enumerate()
+            .map(|(position, expr)| {
+                expr.ok_or_else(|| Error::MissingArgument {
+                    call_span,
+                    keyword: function.parameters[position].clone(),
+                    position,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Asking for an infallible function to abort on error makes no sense,
+        // the same as for the functions of the standard library.
+        if abort_on_error && !function.type_def.is_fallible() {
+            return Err(Error::AbortInfallible {
+                ident_span,
+                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+            });
+        }
+
+        // Any changes the function makes to the target apply once it is called,
+        // rather than where it is defined.
+        if let Some(target) = &function.target {
+            let type_def = match state.target_type_def() {
+                Some(type_def) => type_def.clone().merge(target.clone()),
+                None => target.clone(),
+            };
+
+            state.update_target(super::assignment::Details {
+                type_def,
+                value: None,
+            });
+        }
+
+        Ok(Self {
+            function,
+            arguments,
+            abort_on_error,
+            span: call_span,
+        })
+    }
+
+    pub fn noop() -> Self {
+        let function = UserFunction {
+            ident: Ident::new("noop"),
+            parameters: vec![],
+            block: Block::new(vec![]),
+            type_def: TypeDef::new().null().infallible(),
+            target: None,
+        };
+
+        Self {
+            function: Arc::new(function),
+            arguments: vec![],
+            abort_on_error: false,
+            span: Span::default(),
+        }
+    }
+}
+
+impl Expression for UserFunctionCall {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut variables = HashMap::with_capacity(self.arguments.len());
+        for (ident, expr) in self.function.parameters.iter().zip(&self.arguments) {
+            variables.insert(ident.clone(), expr.resolve(ctx)?);
+        }
+
+        // The function block only has access to its own parameters and
+        // variables, the variables of the caller are restored afterwards.
+        let variables = ctx.state_mut().swap_variables(variables);
+        let resolved = self.function.block.resolve(ctx);
+        ctx.state_mut().swap_variables(variables);
+
+        resolved.map_err(|err| match err {
+            err @ ExpressionError::Abort { .. } => err,
+            ExpressionError::Error {
+                message,
+                mut labels,
+                notes,
+            } => {
+                labels.push(Label::primary(message.clone(), self.span));
+
+                ExpressionError::Error {
+                    message: format!(
+                        r#"function call error for "{}" at ({}:{}): {}"#,
+                        self.function.ident,
+                        self.span.start(),
+                        self.span.end(),
+                        message
+                    ),
+                    labels,
+                    notes,
+                }
+            }
+        })
+    }
+
+    fn type_def(&self, _: &State) -> TypeDef {
+        let mut type_def = self.function.type_def.clone();
+
+        if self.abort_on_error {
+            type_def.fallible = false;
+        }
+
+        type_def
+    }
+}
+
+impl fmt::Display for UserFunctionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.function.ident.fmt(f)?;
+
+        if self.abort_on_error {
+            f.write_str("!")?;
+        }
+
+        f.write_str("(")?;
+
+        let mut iter = self.arguments.iter().peekable();
+        while let Some(arg) = iter.next() {
+            arg.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(")")
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("function already defined")]
+    AlreadyDefined { ident_span: Span, builtin: bool },
+
+    #[error("duplicate function parameter")]
+    DuplicateParameter { parameter_span: Span },
+
+    #[error("wrong number of function arguments")]
+    WrongNumberOfArgs { arguments_span: Span, max: usize },
+
+    #[error("unknown function argument keyword")]
+    UnknownKeyword {
+        keyword_span: Span,
+        ident_span: Span,
+        keywords: Vec<Ident>,
+    },
+
+    #[error("duplicate function argument")]
+    DuplicateArgument { expr_span: Span, keyword: Ident },
+
+    #[error("missing function argument")]
+    MissingArgument {
+        call_span: Span,
+        keyword: Ident,
+        position: usize,
+    },
+
+    #[error("can't abort infallible function")]
+    AbortInfallible { ident_span: Span, abort_span: Span },
+
+    #[error("fallible argument")]
+    FallibleArgument { expr_span: Span },
+
+    #[error("unexpected closure")]
+    UnexpectedClosure { call_span: Span, closure_span: Span },
+}
+
+impl DiagnosticError for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            AlreadyDefined { .. } => 130,
+            DuplicateParameter { .. } => 131,
+            WrongNumberOfArgs { .. } => 106,
+            UnknownKeyword { .. } => 108,
+            DuplicateArgument { .. } => 132,
+            MissingArgument { .. } => 107,
+            AbortInfallible { .. } => 620,
+            FallibleArgument { .. } => 630,
+            UnexpectedClosure { .. } => 109,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            AlreadyDefined {
+                ident_span,
+                builtin,
+            } => {
+                let context = if *builtin {
+                    "a function of the standard library exists with the same name"
+                } else {
+                    "a function with the same name is defined before this one"
+                };
+
+                vec![
+                    Label::primary("this function is already defined", ident_span),
+                    Label::context(context, ident_span),
+                ]
+            }
+
+            DuplicateParameter { parameter_span } => vec![
+                Label::primary("this parameter is already defined", parameter_span),
+                Label::context("function parameters must be unique", parameter_span),
+            ],
+
+            WrongNumberOfArgs {
+                arguments_span,
+                max,
+            } => {
+                let arg = if *max == 1 { "argument" } else { "arguments" };
+
+                vec![
+                    Label::primary("too many function arguments", arguments_span),
+                    Label::context(
+                        format!("this function takes a maximum of {} {}", max, arg),
+                        arguments_span,
+                    ),
+                ]
+            }
+
+            UnknownKeyword {
+                keyword_span,
+                ident_span,
+                keywords,
+            } => vec![
+                Label::primary("unknown keyword", keyword_span),
+                Label::context(
+                    format!(
+                        "this function accepts the following keywords: {}",
+                        keywords
+                            .iter()
+                            .map(|k| format!(r#""{}""#, k))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    ident_span,
+                ),
+            ],
+
+            DuplicateArgument { expr_span, keyword } => vec![
+                Label::primary(
+                    format!(r#"argument "{}" is already provided"#, keyword),
+                    expr_span,
+                ),
+                Label::context("each argument can only be provided once", expr_span),
+            ],
+
+            MissingArgument {
+                call_span,
+                keyword,
+                position,
+            } => vec![Label::primary(
+                format!(
+                    r#"required argument missing: "{}" (position {})"#,
+                    keyword, position
+                ),
+                call_span,
+            )],
+
+            AbortInfallible {
+                ident_span,
+                abort_span,
+            } => vec![
+                Label::primary("this function can't fail", ident_span),
+                Label::context("remove this abort-instruction", abort_span),
+            ],
+
+            FallibleArgument { expr_span } => vec![
+                Label::primary("this expression can fail", expr_span),
+                Label::context(
+                    "handle the error before passing it in as an argument",
+                    expr_span,
+                ),
+            ],
+
+            UnexpectedClosure {
+                call_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("this function does not accept a closure", call_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::*;
+
+        match self {
+            AlreadyDefined { .. } | DuplicateParameter { .. } => vec![Note::SeeDocs(
+                "function definitions".to_owned(),
+                Urls::expression_docs_url("#function_definition"),
+            )],
+            WrongNumberOfArgs { .. } => vec![Note::SeeDocs(
+                "function arguments".to_owned(),
+                Urls::expression_docs_url("#arguments"),
+            )],
+            AbortInfallible { .. } | FallibleArgument { .. } => vec![Note::SeeErrorDocs],
+            _ => vec![],
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::expression::{Block, Resolved};
+use crate::parser::Ident;
+use crate::value::Kind;
+use crate::{Context, Expression, TypeDef, Value};
+
+/// The definition of a closure accepted by a [`Function`](super::Function).
+///
+/// A function can accept a closure for one or more kinds of its input value,
+/// for example to iterate over both the key/value pairs of an object, and the
+/// index/value pairs of an array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub inputs: Vec<Input>,
+}
+
+/// The closure definition for a given kind of input value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    /// The keyword of the parameter providing the value the closure is run
+    /// against.
+    pub parameter_keyword: &'static str,
+
+    /// The kind of the value this input applies to.
+    pub kind: Kind,
+
+    /// The variables the function sets before resolving the closure.
+    pub variables: Vec<Variable>,
+
+    /// The value the closure block is expected to resolve to.
+    pub output: Output,
+
+    /// An example showing how the closure is used for this input, used in
+    /// diagnostic messages.
+    pub example: &'static str,
+}
+
+/// A variable set by the function, before resolving the closure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+    pub kind: VariableKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariableKind {
+    /// The variable always holds a value of the given kind.
+    Exact(Kind),
+
+    /// The variable holds the key (for objects) or index (for arrays) of the
+    /// input value the closure is currently run for.
+    TargetInnerKey,
+
+    /// The variable holds the value of the input value the closure is
+    /// currently run for.
+    ///
+    /// Its type definition is inferred from the type definition of the input
+    /// value.
+    TargetInnerValue,
+}
+
+impl VariableKind {
+    /// The type definition of the variable, given the type definition of the
+    /// input value the closure is run against.
+    pub(crate) fn type_def(&self, input_kind: Kind, target: &TypeDef) -> TypeDef {
+        match self {
+            Self::Exact(kind) => (*kind).into(),
+            Self::TargetInnerKey => {
+                let mut kind = Kind::empty();
+                if input_kind.contains_object() {
+                    kind |= Kind::Bytes;
+                }
+                if input_kind.contains_array() {
+                    kind |= Kind::Integer;
+                }
+
+                kind.into()
+            }
+            Self::TargetInnerValue => {
+                let mut type_def: Option<TypeDef> = None;
+                let mut merge = |values: TypeDef| {
+                    type_def = Some(match type_def.take() {
+                        Some(type_def) => type_def.merge(values),
+                        None => values,
+                    })
+                };
+
+                if input_kind.contains_object() {
+                    merge(target.object_values());
+                }
+                if input_kind.contains_array() {
+                    merge(target.array_values());
+                }
+
+                type_def.unwrap_or_default()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Output {
+    /// The closure block can resolve to any value.
+    Any,
+
+    /// The closure block must resolve to a value of the given kind.
+    Kind(Kind),
+}
+
+/// A compiled closure, passed to the function as part of its
+/// [`ArgumentList`](super::ArgumentList).
+///
+/// Functions run the closure once for every item of a finite, already resolved
+/// value. Closures can't be assigned to variables or passed around, and a
+/// function can't call itself from within its closure, which guarantees that a
+/// program still runs to completion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionClosure {
+    variables: Vec<Ident>,
+    block: Block,
+    type_def: TypeDef,
+}
+
+impl FunctionClosure {
+    pub(crate) fn new(variables: Vec<Ident>, block: Block, type_def: TypeDef) -> Self {
+        Self {
+            variables,
+            block,
+            type_def,
+        }
+    }
+
+    /// The type definition of the value the closure block resolves to.
+    ///
+    /// This is determined at compile-time, while the closure variables are
+    /// defined.
+    pub fn type_def(&self) -> &TypeDef {
+        &self.type_def
+    }
+
+    /// Runs the closure, with its variables set to the given values.
+    ///
+    /// Any variables shadowed by those of the closure are restored afterwards.
+    pub fn run<I>(&self, ctx: &mut Context, values: I) -> Resolved
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let state = ctx.state_mut();
+        let shadowed = self
+            .variables
+            .iter()
+            .zip(values)
+            .map(|(ident, value)| (ident, state.insert_variable(ident.clone(), value)))
+            .collect::<Vec<_>>();
+
+        let resolved = self.block.resolve(ctx);
+
+        let state = ctx.state_mut();
+        for (ident, value) in shadowed {
+            match value {
+                Some(value) => state.insert_variable(ident.clone(), value),
+                None => state.remove_variable(ident),
+            };
+        }
+
+        resolved
+    }
+}
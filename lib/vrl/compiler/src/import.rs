@@ -0,0 +1,157 @@
+use crate::Span;
+use diagnostic::{DiagnosticError, File, Label, Note, Urls};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Resolves the path of an imported file.
+///
+/// Relative paths are looked up in each of the import paths in order, the
+/// first existing file wins. Absolute paths are used as-is.
+pub(crate) fn resolve(path: &str, import_paths: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(path);
+
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_owned());
+    }
+
+    import_paths
+        .iter()
+        .map(|dir| dir.join(path))
+        .find(|path| path.is_file())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unresolved import")]
+    NotFound { path_span: Span, path: String },
+
+    #[error("unreadable import")]
+    Read {
+        path_span: Span,
+        path: String,
+        error: std::io::Error,
+    },
+
+    #[error("cyclic import")]
+    Cyclic { path_span: Span, path: String },
+
+    #[error("unexpected expression in imported file")]
+    UnexpectedExpression { span: Span },
+}
+
+impl DiagnosticError for Error {
+    fn code(&self) -> usize {
+        use Error::*;
+
+        match self {
+            NotFound { .. } => 140,
+            Read { .. } => 141,
+            Cyclic { .. } => 142,
+            UnexpectedExpression { .. } => 143,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::*;
+
+        match self {
+            NotFound { path_span, path } => vec![
+                Label::primary(format!(r#"file "{}" not found"#, path), path_span),
+                Label::context(
+                    "imported files are looked up in the import paths",
+                    path_span,
+                ),
+            ],
+
+            Read {
+                path_span,
+                path,
+                error,
+            } => vec![Label::primary(
+                format!(r#"file "{}" could not be read: {}"#, path, error),
+                path_span,
+            )],
+
+            Cyclic { path_span, path } => vec![
+                Label::primary(
+                    format!(r#"file "{}" is already being imported"#, path),
+                    path_span,
+                ),
+                Label::context("files can't import each other", path_span),
+            ],
+
+            UnexpectedExpression { span } => vec![
+                Label::primary("unexpected expression", span),
+                Label::context(
+                    "imported files can only contain function definitions and imports",
+                    span,
+                ),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeDocs(
+            "imports".to_owned(),
+            Urls::expression_docs_url("#import"),
+        )]
+    }
+}
+
+/// An error raised while compiling an imported file.
+///
+/// The labels of the error point into the imported file.
+#[derive(Debug)]
+pub struct Imported {
+    file: File,
+    error: Box<dyn DiagnosticError>,
+}
+
+impl Imported {
+    /// Ties the error to the imported file, unless it is already tied to a
+    /// file imported by that file.
+    pub(crate) fn wrap(error: Box<dyn DiagnosticError>, file: &File) -> Box<dyn DiagnosticError> {
+        if error.file().is_some() {
+            return error;
+        }
+
+        Box::new(Self {
+            file: file.clone(),
+            error,
+        })
+    }
+}
+
+impl fmt::Display for Imported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for Imported {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl DiagnosticError for Imported {
+    fn code(&self) -> usize {
+        self.error.code()
+    }
+
+    fn message(&self) -> String {
+        self.error.message()
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        self.error.labels()
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        self.error.notes()
+    }
+
+    fn file(&self) -> Option<&File> {
+        Some(&self.file)
+    }
+}
@@ -1,5 +1,6 @@
 mod compiler;
 mod context;
+mod import;
 mod program;
 mod target;
 mod test_util;
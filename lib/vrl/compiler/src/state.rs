@@ -1,6 +1,11 @@
-use crate::expression::assignment;
+use crate::expression::{assignment, UserFunction};
 use crate::{parser::ast::Ident, TypeDef, Value};
-use std::{any::Any, collections::HashMap};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
 /// The state held by the compiler.
 ///
@@ -14,6 +19,15 @@ pub struct Compiler {
     /// stored internal variable type definitions
     variables: HashMap<Ident, assignment::Details>,
 
+    /// functions defined by the program, or by the files it imports.
+    functions: HashMap<Ident, Arc<UserFunction>>,
+
+    /// directories searched for the files imported by the program.
+    import_paths: Vec<PathBuf>,
+
+    /// files imported by the program so far.
+    imported: HashSet<PathBuf>,
+
     /// context passed between the client program and a VRL function.
     external_context: Option<Box<dyn Any>>,
 
@@ -54,8 +68,46 @@ impl Compiler {
         self.variables.get(ident)
     }
 
-    pub(crate) fn insert_variable(&mut self, ident: Ident, details: assignment::Details) {
-        self.variables.insert(ident, details);
+    pub(crate) fn insert_variable(
+        &mut self,
+        ident: Ident,
+        details: assignment::Details,
+    ) -> Option<assignment::Details> {
+        self.variables.insert(ident, details)
+    }
+
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) -> Option<assignment::Details> {
+        self.variables.remove(ident)
+    }
+
+    /// Replaces all variables, returning the previous ones.
+    ///
+    /// This is used to compile the block of a function definition, which only
+    /// has access to its own parameters and variables.
+    pub(crate) fn swap_variables(
+        &mut self,
+        variables: HashMap<Ident, assignment::Details>,
+    ) -> HashMap<Ident, assignment::Details> {
+        std::mem::replace(&mut self.variables, variables)
+    }
+
+    pub(crate) fn function(&self, ident: &Ident) -> Option<&Arc<UserFunction>> {
+        self.functions.get(ident)
+    }
+
+    pub(crate) fn insert_function(&mut self, function: UserFunction) {
+        self.functions
+            .insert(function.ident().clone(), Arc::new(function));
+    }
+
+    pub(crate) fn import_paths(&self) -> &[PathBuf] {
+        &self.import_paths
+    }
+
+    /// Marks the file at the given (canonical) path as imported, returning
+    /// `false` if it has been imported before.
+    pub(crate) fn insert_imported(&mut self, path: PathBuf) -> bool {
+        self.imported.insert(path)
     }
 
     pub(crate) fn target(&self) -> Option<&assignment::Details> {
@@ -66,6 +118,14 @@ impl Compiler {
         self.target = Some(details);
     }
 
+    /// Replaces the target details, returning the previous ones.
+    pub(crate) fn swap_target(
+        &mut self,
+        target: Option<assignment::Details>,
+    ) -> Option<assignment::Details> {
+        std::mem::replace(&mut self.target, target)
+    }
+
     /// Take a snapshot of the current state of the compiler.
     ///
     /// This overwrites any existing snapshot currently stored.
@@ -76,6 +136,9 @@ impl Compiler {
         let snapshot = Self {
             target,
             variables,
+            functions: self.functions.clone(),
+            import_paths: self.import_paths.clone(),
+            imported: self.imported.clone(),
             external_context: None,
             snapshot: None,
         };
@@ -97,6 +160,12 @@ impl Compiler {
         self.target.as_ref().map(|assignment| &assignment.type_def)
     }
 
+    /// Sets the directories searched, in order, for the files imported by the
+    /// program.
+    pub fn set_import_paths(&mut self, paths: Vec<PathBuf>) {
+        self.import_paths = paths;
+    }
+
     /// Sets the external context data for VRL functions to use.
     pub fn set_external_context(&mut self, data: Option<Box<dyn Any>>) {
         self.external_context = data;
@@ -142,7 +211,19 @@ impl Runtime {
         self.variables.get_mut(ident)
     }
 
-    pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) {
-        self.variables.insert(ident, value);
+    pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) -> Option<Value> {
+        self.variables.insert(ident, value)
+    }
+
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) -> Option<Value> {
+        self.variables.remove(ident)
+    }
+
+    /// Replaces all variables, returning the previous ones.
+    pub(crate) fn swap_variables(
+        &mut self,
+        variables: HashMap<Ident, Value>,
+    ) -> HashMap<Ident, Value> {
+        std::mem::replace(&mut self.variables, variables)
     }
 }
@@ -1173,6 +1173,55 @@ impl TypeDef {
         }
     }
 
+    /// Returns the type definition of the values stored in any array defined
+    /// by this type def.
+    ///
+    /// This is used to infer the type of the values a function iterates over.
+    pub fn array_values(&self) -> Self {
+        self.collection_values(|k| match k {
+            TypeKind::Array(array) => Some(array.values().collect()),
+            _ => None,
+        })
+    }
+
+    /// Returns the type definition of the values stored in any object defined
+    /// by this type def.
+    ///
+    /// This is used to infer the type of the values a function iterates over.
+    pub fn object_values(&self) -> Self {
+        self.collection_values(|k| match k {
+            TypeKind::Object(object) => Some(object.values().collect()),
+            _ => None,
+        })
+    }
+
+    fn collection_values<F>(&self, f: F) -> Self
+    where
+        F: Fn(&TypeKind) -> Option<Vec<&KindInfo>>,
+    {
+        let values = match &self.kind {
+            KindInfo::Unknown => vec![],
+            KindInfo::Known(set) => set.iter().filter_map(f).flatten().collect(),
+        };
+
+        // An unknown value means the collection can hold values of any kind,
+        // merging it with known kinds would narrow it down incorrectly.
+        let kind = if values.is_empty() || values.contains(&&KindInfo::Unknown) {
+            KindInfo::Unknown
+        } else {
+            values
+                .into_iter()
+                .cloned()
+                .reduce(|acc, kind| acc.merge(kind, false, false))
+                .unwrap_or(KindInfo::Unknown)
+        };
+
+        Self {
+            fallible: false,
+            kind,
+        }
+    }
+
     fn remove_segment<'a, I>(kind: &KindInfo, mut path: std::iter::Peekable<I>) -> KindInfo
     where
         I: std::iter::Iterator<Item = &'a SegmentBuf> + Clone,
@@ -1381,6 +1430,31 @@ mod tests {
         assert_eq!(kind, expected);
     }
 
+    #[test]
+    fn collection_values() {
+        let type_def = type_def! { object {
+            "foo" => type_def! { bytes },
+            "bar" => type_def! { array [
+                TypeDef::new().integer(),
+            ] },
+        } };
+
+        let mut expected = BTreeSet::new();
+        expected.insert(TypeKind::Bytes);
+        expected.insert(TypeKind::Array({
+            let mut set = BTreeSet::new();
+            set.insert(TypeKind::Integer);
+
+            let mut map = BTreeMap::new();
+            map.insert(Index::Any, KindInfo::Known(set));
+            map
+        }));
+
+        assert_eq!(type_def.object_values().kind, KindInfo::Known(expected));
+        assert!(type_def.array_values().is_unknown());
+        assert!(TypeDef::new().unknown().object_values().is_unknown());
+    }
+
     #[test]
     fn update_path() {
         struct TestCase {
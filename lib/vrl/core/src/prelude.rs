@@ -24,7 +24,10 @@ pub use std::fmt;
 
 // commonly used function types
 
-pub use compiler::function::{ArgumentList, Compiled, Example, FunctionCompileContext, Parameter};
+pub use compiler::function::{
+    closure, closure::FunctionClosure, ArgumentList, Compiled, Example, FunctionCompileContext,
+    Parameter,
+};
 
 // commonly used macros
 pub use compiler::{
@@ -1,4 +1,4 @@
-use crate::{DiagnosticError, Label, Note, Severity, Span};
+use crate::{DiagnosticError, File, Label, Note, Severity, Span};
 use codespan_reporting::diagnostic;
 use std::ops::{Deref, DerefMut};
 
@@ -9,6 +9,7 @@ pub struct Diagnostic {
     message: String,
     labels: Vec<Label>,
     notes: Vec<Note>,
+    file: Option<File>,
 }
 
 impl Diagnostic {
@@ -33,6 +34,7 @@ impl Diagnostic {
             message: message.to_string(),
             labels,
             notes,
+            file: None,
         }
     }
 
@@ -54,6 +56,13 @@ impl Diagnostic {
         self
     }
 
+    /// Ties the diagnostic to a file other than the source it is formatted
+    /// with, so that its labels point into that file instead.
+    pub fn with_file(mut self, file: File) -> Self {
+        self.file = Some(file);
+        self
+    }
+
     pub fn severity(&self) -> Severity {
         self.severity
     }
@@ -70,6 +79,10 @@ impl Diagnostic {
         &self.labels
     }
 
+    pub fn file(&self) -> Option<&File> {
+        self.file.as_ref()
+    }
+
     /// Returns `true` if the diagnostic represents either an
     /// [error](Severity::Error) or [bug](Severity::Bug).
     #[inline]
@@ -111,6 +124,7 @@ impl From<Box<dyn DiagnosticError>> for Diagnostic {
             message: error.message(),
             labels: error.labels(),
             notes: error.notes(),
+            file: error.file().cloned(),
         }
     }
 }
@@ -0,0 +1,26 @@
+/// A source file other than the one a diagnostic is reported for, such as a
+/// file imported by a VRL program.
+///
+/// The labels of a diagnostic tied to a file point into that file.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct File {
+    name: String,
+    source: String,
+}
+
+impl File {
+    pub fn new(name: impl ToString, source: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
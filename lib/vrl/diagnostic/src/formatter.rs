@@ -34,7 +34,6 @@ impl<'a> fmt::Display for Formatter<'a> {
         use std::str::from_utf8;
         use termcolor::Buffer;
 
-        let file = SimpleFile::new("", self.source);
         let config = term::Config::default();
         let mut buffer = if self.color {
             Buffer::ansi()
@@ -45,6 +44,13 @@ impl<'a> fmt::Display for Formatter<'a> {
         f.write_str("\n")?;
 
         for diagnostic in self.diagnostics.iter() {
+            // Diagnostics tied to another file, e.g. one imported by the
+            // program, are shown against that file instead.
+            let file = match diagnostic.file() {
+                Some(file) => SimpleFile::new(file.name(), file.source()),
+                None => SimpleFile::new("", self.source),
+            };
+
             term::emit(&mut buffer, &config, &file, &diagnostic.to_owned().into())
                 .map_err(|_| fmt::Error)?;
         }
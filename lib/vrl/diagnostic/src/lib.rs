@@ -1,4 +1,5 @@
 mod diagnostic;
+mod file;
 mod formatter;
 mod label;
 mod note;
@@ -6,6 +7,7 @@ mod severity;
 mod span;
 
 pub use diagnostic::{Diagnostic, DiagnosticList};
+pub use file::File;
 pub use formatter::Formatter;
 pub use label::Label;
 pub use note::Note;
@@ -41,6 +43,14 @@ pub trait DiagnosticError: std::error::Error {
     fn notes(&self) -> Vec<Note> {
         vec![]
     }
+
+    /// The file the labels of the error point into, if it isn't the source the
+    /// error is reported for.
+    ///
+    /// Defaults to no file.
+    fn file(&self) -> Option<&File> {
+        None
+    }
 }
 
 pub struct Urls;
@@ -246,6 +246,8 @@ pub enum Token<S> {
     False,
     True,
     Abort,
+    Fn,
+    Import,
 
     // tokens
     Colon,
@@ -266,6 +268,7 @@ pub enum Token<S> {
     MergeEquals,
     Bang,
     Question,
+    Arrow,
 
     /// The {L,R}Query token is an "instruction" token. It does not represent
     /// any character in the source, instead it represents the start or end of a
@@ -331,6 +334,8 @@ impl<S> Token<S> {
             Null => Null,
             True => True,
             Abort => Abort,
+            Fn => Fn,
+            Import => Import,
 
             // tokens
             Colon => Colon,
@@ -351,6 +356,7 @@ impl<S> Token<S> {
             MergeEquals => MergeEquals,
             Bang => Bang,
             Question => Question,
+            Arrow => Arrow,
 
             LQuery => LQuery,
             RQuery => RQuery,
@@ -385,6 +391,8 @@ where
             Null => "Null",
             True => "True",
             Abort => "Abort",
+            Fn => "Fn",
+            Import => "Import",
 
             // tokens
             Colon => "Colon",
@@ -405,6 +413,7 @@ where
             MergeEquals => "MergeEquals",
             Bang => "Bang",
             Question => "Question",
+            Arrow => "Arrow",
 
             LQuery => "LQuery",
             RQuery => "RQuery",
@@ -426,6 +435,8 @@ impl<'input> Token<&'input str> {
             "false" => False,
             "null" => Null,
             "abort" => Abort,
+            "fn" => Fn,
+            "import" => Import,
 
             // reserved identifiers
             "array" | "bool" | "boolean" | "break" | "continue" | "do" | "emit" | "float"
@@ -525,6 +536,11 @@ impl<'input> Iterator for Lexer<'input> {
                         Some(Ok(self.token(start, Bang)))
                     }
 
+                    '-' if self.test_peek(|ch| ch == '>') => {
+                        self.bump();
+                        Some(Ok(self.token(start, Arrow)))
+                    }
+
                     '#' => {
                         self.take_until(start, |ch| ch == '\n');
                         continue;
@@ -1663,6 +1679,57 @@ mod test {
         );
     }
 
+    #[test]
+    fn function_call_closures() {
+        test(
+            data(r#"foo() -> |a, b| {}"#),
+            vec![
+                (r#"~~~               "#, FunctionCall("foo")),
+                (r#"   ~              "#, LParen),
+                (r#"    ~             "#, RParen),
+                (r#"      ~~          "#, Arrow),
+                (r#"         ~        "#, Operator("|")),
+                (r#"          ~       "#, Identifier("a")),
+                (r#"           ~      "#, Comma),
+                (r#"             ~    "#, Identifier("b")),
+                (r#"              ~   "#, Operator("|")),
+                (r#"                ~ "#, LBrace),
+                (r#"                 ~"#, RBrace),
+            ],
+        );
+    }
+
+    #[test]
+    fn function_definitions() {
+        test(
+            data(r#"fn foo(a) { a }"#),
+            vec![
+                (r#"~~             "#, Fn),
+                (r#"   ~~~         "#, FunctionCall("foo")),
+                (r#"      ~        "#, LParen),
+                (r#"       ~       "#, Identifier("a")),
+                (r#"        ~      "#, RParen),
+                (r#"          ~    "#, LBrace),
+                (r#"            ~  "#, Identifier("a")),
+                (r#"              ~"#, RBrace),
+            ],
+        );
+    }
+
+    #[test]
+    fn imports() {
+        use StringLiteral as S;
+        use Token::StringLiteral as L;
+
+        test(
+            data(r#"import "foo.vrl""#),
+            vec![
+                (r#"~~~~~~          "#, Import),
+                (r#"       ~~~~~~~~~"#, L(S::Escaped("foo.vrl"))),
+            ],
+        );
+    }
+
     #[test]
     fn function_call_queries() {
         use StringLiteral as S;
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Filter;
+
+impl Function for Filter {
+    fn identifier(&self) -> &'static str {
+        "filter"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"filter({ "a": 1, "b": null, "c": 3 }) -> |_key, value| { !is_null(value) }"#,
+                result: Ok(r#"{ "a": 1, "c": 3 }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"filter([1, 2, 3, 4]) -> |index, _value| { index < 2 }"#,
+                result: Ok(r#"[1, 2]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(FilterFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        let variables = vec![
+            Variable {
+                kind: VariableKind::TargetInnerKey,
+            },
+            Variable {
+                kind: VariableKind::TargetInnerValue,
+            },
+        ];
+
+        Some(Definition {
+            inputs: vec![
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Object,
+                    variables: variables.clone(),
+                    output: Output::Kind(Kind::Boolean),
+                    example: r#"filter(.) -> |key, _value| { key != "foo" }"#,
+                },
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Array,
+                    variables,
+                    output: Output::Kind(Kind::Boolean),
+                    example: r#"filter(.tags) -> |_index, value| { value != "foo" }"#,
+                },
+            ],
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FilterFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for FilterFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                let mut filtered = BTreeMap::new();
+                for (key, value) in object {
+                    let keep = self
+                        .closure
+                        .run(ctx, vec![key.clone().into(), value.clone()])?
+                        .try_boolean()?;
+
+                    if keep {
+                        filtered.insert(key, value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            Value::Array(array) => {
+                let mut filtered = Vec::new();
+                for (index, value) in array.into_iter().enumerate() {
+                    let keep = self
+                        .closure
+                        .run(ctx, vec![(index as i64).into(), value.clone()])?
+                        .try_boolean()?;
+
+                    if keep {
+                        filtered.push(value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Object | Kind::Array,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        // Any key or index can be filtered out.
+        let type_def = self.value.type_def(state).collect_subtypes();
+        let kind = type_def.kind();
+
+        match (kind.contains_object(), kind.contains_array()) {
+            (true, true) => type_def
+                .clone()
+                .restrict_object()
+                .merge(type_def.restrict_array()),
+            (false, true) => type_def.restrict_array(),
+            _ => type_def.restrict_object(),
+        }
+        .infallible()
+    }
+}
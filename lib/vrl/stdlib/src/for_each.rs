@@ -0,0 +1,121 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ForEach;
+
+impl Function for ForEach {
+    fn identifier(&self) -> &'static str {
+        "for_each"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: indoc! {r#"
+                    total = 0
+                    for_each({ "a": 1, "b": 2 }) -> |_key, value| { total = total + value }
+                    total
+                "#},
+                result: Ok("3"),
+            },
+            Example {
+                title: "array",
+                source: indoc! {r#"
+                    keys = []
+                    for_each(["a", "b"]) -> |index, value| { keys = push(keys, value + to_string(index)) }
+                    keys
+                "#},
+                result: Ok(r#"["a0", "b1"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(ForEachFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        let variables = vec![
+            Variable {
+                kind: VariableKind::TargetInnerKey,
+            },
+            Variable {
+                kind: VariableKind::TargetInnerValue,
+            },
+        ];
+
+        Some(Definition {
+            inputs: vec![
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Object,
+                    variables: variables.clone(),
+                    output: Output::Any,
+                    example: r#"for_each(.) -> |key, value| { log(key + ": " + string!(value)) }"#,
+                },
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Array,
+                    variables,
+                    output: Output::Any,
+                    example: r#"for_each(.tags) -> |index, value| { log(value) }"#,
+                },
+            ],
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ForEachFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for ForEachFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                for (key, value) in object {
+                    self.closure.run(ctx, vec![key.into(), value])?;
+                }
+            }
+            Value::Array(array) => {
+                for (index, value) in array.into_iter().enumerate() {
+                    self.closure.run(ctx, vec![(index as i64).into(), value])?;
+                }
+            }
+            value => {
+                return Err(value::Error::Expected {
+                    got: value.kind(),
+                    expected: Kind::Object | Kind::Array,
+                }
+                .into())
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().null()
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapKeys;
+
+impl Function for MapKeys {
+    fn identifier(&self) -> &'static str {
+        "map_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "upcase keys",
+                source: r#"map_keys({ "foo": 1, "bar": 2 }) -> |key| { upcase(key) }"#,
+                result: Ok(r#"{ "FOO": 1, "BAR": 2 }"#),
+            },
+            Example {
+                title: "recursive",
+                source: r#"map_keys({ "a": { "b": [{ "c": true }] } }, recursive: true) -> |key| { "x_" + key }"#,
+                result: Ok(r#"{ "x_a": { "x_b": [{ "x_c": true }] } }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let recursive = arguments.optional("recursive");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapKeysFn {
+            value,
+            recursive,
+            closure,
+        }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::Object,
+                variables: vec![Variable {
+                    kind: VariableKind::TargetInnerKey,
+                }],
+                output: Output::Kind(Kind::Bytes),
+                example: r#"map_keys(.) -> |key| { downcase(key) }"#,
+            }],
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapKeysFn {
+    value: Box<dyn Expression>,
+    recursive: Option<Box<dyn Expression>>,
+    closure: FunctionClosure,
+}
+
+impl MapKeysFn {
+    fn map_object(
+        &self,
+        ctx: &mut Context,
+        object: BTreeMap<String, Value>,
+        recursive: bool,
+    ) -> Result<BTreeMap<String, Value>> {
+        let mut mapped = BTreeMap::new();
+        for (key, value) in object {
+            let value = if recursive {
+                self.map_nested(ctx, value)?
+            } else {
+                value
+            };
+
+            let key = self.closure.run(ctx, vec![key.into()])?;
+            mapped.insert(key.try_bytes_utf8_lossy()?.into_owned(), value);
+        }
+
+        Ok(mapped)
+    }
+
+    /// Maps the keys of any object nested in `value`, which is bounded by the
+    /// depth of the value itself.
+    fn map_nested(&self, ctx: &mut Context, value: Value) -> Resolved {
+        match value {
+            Value::Object(object) => Ok(self.map_object(ctx, object, true)?.into()),
+            Value::Array(array) => {
+                let mut mapped = Vec::with_capacity(array.len());
+                for value in array {
+                    mapped.push(self.map_nested(ctx, value)?);
+                }
+
+                Ok(mapped.into())
+            }
+            value => Ok(value),
+        }
+    }
+}
+
+impl Expression for MapKeysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let recursive = match &self.recursive {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
+
+        let object = self.value.resolve(ctx)?.try_object()?;
+
+        Ok(self.map_object(ctx, object, recursive)?.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        // The keys of nested objects are unknown once mapped.
+        let values = if self.recursive.is_some() {
+            TypeDef::new().unknown()
+        } else {
+            self.value.type_def(state).object_values()
+        };
+
+        TypeDef::new().object::<(), TypeDef>(map! { (): values })
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapValues;
+
+impl Function for MapValues {
+    fn identifier(&self) -> &'static str {
+        "map_values"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"map_values({ "a": 1, "b": 2 }) -> |value| { value * 10 }"#,
+                result: Ok(r#"{ "a": 10, "b": 20 }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"map_values(["foo", "bar"]) -> |value| { upcase(value) }"#,
+                result: Ok(r#"["FOO", "BAR"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapValuesFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::Object | Kind::Array,
+                variables: vec![Variable {
+                    kind: VariableKind::TargetInnerValue,
+                }],
+                output: Output::Any,
+                example: r#"map_values(.) -> |value| { value }"#,
+            }],
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapValuesFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for MapValuesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                let mut mapped = BTreeMap::new();
+                for (key, value) in object {
+                    mapped.insert(key, self.closure.run(ctx, vec![value])?);
+                }
+
+                Ok(mapped.into())
+            }
+            Value::Array(array) => {
+                let mut mapped = Vec::with_capacity(array.len());
+                for value in array {
+                    mapped.push(self.closure.run(ctx, vec![value])?);
+                }
+
+                Ok(mapped.into())
+            }
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Object | Kind::Array,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let kind = self.value.type_def(state).kind();
+        let values = self.closure.type_def().clone().infallible();
+
+        match (kind.contains_object(), kind.contains_array()) {
+            (true, true) => TypeDef::new()
+                .object::<(), TypeDef>(map! { (): values.clone() })
+                .add_array_mapped::<(), TypeDef>(map! { (): values }),
+            (false, true) => TypeDef::new().array_mapped::<(), TypeDef>(map! { (): values }),
+            _ => TypeDef::new().object::<(), TypeDef>(map! { (): values }),
+        }
+    }
+}
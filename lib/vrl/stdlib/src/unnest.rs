@@ -92,6 +92,10 @@ impl Expression for UnnestFn {
                 value = expr.resolve(ctx)?;
                 Box::new(&value as &dyn Target) as Box<&dyn Target>
             }
+            expression::Target::UserFunctionCall(expr) => {
+                value = expr.resolve(ctx)?;
+                Box::new(&value as &dyn Target) as Box<&dyn Target>
+            }
         };
 
         let root = target.get(&LookupBuf::root())?.unwrap_or(Value::Null);
@@ -127,6 +131,9 @@ impl Expression for UnnestFn {
             },
             Target::Internal(v) => invert_array_at_path(&v.type_def(state), self.path.path()),
             Target::FunctionCall(f) => invert_array_at_path(&f.type_def(state), self.path.path()),
+            Target::UserFunctionCall(f) => {
+                invert_array_at_path(&f.type_def(state), self.path.path())
+            }
             Target::Container(c) => invert_array_at_path(&c.type_def(state), self.path.path()),
         }
     }
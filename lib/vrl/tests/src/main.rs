@@ -5,6 +5,7 @@ use chrono::{DateTime, SecondsFormat, Utc};
 use chrono_tz::Tz;
 use glob::glob;
 use shared::TimeZone;
+use std::path::PathBuf;
 use std::str::FromStr;
 use structopt::StructOpt;
 use vrl::{diagnostic::Formatter, state, Runtime, Terminate, Value};
@@ -133,7 +134,10 @@ fn main() {
         let mut functions = stdlib::all();
         functions.append(&mut enrichment::vrl_functions());
         let test_enrichment = Box::new(test_enrichment::test_enrichment_table());
-        let program = vrl::compile(&test.source, &functions, Some(test_enrichment.clone()));
+        let mut compiler_state = state::Compiler::new();
+        compiler_state.set_external_context(Some(test_enrichment.clone()));
+        compiler_state.set_import_paths(vec![PathBuf::from("modules")]);
+        let program = vrl::compile_with_state(&test.source, &functions, &mut compiler_state);
         test_enrichment.finish_load();
 
         let want = test.result.clone();
@@ -17,6 +17,7 @@ pub enum SourceOutputType {
     Any,
     Log,
     Metric,
+    Trace,
 }
 
 impl From<DataType> for SourceOutputType {
@@ -25,6 +26,7 @@ impl From<DataType> for SourceOutputType {
             DataType::Metric => SourceOutputType::Metric,
             DataType::Log => SourceOutputType::Log,
             DataType::Any => SourceOutputType::Any,
+            DataType::Trace => SourceOutputType::Trace,
         }
     }
 }
@@ -0,0 +1,52 @@
+use super::log::{EventDirection, Log};
+use crate::{config::ComponentKey, event::Event, topology::invocations::Invocation};
+
+use async_graphql::Object;
+
+#[derive(Debug)]
+pub struct TransformInvocation {
+    component_key: ComponentKey,
+    input: Option<Log>,
+    outputs: Vec<Log>,
+}
+
+impl TransformInvocation {
+    pub fn new(component_key: ComponentKey, invocation: Invocation) -> Self {
+        let log = |event, direction| match event {
+            Event::Log(event) => Some(Log::new(component_key.clone(), direction, event)),
+            _ => None,
+        };
+
+        let input = log(invocation.input, EventDirection::Input);
+        let outputs = invocation
+            .outputs
+            .into_iter()
+            .filter_map(|event| log(event, EventDirection::Output))
+            .collect();
+
+        Self {
+            component_key,
+            input,
+            outputs,
+        }
+    }
+}
+
+#[Object]
+/// A single invocation of a transform, pairing the event it received with the events it emitted
+impl TransformInvocation {
+    /// Id of the transform
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Log event received by the transform
+    async fn input(&self) -> Option<&Log> {
+        self.input.as_ref()
+    }
+
+    /// Log events emitted by the transform in response to the input, on any of its outputs
+    async fn outputs(&self) -> &[Log] {
+        &self.outputs
+    }
+}
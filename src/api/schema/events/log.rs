@@ -1,19 +1,47 @@
 use super::EventEncodingType;
-use crate::config::OutputId;
+use crate::api::tap::TapPoint;
+use crate::config::ComponentKey;
 use crate::event::{self, Value};
 
-use async_graphql::Object;
+use async_graphql::{Enum, Object};
 use chrono::{DateTime, Utc};
 
+#[derive(Enum, Debug, Copy, Clone, PartialEq, Eq)]
+/// Whether an event was observed going into or coming out of a component
+pub enum EventDirection {
+    /// The event was received by the component
+    Input,
+    /// The event was emitted by the component
+    Output,
+}
+
+impl From<&TapPoint> for EventDirection {
+    fn from(point: &TapPoint) -> Self {
+        match point {
+            TapPoint::Output(_) => Self::Output,
+            TapPoint::Input(..) => Self::Input,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Log {
-    output_id: OutputId,
+    component_key: ComponentKey,
+    direction: EventDirection,
     event: event::LogEvent,
 }
 
 impl Log {
-    pub const fn new(output_id: OutputId, event: event::LogEvent) -> Self {
-        Self { output_id, event }
+    pub const fn new(
+        component_key: ComponentKey,
+        direction: EventDirection,
+        event: event::LogEvent,
+    ) -> Self {
+        Self {
+            component_key,
+            direction,
+            event,
+        }
     }
 
     pub fn get_message(&self) -> Option<String> {
@@ -30,7 +58,12 @@ impl Log {
 impl Log {
     /// Id of the component associated with the log event
     async fn component_id(&self) -> &str {
-        self.output_id.component.id()
+        self.component_key.id()
+    }
+
+    /// Whether the log event went into or came out of the component
+    async fn direction(&self) -> EventDirection {
+        self.direction
     }
 
     /// Log message
@@ -1,4 +1,5 @@
 mod encoding;
+mod invocation;
 mod log;
 mod notification;
 mod output;
@@ -6,7 +7,10 @@ mod output;
 use encoding::EventEncodingType;
 use output::OutputEventsPayload;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::{TapController, TapFilter, TapPatterns},
+    topology::WatchRx,
+};
 
 use async_graphql::{validators::IntRange, Context, Subscription};
 use futures::Stream;
@@ -20,27 +24,77 @@ pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
-    /// A stream of events emitted from matched component ID patterns
+    /// A stream of events emitted from matched component ID patterns, and optionally of the
+    /// events received by components matching `inputPatterns`
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
         patterns: Vec<String>,
+        input_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(IntRange(min = "1", max = "10_000")))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+        filter: Option<String>,
+        #[graphql(default = 1, validator(IntRange(min = "1", max = "1_000_000")))] sample_rate: u32,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
+        let patterns = TapPatterns {
+            outputs: patterns.into_iter().collect(),
+            inputs: input_patterns.into_iter().flatten().collect(),
+            ..Default::default()
+        };
 
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            tap_filter(filter, sample_rate)?,
+            interval as u64,
+            limit as usize,
+        ))
     }
+
+    /// A stream of the invocations of transforms matching the component ID patterns, each
+    /// pairing the event received by the transform with the events it emitted in return. The
+    /// filter and sample rate apply to the received event
+    pub async fn transform_invocations_by_component_id_patterns<'a>(
+        &'a self,
+        ctx: &'a Context<'a>,
+        patterns: Vec<String>,
+        #[graphql(default = 500)] interval: u32,
+        #[graphql(default = 100, validator(IntRange(min = "1", max = "10_000")))] limit: u32,
+        filter: Option<String>,
+        #[graphql(default = 1, validator(IntRange(min = "1", max = "1_000_000")))] sample_rate: u32,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
+        let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
+        let patterns = TapPatterns {
+            invocations: patterns.into_iter().collect(),
+            ..Default::default()
+        };
+
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            tap_filter(filter, sample_rate)?,
+            interval as u64,
+            limit as usize,
+        ))
+    }
+}
+
+/// Creates the filter of a tap. The VRL condition is compiled up front so errors are returned
+/// to the client.
+fn tap_filter(filter: Option<String>, sample_rate: u32) -> async_graphql::Result<TapFilter> {
+    TapFilter::new(filter, sample_rate as usize)
+        .map_err(|error| async_graphql::Error::new(error.to_string()))
 }
 
 /// Creates an events stream based on component ids, and a provided interval. Will emit
 /// control messages that bubble up the application if the sink goes away. The stream contains
-/// all matching events; filtering should be done at the caller level.
+/// the events of matching components which pass the filter.
 fn create_events_stream(
     watch_rx: WatchRx,
-    component_id_patterns: Vec<String>,
+    patterns: TapPatterns,
+    filter: TapFilter,
     interval: u64,
     limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
@@ -56,7 +110,7 @@ fn create_events_stream(
     tokio::spawn(async move {
         // Create a tap controller. When this drops out of scope, clean up will be performed on the
         // event handlers and topology observation that the tap controller provides.
-        let _tap_controller = TapController::new(watch_rx, tap_tx, &component_id_patterns);
+        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns, filter);
 
         // A tick interval to represent when to 'cut' the results back to the client.
         let mut interval = time::interval(time::Duration::from_millis(interval));
@@ -81,8 +135,8 @@ fn create_events_stream(
 
         loop {
             select! {
-                // Process `TapPayload`s. A tap payload could contain log/metric events or a
-                // notification. Notifications are emitted immediately; events buffer until
+                // Process `TapPayload`s. A tap payload could contain log/metric events, a
+                // transform invocation or a notification. Notifications are emitted immediately; events buffer until
                 // the next `interval`.
                 Some(payload) = tap_rx.recv() => {
                     let payload = payload.into();
@@ -1,4 +1,5 @@
 use super::{
+    invocation::TransformInvocation,
     log::Log,
     notification::{EventNotification, EventNotificationType},
 };
@@ -7,11 +8,14 @@ use crate::api::tap::{TapNotification, TapPayload};
 use async_graphql::Union;
 
 #[derive(Union, Debug)]
-/// An event or a notification
+/// An event, a transform invocation or a notification
 pub enum OutputEventsPayload {
     /// Log event
     Log(Log),
 
+    /// Transform invocation
+    TransformInvocation(TransformInvocation),
+
     // Notification
     Notification(EventNotification),
 }
@@ -20,7 +24,12 @@ pub enum OutputEventsPayload {
 impl From<TapPayload> for OutputEventsPayload {
     fn from(t: TapPayload) -> Self {
         match t {
-            TapPayload::Log(output_id, ev) => Self::Log(Log::new(output_id, ev)),
+            TapPayload::Log(point, ev) => {
+                Self::Log(Log::new(point.component().clone(), (&point).into(), ev))
+            }
+            TapPayload::Invocation(component_key, invocation) => {
+                Self::TransformInvocation(TransformInvocation::new(component_key, invocation))
+            }
             TapPayload::Notification(component_key, n) => match n {
                 TapNotification::Matched => Self::Notification(EventNotification::new(
                     component_key,
@@ -2,10 +2,10 @@ use super::{handler, schema, ShutdownTx};
 use crate::{config, topology};
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
-    Data, Request, Schema,
+    Data, Request, Schema, Value,
 };
 use async_graphql_warp::{graphql_subscription_with_data, Response as GQLResponse};
-use std::{convert::Infallible, net::SocketAddr};
+use std::{borrow::Cow, convert::Infallible, net::SocketAddr};
 use tokio::sync::oneshot;
 use warp::{filters::BoxedFilter, http::Response, Filter, Reply};
 
@@ -71,7 +71,9 @@ fn make_routes(playground: bool, watch_tx: topology::WatchRx) -> BoxedFilter<(im
         })
         .or(async_graphql_warp::graphql(schema).and_then(
             |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
+                let mut response = schema.execute(request).await;
+                redact_response(&mut response);
+                Ok::<_, Infallible>(GQLResponse::from(response))
             },
         )),
     );
@@ -117,3 +119,26 @@ fn make_routes(playground: bool, watch_tx: topology::WatchRx) -> BoxedFilter<(im
         )
         .boxed()
 }
+
+/// Masks the config secrets that could have made their way into a response.
+fn redact_response(response: &mut async_graphql::Response) {
+    redact_value(&mut response.data);
+    for error in response.errors.iter_mut() {
+        if let Cow::Owned(message) = config::secret::redact(&error.message) {
+            error.message = message;
+        }
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::String(string) => {
+            if let Cow::Owned(redacted) = config::secret::redact(string) {
+                *string = redacted;
+            }
+        }
+        Value::List(values) => values.iter_mut().for_each(redact_value),
+        Value::Object(fields) => fields.values_mut().for_each(redact_value),
+        _ => {}
+    }
+}
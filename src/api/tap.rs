@@ -1,15 +1,18 @@
 use super::{ShutdownRx, ShutdownTx};
 use crate::topology::fanout::ControlChannel;
 use crate::{
+    conditions::{Condition, ConditionConfig, VrlConfig},
     config::{ComponentKey, OutputId},
     event::{Event, LogEvent},
-    topology::{fanout, WatchRx},
+    topology::{
+        fanout,
+        invocations::{Invocation, InvocationSubscription, InvocationTap},
+        WatchRx,
+    },
 };
 use futures::{future::try_join_all, FutureExt, Sink, SinkExt};
-use itertools::Itertools;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    iter::FromIterator,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -40,12 +43,33 @@ pub enum TapNotification {
     NotMatched,
 }
 
-/// A tap payload can either contain a log/metric event or a notification that's intended
-/// to be communicated back to the client to alert them about the status of the tap request.
+/// Where in the topology a tapped event was observed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TapPoint {
+    /// An output of the component.
+    Output(OutputId),
+    /// An input of the component, being the output of an upstream component.
+    Input(ComponentKey, OutputId),
+}
+
+impl TapPoint {
+    /// The tapped component.
+    pub fn component(&self) -> &ComponentKey {
+        match self {
+            Self::Output(output_id) => &output_id.component,
+            Self::Input(component, _) => component,
+        }
+    }
+}
+
+/// A tap payload can either contain a log/metric event, a transform invocation, or a
+/// notification that's intended to be communicated back to the client to alert them about the
+/// status of the tap request.
 #[derive(Debug)]
 pub enum TapPayload {
-    Log(OutputId, LogEvent),
-    Metric(OutputId, LogEvent),
+    Log(TapPoint, LogEvent),
+    Metric(TapPoint, LogEvent),
+    Invocation(ComponentKey, Invocation),
     Notification(String, TapNotification),
 }
 
@@ -61,19 +85,91 @@ impl TapPayload {
     }
 }
 
+/// The component ID patterns a tap observes, for each part of the topology.
+#[derive(Debug, Clone, Default)]
+pub struct TapPatterns {
+    /// Patterns of the components whose output events are observed.
+    pub outputs: HashSet<String>,
+    /// Patterns of the components whose input events are observed.
+    pub inputs: HashSet<String>,
+    /// Patterns of the transforms whose invocations are observed.
+    pub invocations: HashSet<String>,
+}
+
+impl TapPatterns {
+    /// Every pattern, whatever it applies to.
+    fn all(&self) -> HashSet<&String> {
+        self.outputs
+            .iter()
+            .chain(self.inputs.iter())
+            .chain(self.invocations.iter())
+            .collect()
+    }
+}
+
+/// Server-side filtering of the events relayed to a tap client, applied before they're
+/// serialized so that only the events of interest leave the topology.
+#[derive(Clone)]
+pub struct TapFilter {
+    condition: Option<Box<dyn Condition>>,
+    sample_rate: usize,
+}
+
+impl TapFilter {
+    /// Creates a filter keeping the events matching the VRL `condition`, if any, and out of
+    /// those, one in every `sample_rate`.
+    pub fn new(condition: Option<String>, sample_rate: usize) -> crate::Result<Self> {
+        let condition = condition
+            .map(|source| VrlConfig { source }.build(&Default::default()))
+            .transpose()?;
+
+        Ok(Self {
+            condition,
+            sample_rate: sample_rate.max(1),
+        })
+    }
+
+    /// Whether the event passes the condition and is sampled. `matched` is the caller's count
+    /// of events which passed the condition so far.
+    fn accepts(&self, event: &Event, matched: &mut usize) -> bool {
+        if let Some(condition) = &self.condition {
+            if !condition.check(event) {
+                return false;
+            }
+        }
+
+        let sampled = *matched % self.sample_rate == 0;
+        *matched = matched.wrapping_add(1);
+        sampled
+    }
+}
+
+impl Default for TapFilter {
+    fn default() -> Self {
+        Self {
+            condition: None,
+            sample_rate: 1,
+        }
+    }
+}
+
 /// A `TapSink` is used as an output channel for a topology component, and receives
 /// `Event`s. If these are of type `Event::LogEvent`, they are relayed to the tap client.
 pub struct TapSink {
     tap_tx: TapSender,
-    output_id: OutputId,
+    point: TapPoint,
+    filter: TapFilter,
+    matched: usize,
     buffer: VecDeque<LogEvent>,
 }
 
 impl TapSink {
-    pub fn new(tap_tx: TapSender, output_id: OutputId) -> Self {
+    pub fn new(tap_tx: TapSender, point: TapPoint, filter: TapFilter) -> Self {
         Self {
             tap_tx,
-            output_id,
+            point,
+            filter,
+            matched: 0,
             // Pre-allocate space of 100 events, which matches the default `limit` typically
             // provided to a tap subscription. If there's a higher log volume, this will block
             // until the upstream event handler has processed the event. Generally, there should
@@ -91,11 +187,19 @@ impl Sink<Event> for TapSink {
         Poll::Ready(Ok(()))
     }
 
-    /// If the sink is ready, and the event is of type `LogEvent`, add to the buffer.
+    /// If the sink is ready, and the event is of type `LogEvent` passing the filter, add to
+    /// the buffer.
     fn start_send(mut self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        // If we have a `LogEvent`, and space for it in the buffer, queue it.
+        if !matches!(item, Event::Log(_)) {
+            return Ok(());
+        }
+
+        let this = &mut *self;
+        let accepted = this.filter.accepts(&item, &mut this.matched);
+
+        // If we have an accepted `LogEvent`, and space for it in the buffer, queue it.
         if let Event::Log(ev) = item {
-            if self.buffer.len() < self.buffer.capacity() {
+            if accepted && self.buffer.len() < self.buffer.capacity() {
                 self.buffer.push_back(ev);
             }
         }
@@ -115,13 +219,13 @@ impl Sink<Event> for TapSink {
             // full, return pending to reattempt later.
             match self
                 .tap_tx
-                .try_send(TapPayload::Log(self.output_id.clone(), ev))
+                .try_send(TapPayload::Log(self.point.clone(), ev))
             {
                 Err(tokio_mpsc::error::TrySendError::Closed(payload)) => {
                     debug!(
                         message = "Couldn't send log event.",
                         payload = ?payload,
-                        component_id = ?self.output_id,
+                        tap_point = ?self.point,
                     );
 
                     break;
@@ -150,15 +254,15 @@ impl TapController {
     /// Creates a new tap sink, and spawns a handler for watching for topology changes
     /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
     /// of handlers when the `TapSink` drops out of scope.
-    pub fn new(watch_rx: WatchRx, tap_tx: TapSender, component_id_patterns: &[String]) -> Self {
+    pub fn new(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        filter: TapFilter,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
 
-        tokio::spawn(tap_handler(
-            component_id_patterns.iter().cloned().collect(),
-            tap_tx,
-            watch_rx,
-            shutdown_rx,
-        ));
+        tokio::spawn(tap_handler(patterns, filter, tap_tx, watch_rx, shutdown_rx));
 
         Self { _shutdown }
     }
@@ -196,112 +300,208 @@ async fn send_not_matched(tx: TapSender, pattern: &str) -> Result<(), SendError<
     tx.send(TapPayload::not_matched(pattern)).await
 }
 
+/// Returns the patterns matching the given component or output ID.
+fn matching<'a>(patterns: &'a HashSet<String>, id: &str) -> Vec<&'a String> {
+    patterns
+        .iter()
+        .filter(|pattern| pattern.matches_glob(id))
+        .collect()
+}
+
+/// Connects a `TapSink` to a fanout, returning a shutdown trigger which disconnects it again.
+async fn connect_sink(
+    point: TapPoint,
+    mut control_tx: ControlChannel,
+    tx: TapSender,
+    filter: TapFilter,
+) -> Option<ShutdownTx> {
+    let sink_id = Uuid::new_v4().to_string();
+    let sink = TapSink::new(tx, point.clone(), filter);
+
+    match control_tx
+        .send(fanout::ControlMessage::Add(
+            ComponentKey::from(&sink_id),
+            Box::new(sink),
+        ))
+        .await
+    {
+        Ok(_) => {
+            debug!(message = "Sink connected.", ?sink_id, tap_point = ?point);
+
+            // Create a sink shutdown trigger to remove the sink when matched components change.
+            Some(shutdown_trigger(control_tx, ComponentKey::global(&sink_id)))
+        }
+        Err(error) => {
+            error!(
+                message = "Couldn't connect sink.",
+                ?error,
+                tap_point = ?point,
+                ?sink_id,
+            );
+            None
+        }
+    }
+}
+
+/// Subscribes to the invocations of a transform, relaying those whose input is a `LogEvent`
+/// accepted by the filter. The subscription ends when the returned value is dropped.
+fn subscribe_invocations(
+    component: ComponentKey,
+    tap: &InvocationTap,
+    filter: TapFilter,
+    tx: TapSender,
+) -> InvocationSubscription {
+    let (invocation_tx, mut invocation_rx) = tokio_mpsc::channel(100);
+    let subscription = tap.subscribe(invocation_tx);
+
+    tokio::spawn(async move {
+        let mut matched = 0;
+        while let Some(invocation) = invocation_rx.recv().await {
+            if !matches!(invocation.input, Event::Log(_))
+                || !filter.accepts(&invocation.input, &mut matched)
+            {
+                continue;
+            }
+
+            match tx.try_send(TapPayload::Invocation(component.clone(), invocation)) {
+                Err(tokio_mpsc::error::TrySendError::Closed(_)) => break,
+                Err(tokio_mpsc::error::TrySendError::Full(_)) => {
+                    debug!(
+                        message = "Couldn't send invocation; tap is full.",
+                        component_id = %component,
+                    );
+                }
+                Ok(_) => {}
+            }
+        }
+    });
+
+    subscription
+}
+
 /// Returns a tap handler that listens for topology changes, and connects sinks to observe
 /// `LogEvent`s` when a component matches one or more of the provided patterns.
 async fn tap_handler(
-    component_id_patterns: HashSet<String>,
+    patterns: TapPatterns,
+    filter: TapFilter,
     tx: TapSender,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
 ) {
-    debug!(message = "Started tap.", patterns = ?component_id_patterns);
+    debug!(message = "Started tap.", ?patterns);
+
+    // Sinks register for the current tap. Contains the tapped point of the matched component,
+    // and a shutdown trigger for sending a remove control message when matching sinks change.
+    let mut sinks: HashMap<TapPoint, _> = HashMap::new();
 
-    // Sinks register for the current tap. Contains the id of the matched component, and
-    // a shutdown trigger for sending a remove control message when matching sinks change.
-    let mut sinks: HashMap<OutputId, _> = HashMap::new();
+    // Invocation subscriptions of the matched transforms.
+    let mut subscriptions: HashMap<ComponentKey, InvocationSubscription> = HashMap::new();
+
+    // Patterns that matched on the last iteration, to compare with the latest round of matches
+    // when sending notifications.
+    let mut last_matched: HashSet<String> = HashSet::new();
 
     loop {
         tokio::select! {
             _ = &mut shutdown_rx => break,
             Ok(_) = watch_rx.changed() => {
-                // Get the patterns that matched on the last iteration, to compare with the latest
-                // round of matches when sending notifications.
-                let last_matches = component_id_patterns
-                    .iter()
-                    .filter(|pattern| sinks.keys().any(|id| pattern.matches_glob(&id.to_string())))
-                    .collect::<HashSet<_>>();
-
                 // Cache of matched patterns. A `HashSet` is used here to ignore repetition.
                 let mut matched = HashSet::new();
 
-                // Borrow and clone the latest outputs to register sinks. Since this blocks the
+                // Borrow and clone the latest resource to register sinks. Since this blocks the
                 // watch channel and the returned ref isn't `Send`, this requires a clone.
-                let outputs = watch_rx.borrow().clone();
-
-                // Loop over all outputs, and connect sinks for the components that match one
-                // or more patterns.
-                for (output_id, mut control_tx) in outputs.iter() {
-                    match component_id_patterns
-                        .iter()
-                        .filter(|pattern| pattern.matches_glob(&output_id.to_string()))
-                        .collect_vec()
-                    {
-                        found if !found.is_empty() => {
-                            debug!(
-                                message="Component matched.",
-                                ?output_id, ?component_id_patterns, matched = ?found
-                            );
-
-                            // (Re)connect the sink. This is necessary because a sink may be
-                            // reconfigured with the same id as a previous, and we are not
-                            // getting involved in config diffing at this point.
-                            let sink_id = Uuid::new_v4().to_string();
-                            let sink = TapSink::new(tx.clone(), output_id.clone());
-
-                            // Attempt to connect the sink.
-                            match control_tx
-                                .send(fanout::ControlMessage::Add(ComponentKey::from(&sink_id), Box::new(sink)))
-                                .await
-                            {
-                                Ok(_) => {
-                                    debug!(
-                                        message = "Sink connected.", ?sink_id, ?output_id,
-                                    );
-
-                                    // Create a sink shutdown trigger to remove the sink
-                                    // when matched components change.
-                                    sinks
-                                        .insert(output_id.clone(), shutdown_trigger(control_tx.clone(), ComponentKey::global(&sink_id)));
-                                }
-                                Err(error) => {
-                                    error!(
-                                        message = "Couldn't connect sink.",
-                                        ?error,
-                                        ?output_id,
-                                        ?sink_id,
-                                    );
-                                }
-                            }
+                let resource = watch_rx.borrow().clone();
+
+                // (Re)connect sinks to the outputs matching one or more patterns. This is
+                // necessary because a component may be reconfigured with the same id as a
+                // previous one, and we are not getting involved in config diffing at this point.
+                for (output_id, control_tx) in resource.outputs.iter() {
+                    let found = matching(&patterns.outputs, &output_id.to_string());
+                    if found.is_empty() {
+                        debug!(message = "Component not matched.", ?output_id, ?patterns);
+                        continue;
+                    }
+                    debug!(message = "Component matched.", ?output_id, ?patterns, matched = ?found);
 
-                            matched.extend(found);
-                        }
-                        _ => {
-                            debug!(
-                                message="Component not matched.", ?output_id, ?component_id_patterns
-                            );
+                    let point = TapPoint::Output(output_id.clone());
+                    if let Some(trigger) = connect_sink(point.clone(), control_tx.clone(), tx.clone(), filter.clone()).await {
+                        sinks.insert(point, trigger);
+                    }
+                    matched.extend(found);
+                }
+
+                // The inputs of a component are observed on the outputs feeding it.
+                for (component, inputs) in resource.inputs.iter() {
+                    let found = matching(&patterns.inputs, &component.to_string());
+                    if found.is_empty() {
+                        continue;
+                    }
+                    debug!(message = "Component inputs matched.", ?component, ?patterns, matched = ?found);
+
+                    for input in inputs {
+                        if let Some(control_tx) = resource.outputs.get(input) {
+                            let point = TapPoint::Input(component.clone(), input.clone());
+                            if let Some(trigger) = connect_sink(point.clone(), control_tx.clone(), tx.clone(), filter.clone()).await {
+                                sinks.insert(point, trigger);
+                            }
                         }
                     }
+                    matched.extend(found);
+                }
+
+                // Subscribe to the invocations of matching transforms. A previous subscription
+                // is dropped first, so invocations aren't relayed twice.
+                for (component, tap) in resource.invocations.iter() {
+                    let found = matching(&patterns.invocations, &component.to_string());
+                    if found.is_empty() {
+                        continue;
+                    }
+                    debug!(message = "Transform invocations matched.", ?component, ?patterns, matched = ?found);
+
+                    subscriptions.remove(component);
+                    subscriptions.insert(
+                        component.clone(),
+                        subscribe_invocations(component.clone(), tap, filter.clone(), tx.clone()),
+                    );
+                    matched.extend(found);
                 }
 
                 // Remove components that have gone away.
-                sinks.retain(|id, _| {
-                    outputs.contains_key(id) || {
-                        debug!(message = "Removing component.", component_id = %id);
+                sinks.retain(|point, _| {
+                    let exists = match point {
+                        TapPoint::Output(output_id) => resource.outputs.contains_key(output_id),
+                        TapPoint::Input(component, input) => {
+                            resource.outputs.contains_key(input)
+                                && resource
+                                    .inputs
+                                    .get(component)
+                                    .map_or(false, |inputs| inputs.contains(input))
+                        }
+                    };
+                    exists || {
+                        debug!(message = "Removing component.", tap_point = ?point);
+                        false
+                    }
+                });
+                subscriptions.retain(|component, _| {
+                    resource.invocations.contains_key(component) || {
+                        debug!(message = "Removing transform.", component_id = %component);
                         false
                     }
                 });
 
                 // Send notifications to the client. The # of notifications will always be
                 // exactly equal to the number of patterns, so we can pre-allocate capacity.
-                let mut notifications = Vec::with_capacity(component_id_patterns.len());
+                let all_patterns = patterns.all();
+                let mut notifications = Vec::with_capacity(all_patterns.len());
 
                 // Matched notifications.
-                for pattern in matched.difference(&last_matches) {
+                for pattern in matched.iter().filter(|pattern| !last_matched.contains(**pattern)) {
                     notifications.push(send_matched(tx.clone(), pattern).boxed());
                 }
 
                 // Not matched notifications.
-                for pattern in HashSet::from_iter(&component_id_patterns).difference(&matched) {
+                for pattern in all_patterns.difference(&matched) {
                     notifications.push(send_not_matched(tx.clone(), pattern).boxed());
                 }
 
@@ -311,18 +511,21 @@ async fn tap_handler(
                     debug!("Couldn't send notification(s); tap gone away.");
                     break;
                 }
+
+                last_matched = matched.into_iter().cloned().collect();
             }
         }
     }
 
-    debug!(message = "Stopped tap.", patterns = ?component_id_patterns);
+    debug!(message = "Stopped tap.", ?patterns);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::event::{Metric, MetricKind, MetricValue};
+    use crate::event::{Metric, MetricKind, MetricValue, Value};
+    use crate::topology::TapResource;
     use futures::SinkExt;
     use tokio::sync::watch;
 
@@ -354,17 +557,28 @@ mod tests {
         let mut outputs = HashMap::new();
         outputs.insert(id.clone(), control_tx);
 
-        let (watch_tx, watch_rx) = watch::channel(HashMap::new());
+        let (watch_tx, watch_rx) = watch::channel(TapResource::default());
         let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
 
         let _controller = TapController::new(
             watch_rx,
             sink_tx,
-            &[pattern_matched.to_string(), pattern_not_matched.to_string()],
+            TapPatterns {
+                outputs: vec![pattern_matched.to_string(), pattern_not_matched.to_string()]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+            TapFilter::default(),
         );
 
         // Add the outputs to trigger a change event.
-        watch_tx.send(outputs).unwrap();
+        watch_tx
+            .send(TapResource {
+                outputs,
+                ..Default::default()
+            })
+            .unwrap();
 
         // First two events should contain a notification that one pattern matched, and
         // one that didn't.
@@ -402,7 +616,126 @@ mod tests {
         // 3rd payload should be the log event
         assert!(matches!(
             sink_rx.recv().await,
-            Some(TapPayload::Log(returned_id, _)) if returned_id == id
+            Some(TapPayload::Log(TapPoint::Output(returned_id), _)) if returned_id == id
         ));
     }
+
+    #[tokio::test]
+    /// Tapping the inputs of a component should relay the events of the outputs feeding it.
+    async fn sink_input_events() {
+        let upstream = OutputId::from(&ComponentKey::global("upstream"));
+        let component = ComponentKey::global("transform");
+
+        let (mut fanout, control_tx) = fanout::Fanout::new();
+        let mut resource = TapResource::default();
+        resource.outputs.insert(upstream.clone(), control_tx);
+        resource
+            .inputs
+            .insert(component.clone(), vec![upstream.clone()]);
+
+        let (watch_tx, watch_rx) = watch::channel(TapResource::default());
+        let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
+
+        let _controller = TapController::new(
+            watch_rx,
+            sink_tx,
+            TapPatterns {
+                inputs: vec!["trans*".to_string()].into_iter().collect(),
+                ..Default::default()
+            },
+            TapFilter::default(),
+        );
+        watch_tx.send(resource).unwrap();
+
+        assert!(matches!(
+            sink_rx.recv().await,
+            Some(TapPayload::Notification(pattern, TapNotification::Matched)) if pattern == "trans*"
+        ));
+
+        let _ = fanout.send(Event::new_empty_log()).await.unwrap();
+
+        assert!(matches!(
+            sink_rx.recv().await,
+            Some(TapPayload::Log(TapPoint::Input(returned_component, returned_id), _))
+                if returned_component == component && returned_id == upstream
+        ));
+    }
+
+    #[tokio::test]
+    /// Tapping the invocations of a transform should relay the ones accepted by the filter.
+    async fn relays_invocations() {
+        let component = ComponentKey::global("transform");
+        let tap = InvocationTap::default();
+
+        let mut resource = TapResource::default();
+        resource.invocations.insert(component.clone(), tap.clone());
+
+        let (watch_tx, watch_rx) = watch::channel(TapResource::default());
+        let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
+
+        let _controller = TapController::new(
+            watch_rx,
+            sink_tx,
+            TapPatterns {
+                invocations: vec!["transform".to_string()].into_iter().collect(),
+                ..Default::default()
+            },
+            TapFilter::new(Some(r#".message == "keep""#.to_string()), 1).unwrap(),
+        );
+        watch_tx.send(resource).unwrap();
+
+        assert!(matches!(
+            sink_rx.recv().await,
+            Some(TapPayload::Notification(pattern, TapNotification::Matched)) if pattern == "transform"
+        ));
+        assert!(tap.is_active());
+
+        for message in &["drop", "keep"] {
+            tap.send(Invocation {
+                input: Event::from(*message),
+                outputs: vec![Event::from("out")],
+            });
+        }
+
+        match sink_rx.recv().await {
+            Some(TapPayload::Invocation(returned_component, invocation)) => {
+                assert_eq!(returned_component, component);
+                assert_eq!(invocation.input, Event::from("keep"));
+                assert_eq!(invocation.outputs, vec![Event::from("out")]);
+            }
+            _ => panic!("unexpected payload"),
+        }
+    }
+
+    #[tokio::test]
+    /// A tap sink should only relay the sampled log events which pass the filter.
+    async fn sink_filters_and_samples_events() {
+        let id = OutputId::from(&ComponentKey::global("test"));
+        let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
+        let filter = TapFilter::new(Some(".keep == true".to_string()), 2).unwrap();
+        let mut sink = TapSink::new(sink_tx, TapPoint::Output(id), filter);
+
+        for index in 0..8 {
+            let mut log = LogEvent::default();
+            log.insert("keep", index % 2 == 0);
+            log.insert("index", index);
+            sink.send(Event::Log(log)).await.unwrap();
+        }
+        drop(sink);
+
+        let mut indexes = Vec::new();
+        while let Some(payload) = sink_rx.recv().await {
+            match payload {
+                TapPayload::Log(_, log) => indexes.push(log.get("index").unwrap().clone()),
+                _ => panic!("unexpected payload"),
+            }
+        }
+        assert_eq!(indexes, vec![Value::from(0), Value::from(4)]);
+    }
+
+    #[test]
+    /// An invalid VRL condition should be rejected when creating the filter.
+    fn invalid_filter() {
+        assert!(TapFilter::new(Some(".keep ==".to_string()), 1).is_err());
+    }
 }
@@ -15,12 +15,14 @@ enum BufferConfigKind {
     Memory,
     #[cfg(feature = "disk-buffer")]
     Disk,
+    #[cfg(feature = "disk-buffer-v2")]
+    DiskV2,
 }
 
-#[cfg(feature = "disk-buffer")]
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
-#[cfg(not(feature = "disk-buffer"))]
-const ALL_FIELDS: [&str; 3] = ["type", "max_events", "when_full"];
+#[cfg(feature = "disk-buffer-v2")]
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "when_full", "overflow"];
+#[cfg(not(feature = "disk-buffer-v2"))]
+const ALL_FIELDS: [&str; 4] = ["type", "max_events", "when_full", "overflow"];
 
 struct BufferConfigVisitor;
 
@@ -37,9 +39,10 @@ impl<'de> Visitor<'de> for BufferConfigVisitor {
     {
         let mut kind: Option<BufferConfigKind> = None;
         let mut max_events: Option<usize> = None;
-        #[cfg(feature = "disk-buffer")]
+        #[cfg(feature = "disk-buffer-v2")]
         let mut max_size: Option<usize> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut overflow: Option<BufferConfig> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -54,7 +57,7 @@ impl<'de> Visitor<'de> for BufferConfigVisitor {
                     }
                     max_events = Some(map.next_value()?);
                 }
-                #[cfg(feature = "disk-buffer")]
+                #[cfg(feature = "disk-buffer-v2")]
                 "max_size" => {
                     if max_size.is_some() {
                         return Err(Error::duplicate_field("max_size"));
@@ -67,6 +70,12 @@ impl<'de> Visitor<'de> for BufferConfigVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "overflow" => {
+                    if overflow.is_some() {
+                        return Err(Error::duplicate_field("overflow"));
+                    }
+                    overflow = Some(map.next_value()?);
+                }
                 other => {
                     return Err(Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -74,34 +83,76 @@ impl<'de> Visitor<'de> for BufferConfigVisitor {
         }
         let kind = kind.unwrap_or(BufferConfigKind::Memory);
         let when_full = when_full.unwrap_or_default();
-        match kind {
+        let overflow = match (when_full, overflow) {
+            (WhenFull::Overflow, Some(overflow)) => Some(Box::new(overflow)),
+            (WhenFull::Overflow, None) => return Err(Error::missing_field("overflow")),
+            (_, Some(_)) => {
+                return Err(Error::custom(
+                    "`overflow` can only be set along with `when_full = \"overflow\"`",
+                ))
+            }
+            (_, None) => None,
+        };
+        let config = match kind {
             BufferConfigKind::Memory => {
-                #[cfg(feature = "disk-buffer")]
+                #[cfg(feature = "disk-buffer-v2")]
                 if max_size.is_some() {
                     return Err(Error::unknown_field(
                         "max_size",
-                        &["type", "max_events", "when_full"],
+                        &["type", "max_events", "when_full", "overflow"],
                     ));
                 }
-                Ok(BufferConfig::Memory {
+                BufferConfig::Memory {
                     max_events: max_events.unwrap_or_else(BufferConfig::memory_max_events),
                     when_full,
-                })
+                    overflow,
+                }
             }
             #[cfg(feature = "disk-buffer")]
             BufferConfigKind::Disk => {
                 if max_events.is_some() {
                     return Err(Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "overflow"],
+                    ));
+                }
+                BufferConfig::Disk {
+                    max_size: max_size.ok_or_else(|| Error::missing_field("max_size"))?,
+                    when_full,
+                    overflow,
+                }
+            }
+            #[cfg(feature = "disk-buffer-v2")]
+            BufferConfigKind::DiskV2 => {
+                if max_events.is_some() {
+                    return Err(Error::unknown_field(
+                        "max_events",
+                        &["type", "max_size", "when_full", "overflow"],
                     ));
                 }
-                Ok(BufferConfig::Disk {
+                BufferConfig::DiskV2 {
                     max_size: max_size.ok_or_else(|| Error::missing_field("max_size"))?,
                     when_full,
-                })
+                    overflow,
+                }
+            }
+        };
+
+        // Disk buffers of the same type would share their data directory,
+        // wherever they are in the chain of overflow buffers.
+        #[cfg(feature = "disk-buffer-v2")]
+        if !matches!(config, BufferConfig::Memory { .. }) {
+            let mut next = config.overflow();
+            while let Some(overflow) = next {
+                if std::mem::discriminant(&config) == std::mem::discriminant(overflow) {
+                    return Err(Error::custom(
+                        "a disk buffer can't overflow into a disk buffer of the same type",
+                    ));
+                }
+                next = overflow.overflow();
             }
         }
+        Ok(config)
     }
 }
 
@@ -123,12 +174,32 @@ pub enum BufferConfig {
         max_events: usize,
         #[serde(default)]
         when_full: WhenFull,
+        /// The buffer that events are written to while this one is full, if
+        /// `when_full` is `overflow`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        overflow: Option<Box<BufferConfig>>,
     },
     #[cfg(feature = "disk-buffer")]
     Disk {
         max_size: usize,
         #[serde(default)]
         when_full: WhenFull,
+        /// The buffer that events are written to while this one is full, if
+        /// `when_full` is `overflow`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        overflow: Option<Box<BufferConfig>>,
+    },
+    /// Disk buffer storing events in segment files, without depending on
+    /// LevelDB.
+    #[cfg(feature = "disk-buffer-v2")]
+    DiskV2 {
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+        /// The buffer that events are written to while this one is full, if
+        /// `when_full` is `overflow`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        overflow: Option<Box<BufferConfig>>,
     },
 }
 
@@ -137,6 +208,7 @@ impl Default for BufferConfig {
         BufferConfig::Memory {
             max_events: BufferConfig::memory_max_events(),
             when_full: Default::default(),
+            overflow: None,
         }
     }
 }
@@ -149,17 +221,26 @@ impl BufferConfig {
         500
     }
 
-    #[cfg_attr(not(feature = "disk-buffer"), allow(unused))]
     pub fn build(
         &self,
         data_dir: &Option<PathBuf>,
         sink_id: &ComponentKey,
         span: Span,
     ) -> Result<(BufferInputCloner<Event>, EventStream, Acker), String> {
+        build(self.variant(data_dir, sink_id)?, span)
+    }
+
+    #[cfg_attr(not(feature = "disk-buffer-v2"), allow(unused))]
+    fn variant(
+        &self,
+        data_dir: &Option<PathBuf>,
+        sink_id: &ComponentKey,
+    ) -> Result<Variant, String> {
         let variant = match &self {
             BufferConfig::Memory {
                 max_events,
                 when_full,
+                ..
             } => Variant::Memory {
                 max_events: *max_events,
                 when_full: *when_full,
@@ -169,6 +250,7 @@ impl BufferConfig {
             BufferConfig::Disk {
                 max_size,
                 when_full,
+                ..
             } => Variant::Disk {
                 max_size: *max_size,
                 when_full: *when_full,
@@ -178,19 +260,56 @@ impl BufferConfig {
                     .to_path_buf(),
                 id: sink_id.to_string(),
             },
+            #[cfg(feature = "disk-buffer-v2")]
+            BufferConfig::DiskV2 {
+                max_size,
+                when_full,
+                ..
+            } => Variant::DiskV2 {
+                max_size: *max_size,
+                when_full: *when_full,
+                data_dir: data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?
+                    .to_path_buf(),
+                id: sink_id.to_string(),
+            },
         };
-        build(variant, span)
+
+        Ok(match self.overflow() {
+            Some(overflow) => Variant::Overflow {
+                base: Box::new(variant),
+                overflow: Box::new(overflow.variant(data_dir, sink_id)?),
+            },
+            None => variant,
+        })
+    }
+
+    /// The buffer that events are written to while this one is full.
+    pub fn overflow(&self) -> Option<&BufferConfig> {
+        match self {
+            BufferConfig::Memory { overflow, .. } => overflow.as_deref(),
+            #[cfg(feature = "disk-buffer")]
+            BufferConfig::Disk { overflow, .. } => overflow.as_deref(),
+            #[cfg(feature = "disk-buffer-v2")]
+            BufferConfig::DiskV2 { overflow, .. } => overflow.as_deref(),
+        }
     }
 
     /// Resources that the sink is using.
-    #[cfg_attr(not(feature = "disk-buffer"), allow(unused))]
-    #[allow(clippy::missing_const_for_fn)] // False positive, allocations are not allowed in const fns
+    #[cfg_attr(not(feature = "disk-buffer-v2"), allow(unused))]
     pub fn resources(&self, sink_id: &str) -> Vec<Resource> {
-        match self {
+        let mut resources = match self {
             BufferConfig::Memory { .. } => Vec::new(),
             #[cfg(feature = "disk-buffer")]
             BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_id.to_string())],
+            #[cfg(feature = "disk-buffer-v2")]
+            BufferConfig::DiskV2 { .. } => vec![Resource::DiskBuffer(sink_id.to_string())],
+        };
+        if let Some(overflow) = self.overflow() {
+            resources.extend(overflow.resources(sink_id));
         }
+        resources
     }
 }
 
@@ -212,6 +331,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 500,
                 when_full: WhenFull::Block,
+                overflow: None,
             },
         );
 
@@ -223,6 +343,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 100,
                 when_full: WhenFull::Block,
+                overflow: None,
             },
         );
 
@@ -234,6 +355,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 500,
                 when_full: WhenFull::DropNewest,
+                overflow: None,
             },
         );
 
@@ -246,10 +368,107 @@ mod test {
             BufferConfig::Disk {
                 max_size: 1024,
                 when_full: WhenFull::Block,
+                overflow: None,
+            },
+        );
+
+        #[cfg(feature = "disk-buffer-v2")]
+        check(
+            r#"
+          type = "disk_v2"
+          max_size = 1024
+          when_full = "drop_newest"
+          "#,
+            BufferConfig::DiskV2 {
+                max_size: 1024,
+                when_full: WhenFull::DropNewest,
+                overflow: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_overflow() {
+        check(
+            r#"
+          type = "memory"
+          max_events = 100
+          when_full = "overflow"
+
+          [overflow]
+          type = "memory"
+          max_events = 1000
+          "#,
+            BufferConfig::Memory {
+                max_events: 100,
+                when_full: WhenFull::Overflow,
+                overflow: Some(Box::new(BufferConfig::Memory {
+                    max_events: 1000,
+                    when_full: WhenFull::Block,
+                    overflow: None,
+                })),
             },
         );
     }
 
+    #[cfg(feature = "disk-buffer")]
+    #[test]
+    fn parse_overflow_into_same_disk_type() {
+        let source = r#"
+    type = "disk"
+    max_size = 1024
+    when_full = "overflow"
+
+    [overflow]
+    type = "disk"
+    max_size = 2048
+    "#;
+        assert!(toml::from_str::<BufferConfig>(source).is_err());
+    }
+
+    #[cfg(feature = "disk-buffer-v2")]
+    #[test]
+    fn parse_overflow_through_memory_into_same_disk_type() {
+        let source = r#"
+    type = "disk_v2"
+    max_size = 1024
+    when_full = "overflow"
+
+    [overflow]
+    type = "memory"
+    when_full = "overflow"
+
+    [overflow.overflow]
+    type = "disk_v2"
+    max_size = 2048
+    "#;
+        assert!(toml::from_str::<BufferConfig>(source).is_err());
+    }
+
+    #[test]
+    fn parse_overflow_without_buffer() {
+        let source = r#"
+    max_events = 100
+    when_full = "overflow"
+    "#;
+        let error = toml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "missing field `overflow` at line 1 column 1"
+        );
+    }
+
+    #[test]
+    fn parse_overflow_without_when_full() {
+        let source = r#"
+    max_events = 100
+
+    [overflow]
+    max_events = 1000
+    "#;
+        assert!(toml::from_str::<BufferConfig>(source).is_err());
+    }
+
     #[test]
     fn parse_without_tag() {
         check(
@@ -259,6 +478,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 100,
                 when_full: WhenFull::Block,
+                overflow: None,
             },
         );
     }
@@ -272,7 +492,7 @@ mod test {
         let error = toml::from_str::<BufferConfig>(source).unwrap_err();
         assert_eq!(
             error.to_string(),
-            "unknown field `max_size`, expected one of `type`, `max_events`, `when_full` at line 1 column 1"
+            "unknown field `max_size`, expected one of `type`, `max_events`, `when_full`, `overflow` at line 1 column 1"
         );
     }
 }
@@ -1,7 +1,9 @@
-use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use crate::codecs::{
+    BoxedFramer, BoxedFramingEncoder, BoxedFramingError, FramingConfig, FramingEncoderConfig,
+};
 use bytes::{Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
-use tokio_util::codec::Decoder;
+use tokio_util::codec::{Decoder, Encoder};
 
 /// Config used to build a `BytesDecoderConfig`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -21,6 +23,24 @@ impl FramingConfig for BytesDecoderConfig {
     }
 }
 
+/// Config used to build a `BytesCodec` for encoding.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BytesEncoderConfig;
+
+impl BytesEncoderConfig {
+    /// Creates a new `BytesEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[typetag::serde(name = "bytes")]
+impl FramingEncoderConfig for BytesEncoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramingEncoder> {
+        Ok(Box::new(BytesCodec::new()))
+    }
+}
+
 /// A codec for passing through bytes as-is.
 ///
 /// This is basically a no-op and is used to convert from `BytesMut` to `Bytes`.
@@ -64,6 +84,15 @@ impl Decoder for BytesCodec {
     }
 }
 
+impl Encoder<Bytes> for BytesCodec {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.extend_from_slice(&item);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +133,15 @@ mod tests {
         assert_eq!(reader.next().await.unwrap().unwrap(), "");
         assert!(reader.next().await.is_none());
     }
+
+    #[test]
+    fn encode_frame() {
+        let mut output = BytesMut::new();
+        let mut encoder = BytesCodec::new();
+
+        encoder.encode(Bytes::from("foo"), &mut output).unwrap();
+        encoder.encode(Bytes::from("bar"), &mut output).unwrap();
+
+        assert_eq!(output, "foobar");
+    }
 }
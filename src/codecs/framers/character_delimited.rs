@@ -1,7 +1,9 @@
-use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use crate::codecs::{
+    BoxedFramer, BoxedFramingEncoder, BoxedFramingError, FramingConfig, FramingEncoderConfig,
+};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
-use std::{cmp, io, usize};
+use std::{cmp, usize};
 use tokio_util::codec::{Decoder, Encoder};
 
 /// Config used to build a `CharacterDelimitedCodec`.
@@ -38,6 +40,42 @@ impl FramingConfig for CharacterDelimitedDecoderConfig {
     }
 }
 
+/// Config used to build a `CharacterDelimitedCodec` for encoding.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CharacterDelimitedEncoderConfig {
+    character_delimited: CharacterDelimitedEncoderOptions,
+}
+
+/// Options for building a `CharacterDelimitedCodec` for encoding.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CharacterDelimitedEncoderOptions {
+    /// The character that delimits byte sequences.
+    delimiter: char,
+}
+
+impl CharacterDelimitedEncoderConfig {
+    /// Creates a new `CharacterDelimitedEncoderConfig` with the specified
+    /// delimiter.
+    pub const fn new(delimiter: char) -> Self {
+        Self {
+            character_delimited: CharacterDelimitedEncoderOptions { delimiter },
+        }
+    }
+}
+
+#[typetag::serde(name = "character_delimited")]
+impl FramingEncoderConfig for CharacterDelimitedEncoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramingEncoder> {
+        Ok(Box::new(CharacterDelimitedCodec::new(
+            self.character_delimited.delimiter,
+        )))
+    }
+
+    fn newline_delimited(&self) -> bool {
+        self.character_delimited.delimiter == '\n'
+    }
+}
+
 /// A codec for handling bytes that are delimited by (a) chosen character(s).
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct CharacterDelimitedCodec {
@@ -167,13 +205,15 @@ impl<T> Encoder<T> for CharacterDelimitedCodec
 where
     T: AsRef<[u8]>,
 {
-    type Error = io::Error;
+    type Error = BoxedFramingError;
 
-    fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), io::Error> {
+    fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), Self::Error> {
         let item = item.as_ref();
-        buf.reserve(item.len() + 1);
+        let mut delimiter = [0; 4];
+        let delimiter = self.delimiter.encode_utf8(&mut delimiter).as_bytes();
+        buf.reserve(item.len() + delimiter.len());
         buf.put(item);
-        buf.put_u8(self.delimiter as u8);
+        buf.put(delimiter);
         Ok(())
     }
 }
@@ -202,6 +242,16 @@ mod tests {
         assert_eq!(b"abc\n", &buf[..]);
     }
 
+    #[test]
+    fn character_delimited_encode_multibyte_delimiter() {
+        let mut codec = CharacterDelimitedCodec::new('§');
+
+        let mut buf = BytesMut::new();
+        codec.encode(b"abc", &mut buf).unwrap();
+
+        assert_eq!("abc§".as_bytes(), &buf[..]);
+    }
+
     #[test]
     fn decode_max_length() {
         const MAX_LENGTH: usize = 6;
@@ -1,7 +1,9 @@
-use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use crate::codecs::{
+    BoxedFramer, BoxedFramingEncoder, BoxedFramingError, FramingConfig, FramingEncoderConfig,
+};
 use bytes::{Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
-use tokio_util::codec::Decoder;
+use tokio_util::codec::{Decoder, Encoder};
 
 /// Config used to build a `LengthDelimitedCodec`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -14,6 +16,17 @@ impl FramingConfig for LengthDelimitedDecoderConfig {
     }
 }
 
+/// Config used to build a `LengthDelimitedCodec` for encoding.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LengthDelimitedEncoderConfig;
+
+#[typetag::serde(name = "length_delimited")]
+impl FramingEncoderConfig for LengthDelimitedEncoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramingEncoder> {
+        Ok(Box::new(LengthDelimitedCodec::new()))
+    }
+}
+
 /// A codec for handling bytes sequences whose length is encoded in a frame head.
 ///
 /// Currently, this expects a length header in 32-bit MSB by default; options to
@@ -68,6 +81,14 @@ impl Decoder for LengthDelimitedCodec {
     }
 }
 
+impl Encoder<Bytes> for LengthDelimitedCodec {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.0.encode(item, dst).map_err(Into::into)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +180,15 @@ mod tests {
         assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "bar");
         assert_eq!(decoder.decode_eof(&mut input).unwrap(), None);
     }
+
+    #[test]
+    fn encode_frames() {
+        let mut output = BytesMut::new();
+        let mut encoder = LengthDelimitedCodec::new();
+
+        encoder.encode(Bytes::from("foo"), &mut output).unwrap();
+        encoder.encode(Bytes::from("bar"), &mut output).unwrap();
+
+        assert_eq!(output, "\x00\x00\x00\x03foo\x00\x00\x00\x03bar");
+    }
 }
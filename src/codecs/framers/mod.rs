@@ -6,11 +6,19 @@ mod length_delimited;
 mod newline_delimited;
 mod octet_counting;
 
-pub use self::bytes::{BytesCodec, BytesDecoderConfig};
-pub use character_delimited::{CharacterDelimitedCodec, CharacterDelimitedDecoderConfig};
-pub use length_delimited::{LengthDelimitedCodec, LengthDelimitedDecoderConfig};
-pub use newline_delimited::{NewlineDelimitedCodec, NewlineDelimitedDecoderConfig};
-pub use octet_counting::{OctetCountingCodec, OctetCountingDecoderConfig};
+pub use self::bytes::{BytesCodec, BytesDecoderConfig, BytesEncoderConfig};
+pub use character_delimited::{
+    CharacterDelimitedCodec, CharacterDelimitedDecoderConfig, CharacterDelimitedEncoderConfig,
+};
+pub use length_delimited::{
+    LengthDelimitedCodec, LengthDelimitedDecoderConfig, LengthDelimitedEncoderConfig,
+};
+pub use newline_delimited::{
+    NewlineDelimitedCodec, NewlineDelimitedDecoderConfig, NewlineDelimitedEncoderConfig,
+};
+pub use octet_counting::{
+    OctetCountingCodec, OctetCountingDecoderConfig, OctetCountingEncoderConfig,
+};
 
 use crate::sources::util::TcpError;
 use ::bytes::Bytes;
@@ -89,3 +97,44 @@ pub trait FramingConfig: Debug + DynClone + Send + Sync {
 }
 
 dyn_clone::clone_trait_object!(FramingConfig);
+
+/// Frame encoded byte messages so that they can be written to a byte stream /
+/// byte message.
+pub trait FramingEncoder:
+    tokio_util::codec::Encoder<Bytes, Error = BoxedFramingError> + DynClone + Debug + Send + Sync
+{
+}
+
+/// Default implementation for `FramingEncoder`s that implement
+/// `tokio_util::codec::Encoder`.
+impl<Encoder> FramingEncoder for Encoder where
+    Encoder:
+        tokio_util::codec::Encoder<Bytes, Error = BoxedFramingError> + Clone + Debug + Send + Sync
+{
+}
+
+dyn_clone::clone_trait_object!(FramingEncoder);
+
+/// A `Box` containing a `FramingEncoder`.
+pub type BoxedFramingEncoder = Box<dyn FramingEncoder>;
+
+/// Define options for a framing encoder and build it from the config object.
+///
+/// Implementors must annotate the struct with `#[typetag::serde(name = "...")]`
+/// to define which value should be read from the `method` key to select their
+/// implementation.
+#[typetag::serde(tag = "method")]
+pub trait FramingEncoderConfig: Debug + DynClone + Send + Sync {
+    /// Builds a framing encoder from this configuration.
+    ///
+    /// Fails if the configuration is invalid.
+    fn build(&self) -> crate::Result<BoxedFramingEncoder>;
+
+    /// Whether the frames are delimited by newlines, which some serializers
+    /// take into account for the media type of the payload.
+    fn newline_delimited(&self) -> bool {
+        false
+    }
+}
+
+dyn_clone::clone_trait_object!(FramingEncoderConfig);
@@ -1,7 +1,10 @@
-use crate::codecs::{BoxedFramer, BoxedFramingError, CharacterDelimitedCodec, FramingConfig};
+use crate::codecs::{
+    BoxedFramer, BoxedFramingEncoder, BoxedFramingError, CharacterDelimitedCodec, FramingConfig,
+    FramingEncoderConfig,
+};
 use bytes::{Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
-use tokio_util::codec::Decoder;
+use tokio_util::codec::{Decoder, Encoder};
 
 /// Config used to build a `NewlineDelimitedCodec`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
@@ -60,6 +63,28 @@ impl FramingConfig for NewlineDelimitedDecoderConfig {
     }
 }
 
+/// Config used to build a `NewlineDelimitedCodec` for encoding.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NewlineDelimitedEncoderConfig;
+
+impl NewlineDelimitedEncoderConfig {
+    /// Creates a new `NewlineDelimitedEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[typetag::serde(name = "newline_delimited")]
+impl FramingEncoderConfig for NewlineDelimitedEncoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramingEncoder> {
+        Ok(Box::new(NewlineDelimitedCodec::new()))
+    }
+
+    fn newline_delimited(&self) -> bool {
+        true
+    }
+}
+
 /// A codec for handling bytes that are delimited by (a) newline(s).
 #[derive(Debug, Clone)]
 pub struct NewlineDelimitedCodec(CharacterDelimitedCodec);
@@ -99,6 +124,14 @@ impl Decoder for NewlineDelimitedCodec {
     }
 }
 
+impl Encoder<Bytes> for NewlineDelimitedCodec {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.0.encode(item, dst)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +199,15 @@ mod tests {
         assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "baz");
         assert_eq!(decoder.decode_eof(&mut input).unwrap(), None);
     }
+
+    #[test]
+    fn encode_bytes_with_newlines() {
+        let mut output = BytesMut::new();
+        let mut encoder = NewlineDelimitedCodec::new();
+
+        encoder.encode(Bytes::from("foo"), &mut output).unwrap();
+        encoder.encode(Bytes::from("bar"), &mut output).unwrap();
+
+        assert_eq!(output, "foo\nbar\n");
+    }
 }
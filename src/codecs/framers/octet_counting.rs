@@ -1,5 +1,7 @@
-use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
-use bytes::{Buf, Bytes, BytesMut};
+use crate::codecs::{
+    BoxedFramer, BoxedFramingEncoder, BoxedFramingError, FramingConfig, FramingEncoderConfig,
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
 use std::io;
 use tokio_util::codec::{LinesCodec, LinesCodecError};
@@ -35,6 +37,17 @@ impl FramingConfig for OctetCountingDecoderConfig {
     }
 }
 
+/// Config used to build a `OctetCountingCodec` for encoding.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OctetCountingEncoderConfig;
+
+#[typetag::serde(name = "octet_counting")]
+impl FramingEncoderConfig for OctetCountingEncoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramingEncoder> {
+        Ok(Box::new(OctetCountingCodec::new()))
+    }
+}
+
 /// Codec using the `Octet Counting` format as specified in
 /// https://tools.ietf.org/html/rfc6587#section-3.4.1.
 #[derive(Clone, Debug)]
@@ -281,11 +294,24 @@ impl tokio_util::codec::Decoder for OctetCountingCodec {
     }
 }
 
+impl tokio_util::codec::Encoder<Bytes> for OctetCountingCodec {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let length = item.len().to_string();
+        dst.reserve(length.len() + 1 + item.len());
+        dst.put(length.as_bytes());
+        dst.put_u8(b' ');
+        dst.put(item);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use bytes::BufMut;
-    use tokio_util::codec::Decoder;
+    use tokio_util::codec::{Decoder, Encoder};
 
     #[test]
     fn non_octet_decode_works_with_multiple_frames() {
@@ -406,4 +432,27 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(b"32 something valid"[..], buffer);
     }
+
+    #[test]
+    fn octet_encode_prefixes_length() {
+        let mut encoder = OctetCountingCodec::new();
+        let mut buffer = BytesMut::new();
+
+        encoder.encode(Bytes::from("foo"), &mut buffer).unwrap();
+        encoder
+            .encode(Bytes::from("hello world"), &mut buffer)
+            .unwrap();
+
+        assert_eq!(b"3 foo11 hello world"[..], buffer);
+    }
+
+    #[test]
+    fn octet_encode_roundtrip() {
+        let mut codec = OctetCountingCodec::new();
+        let mut buffer = BytesMut::new();
+
+        codec.encode(Bytes::from("foo bar"), &mut buffer).unwrap();
+
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), "foo bar");
+    }
 }
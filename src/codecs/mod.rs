@@ -5,16 +5,21 @@
 
 mod framers;
 mod parsers;
+mod serializers;
 
 use crate::{
+    dropped::DroppedSender,
     event::Event,
-    internal_events::{DecoderFramingFailed, DecoderParseFailed},
+    internal_events::{
+        DecoderFramingFailed, DecoderParseFailed, EncoderFramingFailed, EncoderSerializeFailed,
+    },
     sources::util::TcpError,
 };
 use bytes::{Bytes, BytesMut};
 pub use framers::*;
 pub use parsers::*;
 use serde::{Deserialize, Serialize};
+pub use serializers::*;
 use smallvec::SmallVec;
 
 /// An error that occurred while decoding structured events from a byte stream /
@@ -79,6 +84,12 @@ impl Decoder {
         Self { framer, parser }
     }
 
+    /// Resolves what parsing the frame requires, such as its schema from a
+    /// schema registry, before it's decoded.
+    pub async fn resolve(&self, frame: &[u8]) {
+        self.parser.resolve(frame).await
+    }
+
     /// Handles the framing result and parses it into a structured event, if
     /// possible.
     ///
@@ -152,3 +163,227 @@ impl DecodingConfig {
         Ok(Decoder::new(framer, parser))
     }
 }
+
+/// An error that occurred while encoding structured events into byte frames.
+#[derive(Debug)]
+pub enum EncodingError {
+    /// The error occurred while framing the serialized bytes.
+    FramingError(BoxedFramingError),
+    /// The error occurred while serializing a structured event into bytes.
+    SerializingError(crate::Error),
+}
+
+impl std::fmt::Display for EncodingError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FramingError(error) => write!(formatter, "FramingError({})", error),
+            Self::SerializingError(error) => write!(formatter, "SerializingError({})", error),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+impl From<std::io::Error> for EncodingError {
+    fn from(error: std::io::Error) -> Self {
+        Self::FramingError(Box::new(error))
+    }
+}
+
+#[derive(Debug, Clone)]
+/// An encoder that can encode structured events into byte frames.
+pub struct Encoder {
+    framer: BoxedFramingEncoder,
+    serializer: BoxedSerializer,
+    newline_delimited: bool,
+    dropped: Option<DroppedSender>,
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self {
+            framer: Box::new(NewlineDelimitedCodec::new()),
+            serializer: Box::new(RawMessageSerializer::new()),
+            newline_delimited: true,
+            dropped: None,
+        }
+    }
+}
+
+impl Encoder {
+    /// Creates a new `Encoder` with the specified `Serializer` to produce bytes
+    /// from a structured event, and the `FramingEncoder` to wrap these bytes
+    /// into a frame.
+    pub fn new(framer: BoxedFramingEncoder, serializer: BoxedSerializer) -> Self {
+        Self {
+            framer,
+            serializer,
+            newline_delimited: false,
+            dropped: None,
+        }
+    }
+
+    /// The media type of a payload made of the frames produced by this
+    /// encoder, as determined by its serializer and framing.
+    pub fn content_type(&self) -> &'static str {
+        self.serializer.content_type(self.newline_delimited)
+    }
+
+    /// Marks the frames produced by this encoder as delimited by newlines.
+    pub fn with_newline_delimited(mut self, newline_delimited: bool) -> Self {
+        self.newline_delimited = newline_delimited;
+        self
+    }
+
+    /// Sends the events that fail to be encoded to `dropped`, instead of
+    /// discarding them.
+    pub fn with_dropped(mut self, dropped: Option<DroppedSender>) -> Self {
+        self.dropped = dropped;
+        self
+    }
+}
+
+impl tokio_util::codec::Encoder<Event> for Encoder {
+    type Error = EncodingError;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut payload = BytesMut::new();
+
+        // The serializer consumes the event, so it is only kept around if it
+        // would be rerouted on failure.
+        let original = self.dropped.as_ref().map(|_| event.clone());
+        let dropped = self.dropped.as_ref();
+        let reroute = |original: Option<Event>, error: &dyn std::fmt::Display| {
+            if let (Some(dropped), Some(event)) = (dropped, original) {
+                dropped.send(event, "encoding_failed", error);
+            }
+        };
+
+        // Serialize the structured event into bytes.
+        if let Err(error) = self.serializer.serialize(event, &mut payload) {
+            emit!(&EncoderSerializeFailed { error: &error });
+            reroute(original, &error);
+            return Err(EncodingError::SerializingError(error));
+        }
+
+        // Wrap the bytes into a frame.
+        if let Err(error) = self.framer.encode(payload.freeze(), buffer) {
+            emit!(&EncoderFramingFailed { error: &error });
+            reroute(original, &error);
+            return Err(EncodingError::FramingError(error));
+        }
+
+        Ok(())
+    }
+}
+
+/// Config used to build an `Encoder`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EncodingConfig {
+    /// The framing config.
+    framing: Box<dyn FramingEncoderConfig>,
+    /// The serializer config.
+    serializer: Box<dyn SerializerConfig>,
+}
+
+impl EncodingConfig {
+    /// Creates a new `EncodingConfig` with the provided `FramingEncoderConfig`
+    /// and `SerializerConfig`.
+    pub fn new(
+        framing: Box<dyn FramingEncoderConfig>,
+        serializer: Box<dyn SerializerConfig>,
+    ) -> Self {
+        Self {
+            framing,
+            serializer,
+        }
+    }
+
+    /// Builds an `Encoder` from the provided configuration.
+    pub fn build(&self) -> crate::Result<Encoder> {
+        // Build the framer.
+        let framer: BoxedFramingEncoder = self.framing.build()?;
+
+        // Build the serializer.
+        let serializer: BoxedSerializer = self.serializer.build()?;
+
+        Ok(Encoder::new(framer, serializer)
+            .with_newline_delimited(self.framing.newline_delimited()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_util::codec::Encoder as _;
+
+    #[test]
+    fn encode_framed_events() {
+        let config: EncodingConfig = toml::from_str(
+            r#"
+            framing.method = "octet_counting"
+            serializer.codec = "json"
+            "#,
+        )
+        .unwrap();
+        let mut encoder = config.build().unwrap();
+        let mut buffer = BytesMut::new();
+
+        encoder.encode(Event::from("foo"), &mut buffer).unwrap();
+        encoder.encode(Event::from("bar"), &mut buffer).unwrap();
+
+        let mut decoder = Decoder::new(
+            Box::new(OctetCountingCodec::new()),
+            Box::new(JsonParser::new()),
+        );
+        let messages = std::iter::from_fn(|| {
+            tokio_util::codec::Decoder::decode(&mut decoder, &mut buffer).unwrap()
+        })
+        .flat_map(|(events, _)| events)
+        .map(|event| event.as_log()[crate::config::log_schema().message_key()].clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(messages, vec!["foo".into(), "bar".into()]);
+    }
+
+    #[test]
+    fn encode_with_default_encoder() {
+        let mut encoder = Encoder::default();
+        let mut buffer = BytesMut::new();
+
+        encoder.encode(Event::from("foo"), &mut buffer).unwrap();
+
+        assert_eq!(buffer, "foo\n");
+    }
+
+    #[test]
+    fn content_type_depends_on_framing() {
+        let content_type = |framing: &str| {
+            let config: EncodingConfig = toml::from_str(&format!(
+                r#"
+                {}
+                serializer.codec = "json"
+                "#,
+                framing
+            ))
+            .unwrap();
+            config.build().unwrap().content_type()
+        };
+
+        assert_eq!(
+            content_type(r#"framing.method = "newline_delimited""#),
+            "application/x-ndjson"
+        );
+        assert_eq!(
+            content_type(
+                r#"framing.method = "character_delimited"
+                framing.character_delimited.delimiter = "\n""#
+            ),
+            "application/x-ndjson"
+        );
+        assert_eq!(
+            content_type(r#"framing.method = "bytes""#),
+            "application/json"
+        );
+    }
+}
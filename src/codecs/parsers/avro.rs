@@ -0,0 +1,271 @@
+use super::schema_registry::{
+    schema_id, split_schema_id, RegistrySchema, SchemaCache, SchemaRegistryClient,
+    SchemaRegistryConfig,
+};
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+use async_trait::async_trait;
+use avro_rs::{types::Value as AvroValue, Schema};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use std::{fs, path::PathBuf, sync::Arc};
+
+/// Config used to build an `AvroParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AvroParserConfig {
+    /// The schema registry to fetch schemas from, in which case messages are
+    /// expected in the Confluent wire format.
+    pub schema_registry: Option<SchemaRegistryConfig>,
+    /// The path of a file holding the JSON schema of the messages, which are
+    /// then plain Avro datums.
+    pub schema_path: Option<PathBuf>,
+}
+
+#[typetag::serde(name = "avro")]
+impl ParserConfig for AvroParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        let schemas = match (&self.schema_registry, &self.schema_path) {
+            (Some(registry), None) => AvroSchemas::Registry(Arc::new(SchemaCache::new(
+                SchemaRegistryClient::new(registry)?,
+            ))),
+            (None, Some(path)) => {
+                let schema = fs::read_to_string(path)
+                    .map_err(|error| format!("Could not read Avro schema {:?}: {}", path, error))?;
+                AvroSchemas::Static(Arc::new(Schema::parse_str(&schema)?))
+            }
+            _ => {
+                return Err(
+                    "The `avro` codec requires exactly one of `schema_registry` and `schema_path`."
+                        .into(),
+                )
+            }
+        };
+
+        Ok(Box::new(AvroParser { schemas }))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum AvroSchemas {
+    Static(Arc<Schema>),
+    Registry(Arc<SchemaCache<Schema>>),
+}
+
+#[async_trait]
+impl RegistrySchema for Schema {
+    async fn fetch(client: &SchemaRegistryClient, id: u32) -> crate::Result<Self> {
+        let registered = client.schema_by_id(id, None).await?;
+        match registered.schema_type.as_deref() {
+            None | Some("AVRO") => Ok(Schema::parse_str(&registered.schema)?),
+            Some(schema_type) => Err(format!("Schema {} is a {} schema.", id, schema_type).into()),
+        }
+    }
+}
+
+/// Parser that builds an `Event` from a byte frame containing an Avro datum.
+#[derive(Debug, Clone)]
+pub struct AvroParser {
+    schemas: AvroSchemas,
+}
+
+impl Parser for AvroParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let value = match &self.schemas {
+            AvroSchemas::Static(schema) => decode(schema, &bytes)?,
+            AvroSchemas::Registry(schemas) => {
+                let (id, payload) = split_schema_id(bytes)?;
+                let schema = schemas.get(id)?;
+                decode(&schema, &payload)?
+            }
+        };
+
+        let mut log = match to_value(value)? {
+            Value::Map(fields) => LogEvent::from(fields),
+            value => {
+                let mut log = LogEvent::default();
+                log.insert(log_schema().message_key(), value);
+                log
+            }
+        };
+
+        let timestamp_key = log_schema().timestamp_key();
+        if !log.contains(timestamp_key) {
+            log.insert(timestamp_key, Utc::now());
+        }
+
+        Ok(smallvec![log.into()])
+    }
+
+    fn resolve<'a>(&'a self, frame: &'a [u8]) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if let AvroSchemas::Registry(schemas) = &self.schemas {
+                if let Some(id) = schema_id(frame) {
+                    // Failures are reported when parsing the frame.
+                    let _ = schemas.resolve(id).await;
+                }
+            }
+        })
+    }
+}
+
+fn decode(schema: &Schema, mut bytes: &[u8]) -> crate::Result<AvroValue> {
+    avro_rs::from_avro_datum(schema, &mut bytes, None)
+        .map_err(|error| format!("Error parsing Avro: {}", error).into())
+}
+
+fn to_value(value: AvroValue) -> crate::Result<Value> {
+    Ok(match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(boolean) => boolean.into(),
+        AvroValue::Int(int) | AvroValue::Date(int) | AvroValue::TimeMillis(int) => {
+            Value::Integer(int.into())
+        }
+        AvroValue::Long(long) | AvroValue::TimeMicros(long) => Value::Integer(long),
+        AvroValue::Float(float) => Value::Float(float.into()),
+        AvroValue::Double(double) => Value::Float(double),
+        AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes) => Value::Bytes(bytes.into()),
+        AvroValue::String(string) | AvroValue::Enum(_, string) => string.into(),
+        AvroValue::Uuid(uuid) => uuid.to_string().into(),
+        AvroValue::TimestampMillis(millis) => Utc
+            .timestamp_millis_opt(millis)
+            .single()
+            .ok_or_else(|| format!("Invalid Avro timestamp: {} milliseconds.", millis))?
+            .into(),
+        AvroValue::TimestampMicros(micros) => Utc
+            .timestamp_opt(
+                micros.div_euclid(1_000_000),
+                (micros.rem_euclid(1_000_000) * 1_000) as u32,
+            )
+            .single()
+            .ok_or_else(|| format!("Invalid Avro timestamp: {} microseconds.", micros))?
+            .into(),
+        AvroValue::Union(value) => to_value(*value)?,
+        AvroValue::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(to_value)
+                .collect::<crate::Result<_>>()?,
+        ),
+        AvroValue::Map(fields) => Value::Map(
+            fields
+                .into_iter()
+                .map(|(name, value)| Ok((name, to_value(value)?)))
+                .collect::<crate::Result<_>>()?,
+        ),
+        AvroValue::Record(fields) => Value::Map(
+            fields
+                .into_iter()
+                .map(|(name, value)| Ok((name, to_value(value)?)))
+                .collect::<crate::Result<_>>()?,
+        ),
+        value => return Err(format!("Unsupported Avro value: {:?}", value).into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avro_rs::types::Record;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "event",
+        "fields": [
+            {"name": "message", "type": "string"},
+            {"name": "count", "type": "long"},
+            {"name": "host", "type": ["null", "string"]}
+        ]
+    }"#;
+
+    fn datum() -> Vec<u8> {
+        let schema = Schema::parse_str(SCHEMA).unwrap();
+        let mut record = Record::new(&schema).unwrap();
+        record.put("message", "hello");
+        record.put("count", 42i64);
+        record.put("host", AvroValue::Union(Box::new(AvroValue::Null)));
+        avro_rs::to_avro_datum(&schema, record).unwrap()
+    }
+
+    fn assert_parsed(events: SmallVec<[Event; 1]>) {
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["count"], 42.into());
+        assert_eq!(log["host"], Value::Null);
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn parses_with_static_schema() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SCHEMA.as_bytes()).unwrap();
+        let parser = AvroParserConfig {
+            schema_path: Some(file.path().into()),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+
+        assert_parsed(parser.parse(datum().into()).unwrap());
+        assert!(parser.parse(Bytes::from("\x02")).is_err());
+    }
+
+    #[tokio::test]
+    async fn parses_with_schema_registry() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/3"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "schema": SCHEMA })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let parser = AvroParserConfig {
+            schema_registry: Some(SchemaRegistryConfig {
+                url: server.uri().parse().unwrap(),
+                auth: None,
+                tls: None,
+                timeout_secs: 10,
+            }),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+
+        let mut message = vec![0, 0, 0, 0, 3];
+        message.extend(datum());
+        parser.resolve(&message).await;
+        assert_parsed(parser.parse(message.clone().into()).unwrap());
+        // The schema is cached.
+        assert_parsed(parser.parse(message.into()).unwrap());
+
+        // Messages must be in the Confluent wire format.
+        assert!(parser.parse(datum().into()).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert!(to_value(AvroValue::TimestampMillis(i64::MAX)).is_err());
+        assert!(to_value(AvroValue::TimestampMicros(i64::MIN)).is_err());
+        assert!(to_value(AvroValue::TimestampMillis(0)).is_ok());
+    }
+
+    #[test]
+    fn requires_one_schema_source() {
+        assert!(AvroParserConfig::default().build().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent},
+};
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+/// Config used to build a `CsvParser`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CsvParserConfig {
+    csv: CsvParserOptions,
+}
+
+/// Options for building a `CsvParser`.
+#[derive(Debug, Clone, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+pub struct CsvParserOptions {
+    /// The names of the fields the columns are stored in, in order.
+    headers: Vec<String>,
+    /// The character that separates the columns.
+    #[serde(default = "default_delimiter")]
+    #[derivative(Default(value = "default_delimiter()"))]
+    delimiter: char,
+}
+
+const fn default_delimiter() -> char {
+    ','
+}
+
+impl CsvParserConfig {
+    /// Creates a new `CsvParserConfig` that stores the columns in the given
+    /// fields.
+    pub fn new(headers: Vec<String>) -> Self {
+        Self {
+            csv: CsvParserOptions {
+                headers,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[typetag::serde(name = "csv")]
+impl ParserConfig for CsvParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        if self.csv.headers.is_empty() {
+            return Err("At least one header must be configured for the \"csv\" codec.".into());
+        }
+
+        if !self.csv.delimiter.is_ascii() {
+            return Err("The delimiter of the \"csv\" codec must be an ASCII character.".into());
+        }
+
+        Ok(Box::new(CsvParser {
+            headers: self.csv.headers.clone(),
+            delimiter: self.csv.delimiter as u8,
+        }))
+    }
+}
+
+/// Parser that builds an `Event` from each CSV record of a byte frame.
+///
+/// Records with fewer columns than headers leave the remaining fields unset,
+/// while records with more columns are rejected.
+#[derive(Debug, Clone)]
+pub struct CsvParser {
+    headers: Vec<String>,
+    delimiter: u8,
+}
+
+impl Parser for CsvParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(&bytes[..]);
+
+        let timestamp = Utc::now();
+        let mut events = SmallVec::new();
+        for record in reader.byte_records() {
+            let record = record.map_err(|error| format!("Error parsing CSV: {}", error))?;
+            if record.len() > self.headers.len() {
+                return Err(format!(
+                    "CSV record has {} columns, but only {} headers are configured.",
+                    record.len(),
+                    self.headers.len()
+                )
+                .into());
+            }
+
+            let mut log = LogEvent::default();
+            for (header, column) in self.headers.iter().zip(record.iter()) {
+                log.insert(header.as_str(), Bytes::copy_from_slice(column));
+            }
+
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, timestamp);
+            }
+
+            events.push(log.into());
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser(config: CsvParserConfig) -> BoxedParser {
+        config.build().unwrap()
+    }
+
+    #[test]
+    fn parse_csv_columns_in_order() {
+        let parser = parser(CsvParserConfig::new(vec![
+            "nested.number".into(),
+            "quoted".into(),
+            "missing".into(),
+        ]));
+
+        let events = parser.parse(Bytes::from(r#"3,"a ""b"", c""#)).unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["nested.number"], "3".into());
+        assert_eq!(log["quoted"], r#"a "b", c"#.into());
+        assert!(log.get("missing").is_none());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn parse_csv_custom_delimiter_and_multiple_records() {
+        let config: CsvParserConfig = toml::from_str(
+            r#"
+            csv.headers = ["a", "b"]
+            csv.delimiter = "\t"
+            "#,
+        )
+        .unwrap();
+
+        let events = parser(config).parse(Bytes::from("1\t2\n3\t4")).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["b"], "2".into());
+        assert_eq!(events[1].as_log()["a"], "3".into());
+    }
+
+    #[test]
+    fn error_too_many_columns() {
+        let parser = parser(CsvParserConfig::new(vec!["a".into()]));
+        assert!(parser.parse(Bytes::from("1,2")).is_err());
+    }
+
+    #[test]
+    fn reject_empty_headers() {
+        assert!(CsvParserConfig::new(Vec::new()).build().is_err());
+    }
+}
@@ -0,0 +1,421 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+use bytes::{Buf, Bytes};
+use chrono::{TimeZone, Utc};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use std::{
+    collections::{HashMap, VecDeque},
+    io::Read,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The magic bytes starting each chunk of a chunked GELF message.
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+/// The length of the header of each chunk: the magic bytes, the message id,
+/// the sequence number and the sequence count.
+const CHUNK_HEADER_LENGTH: usize = 12;
+/// The maximum number of chunks of a message, as set by the GELF specification.
+const MAX_CHUNKS: u8 = 128;
+/// The time after which incomplete chunked messages are dropped, as set by the
+/// GELF specification.
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Config used to build a `GelfParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GelfParserConfig {
+    #[serde(default)]
+    gelf: GelfParserOptions,
+}
+
+/// Options for building a `GelfParser`.
+#[derive(Debug, Clone, Derivative, Deserialize, Serialize, PartialEq)]
+#[derivative(Default)]
+pub struct GelfParserOptions {
+    /// The maximum size of a message once decompressed, over which it's
+    /// rejected.
+    #[serde(default = "default_max_decompressed_bytes")]
+    #[derivative(Default(value = "default_max_decompressed_bytes()"))]
+    max_decompressed_bytes: usize,
+    /// The maximum number of chunked messages buffered until all of their
+    /// chunks were received, over which the oldest ones are dropped.
+    #[serde(default = "default_max_pending_messages")]
+    #[derivative(Default(value = "default_max_pending_messages()"))]
+    max_pending_messages: usize,
+}
+
+const fn default_max_decompressed_bytes() -> usize {
+    8 * 1024 * 1024
+}
+
+const fn default_max_pending_messages() -> usize {
+    1000
+}
+
+#[typetag::serde(name = "gelf")]
+impl ParserConfig for GelfParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(GelfParser {
+            pending: Default::default(),
+            max_decompressed_bytes: self.gelf.max_decompressed_bytes,
+            max_pending_messages: self.gelf.max_pending_messages,
+        }))
+    }
+}
+
+impl GelfParserConfig {
+    /// Creates a new `GelfParserConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// A chunked message of which only some chunks were received yet.
+#[derive(Debug)]
+struct PendingMessage {
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    first_seen: Instant,
+}
+
+/// The pending chunked messages, along with the order in which they were first
+/// seen so that the oldest ones are dropped without scanning all of them.
+#[derive(Debug, Default)]
+struct PendingMessages {
+    messages: HashMap<u64, PendingMessage>,
+    order: VecDeque<(u64, Instant)>,
+}
+
+impl PendingMessages {
+    /// Drops the messages which timed out, and the oldest ones while there are
+    /// more than `max_messages`.
+    fn evict(&mut self, max_messages: usize) {
+        while let Some(&(id, first_seen)) = self.order.front() {
+            // Messages which were completed, or whose id was reused since, are
+            // only left in the order.
+            let current = self
+                .messages
+                .get(&id)
+                .map_or(false, |message| message.first_seen == first_seen);
+            if current
+                && first_seen.elapsed() < CHUNK_TIMEOUT
+                && self.messages.len() <= max_messages
+            {
+                break;
+            }
+
+            self.order.pop_front();
+            if current {
+                self.messages.remove(&id);
+            }
+        }
+    }
+}
+
+/// Parser that builds an `Event` from a byte frame containing a GELF message,
+/// which may be compressed with gzip or zlib.
+///
+/// Frames holding a chunk of a chunked message, as sent over UDP, are buffered
+/// until all the chunks of the message were received. Clones of the parser
+/// share the chunks buffered so far.
+#[derive(Debug, Clone)]
+pub struct GelfParser {
+    pending: Arc<Mutex<PendingMessages>>,
+    max_decompressed_bytes: usize,
+    max_pending_messages: usize,
+}
+
+impl GelfParser {
+    /// Creates a new `GelfParser`.
+    pub fn new() -> Self {
+        Self {
+            pending: Default::default(),
+            max_decompressed_bytes: default_max_decompressed_bytes(),
+            max_pending_messages: default_max_pending_messages(),
+        }
+    }
+
+    /// Buffers a chunk, returning the reassembled message once all of its
+    /// chunks were received.
+    fn reassemble(&self, mut chunk: Bytes) -> crate::Result<Option<Bytes>> {
+        if chunk.len() < CHUNK_HEADER_LENGTH {
+            return Err("Truncated GELF chunk.".into());
+        }
+        chunk.advance(CHUNK_MAGIC.len());
+        let id = chunk.get_u64();
+        let sequence_number = chunk.get_u8();
+        let sequence_count = chunk.get_u8();
+        if sequence_count == 0 || sequence_count > MAX_CHUNKS {
+            return Err(format!("Invalid GELF chunk count {}.", sequence_count).into());
+        }
+        if sequence_number >= sequence_count {
+            return Err(format!(
+                "Invalid GELF chunk number {} of {}.",
+                sequence_number, sequence_count
+            )
+            .into());
+        }
+
+        let mut pending = self.pending.lock().expect("poisoned lock");
+        // Room is made for the message if it's new.
+        let max_messages = if pending.messages.contains_key(&id) {
+            self.max_pending_messages
+        } else {
+            self.max_pending_messages.saturating_sub(1)
+        };
+        pending.evict(max_messages);
+
+        let PendingMessages { messages, order } = &mut *pending;
+        let message = messages.entry(id).or_insert_with(|| {
+            let first_seen = Instant::now();
+            order.push_back((id, first_seen));
+            PendingMessage {
+                chunks: vec![None; sequence_count as usize],
+                received: 0,
+                first_seen,
+            }
+        });
+        if message.chunks.len() != sequence_count as usize {
+            messages.remove(&id);
+            return Err("GELF chunks of the same message have different counts.".into());
+        }
+        let slot = &mut message.chunks[sequence_number as usize];
+        if slot.is_none() {
+            message.received += 1;
+        }
+        *slot = Some(chunk);
+        if message.received < message.chunks.len() {
+            return Ok(None);
+        }
+
+        let message = messages.remove(&id).expect("message is pending");
+        let mut bytes = Vec::new();
+        for chunk in message.chunks.into_iter().flatten() {
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(Some(bytes.into()))
+    }
+}
+
+impl Parser for GelfParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let bytes = if bytes.starts_with(&CHUNK_MAGIC) {
+            match self.reassemble(bytes)? {
+                Some(bytes) => bytes,
+                None => return Ok(smallvec![]),
+            }
+        } else {
+            bytes
+        };
+
+        let bytes = decompress(bytes, self.max_decompressed_bytes)?;
+        let message: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(&bytes)
+            .map_err(|error| format!("Error parsing GELF: {}", error))?;
+
+        Ok(smallvec![to_log(message)?.into()])
+    }
+}
+
+fn decompress(bytes: Bytes, max_decompressed_bytes: usize) -> crate::Result<Bytes> {
+    let reader: Box<dyn Read + '_> = match bytes.get(..2) {
+        Some([0x1f, 0x8b]) => Box::new(GzDecoder::new(&bytes[..])),
+        Some([0x78, _]) => Box::new(ZlibDecoder::new(&bytes[..])),
+        _ => return Ok(bytes),
+    };
+
+    // One more byte than allowed is read to tell whether the message is over
+    // the limit.
+    let mut decompressed = Vec::new();
+    reader
+        .take(max_decompressed_bytes as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > max_decompressed_bytes {
+        return Err(format!(
+            "GELF message is larger than {} bytes once decompressed.",
+            max_decompressed_bytes
+        )
+        .into());
+    }
+    Ok(decompressed.into())
+}
+
+fn to_log(message: serde_json::Map<String, serde_json::Value>) -> crate::Result<LogEvent> {
+    for field in &["version", "host", "short_message"] {
+        if !message.contains_key(*field) {
+            return Err(format!("GELF message is missing the {:?} field.", field).into());
+        }
+    }
+
+    let mut log = LogEvent::default();
+    for (key, value) in message {
+        match key.as_str() {
+            "short_message" => {
+                log.insert(log_schema().message_key(), Value::from(value));
+            }
+            "host" => {
+                log.insert(log_schema().host_key(), Value::from(value));
+            }
+            "timestamp" => {
+                let seconds = value
+                    .as_f64()
+                    .ok_or("GELF message has a non-numeric \"timestamp\" field.")?;
+                // Timestamps are rounded to microseconds, beyond which `f64`s
+                // holding them aren't precise anyway.
+                let micros = (seconds * 1_000_000.0).round() as i64;
+                let timestamp = Utc
+                    .timestamp_opt(
+                        micros.div_euclid(1_000_000),
+                        (micros.rem_euclid(1_000_000) * 1_000) as u32,
+                    )
+                    .single()
+                    .ok_or_else(|| format!("GELF message has an invalid timestamp {}.", seconds))?;
+                log.insert(log_schema().timestamp_key(), timestamp);
+            }
+            // Additional fields are prefixed with an underscore, which is
+            // dropped from their name.
+            _ => match key.strip_prefix('_') {
+                Some(name) => {
+                    log.insert(name, Value::from(value));
+                }
+                None => {
+                    log.insert(key.as_str(), Value::from(value));
+                }
+            },
+        }
+    }
+
+    let timestamp_key = log_schema().timestamp_key();
+    if !log.contains(timestamp_key) {
+        log.insert(timestamp_key, Utc::now());
+    }
+
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    const MESSAGE: &str = r#"{
+        "version": "1.1",
+        "host": "example.org",
+        "short_message": "A short message",
+        "full_message": "Backtrace here",
+        "timestamp": 1385053862.3072,
+        "level": 1,
+        "_user_id": 9001
+    }"#;
+
+    fn assert_parsed(events: SmallVec<[Event; 1]>) {
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "A short message".into());
+        assert_eq!(log[log_schema().host_key()], "example.org".into());
+        assert_eq!(log["full_message"], "Backtrace here".into());
+        assert_eq!(log["level"], 1.into());
+        assert_eq!(log["user_id"], 9001.into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1385053862, 307_200_000).into()
+        );
+    }
+
+    fn chunk(id: u64, number: u8, count: u8, payload: &[u8]) -> Bytes {
+        let mut chunk = CHUNK_MAGIC.to_vec();
+        chunk.extend_from_slice(&id.to_be_bytes());
+        chunk.extend_from_slice(&[number, count]);
+        chunk.extend_from_slice(payload);
+        chunk.into()
+    }
+
+    #[test]
+    fn parse_gelf() {
+        assert_parsed(GelfParser::new().parse(Bytes::from(MESSAGE)).unwrap());
+    }
+
+    #[test]
+    fn parse_compressed_gelf() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(MESSAGE.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_parsed(GelfParser::new().parse(compressed.into()).unwrap());
+    }
+
+    #[test]
+    fn reassemble_chunked_gelf() {
+        let parser = GelfParser::new();
+        let (first, second) = MESSAGE.as_bytes().split_at(40);
+
+        assert!(parser.parse(chunk(7, 1, 2, second)).unwrap().is_empty());
+        // Chunks of other messages are buffered separately.
+        assert!(parser.parse(chunk(8, 0, 2, first)).unwrap().is_empty());
+        // Clones share the buffered chunks.
+        assert_parsed(parser.clone().parse(chunk(7, 0, 2, first)).unwrap());
+        assert_eq!(parser.pending.lock().unwrap().messages.len(), 1);
+
+        assert!(parser.parse(chunk(9, 2, 2, first)).is_err());
+        assert!(parser.parse(chunk(9, 0, 129, first)).is_err());
+    }
+
+    #[test]
+    fn drops_oldest_chunked_gelf() {
+        let parser = GelfParser {
+            max_pending_messages: 2,
+            ..GelfParser::new()
+        };
+        let (first, second) = MESSAGE.as_bytes().split_at(40);
+
+        for id in 0..3 {
+            assert!(parser.parse(chunk(id, 1, 2, second)).unwrap().is_empty());
+        }
+        let ids = parser
+            .pending
+            .lock()
+            .unwrap()
+            .messages
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&0));
+
+        // The dropped message starts over.
+        assert!(parser.parse(chunk(0, 0, 2, first)).unwrap().is_empty());
+        assert_parsed(parser.parse(chunk(2, 0, 2, first)).unwrap());
+    }
+
+    #[test]
+    fn error_decompressed_too_large() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(MESSAGE.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let parser = GelfParser {
+            max_decompressed_bytes: MESSAGE.len() - 1,
+            ..GelfParser::new()
+        };
+        assert!(parser.parse(compressed.into()).is_err());
+    }
+
+    #[test]
+    fn error_invalid_timestamp() {
+        let input = Bytes::from(
+            r#"{"version": "1.1", "host": "example.org", "short_message": "A", "timestamp": 1e300}"#,
+        );
+        assert!(GelfParser::new().parse(input).is_err());
+    }
+
+    #[test]
+    fn error_missing_fields() {
+        let input = Bytes::from(r#"{"version": "1.1", "host": "example.org"}"#);
+        assert!(GelfParser::new().parse(input).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent},
+};
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+
+/// Config used to build a `LogfmtParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LogfmtParserConfig;
+
+#[typetag::serde(name = "logfmt")]
+impl ParserConfig for LogfmtParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(LogfmtParser::new()))
+    }
+}
+
+impl LogfmtParserConfig {
+    /// Creates a new `LogfmtParserConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Parser that builds an `Event` from a byte frame containing a logfmt line.
+///
+/// Keys without a value are skipped, as in the `logfmt_parser` transform, and
+/// all values are kept as strings.
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtParser;
+
+impl LogfmtParser {
+    /// Creates a new `LogfmtParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for LogfmtParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let line = std::str::from_utf8(&bytes)
+            .map_err(|error| format!("Error parsing logfmt: {}", error))?;
+        if line.trim().is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut log = LogEvent::default();
+        for logfmt::Pair { key, val } in logfmt::parse(line) {
+            if let Some(val) = val {
+                log.insert(key, val);
+            }
+        }
+
+        let timestamp_key = log_schema().timestamp_key();
+        if !log.contains(timestamp_key) {
+            log.insert(timestamp_key, Utc::now());
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_logfmt() {
+        let input = Bytes::from(r#"level=info msg="hello world" flag count=3"#);
+        let events = LogfmtParser::new().parse(input).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log["msg"], "hello world".into());
+        assert_eq!(log["count"], "3".into());
+        assert!(log.get("flag").is_none());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn skip_empty() {
+        assert!(LogfmtParser::new()
+            .parse(Bytes::from(" "))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -3,19 +3,36 @@
 
 #![deny(missing_docs)]
 
+mod avro;
 mod bytes;
+mod csv;
+mod gelf;
 mod json;
+mod logfmt;
+mod msgpack;
+mod native;
+mod protobuf;
+mod schema_registry;
 #[cfg(feature = "sources-syslog")]
 mod syslog;
 
+pub use self::avro::{AvroParser, AvroParserConfig};
 pub use self::bytes::{BytesParser, BytesParserConfig};
+pub use self::csv::{CsvParser, CsvParserConfig};
+pub use self::gelf::{GelfParser, GelfParserConfig};
+pub use self::logfmt::{LogfmtParser, LogfmtParserConfig};
+pub use self::msgpack::{MsgpackParser, MsgpackParserConfig};
+pub use self::native::{NativeParser, NativeParserConfig};
+pub use self::protobuf::{ProtobufParser, ProtobufParserConfig};
+pub use self::schema_registry::SchemaRegistryConfig;
 #[cfg(feature = "sources-syslog")]
 pub use self::syslog::{SyslogParser, SyslogParserConfig};
 pub use json::{JsonParser, JsonParserConfig};
 
-use crate::event::Event;
+use crate::{config::DataType, event::Event};
 use ::bytes::Bytes;
 use dyn_clone::DynClone;
+use futures::future::{self, BoxFuture};
 use smallvec::SmallVec;
 use std::fmt::Debug;
 
@@ -28,6 +45,13 @@ pub trait Parser: DynClone + Debug + Send + Sync {
     /// array. However, we optimize the most common case of emitting one event
     /// by not requiring heap allocations for it.
     fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>>;
+
+    /// Resolves what parsing the frame requires ahead of parsing it, such as
+    /// its schema from a schema registry, so that `parse` doesn't have to wait
+    /// for it.
+    fn resolve<'a>(&'a self, _frame: &'a [u8]) -> BoxFuture<'a, ()> {
+        Box::pin(future::ready(()))
+    }
 }
 
 dyn_clone::clone_trait_object!(Parser);
@@ -46,6 +70,12 @@ pub trait ParserConfig: Debug + DynClone + Send + Sync {
     ///
     /// Fails if the configuration is invalid.
     fn build(&self) -> crate::Result<BoxedParser>;
+
+    /// The type of the events built by the parser. Sources decoding their
+    /// input with it declare it as their output type.
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
 }
 
 dyn_clone::clone_trait_object!(ParserConfig);
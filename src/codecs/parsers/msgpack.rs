@@ -0,0 +1,174 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use std::collections::BTreeMap;
+
+/// Config used to build a `MsgpackParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackParserConfig;
+
+#[typetag::serde(name = "msgpack")]
+impl ParserConfig for MsgpackParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(MsgpackParser::new()))
+    }
+}
+
+impl MsgpackParserConfig {
+    /// Creates a new `MsgpackParserConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Parser that builds `Event`s from a byte frame containing a MessagePack map,
+/// or an array of maps.
+#[derive(Debug, Clone, Default)]
+pub struct MsgpackParser;
+
+impl MsgpackParser {
+    /// Creates a new `MsgpackParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for MsgpackParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let value = rmpv::decode::read_value(&mut &bytes[..])
+            .map_err(|error| format!("Error parsing MessagePack: {}", error))?;
+
+        let mut events = match value {
+            rmpv::Value::Array(values) => values
+                .into_iter()
+                .map(to_event)
+                .collect::<crate::Result<SmallVec<[Event; 1]>>>()?,
+            value => smallvec![to_event(value)?],
+        };
+
+        let timestamp = Utc::now();
+        for event in &mut events {
+            let log = event.as_mut_log();
+            let timestamp_key = log_schema().timestamp_key();
+
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, timestamp);
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+fn to_event(value: rmpv::Value) -> crate::Result<Event> {
+    match to_value(value) {
+        Value::Map(fields) => Ok(LogEvent::from(fields).into()),
+        value => Err(format!("Expected a MessagePack map, got {:?}.", value).into()),
+    }
+}
+
+fn to_value(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(boolean) => Value::Boolean(boolean),
+        rmpv::Value::Integer(int) => int
+            .as_i64()
+            .map(Value::Integer)
+            // Integers too large for an `i64` are kept as strings, as when
+            // parsing JSON.
+            .unwrap_or_else(|| Value::Bytes(int.to_string().into())),
+        rmpv::Value::F32(float) => Value::Float(float.into()),
+        rmpv::Value::F64(float) => Value::Float(float),
+        rmpv::Value::String(string) => Value::Bytes(string.into_bytes().into()),
+        rmpv::Value::Binary(bytes) => Value::Bytes(bytes.into()),
+        rmpv::Value::Array(values) => Value::Array(values.into_iter().map(to_value).collect()),
+        // Maps with keys other than strings can't be represented, so these
+        // keys are dropped.
+        rmpv::Value::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .filter_map(|(key, value)| match key {
+                    rmpv::Value::String(key) => key.into_str().map(|key| (key, to_value(value))),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        rmpv::Value::Ext(code, bytes) => {
+            let mut fields = BTreeMap::new();
+            fields.insert("msgpack_extension_code".into(), Value::Integer(code.into()));
+            fields.insert("bytes".into(), Value::Bytes(bytes.into()));
+            Value::Map(fields)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: rmpv::Value) -> Bytes {
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &value).unwrap();
+        bytes.into()
+    }
+
+    fn map(entries: Vec<(&str, rmpv::Value)>) -> rmpv::Value {
+        rmpv::Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn parse_msgpack() {
+        let input = encode(map(vec![
+            ("foo", 123.into()),
+            ("nested", map(vec![("bar", "baz".into())])),
+            (
+                "list",
+                rmpv::Value::Array(vec![true.into(), rmpv::Value::Nil]),
+            ),
+        ]));
+        let events = MsgpackParser::new().parse(input).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["foo"], 123.into());
+        assert_eq!(log["nested.bar"], "baz".into());
+        assert_eq!(log["list"], Value::Array(vec![true.into(), Value::Null]));
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn parse_msgpack_array() {
+        let input = encode(rmpv::Value::Array(vec![
+            map(vec![("foo", 123.into())]),
+            map(vec![("bar", 456.into())]),
+        ]));
+        let events = MsgpackParser::new().parse(input).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["foo"], 123.into());
+        assert_eq!(events[1].as_log()["bar"], 456.into());
+    }
+
+    #[test]
+    fn error_not_a_map() {
+        assert!(MsgpackParser::new().parse(encode("foo".into())).is_err());
+        assert!(MsgpackParser::new()
+            .parse(Bytes::from(&[0xc1][..]))
+            .is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::DataType,
+    event::{proto, Event},
+};
+use bytes::Bytes;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+
+/// Config used to build a `NativeParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeParserConfig;
+
+#[typetag::serde(name = "native")]
+impl ParserConfig for NativeParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(NativeParser::new()))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+}
+
+impl NativeParserConfig {
+    /// Creates a new `NativeParserConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Parser that builds an `Event` from a byte frame containing Vector's native
+/// protobuf representation, as used between Vector instances.
+#[derive(Debug, Clone, Default)]
+pub struct NativeParser;
+
+impl NativeParser {
+    /// Creates a new `NativeParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for NativeParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let wrapper = proto::EventWrapper::decode(bytes)
+            .map_err(|error| format!("Error parsing native event: {}", error))?;
+        // An empty frame decodes to a wrapper without any event.
+        match wrapper.event {
+            Some(_) => Ok(smallvec![wrapper.into()]),
+            None => Err("Error parsing native event: missing event.".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+    use bytes::BytesMut;
+
+    #[test]
+    fn parse_native_roundtrip() {
+        for event in vec![
+            Event::from("foo"),
+            Event::Metric(Metric::new(
+                "foos",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 2.0 },
+            )),
+        ] {
+            let mut buffer = BytesMut::new();
+            proto::EventWrapper::from(event.clone())
+                .encode(&mut buffer)
+                .unwrap();
+
+            let events = NativeParser::new().parse(buffer.freeze()).unwrap();
+            assert_eq!(events.into_vec(), vec![event]);
+        }
+    }
+
+    #[test]
+    fn error_empty_native() {
+        assert!(NativeParser::new().parse(Bytes::new()).is_err());
+    }
+
+    #[test]
+    fn error_invalid_native() {
+        assert!(NativeParser::new()
+            .parse(Bytes::from("\x0a\x05foo"))
+            .is_err());
+    }
+}
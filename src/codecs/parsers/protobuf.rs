@@ -0,0 +1,647 @@
+use super::schema_registry::{
+    schema_id, split_schema_id, RegistrySchema, SchemaCache, SchemaRegistryClient,
+    SchemaRegistryConfig,
+};
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use chrono::{TimeZone, Utc};
+use futures::future::BoxFuture;
+use prost::{
+    encoding::{check_wire_type, decode_key, decode_varint, skip_field, DecodeContext, WireType},
+    Message,
+};
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+    FileDescriptorSet,
+};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    convert::TryFrom,
+    fs,
+    path::PathBuf,
+    sync::Arc,
+};
+
+const TIMESTAMP_TYPE: &str = ".google.protobuf.Timestamp";
+
+/// Config used to build a `ProtobufParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProtobufParserConfig {
+    /// The schema registry to fetch schemas from, in which case messages are
+    /// expected in the Confluent wire format.
+    pub schema_registry: Option<SchemaRegistryConfig>,
+    /// The path of a file holding a `FileDescriptorSet`, as written by `protoc
+    /// --descriptor_set_out`, describing plain Protobuf messages.
+    pub descriptor_set_path: Option<PathBuf>,
+    /// The fully qualified name of the type of the messages, required along
+    /// with `descriptor_set_path`.
+    pub message_type: Option<String>,
+}
+
+#[typetag::serde(name = "protobuf")]
+impl ParserConfig for ProtobufParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        let schemas = match (
+            &self.schema_registry,
+            &self.descriptor_set_path,
+            &self.message_type,
+        ) {
+            (Some(registry), None, None) => ProtobufSchemas::Registry(Arc::new(
+                SchemaCache::new(SchemaRegistryClient::new(registry)?),
+            )),
+            (None, Some(path), Some(message_type)) => {
+                let descriptors = fs::read(path).map_err(|error| {
+                    format!("Could not read Protobuf descriptor set {:?}: {}", path, error)
+                })?;
+                let descriptors = FileDescriptorSet::decode(&descriptors[..])?;
+
+                let mut pool = DescriptorPool::default();
+                for file in &descriptors.file {
+                    pool.add_file(file);
+                }
+                let message_type = format!(".{}", message_type.trim_start_matches('.'));
+                pool.message(&message_type)?;
+
+                ProtobufSchemas::Static(Arc::new(ProtobufSchema { pool, message_type }))
+            }
+            _ => return Err("The `protobuf` codec requires either `schema_registry`, or `descriptor_set_path` and `message_type`.".into()),
+        };
+
+        Ok(Box::new(ProtobufParser { schemas }))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ProtobufSchemas {
+    Static(Arc<ProtobufSchema>),
+    Registry(Arc<SchemaCache<RegisteredProtobufSchema>>),
+}
+
+#[derive(Debug)]
+struct ProtobufSchema {
+    pool: DescriptorPool,
+    message_type: String,
+}
+
+/// The file of a schema from the registry, along with the files it references.
+#[derive(Debug)]
+struct RegisteredProtobufSchema {
+    pool: DescriptorPool,
+    file: FileDescriptorProto,
+}
+
+#[async_trait]
+impl RegistrySchema for RegisteredProtobufSchema {
+    async fn fetch(client: &SchemaRegistryClient, id: u32) -> crate::Result<Self> {
+        let registered = client.schema_by_id(id, Some("serialized")).await?;
+        if registered.schema_type.as_deref() != Some("PROTOBUF") {
+            return Err(format!("Schema {} isn't a Protobuf schema.", id).into());
+        }
+        let file = FileDescriptorProto::decode(&base64::decode(&registered.schema)?[..])?;
+
+        let mut pool = DescriptorPool::default();
+        pool.add_file(&file);
+
+        // References are fetched by subject and version, each only once.
+        let mut references = registered.references;
+        let mut fetched = HashSet::new();
+        while let Some(reference) = references.pop() {
+            if !fetched.insert((reference.subject.clone(), reference.version)) {
+                continue;
+            }
+            let registered = client
+                .schema_by_version(&reference.subject, reference.version, Some("serialized"))
+                .await?;
+            let file = FileDescriptorProto::decode(&base64::decode(&registered.schema)?[..])?;
+            pool.add_file(&file);
+            references.extend(registered.references);
+        }
+
+        Ok(Self { pool, file })
+    }
+}
+
+impl RegisteredProtobufSchema {
+    /// Resolves the message indexes of the Confluent wire format, which are the
+    /// path to the type of the message through the messages nested in the file.
+    fn message_type(&self, payload: &mut Bytes) -> crate::Result<String> {
+        let count = decode_zigzag(payload)?;
+        let indexes = if count == 0 {
+            vec![0]
+        } else {
+            (0..count)
+                .map(|_| decode_zigzag(payload))
+                .collect::<crate::Result<Vec<_>>>()?
+        };
+
+        let mut message_type = match self.file.package() {
+            "" => String::new(),
+            package => format!(".{}", package),
+        };
+        let mut messages = &self.file.message_type;
+        for index in indexes {
+            let message = usize::try_from(index)
+                .ok()
+                .and_then(|index| messages.get(index))
+                .ok_or_else(|| format!("Invalid message index {} in the schema.", index))?;
+            message_type = format!("{}.{}", message_type, message.name());
+            messages = &message.nested_type;
+        }
+        Ok(message_type)
+    }
+}
+
+/// Parser that builds an `Event` from a byte frame containing a Protobuf message.
+#[derive(Debug, Clone)]
+pub struct ProtobufParser {
+    schemas: ProtobufSchemas,
+}
+
+impl Parser for ProtobufParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<SmallVec<[Event; 1]>> {
+        let fields = match &self.schemas {
+            ProtobufSchemas::Static(schema) => {
+                schema.pool.decode_message(&schema.message_type, &bytes)?
+            }
+            ProtobufSchemas::Registry(schemas) => {
+                let (id, mut payload) = split_schema_id(bytes)?;
+                let schema = schemas.get(id)?;
+                let message_type = schema.message_type(&mut payload)?;
+                schema.pool.decode_message(&message_type, &payload)?
+            }
+        };
+
+        let mut log = LogEvent::from(fields);
+        let timestamp_key = log_schema().timestamp_key();
+        if !log.contains(timestamp_key) {
+            log.insert(timestamp_key, Utc::now());
+        }
+
+        Ok(smallvec![log.into()])
+    }
+
+    fn resolve<'a>(&'a self, frame: &'a [u8]) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            if let ProtobufSchemas::Registry(schemas) = &self.schemas {
+                if let Some(id) = schema_id(frame) {
+                    // Failures are reported when parsing the frame.
+                    let _ = schemas.resolve(id).await;
+                }
+            }
+        })
+    }
+}
+
+fn decode_zigzag(buf: &mut impl Buf) -> crate::Result<i64> {
+    let value = decode_varint(buf)?;
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+/// The message and enum types of Protobuf files, by their fully qualified names.
+#[derive(Debug, Default)]
+struct DescriptorPool {
+    messages: HashMap<String, DescriptorProto>,
+    enums: HashMap<String, EnumDescriptorProto>,
+}
+
+impl DescriptorPool {
+    fn add_file(&mut self, file: &FileDescriptorProto) {
+        let prefix = match file.package() {
+            "" => String::new(),
+            package => format!(".{}", package),
+        };
+        for message in &file.message_type {
+            self.add_message(&prefix, message);
+        }
+        for enum_type in &file.enum_type {
+            self.enums.insert(
+                format!("{}.{}", prefix, enum_type.name()),
+                enum_type.clone(),
+            );
+        }
+    }
+
+    fn add_message(&mut self, prefix: &str, message: &DescriptorProto) {
+        let name = format!("{}.{}", prefix, message.name());
+        for nested in &message.nested_type {
+            self.add_message(&name, nested);
+        }
+        for enum_type in &message.enum_type {
+            self.enums
+                .insert(format!("{}.{}", name, enum_type.name()), enum_type.clone());
+        }
+        self.messages.insert(name, message.clone());
+    }
+
+    fn message(&self, name: &str) -> crate::Result<&DescriptorProto> {
+        self.messages
+            .get(name)
+            .ok_or_else(|| format!("Unknown Protobuf message type {:?}.", name).into())
+    }
+
+    fn decode_message(
+        &self,
+        message_type: &str,
+        mut buf: &[u8],
+    ) -> crate::Result<BTreeMap<String, Value>> {
+        let message = self.message(message_type)?;
+
+        let mut fields = BTreeMap::new();
+        while buf.has_remaining() {
+            let (number, wire_type) = decode_key(&mut buf)?;
+            let field = match message
+                .field
+                .iter()
+                .find(|field| field.number() == number as i32)
+            {
+                Some(field) => field,
+                None => {
+                    skip_field(wire_type, number, &mut buf, DecodeContext::default())?;
+                    continue;
+                }
+            };
+
+            let mut values = self.decode_field(field, wire_type, &mut buf)?;
+            if field.label() == Label::Repeated {
+                let array = fields
+                    .entry(field.name().to_owned())
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(array) = array {
+                    array.extend(values);
+                }
+            } else if let Some(value) = values.pop() {
+                fields.insert(field.name().to_owned(), value);
+            }
+        }
+
+        // Map fields are encoded as repeated entry messages, which are turned
+        // back into maps.
+        for field in &message.field {
+            if field.r#type() == Type::Message && self.is_map_entry(field.type_name()) {
+                if let Some(Value::Array(entries)) = fields.remove(field.name()) {
+                    let map = entries
+                        .into_iter()
+                        .filter_map(|entry| match entry {
+                            Value::Map(mut entry) => {
+                                let key = entry.remove("key").map(map_key).unwrap_or_default();
+                                Some((key, entry.remove("value").unwrap_or(Value::Null)))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    fields.insert(field.name().to_owned(), Value::Map(map));
+                }
+            }
+        }
+
+        Ok(fields)
+    }
+
+    fn is_map_entry(&self, message_type: &str) -> bool {
+        self.messages
+            .get(message_type)
+            .and_then(|message| message.options.as_ref())
+            .map_or(false, |options| options.map_entry())
+    }
+
+    /// Decodes the values of a field, of which packed repeated fields hold many.
+    fn decode_field(
+        &self,
+        field: &FieldDescriptorProto,
+        wire_type: WireType,
+        buf: &mut &[u8],
+    ) -> crate::Result<Vec<Value>> {
+        let field_type = field.r#type();
+        if wire_type == WireType::LengthDelimited && wire_type_of(field_type)? != wire_type {
+            let mut packed = split_length_delimited(buf)?;
+            let mut values = Vec::new();
+            while packed.has_remaining() {
+                values.push(self.decode_value(field, &mut packed)?);
+            }
+            return Ok(values);
+        }
+
+        check_wire_type(wire_type_of(field_type)?, wire_type)?;
+        Ok(vec![self.decode_value(field, buf)?])
+    }
+
+    fn decode_value(&self, field: &FieldDescriptorProto, buf: &mut &[u8]) -> crate::Result<Value> {
+        Ok(match field.r#type() {
+            Type::Double => Value::Float(fixed(buf, 8, |buf| buf.get_f64_le())?),
+            Type::Float => Value::Float(fixed(buf, 4, |buf| buf.get_f32_le())?.into()),
+            Type::Int64 | Type::Uint64 => Value::Integer(decode_varint(buf)? as i64),
+            Type::Int32 => Value::Integer((decode_varint(buf)? as i32).into()),
+            Type::Uint32 => Value::Integer((decode_varint(buf)? as u32).into()),
+            Type::Sint32 | Type::Sint64 => Value::Integer(decode_zigzag(buf)?),
+            Type::Fixed64 | Type::Sfixed64 => {
+                Value::Integer(fixed(buf, 8, |buf| buf.get_i64_le())?)
+            }
+            Type::Fixed32 => Value::Integer(fixed(buf, 4, |buf| buf.get_u32_le())?.into()),
+            Type::Sfixed32 => Value::Integer(fixed(buf, 4, |buf| buf.get_i32_le())?.into()),
+            Type::Bool => Value::Boolean(decode_varint(buf)? != 0),
+            Type::Enum => {
+                let number = decode_varint(buf)? as i32;
+                self.enums
+                    .get(field.type_name())
+                    .and_then(|enum_type| {
+                        enum_type
+                            .value
+                            .iter()
+                            .find(|value| value.number() == number)
+                    })
+                    .map_or_else(
+                        || Value::Integer(number.into()),
+                        |value| value.name().to_owned().into(),
+                    )
+            }
+            Type::String | Type::Bytes => {
+                Value::Bytes(Bytes::copy_from_slice(split_length_delimited(buf)?))
+            }
+            Type::Message if field.type_name() == TIMESTAMP_TYPE => {
+                decode_timestamp(split_length_delimited(buf)?)?
+            }
+            Type::Message => {
+                Value::Map(self.decode_message(field.type_name(), split_length_delimited(buf)?)?)
+            }
+            Type::Group => return Err("Protobuf groups aren't supported.".into()),
+        })
+    }
+}
+
+fn wire_type_of(field_type: Type) -> crate::Result<WireType> {
+    Ok(match field_type {
+        Type::Int64
+        | Type::Uint64
+        | Type::Int32
+        | Type::Uint32
+        | Type::Sint32
+        | Type::Sint64
+        | Type::Bool
+        | Type::Enum => WireType::Varint,
+        Type::Double | Type::Fixed64 | Type::Sfixed64 => WireType::SixtyFourBit,
+        Type::Float | Type::Fixed32 | Type::Sfixed32 => WireType::ThirtyTwoBit,
+        Type::String | Type::Bytes | Type::Message => WireType::LengthDelimited,
+        Type::Group => return Err("Protobuf groups aren't supported.".into()),
+    })
+}
+
+fn split_length_delimited<'a>(buf: &mut &'a [u8]) -> crate::Result<&'a [u8]> {
+    let len = decode_varint(buf)? as usize;
+    if len > buf.len() {
+        return Err("Truncated Protobuf message.".into());
+    }
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(value)
+}
+
+fn fixed<T>(buf: &mut &[u8], size: usize, get: impl FnOnce(&mut &[u8]) -> T) -> crate::Result<T> {
+    if buf.len() < size {
+        return Err("Truncated Protobuf message.".into());
+    }
+    Ok(get(buf))
+}
+
+fn decode_timestamp(buf: &[u8]) -> crate::Result<Value> {
+    let timestamp = prost_types::Timestamp::decode(buf)?;
+    Ok(Utc
+        .timestamp_opt(timestamp.seconds, timestamp.nanos.max(0) as u32)
+        .single()
+        .ok_or_else(|| format!("Invalid Protobuf timestamp: {:?}.", timestamp))?
+        .into())
+}
+
+fn map_key(key: Value) -> String {
+    match key {
+        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        key => key.to_string_lossy(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::{EnumValueDescriptorProto, MessageOptions};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn field(name: &str, number: i32, label: Label, field_type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+            label: Some(label as i32),
+            r#type: Some(field_type as i32),
+            ..Default::default()
+        }
+    }
+
+    fn typed_field(
+        name: &str,
+        number: i32,
+        label: Label,
+        field_type: Type,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            type_name: Some(type_name.into()),
+            ..field(name, number, label, field_type)
+        }
+    }
+
+    // package test;
+    // message Event {
+    //   enum Level { INFO = 0; ERROR = 1; }
+    //   message Labels { map<string, string> values = 1; }
+    //   string message = 1;
+    //   sint64 count = 2;
+    //   repeated int32 codes = 3;
+    //   Level level = 4;
+    //   Labels labels = 5;
+    // }
+    fn file() -> FileDescriptorProto {
+        let entry = DescriptorProto {
+            name: Some("ValuesEntry".into()),
+            field: vec![
+                field("key", 1, Label::Optional, Type::String),
+                field("value", 2, Label::Optional, Type::String),
+            ],
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let labels = DescriptorProto {
+            name: Some("Labels".into()),
+            field: vec![typed_field(
+                "values",
+                1,
+                Label::Repeated,
+                Type::Message,
+                ".test.Event.Labels.ValuesEntry",
+            )],
+            nested_type: vec![entry],
+            ..Default::default()
+        };
+        let level = EnumDescriptorProto {
+            name: Some("Level".into()),
+            value: vec![
+                EnumValueDescriptorProto {
+                    name: Some("INFO".into()),
+                    number: Some(0),
+                    ..Default::default()
+                },
+                EnumValueDescriptorProto {
+                    name: Some("ERROR".into()),
+                    number: Some(1),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let event = DescriptorProto {
+            name: Some("Event".into()),
+            field: vec![
+                field("message", 1, Label::Optional, Type::String),
+                field("count", 2, Label::Optional, Type::Sint64),
+                field("codes", 3, Label::Repeated, Type::Int32),
+                typed_field("level", 4, Label::Optional, Type::Enum, ".test.Event.Level"),
+                typed_field(
+                    "labels",
+                    5,
+                    Label::Optional,
+                    Type::Message,
+                    ".test.Event.Labels",
+                ),
+            ],
+            nested_type: vec![labels],
+            enum_type: vec![level],
+            ..Default::default()
+        };
+        FileDescriptorProto {
+            name: Some("test.proto".into()),
+            package: Some("test".into()),
+            message_type: vec![event],
+            ..Default::default()
+        }
+    }
+
+    fn message() -> Vec<u8> {
+        vec![
+            0x0a, 5, b'h', b'e', b'l', b'l', b'o', // message = "hello"
+            0x10, 3, // count = -2
+            0x1a, 2, 200, 1, // codes = [200], packed
+            0x18, 7, // codes += 7, unpacked
+            0x20, 1, // level = ERROR
+            0x2a, 10, 0x0a, 8, 0x0a, 1, b'a', 0x12, 3, b'o', b'n', b'e', // labels = {a: one}
+            0x78, 1, // unknown field 15, skipped
+        ]
+    }
+
+    fn assert_parsed(events: SmallVec<[Event; 1]>) {
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["count"], (-2).into());
+        assert_eq!(log["codes"], Value::Array(vec![200.into(), 7.into()]));
+        assert_eq!(log["level"], "ERROR".into());
+        assert_eq!(log["labels.values.a"], "one".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn parses_with_descriptor_set() {
+        let descriptors = FileDescriptorSet { file: vec![file()] };
+        let mut descriptors_file = NamedTempFile::new().unwrap();
+        descriptors_file
+            .write_all(&descriptors.encode_to_vec())
+            .unwrap();
+
+        let config = ProtobufParserConfig {
+            descriptor_set_path: Some(descriptors_file.path().into()),
+            message_type: Some("test.Event".into()),
+            ..Default::default()
+        };
+        let parser = config.build().unwrap();
+        assert_parsed(parser.parse(message().into()).unwrap());
+
+        // Truncated messages are rejected.
+        let mut truncated = message();
+        truncated.truncate(4);
+        assert!(parser.parse(truncated.into()).is_err());
+
+        let config = ProtobufParserConfig {
+            message_type: Some("test.Unknown".into()),
+            ..config
+        };
+        assert!(config.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn parses_with_schema_registry() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/schemas/ids/5"))
+            .and(query_param("format", "serialized"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "schemaType": "PROTOBUF",
+                "schema": base64::encode(file().encode_to_vec()),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let parser = ProtobufParserConfig {
+            schema_registry: Some(SchemaRegistryConfig {
+                url: server.uri().parse().unwrap(),
+                auth: None,
+                tls: None,
+                timeout_secs: 10,
+            }),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+
+        // The message indexes are either a single 0 for the first message, or
+        // their count followed by the indexes, all zigzag encoded.
+        for indexes in vec![vec![0], vec![2, 0]] {
+            let mut bytes = vec![0, 0, 0, 0, 5];
+            bytes.extend(indexes);
+            bytes.extend(message());
+            assert_parsed(parser.parse(bytes.into()).unwrap());
+        }
+
+        // Nested message types can be referenced too.
+        let mut bytes = vec![0, 0, 0, 0, 5, 4, 0, 0];
+        bytes.extend(vec![0x0a, 8, 0x0a, 1, b'a', 0x12, 3, b'o', b'n', b'e']);
+        let events = parser.parse(bytes.into()).unwrap();
+        assert_eq!(events[0].as_log()["values.a"], "one".into());
+
+        let bytes = vec![0, 0, 0, 0, 5, 2, 2];
+        assert!(parser.parse(bytes.into()).is_err());
+    }
+
+    #[test]
+    fn requires_schema_source() {
+        assert!(ProtobufParserConfig::default().build().is_err());
+        assert!(ProtobufParserConfig {
+            descriptor_set_path: Some("/dev/null".into()),
+            ..Default::default()
+        }
+        .build()
+        .is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    codecs::{BoxedSerializer, Serializer, SerializerConfig},
+    event::Event,
+};
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+
+/// Config used to build a `JsonSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct JsonSerializerConfig;
+
+#[typetag::serde(name = "json")]
+impl SerializerConfig for JsonSerializerConfig {
+    fn build(&self) -> crate::Result<BoxedSerializer> {
+        Ok(Box::new(JsonSerializer::new()))
+    }
+}
+
+impl JsonSerializerConfig {
+    /// Creates a new `JsonSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the JSON format.
+#[derive(Debug, Clone, Default)]
+pub struct JsonSerializer;
+
+impl JsonSerializer {
+    /// Creates a new `JsonSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Serializer for JsonSerializer {
+    fn serialize(&self, event: Event, buffer: &mut BytesMut) -> crate::Result<()> {
+        let writer = buffer.writer();
+        match event {
+            Event::Log(log) => serde_json::to_writer(writer, &log),
+            Event::Metric(metric) => serde_json::to_writer(writer, &metric),
+            Event::Trace(trace) => serde_json::to_writer(writer, &trace),
+        }
+        .map_err(Into::into)
+    }
+
+    fn content_type(&self, newline_delimited: bool) -> &'static str {
+        // A payload of newline delimited frames is newline delimited JSON,
+        // while a single JSON document is sent as is.
+        if newline_delimited {
+            "application/x-ndjson"
+        } else {
+            "application/json"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+    use shared::btreemap;
+
+    #[test]
+    fn serialize_json_log() {
+        let event = Event::from(btreemap! {
+            "foo" => "bar",
+            "baz" => 123,
+        });
+        let mut buffer = BytesMut::new();
+
+        JsonSerializer::new().serialize(event, &mut buffer).unwrap();
+
+        assert_eq!(buffer.freeze(), r#"{"baz":123,"foo":"bar"}"#);
+    }
+
+    #[test]
+    fn serialize_json_metric() {
+        let event = Event::Metric(Metric::new(
+            "foos",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        ));
+        let mut buffer = BytesMut::new();
+
+        JsonSerializer::new().serialize(event, &mut buffer).unwrap();
+
+        assert_eq!(
+            buffer.freeze(),
+            r#"{"name":"foos","kind":"incremental","counter":{"value":2.0}}"#
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    codecs::{BoxedSerializer, Serializer, SerializerConfig},
+    event::Event,
+};
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use shared::encode_logfmt;
+use std::collections::BTreeMap;
+
+/// Config used to build a `LogfmtSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LogfmtSerializerConfig;
+
+#[typetag::serde(name = "logfmt")]
+impl SerializerConfig for LogfmtSerializerConfig {
+    fn build(&self) -> crate::Result<BoxedSerializer> {
+        Ok(Box::new(LogfmtSerializer::new()))
+    }
+}
+
+impl LogfmtSerializerConfig {
+    /// Creates a new `LogfmtSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the logfmt format.
+///
+/// Nested fields are flattened into dot-separated keys.
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtSerializer;
+
+impl LogfmtSerializer {
+    /// Creates a new `LogfmtSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Serializer for LogfmtSerializer {
+    fn serialize(&self, event: Event, buffer: &mut BytesMut) -> crate::Result<()> {
+        let encoded = match event {
+            Event::Log(log) => encode_logfmt::to_string(log.into_parts().0)?,
+            Event::Trace(trace) => encode_logfmt::to_string(trace.into_parts().0)?,
+            Event::Metric(metric) => {
+                // Metrics have no field representation of their own, so the
+                // fields of their JSON representation are used instead.
+                let fields: BTreeMap<String, serde_json::Value> =
+                    serde_json::from_value(serde_json::to_value(&metric)?)?;
+                encode_logfmt::to_string(fields)?
+            }
+        };
+        buffer.extend_from_slice(encoded.as_bytes());
+        Ok(())
+    }
+
+    fn content_type(&self, _newline_delimited: bool) -> &'static str {
+        "text/plain"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+    use shared::btreemap;
+
+    #[test]
+    fn serialize_logfmt_log() {
+        let mut log = LogEvent::from(btreemap! {
+            "foo" => "bar baz",
+        });
+        log.insert("nested.key", 1);
+        let event = Event::from(log);
+        let mut buffer = BytesMut::new();
+
+        LogfmtSerializer::new()
+            .serialize(event, &mut buffer)
+            .unwrap();
+
+        assert_eq!(buffer.freeze(), r#"foo="bar baz" nested.key=1"#);
+    }
+
+    #[test]
+    fn serialize_logfmt_metric() {
+        let event = Event::Metric(Metric::new(
+            "foos",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        ));
+        let mut buffer = BytesMut::new();
+
+        LogfmtSerializer::new()
+            .serialize(event, &mut buffer)
+            .unwrap();
+
+        assert_eq!(
+            buffer.freeze(),
+            "counter.value=2 kind=incremental name=foos"
+        );
+    }
+}
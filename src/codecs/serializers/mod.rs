@@ -0,0 +1,70 @@
+//! A collection of serializers that can be used to serialize structured events
+//! into byte frames.
+
+#![deny(missing_docs)]
+
+mod csv;
+mod json;
+mod logfmt;
+mod native;
+mod raw_message;
+
+pub use self::csv::{CsvSerializer, CsvSerializerConfig};
+pub use json::{JsonSerializer, JsonSerializerConfig};
+pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
+pub use native::{NativeSerializer, NativeSerializerConfig};
+pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
+
+use crate::event::{Event, LogEvent};
+use ::bytes::BytesMut;
+use dyn_clone::DynClone;
+use std::fmt::Debug;
+
+/// Serialize structured events into bytes.
+pub trait Serializer: DynClone + Debug + Send + Sync {
+    /// Serializes a structured event and appends the result to `buffer`.
+    ///
+    /// The serialized bytes are not delimited in any way, adding a frame around
+    /// them is the responsibility of the framing encoder.
+    fn serialize(&self, event: Event, buffer: &mut BytesMut) -> crate::Result<()>;
+
+    /// The media type of a payload made of the frames of serialized events,
+    /// as sent in the `Content-Type` header of HTTP requests.
+    ///
+    /// `newline_delimited` tells whether the frames are delimited by newlines.
+    fn content_type(&self, newline_delimited: bool) -> &'static str;
+}
+
+dyn_clone::clone_trait_object!(Serializer);
+
+/// A `Box` containing a `Serializer`.
+pub type BoxedSerializer = Box<dyn Serializer>;
+
+/// Define options for a serializer and build it from the config object.
+///
+/// Implementors must annotate the struct with `#[typetag::serde(name = "...")]`
+/// to define which value should be read from the `codec` key to select their
+/// implementation.
+#[typetag::serde(tag = "codec")]
+pub trait SerializerConfig: Debug + DynClone + Send + Sync {
+    /// Builds a serializer from this configuration.
+    ///
+    /// Fails if the configuration is invalid.
+    fn build(&self) -> crate::Result<BoxedSerializer>;
+}
+
+dyn_clone::clone_trait_object!(SerializerConfig);
+
+/// Extracts the log event for serializers that only operate on fields, since
+/// spans share their representation with logs.
+fn into_log(event: Event, codec: &str) -> crate::Result<LogEvent> {
+    match event {
+        Event::Log(log) => Ok(log),
+        Event::Trace(trace) => Ok(trace.into()),
+        Event::Metric(_) => Err(format!(
+            "Serializing metrics is not supported by the {:?} codec.",
+            codec
+        )
+        .into()),
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    codecs::{BoxedSerializer, Serializer, SerializerConfig},
+    event::{proto, Event},
+};
+use bytes::BytesMut;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+/// Config used to build a `NativeSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeSerializerConfig;
+
+#[typetag::serde(name = "native")]
+impl SerializerConfig for NativeSerializerConfig {
+    fn build(&self) -> crate::Result<BoxedSerializer> {
+        Ok(Box::new(NativeSerializer::new()))
+    }
+}
+
+impl NativeSerializerConfig {
+    /// Creates a new `NativeSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using Vector's native protobuf
+/// representation, as used between Vector instances.
+#[derive(Debug, Clone, Default)]
+pub struct NativeSerializer;
+
+impl NativeSerializer {
+    /// Creates a new `NativeSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Serializer for NativeSerializer {
+    fn serialize(&self, event: Event, buffer: &mut BytesMut) -> crate::Result<()> {
+        proto::EventWrapper::from(event)
+            .encode(buffer)
+            .map_err(Into::into)
+    }
+
+    fn content_type(&self, _newline_delimited: bool) -> &'static str {
+        "application/octet-stream"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn serialize_native_roundtrip() {
+        for event in vec![
+            Event::from("foo"),
+            Event::Metric(Metric::new(
+                "foos",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 2.0 },
+            )),
+        ] {
+            let mut buffer = BytesMut::new();
+            NativeSerializer::new()
+                .serialize(event.clone(), &mut buffer)
+                .unwrap();
+
+            let decoded = Event::from(proto::EventWrapper::decode(buffer).unwrap());
+            assert_eq!(decoded, event);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    codecs::{BoxedSerializer, Serializer, SerializerConfig},
+    config::log_schema,
+    event::Event,
+};
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+
+/// Config used to build a `RawMessageSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RawMessageSerializerConfig;
+
+#[typetag::serde(name = "raw_message")]
+impl SerializerConfig for RawMessageSerializerConfig {
+    fn build(&self) -> crate::Result<BoxedSerializer> {
+        Ok(Box::new(RawMessageSerializer::new()))
+    }
+}
+
+impl RawMessageSerializerConfig {
+    /// Creates a new `RawMessageSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Serializer that writes the message field of an `Event` as-is.
+///
+/// Events without a message field are serialized to an empty byte sequence.
+#[derive(Debug, Clone, Default)]
+pub struct RawMessageSerializer;
+
+impl RawMessageSerializer {
+    /// Creates a new `RawMessageSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Serializer for RawMessageSerializer {
+    fn serialize(&self, event: Event, buffer: &mut BytesMut) -> crate::Result<()> {
+        let log = super::into_log(event, "raw_message")?;
+        if let Some(message) = log.get(log_schema().message_key()) {
+            buffer.extend_from_slice(&message.as_bytes());
+        }
+        Ok(())
+    }
+
+    fn content_type(&self, _newline_delimited: bool) -> &'static str {
+        "text/plain"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+    use shared::btreemap;
+
+    #[test]
+    fn serialize_raw_message() {
+        let event = Event::from("foo bar");
+        let mut buffer = BytesMut::new();
+
+        RawMessageSerializer::new()
+            .serialize(event, &mut buffer)
+            .unwrap();
+
+        assert_eq!(buffer.freeze(), "foo bar");
+    }
+
+    #[test]
+    fn serialize_missing_message() {
+        let event = Event::from(btreemap! {
+            "foo" => "bar",
+        });
+        let mut buffer = BytesMut::new();
+
+        RawMessageSerializer::new()
+            .serialize(event, &mut buffer)
+            .unwrap();
+
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn reject_metrics() {
+        let event = Event::Metric(Metric::new(
+            "foos",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        ));
+        let mut buffer = BytesMut::new();
+
+        assert!(RawMessageSerializer::new()
+            .serialize(event, &mut buffer)
+            .is_err());
+    }
+}
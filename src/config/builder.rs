@@ -3,7 +3,7 @@ use super::api;
 #[cfg(feature = "datadog-pipelines")]
 use super::datadog;
 use super::{
-    compiler, provider, ComponentKey, Config, EnrichmentTableConfig, EnrichmentTableOuter,
+    compiler, provider, secret, ComponentKey, Config, EnrichmentTableConfig, EnrichmentTableOuter,
     HealthcheckOptions, SinkConfig, SinkOuter, SourceConfig, SourceOuter, TestDefinition,
     TransformOuter,
 };
@@ -35,6 +35,8 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub tests: Vec<TestDefinition>,
     pub provider: Option<Box<dyn provider::ProviderConfig>>,
+    #[serde(default)]
+    pub secret: IndexMap<ComponentKey, Box<dyn secret::SecretBackend>>,
 }
 
 impl Clone for ConfigBuilder {
@@ -88,6 +90,7 @@ impl From<Config> for ConfigBuilder {
             sinks,
             transforms,
             provider: None,
+            secret: IndexMap::new(),
             tests,
         }
     }
@@ -214,6 +217,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate enrichment_table name found: {}", k));
             }
         });
+        with.secret.keys().for_each(|k| {
+            if self.secret.contains_key(k) {
+                errors.push(format!("duplicate secret name found: {}", k));
+            }
+        });
         with.sources.keys().for_each(|k| {
             if self.sources.contains_key(k) {
                 errors.push(format!("duplicate source id found: {}", k));
@@ -239,6 +247,7 @@ impl ConfigBuilder {
         }
 
         self.enrichment_tables.extend(with.enrichment_tables);
+        self.secret.extend(with.secret);
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
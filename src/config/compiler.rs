@@ -8,7 +8,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
     let mut errors = Vec::new();
 
     // component names should not have dots in the configuration file
-    // but components can expand (like route) to have components with a dot
+    // but components can expand (like compound) to have components with a dot
     // so this check should be done before expanding components
     if let Err(name_errors) = validation::check_names(
         builder
@@ -32,6 +32,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(type_errors);
     }
 
+    if let Err(reroute_errors) = validation::check_reroute_dropped(&builder) {
+        errors.extend(reroute_errors);
+    }
+
     let ConfigBuilder {
         global,
         #[cfg(feature = "api")]
@@ -45,6 +49,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         transforms,
         tests,
         provider: _,
+        secret: _,
     } = builder;
 
     let graph = match Graph::new(&sources, &transforms, &sinks) {
@@ -166,6 +171,15 @@ fn expand_globs(config: &mut ConfigBuilder) {
                 .to_string()
             })
         }))
+        .chain(config.sinks.iter().flat_map(|(key, s)| {
+            s.named_outputs().into_iter().map(move |port| {
+                OutputId {
+                    component: key.clone(),
+                    port: Some(port),
+                }
+                .to_string()
+            })
+        }))
         .collect::<IndexSet<String>>();
 
     for (id, transform) in config.transforms.iter_mut() {
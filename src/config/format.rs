@@ -2,14 +2,15 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
-use serde::de;
+use serde::{de, Deserialize, Serialize};
 use std::path::Path;
 
 /// A type alias to better capture the semantics.
 pub type FormatHint = Option<Format>;
 
 /// The format used to represent the configuration data.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Format {
     /// TOML format is used.
     Toml,
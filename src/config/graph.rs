@@ -10,10 +10,12 @@ pub enum Node {
     Transform {
         in_ty: DataType,
         out_ty: DataType,
+        primary_output: bool,
         named_outputs: Vec<String>,
     },
     Sink {
         ty: DataType,
+        named_outputs: Vec<String>,
     },
 }
 
@@ -71,6 +73,7 @@ impl Graph {
                 Node::Transform {
                     in_ty: config.inner.input_type(),
                     out_ty: config.inner.output_type(),
+                    primary_output: config.inner.has_primary_output(),
                     named_outputs: config.inner.named_outputs(),
                 },
             );
@@ -81,6 +84,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input_type(),
+                    named_outputs: config.named_outputs(),
                 },
             );
         }
@@ -177,7 +181,10 @@ impl Graph {
                     (Node::Source { ty: ty1 }, Node::Sink { ty: ty2, .. })
                     | (Node::Source { ty: ty1 }, Node::Transform { in_ty: ty2, .. })
                     | (Node::Transform { out_ty: ty1, .. }, Node::Transform { in_ty: ty2, .. })
-                    | (Node::Transform { out_ty: ty1, .. }, Node::Sink { ty: ty2, .. }) => {
+                    | (Node::Transform { out_ty: ty1, .. }, Node::Sink { ty: ty2, .. })
+                    // Events rerouted by a sink are the ones it has received.
+                    | (Node::Sink { ty: ty1, .. }, Node::Transform { in_ty: ty2, .. })
+                    | (Node::Sink { ty: ty1, .. }, Node::Sink { ty: ty2, .. }) => {
                         if ty1 != ty2 && ty1 != DataType::Any && ty2 != DataType::Any {
                             errors.push(format!(
                                 "Data type mismatch between {} ({:?}) and {} ({:?})",
@@ -185,7 +192,7 @@ impl Graph {
                             ));
                         }
                     }
-                    (Node::Sink { .. }, _) | (_, Node::Source { .. }) => unreachable!(),
+                    (_, Node::Source { .. }) => unreachable!(),
                 }
             }
         }
@@ -203,10 +210,20 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
+                Node::Sink { named_outputs, .. } => named_outputs
+                    .iter()
+                    .map(|n| OutputId::from((key, n.clone())))
+                    .collect(),
                 Node::Source { .. } => vec![key.clone().into()],
-                Node::Transform { named_outputs, .. } => {
-                    let mut outputs = vec![key.clone().into()];
+                Node::Transform {
+                    primary_output,
+                    named_outputs,
+                    ..
+                } => {
+                    let mut outputs = Vec::new();
+                    if *primary_output {
+                        outputs.push(key.clone().into());
+                    }
                     outputs.extend(
                         named_outputs
                             .clone()
@@ -226,8 +243,8 @@ impl Graph {
     /// When we get a dotted path in the `inputs` section of a user's config, we need to determine
     /// which of a few things that represents:
     ///
-    ///   1. A component that's part of an expanded macro (e.g. `route.branch`)
-    ///   2. A named output of a branching transform (e.g. `name.errors`)
+    ///   1. A component that's part of an expanded macro (e.g. `compound.step`)
+    ///   2. A named output of a branching transform (e.g. `route.branch` or `name.errors`)
     ///
     /// A naive way to do that is to compare the string representation of all valid inputs to the
     /// provided string and pick the one that matches. This works better if you can assume that there
@@ -329,6 +346,7 @@ mod test {
                 Node::Transform {
                     in_ty,
                     out_ty,
+                    primary_output: true,
                     named_outputs: Default::default(),
                 },
             );
@@ -348,10 +366,24 @@ mod test {
             }
         }
 
+        fn add_sink_output(&mut self, id: &str, name: &str) {
+            let id = id.into();
+            match self.nodes.get_mut(&id) {
+                Some(Node::Sink { named_outputs, .. }) => named_outputs.push(name.into()),
+                _ => panic!("invalid sink"),
+            }
+        }
+
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    named_outputs: Default::default(),
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -562,6 +594,57 @@ mod test {
         );
     }
 
+    #[test]
+    fn disallows_missing_primary_output() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_transform("router", DataType::Any, DataType::Any, vec!["log_source"]);
+        graph.add_transform_output("router", "errors");
+        if let Some(Node::Transform { primary_output, .. }) =
+            graph.nodes.get_mut(&ComponentKey::from("router"))
+        {
+            *primary_output = false;
+        }
+        graph.add_sink("errors_sink", DataType::Log, vec![]);
+        graph.add_sink("bad_sink", DataType::Log, vec![]);
+
+        assert_eq!(Ok(()), graph.test_add_input("errors_sink", "router.errors"));
+
+        let expected = "Input \"router\" for sink \"bad_sink\" doesn't match any components.";
+        assert_eq!(
+            Err(expected.to_string()),
+            graph.test_add_input("bad_sink", "router")
+        );
+    }
+
+    #[test]
+    fn allows_sink_outputs_as_inputs() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_sink("http", DataType::Log, vec!["log_source"]);
+        graph.add_sink_output("http", "dropped");
+        graph.add_sink("archive", DataType::Any, vec![]);
+        graph.add_sink("metric_sink", DataType::Metric, vec![]);
+
+        assert_eq!(Ok(()), graph.test_add_input("archive", "http.dropped"));
+        assert_eq!(Ok(()), graph.typecheck());
+
+        // sinks don't have a default output
+        let expected = "Input \"http\" for sink \"archive\" doesn't match any components.";
+        assert_eq!(
+            Err(expected.to_string()),
+            graph.test_add_input("archive", "http")
+        );
+
+        assert_eq!(Ok(()), graph.test_add_input("metric_sink", "http.dropped"));
+        assert_eq!(
+            Err(vec![
+                "Data type mismatch between http (Log) and metric_sink (Metric)".into()
+            ]),
+            graph.typecheck()
+        );
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
@@ -579,6 +662,7 @@ mod test {
             Node::Transform {
                 in_ty: DataType::Any,
                 out_ty: DataType::Any,
+                primary_output: true,
                 named_outputs: vec![String::from("bar")],
             },
         );
@@ -593,6 +677,7 @@ mod test {
             Node::Transform {
                 in_ty: DataType::Any,
                 out_ty: DataType::Any,
+                primary_output: true,
                 named_outputs: vec![String::from("errors")],
             },
         );
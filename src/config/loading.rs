@@ -1,5 +1,6 @@
 use super::{
-    builder::ConfigBuilder, format, validation, vars, Config, ConfigPath, Format, FormatHint,
+    builder::ConfigBuilder, format, secret, validation, vars, Config, ConfigPath, Format,
+    FormatHint,
 };
 use crate::signal;
 use glob::glob;
@@ -30,6 +31,12 @@ fn default_config_paths() -> Vec<ConfigPath> {
     )]
 }
 
+/// The kind and id of the single component defined by an input, if it's namespaced.
+type Namespace = Option<(String, String)>;
+
+/// The component kinds which can be namespaced in their own directory.
+const COMPONENT_KINDS: &[&str] = &["enrichment_tables", "sources", "transforms", "sinks"];
+
 lazy_static! {
     pub static ref CONFIG_PATHS: Mutex<Vec<ConfigPath>> = Mutex::default();
 }
@@ -182,6 +189,76 @@ pub fn load_builder_from_paths(
     }
 }
 
+/// Loads a config from a directory in which every component is defined in a file of its own,
+/// named after the component id and placed in a sub-directory named after its kind, e.g.
+/// `sources/in.toml`. Files at the root of the directory are loaded as regular configs.
+pub fn load_builder_from_dir(path: &Path) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    let mut inputs = Vec::new();
+    let mut errors = Vec::new();
+
+    let entries = read_dir_paths(path)
+        .map_err(|err| vec![format!("Could not read config dir: {:?}, {}.", path, err)])?;
+
+    for entry in entries {
+        if entry.is_dir() {
+            let kind = match entry.file_name().and_then(|name| name.to_str()) {
+                Some(kind) if COMPONENT_KINDS.contains(&kind) => kind,
+                // skip hidden directories, such as those of version control
+                Some(name) if name.starts_with('.') => continue,
+                _ => {
+                    errors.push(format!("Unknown component kind directory: {:?}.", entry));
+                    continue;
+                }
+            };
+
+            let files = match read_dir_paths(&entry) {
+                Ok(files) => files,
+                Err(err) => {
+                    errors.push(format!("Could not read config dir: {:?}, {}.", entry, err));
+                    continue;
+                }
+            };
+
+            for file_path in files {
+                // skip any unknown file formats
+                if let Ok(format) = Format::from_path(&file_path) {
+                    let id = file_path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .map(ToOwned::to_owned);
+                    match (id, open_config(&file_path)) {
+                        (Some(id), Some(file)) => {
+                            inputs.push((file, Some(format), Some((kind.to_owned(), id))))
+                        }
+                        _ => errors.push(format!("Could not load config file: {:?}.", file_path)),
+                    }
+                }
+            }
+        } else if let Ok(format) = Format::from_path(&entry) {
+            match open_config(&entry) {
+                Some(file) => inputs.push((file, Some(format), None)),
+                None => errors.push(format!("Config file not found in path: {:?}.", entry)),
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        load_from_namespaced_inputs(inputs)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Lists the paths in a directory, sorted so loading order doesn't depend on the file system.
+fn read_dir_paths(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = path
+        .read_dir()?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
 pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<String>> {
     let (builder, load_warnings) = load_from_inputs(std::iter::once((input.as_bytes(), format)))?;
     let (config, build_warnings) = builder.build_with_warnings()?;
@@ -195,16 +272,48 @@ pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<Stri
 
 fn load_from_inputs(
     inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint)>,
+) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    load_from_namespaced_inputs(
+        inputs
+            .into_iter()
+            .map(|(input, format)| (input, format, None)),
+    )
+}
+
+fn load_from_namespaced_inputs(
+    inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint, Namespace)>,
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
     let mut config = Config::builder();
+    let mut sources = Vec::new();
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
-    for (input, format) in inputs {
-        if let Err(errs) = load(input, format).and_then(|(n, mut warn)| {
-            warnings.append(&mut warn);
-            config.append(n)
-        }) {
+    for (input, format, namespace) in inputs {
+        match prepare_input(input) {
+            Ok((source, mut warn)) => {
+                warnings.append(&mut warn);
+                sources.push((source, format, namespace));
+            }
+            Err(errs) => errors.extend(errs),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Secrets can be referenced from any input, so the backends of every input must be known
+    // before the first one is deserialized.
+    let secrets = secret::retrieve(
+        sources
+            .iter()
+            .map(|(source, format, _)| (source.as_str(), *format)),
+    )?;
+
+    for (source, format, namespace) in sources {
+        if let Err(errs) = deserialize_namespaced(&source, format, namespace, &secrets)
+            .and_then(|n| config.append(n))
+        {
             // TODO: add back paths
             errors.extend(errs.iter().map(|e| e.to_string()));
         }
@@ -217,6 +326,36 @@ fn load_from_inputs(
     }
 }
 
+/// Deserializes a config, or a single component when the input is namespaced, interpolating the
+/// secrets it references.
+fn deserialize_namespaced(
+    source: &str,
+    format: FormatHint,
+    namespace: Namespace,
+    secrets: &HashMap<String, String>,
+) -> Result<ConfigBuilder, Vec<String>> {
+    match namespace {
+        None if secrets.is_empty() => format::deserialize(source, format),
+        None => serde_json::from_value(secret::interpolate(source, format, secrets)?)
+            .map_err(|error| vec![error.to_string()]),
+        Some((kind, id)) => {
+            let component = if secrets.is_empty() {
+                format::deserialize(source, format)?
+            } else {
+                secret::interpolate(source, format, secrets)?
+            };
+
+            let mut components = serde_json::Map::new();
+            components.insert(id.clone(), component);
+            let mut config = serde_json::Map::new();
+            config.insert(kind.clone(), components.into());
+
+            serde_json::from_value(config.into())
+                .map_err(|error| vec![format!("{}.{}: {}", kind, id, error)])
+        }
+    }
+}
+
 fn open_config(path: &Path) -> Option<File> {
     match File::open(path) {
         Ok(f) => Some(f),
@@ -233,9 +372,17 @@ fn open_config(path: &Path) -> Option<File> {
 }
 
 pub fn load(
-    mut input: impl std::io::Read,
+    input: impl std::io::Read,
     format: FormatHint,
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    let (source, warnings) = prepare_input(input)?;
+    let secrets = secret::retrieve(std::iter::once((source.as_str(), format)))?;
+
+    deserialize_namespaced(&source, format, None, &secrets).map(|builder| (builder, warnings))
+}
+
+/// Reads the input and interpolates the environment variables it references.
+fn prepare_input(mut input: impl std::io::Read) -> Result<(String, Vec<String>), Vec<String>> {
     let mut source_string = String::new();
     input
         .read_to_string(&mut source_string)
@@ -247,7 +394,6 @@ pub fn load(
             vars.insert("HOSTNAME".into(), hostname);
         }
     }
-    let (with_vars, warnings) = vars::interpolate(&source_string, &vars);
 
-    format::deserialize(&with_vars, format).map(|builder| (builder, warnings))
+    Ok(vars::interpolate(&source_string, &vars))
 }
@@ -1,6 +1,7 @@
 use crate::{
     buffers::Acker,
     conditions,
+    dropped::DroppedSender,
     event::Metric,
     shutdown::ShutdownSignal,
     sinks::{self, util::UriSerde},
@@ -30,6 +31,7 @@ mod graph;
 mod id;
 mod loading;
 pub mod provider;
+pub mod secret;
 mod unit_test;
 mod validation;
 mod vars;
@@ -40,8 +42,8 @@ pub use diff::ConfigDiff;
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, ComponentScope, OutputId};
 pub use loading::{
-    load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider, load_from_str,
-    merge_path_lists, process_paths, CONFIG_PATHS,
+    load, load_builder_from_dir, load_builder_from_paths, load_from_paths,
+    load_from_paths_with_provider, load_from_str, merge_path_lists, process_paths, CONFIG_PATHS,
 };
 pub use unit_test::build_unit_tests_main as build_unit_tests;
 pub use validation::warnings;
@@ -256,6 +258,12 @@ pub struct SinkOuter<T> {
     )]
     proxy: ProxyConfig,
 
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    reroute_dropped: bool,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -269,6 +277,7 @@ impl<T> SinkOuter<T> {
             healthcheck_uri: None,
             inner,
             proxy: Default::default(),
+            reroute_dropped: false,
         }
     }
 
@@ -300,6 +309,15 @@ impl<T> SinkOuter<T> {
         &self.proxy
     }
 
+    /// Whether events dropped by the sink are sent to its `dropped` output.
+    pub const fn reroute_dropped(&self) -> bool {
+        self.reroute_dropped
+    }
+
+    pub fn named_outputs(&self) -> Vec<String> {
+        crate::dropped::named_outputs(self.reroute_dropped)
+    }
+
     fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U> {
         let inputs = self.inputs.iter().map(f).collect();
         self.with_inputs(inputs)
@@ -313,6 +331,7 @@ impl<T> SinkOuter<T> {
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            reroute_dropped: self.reroute_dropped,
         }
     }
 }
@@ -364,6 +383,12 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
     fn resources(&self) -> Vec<Resource> {
         Vec::new()
     }
+
+    /// Whether the sink sends the events it drops to the sender of
+    /// `SinkContext::dropped`, so that `reroute_dropped` can be enabled.
+    fn can_reroute_dropped(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -372,6 +397,7 @@ pub struct SinkContext {
     pub(super) healthcheck: SinkHealthcheckOptions,
     pub(super) globals: GlobalOptions,
     pub(super) proxy: ProxyConfig,
+    pub(super) dropped: Option<DroppedSender>,
 }
 
 impl SinkContext {
@@ -382,6 +408,7 @@ impl SinkContext {
             healthcheck: SinkHealthcheckOptions::default(),
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
+            dropped: None,
         }
     }
 
@@ -396,6 +423,12 @@ impl SinkContext {
     pub const fn proxy(&self) -> &ProxyConfig {
         &self.proxy
     }
+
+    /// Where to send dropped events to, if the sink has `reroute_dropped`
+    /// enabled.
+    pub fn dropped(&self) -> Option<DroppedSender> {
+        self.dropped.clone()
+    }
 }
 
 pub type SinkDescription = ComponentDescription<Box<dyn SinkConfig>>;
@@ -687,6 +720,92 @@ mod test {
         assert_eq!("that", config.global.log_schema.message_key().to_string());
         assert_eq!("then", config.global.log_schema.timestamp_key().to_string());
     }
+    #[cfg(feature = "secrets")]
+    #[test]
+    fn secrets_from_file_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("host_key"), "from_secret\n").unwrap();
+
+        let config = load_from_str(
+            &format!(
+                indoc! {r#"
+                    [secret.files]
+                      type = "file"
+                      path = {:?}
+
+                    [log_schema]
+                      host_key = "SECRET[files.host_key]"
+
+                    [sources.in]
+                      type = "file"
+                      include = ["/var/log/messages"]
+
+                    [sinks.out]
+                      type = "console"
+                      inputs = ["in"]
+                      encoding = "json"
+                "#},
+                dir.path()
+            ),
+            Some(Format::Toml),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "from_secret",
+            config.global.log_schema.host_key().to_string()
+        );
+    }
+
+    #[test]
+    fn undefined_secret_backend() {
+        let errors = load_from_str(
+            indoc! {r#"
+                [log_schema]
+                  host_key = "SECRET[missing.host_key]"
+
+                [sources.in]
+                  type = "file"
+                  include = ["/var/log/messages"]
+
+                [sinks.out]
+                  type = "console"
+                  inputs = ["in"]
+                  encoding = "json"
+            "#},
+            Some(Format::Toml),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec!["Secret backend \"missing\" is not defined.".to_owned()]
+        );
+    }
+
+    #[test]
+    fn reroute_dropped_without_serializer() {
+        let errors = load_from_str(
+            indoc! {r#"
+                [sources.in]
+                  type = "file"
+                  include = ["/var/log/messages"]
+
+                [sinks.out]
+                  type = "console"
+                  inputs = ["in"]
+                  encoding = "json"
+                  reroute_dropped = true
+            "#},
+            Some(Format::Toml),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec!["Sink \"out\": `reroute_dropped` is not supported by the `console` sink with this configuration".to_owned()]
+        );
+    }
 
     #[test]
     fn config_append() {
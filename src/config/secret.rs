@@ -0,0 +1,386 @@
+use super::{format, ComponentKey, FormatHint};
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::RwLock,
+};
+
+/// Placeholder shown in place of a secret value.
+pub const REDACTED: &str = "**REDACTED**";
+
+lazy_static! {
+    static ref SECRET_REFERENCE: Regex = Regex::new(r"SECRET\[([\w-]+)\.([^\]\s]+)\]").unwrap();
+    /// The secret values retrieved for the current config, kept so they can be masked in any
+    /// output.
+    static ref RETRIEVED: RwLock<HashSet<String>> = RwLock::default();
+}
+
+#[typetag::serde(tag = "type")]
+pub trait SecretBackend: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
+    /// Retrieves the values of the given keys. Every requested key must be present in the
+    /// returned map.
+    fn retrieve(&mut self, keys: HashSet<String>) -> crate::Result<HashMap<String, String>>;
+}
+
+dyn_clone::clone_trait_object!(SecretBackend);
+
+/// The `secret` section of a config, deserialized on its own before the rest of the config so
+/// the backends are available to resolve secrets referenced anywhere else.
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct SecretBackendLoader {
+    #[serde(default)]
+    secret: IndexMap<ComponentKey, Box<dyn SecretBackend>>,
+}
+
+/// Retrieves every secret referenced in the string values of the inputs from the backends
+/// defined in them. The returned map is keyed by `<backend>.<key>`.
+///
+/// The secrets retrieved for a previous config, before it was reloaded, are forgotten.
+pub fn retrieve<'a>(
+    inputs: impl IntoIterator<Item = (&'a str, FormatHint)> + Clone,
+) -> Result<HashMap<String, String>, Vec<String>> {
+    let mut keys = HashMap::new();
+    for (input, format) in inputs.clone() {
+        // Only inputs that may reference secrets are parsed here.
+        if SECRET_REFERENCE.is_match(input) {
+            let value = format::deserialize::<serde_json::Value>(input, format)?;
+            visit_strings(&value, &mut |string| collect_keys(string, &mut keys));
+        }
+    }
+
+    if keys.is_empty() {
+        RETRIEVED.write().expect("poisoned lock").clear();
+        return Ok(HashMap::new());
+    }
+
+    let mut backends = IndexMap::new();
+    let mut errors = Vec::new();
+    for (input, format) in inputs {
+        match format::deserialize::<SecretBackendLoader>(input, format) {
+            Ok(loader) => {
+                for (name, backend) in loader.secret {
+                    backends.entry(name).or_insert(backend);
+                }
+            }
+            Err(errs) => errors.extend(errs),
+        }
+    }
+
+    let mut secrets = HashMap::new();
+    for (name, keys) in keys {
+        let backend = match backends.get_mut(&ComponentKey::from(name.as_str())) {
+            Some(backend) => backend,
+            None => {
+                errors.push(format!("Secret backend {:?} is not defined.", name));
+                continue;
+            }
+        };
+
+        match backend.retrieve(keys.clone()) {
+            Ok(mut values) => {
+                for key in keys {
+                    match values.remove(&key) {
+                        Some(value) => {
+                            secrets.insert(format!("{}.{}", name, key), value);
+                        }
+                        None => errors.push(format!(
+                            "Secret {:?} was not returned by backend {:?}.",
+                            key, name
+                        )),
+                    }
+                }
+            }
+            Err(error) => errors.push(format!(
+                "Failed to retrieve secrets from backend {:?}: {}.",
+                name, error
+            )),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    *RETRIEVED.write().expect("poisoned lock") = secrets
+        .values()
+        .filter(|value| !value.is_empty())
+        .cloned()
+        .collect();
+
+    Ok(secrets)
+}
+
+/// Calls `f` with every string value found in `value`.
+fn visit_strings(value: &serde_json::Value, f: &mut impl FnMut(&str)) {
+    match value {
+        serde_json::Value::String(string) => f(string),
+        serde_json::Value::Array(values) => values.iter().for_each(|value| visit_strings(value, f)),
+        serde_json::Value::Object(map) => map.values().for_each(|value| visit_strings(value, f)),
+        _ => {}
+    }
+}
+
+/// Collects the keys referenced in the input, grouped by backend.
+fn collect_keys(input: &str, keys: &mut HashMap<String, HashSet<String>>) {
+    for caps in SECRET_REFERENCE.captures_iter(input) {
+        keys.entry(caps[1].to_owned())
+            .or_default()
+            .insert(caps[2].to_owned());
+    }
+}
+
+/// Parses the input, replacing every secret reference in its string values by the value of the
+/// secret.
+///
+/// Secrets are interpolated once the input is parsed, so that their values never need to be
+/// escaped for the format of the input, and references in comments are ignored.
+pub fn interpolate(
+    input: &str,
+    format: FormatHint,
+    secrets: &HashMap<String, String>,
+) -> Result<serde_json::Value, Vec<String>> {
+    let mut value = format::deserialize::<serde_json::Value>(input, format)?;
+    let mut errors = Vec::new();
+    interpolate_strings(&mut value, secrets, &mut errors);
+
+    if errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(errors)
+    }
+}
+
+fn interpolate_strings(
+    value: &mut serde_json::Value,
+    secrets: &HashMap<String, String>,
+    errors: &mut Vec<String>,
+) {
+    match value {
+        serde_json::Value::String(string) => {
+            if let Cow::Owned(interpolated) = interpolate_str(string, secrets, errors) {
+                *string = interpolated;
+            }
+        }
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| interpolate_strings(value, secrets, errors)),
+        serde_json::Value::Object(map) => map
+            .values_mut()
+            .for_each(|value| interpolate_strings(value, secrets, errors)),
+        _ => {}
+    }
+}
+
+/// Replaces every secret reference in the input by its value.
+fn interpolate_str<'a>(
+    input: &'a str,
+    secrets: &HashMap<String, String>,
+    errors: &mut Vec<String>,
+) -> Cow<'a, str> {
+    SECRET_REFERENCE.replace_all(input, |caps: &Captures<'_>| {
+        let reference = format!("{}.{}", &caps[1], &caps[2]);
+        secrets.get(&reference).cloned().unwrap_or_else(|| {
+            errors.push(format!("Unknown secret in config. name = {:?}", reference));
+            String::new()
+        })
+    })
+}
+
+/// Masks every retrieved secret value found in the input.
+pub fn redact(input: &str) -> Cow<'_, str> {
+    mask(input, &RETRIEVED.read().expect("poisoned lock"))
+}
+
+fn mask<'a>(input: &'a str, secrets: &HashSet<String>) -> Cow<'a, str> {
+    let mut output = Cow::Borrowed(input);
+    for secret in secrets.iter() {
+        if output.contains(secret.as_str()) {
+            output = Cow::Owned(output.replace(secret.as_str(), REDACTED));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::Format;
+
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    struct MockBackend {
+        prefix: String,
+    }
+
+    #[typetag::serde(name = "mock")]
+    impl SecretBackend for MockBackend {
+        fn retrieve(&mut self, keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+            Ok(keys
+                .into_iter()
+                .filter(|key| key != "missing")
+                .map(|key| (key.clone(), format!("{}{}", self.prefix, key)))
+                .collect())
+        }
+    }
+
+    #[test]
+    fn collects_keys() {
+        let mut keys = HashMap::new();
+        collect_keys(
+            "a = SECRET[first.one]\nb = \"SECRET[first.two]\"\nc = SECRET[sec-ond.path/three]",
+            &mut keys,
+        );
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            keys["first"],
+            vec!["one".to_owned(), "two".to_owned()]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            keys["sec-ond"],
+            vec!["path/three".to_owned()].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        let secrets = vec![
+            ("backend.key".into(), "value".into()),
+            ("backend.other".into(), "other".into()),
+        ]
+        .into_iter()
+        .collect();
+        let interpolate = |input| {
+            let mut errors = Vec::new();
+            let interpolated = interpolate_str(input, &secrets, &mut errors).into_owned();
+            if errors.is_empty() {
+                Ok(interpolated)
+            } else {
+                Err(errors)
+            }
+        };
+
+        assert_eq!(Ok("value".to_owned()), interpolate("SECRET[backend.key]"));
+        assert_eq!(
+            Ok("x value other y".to_owned()),
+            interpolate("x SECRET[backend.key] SECRET[backend.other] y")
+        );
+        assert_eq!(
+            Ok("SECRET[backend]".to_owned()),
+            interpolate("SECRET[backend]")
+        );
+        assert_eq!(
+            Ok("SECRET[backend.]".to_owned()),
+            interpolate("SECRET[backend.]")
+        );
+        assert!(interpolate("SECRET[backend.unknown]").is_err());
+    }
+
+    #[test]
+    fn interpolates_parsed_values() {
+        let secrets = vec![("backend.key".into(), "a \"quoted\"\nvalue".into())]
+            .into_iter()
+            .collect();
+        let input = r#"
+            # SECRET[backend.unknown] is ignored in comments.
+            [sources.in]
+            type = "stdin"
+            host_key = "SECRET[backend.key]"
+            tags = ["SECRET[backend.key]"]
+        "#;
+
+        let value = interpolate(input, Some(Format::Toml), &secrets).unwrap();
+        assert_eq!(value["sources"]["in"]["host_key"], "a \"quoted\"\nvalue");
+        assert_eq!(value["sources"]["in"]["tags"][0], "a \"quoted\"\nvalue");
+    }
+
+    #[test]
+    fn retrieves_secrets() {
+        let input = r#"
+            [secret.mock]
+            type = "mock"
+            prefix = "retrieved-"
+
+            [sources.in]
+            type = "stdin"
+            host_key = "SECRET[mock.host]"
+        "#;
+
+        let secrets = retrieve(vec![(input, Some(Format::Toml))]).unwrap();
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets["mock.host"], "retrieved-host");
+    }
+
+    #[test]
+    fn masks_secrets() {
+        let secrets = vec!["retrieved-host".to_owned()].into_iter().collect();
+        assert_eq!(
+            mask("the host is retrieved-host", &secrets),
+            format!("the host is {}", REDACTED)
+        );
+        assert!(matches!(mask("the host is", &secrets), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn ignores_secrets_in_comments() {
+        let input = r#"
+            # host_key = "SECRET[undefined.host]"
+            [sources.in]
+            type = "stdin"
+        "#;
+
+        assert!(retrieve(vec![(input, Some(Format::Toml))])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn retrieves_secrets_across_inputs() {
+        let first = r#"
+            [secret.mock]
+            type = "mock"
+            prefix = "across-"
+        "#;
+        let second = r#"
+            [sources.in]
+            type = "stdin"
+            host_key = "SECRET[mock.host]"
+        "#;
+
+        let secrets = retrieve(vec![
+            (first, Some(Format::Toml)),
+            (second, Some(Format::Toml)),
+        ])
+        .unwrap();
+        assert_eq!(secrets["mock.host"], "across-host");
+    }
+
+    #[test]
+    fn retrieve_errors() {
+        let input = r#"
+            [secret.mock]
+            type = "mock"
+            prefix = ""
+
+            [sources.in]
+            type = "stdin"
+            host_key = "SECRET[mock.missing]"
+            other = "SECRET[undefined.key]"
+        "#;
+
+        let mut errors = retrieve(vec![(input, Some(Format::Toml))]).unwrap_err();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "Secret \"missing\" was not returned by backend \"mock\".".to_owned(),
+                "Secret backend \"undefined\" is not defined.".to_owned(),
+            ]
+        );
+    }
+}
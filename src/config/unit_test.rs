@@ -1,13 +1,13 @@
 use super::{
     graph::Graph, ComponentKey, Config, ConfigBuilder, ConfigDiff, ConfigPath, GlobalOptions,
-    TestDefinition, TestInput, TestInputValue, TransformConfig, TransformContext,
+    OutputId, TestDefinition, TestInput, TestInputValue, TransformConfig, TransformContext,
 };
 use crate::{
     conditions::Condition,
     config,
     event::{Event, Value},
     topology::builder::load_enrichment_tables,
-    transforms::Transform,
+    transforms::{Transform, TransformOutputsBuf},
 };
 use indexmap::IndexMap;
 use std::collections::HashMap;
@@ -84,18 +84,19 @@ pub struct UnitTest {
     inputs: Vec<(Vec<ComponentKey>, Event)>,
     transforms: IndexMap<ComponentKey, UnitTestTransform>,
     checks: Vec<UnitTestCheck>,
-    no_outputs_from: Vec<ComponentKey>,
+    no_outputs_from: Vec<OutputId>,
     globals: GlobalOptions,
 }
 
 struct UnitTestTransform {
     transform: Transform,
     config: Box<dyn TransformConfig>,
-    next: Vec<ComponentKey>,
+    /// The transforms fed by each of the outputs of this one.
+    next: Vec<(OutputId, ComponentKey)>,
 }
 
 struct UnitTestCheck {
-    extract_from: ComponentKey,
+    extract_from: OutputId,
     conditions: Vec<Box<dyn Condition>>,
 }
 
@@ -103,6 +104,7 @@ fn event_to_string(event: &Event) -> String {
     match event {
         Event::Log(log) => serde_json::to_string(&log).unwrap_or_else(|_| "{}".into()),
         Event::Metric(metric) => serde_json::to_string(&metric).unwrap_or_else(|_| "{}".into()),
+        Event::Trace(trace) => serde_json::to_string(&trace).unwrap_or_else(|_| "{}".into()),
     }
 }
 
@@ -127,20 +129,27 @@ fn events_to_string(name: &str, events: &[Event]) -> String {
 
 fn walk(
     node: &ComponentKey,
-    mut inputs: Vec<Event>,
+    inputs: Vec<Event>,
     transforms: &mut IndexMap<ComponentKey, UnitTestTransform>,
-    aggregated_results: &mut HashMap<ComponentKey, (Vec<Event>, Vec<Event>)>,
+    aggregated_results: &mut HashMap<OutputId, (Vec<Event>, Vec<Event>)>,
     globals: &GlobalOptions,
 ) {
-    let mut results = Vec::new();
+    let mut results: IndexMap<OutputId, Vec<Event>> = IndexMap::new();
+    results.insert(node.into(), Vec::new());
     let mut targets = Vec::new();
 
     // Use `remove` to take ownership.
     if let Some((key, mut target)) = transforms.remove_entry(node) {
+        let named_outputs = target.config.named_outputs();
+        for name in &named_outputs {
+            results.insert(OutputId::from((node, name.clone())), Vec::new());
+        }
+        let primary = &mut results[0];
+
         match target.transform {
             Transform::Function(ref mut t) => {
                 for input in inputs.clone() {
-                    t.transform(&mut results, input)
+                    t.transform(primary, input)
                 }
                 targets = target.next.clone();
                 transforms.insert(key, target);
@@ -148,10 +157,24 @@ fn walk(
             Transform::FallibleFunction(ref mut t) => {
                 let mut err_buf = Vec::new();
                 for input in inputs.clone() {
-                    t.transform(&mut results, &mut err_buf, input)
+                    t.transform(primary, &mut err_buf, input)
+                }
+                if let Some(name) = named_outputs.first() {
+                    results[&OutputId::from((node, name.clone()))] = err_buf;
+                }
+
+                targets = target.next.clone();
+                transforms.insert(key, target);
+            }
+            Transform::Synchronous(ref mut t) => {
+                let mut buf = TransformOutputsBuf::new_with_capacity(named_outputs.clone(), 1);
+                for input in inputs.clone() {
+                    t.transform(input, &mut buf)
+                }
+                *primary = buf.take_primary();
+                for name in named_outputs {
+                    results[&OutputId::from((node, name.clone()))] = buf.take_named(&name);
                 }
-                // unit tests don't currently support multiple outputs, so just throw these away
-                err_buf.clear();
 
                 targets = target.next.clone();
                 transforms.insert(key, target);
@@ -162,7 +185,7 @@ fn walk(
                 let out_stream = t.transform(Box::pin(in_stream));
                 // TODO(new-transform-enum): Handle Many
                 let out_iter = futures::executor::block_on_stream(out_stream);
-                results.extend(out_iter);
+                primary.extend(out_iter);
                 targets = target.next.clone();
                 // TODO: This is a hack.
                 // Our tasktransforms must consume the transform to attach it to an input stream, so we rebuild it between input streams.
@@ -176,21 +199,24 @@ fn walk(
         }
     }
 
-    for child in targets {
+    for (output, child) in targets {
         walk(
             &child,
-            results.clone(),
+            results[&output].clone(),
             transforms,
             aggregated_results,
             globals,
         );
     }
 
-    if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(node) {
-        inputs.append(&mut e_inputs);
-        results.append(&mut e_results);
+    for (output, mut results) in results {
+        let mut inputs = inputs.clone();
+        if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(&output) {
+            inputs.append(&mut e_inputs);
+            results.append(&mut e_results);
+        }
+        aggregated_results.insert(output, (inputs, results));
     }
-    aggregated_results.insert(node.clone(), (inputs, results));
 }
 
 impl UnitTest {
@@ -445,13 +471,36 @@ async fn build_unit_test(
 
     config.transforms.iter().for_each(|(k, t)| {
         t.inputs.iter().for_each(|i| {
-            // TODO: this is intentionally ignoring named outputs for now
             if let Some(outputs) = transform_outputs.get_mut(&i.component) {
                 outputs.insert(k.clone(), ());
             }
         })
     });
 
+    // Outputs are referred to like inputs are, including the named outputs
+    // of transforms.
+    let available_outputs = config
+        .transforms
+        .iter()
+        .flat_map(|(k, t)| {
+            std::iter::once(OutputId::from(k))
+                .filter(move |_| t.inner.has_primary_output())
+                .chain(
+                    t.inner
+                        .named_outputs()
+                        .into_iter()
+                        .map(move |name| OutputId::from((k, name))),
+                )
+        })
+        .map(|output| (output.to_string(), output))
+        .collect::<HashMap<_, _>>();
+    let resolve_output = |key: &ComponentKey| {
+        available_outputs
+            .get(&key.to_string())
+            .cloned()
+            .unwrap_or_else(|| key.into())
+    };
+
     for (i, (input_target, _)) in inputs.iter().enumerate() {
         for target in input_target {
             if !transform_outputs.contains_key(target) {
@@ -468,10 +517,10 @@ async fn build_unit_test(
 
     let mut leaves: IndexMap<ComponentKey, ()> = IndexMap::new();
     definition.outputs.iter().for_each(|o| {
-        leaves.insert(o.extract_from.clone(), ());
+        leaves.insert(resolve_output(&o.extract_from).component, ());
     });
     definition.no_outputs_from.iter().for_each(|o| {
-        leaves.insert(o.clone(), ());
+        leaves.insert(resolve_output(o).component, ());
     });
 
     // Reduce the configured transforms into just the ones connecting our test
@@ -508,7 +557,17 @@ async fn build_unit_test(
                         UnitTestTransform {
                             transform,
                             config: transform_config.inner.clone(),
-                            next: outputs.into_iter().map(|(k, _)| k).collect(),
+                            next: outputs
+                                .into_iter()
+                                .flat_map(|(k, _)| {
+                                    config.transforms[&k]
+                                        .inputs
+                                        .iter()
+                                        .filter(|i| &i.component == id)
+                                        .map(move |i| (i.clone(), k.clone()))
+                                        .collect::<Vec<_>>()
+                                })
+                                .collect(),
                         },
                     );
                 }
@@ -524,7 +583,7 @@ async fn build_unit_test(
     }
 
     definition.outputs.iter().for_each(|o| {
-        if !transforms.contains_key(&o.extract_from) {
+        if !transforms.contains_key(&resolve_output(&o.extract_from).component) {
             let targets = inputs.iter().map(|(i, _)| i).flatten().collect::<Vec<_>>();
             if targets.len() == 1 {
                 errors.push(format!(
@@ -563,7 +622,7 @@ async fn build_unit_test(
             }
 
             UnitTestCheck {
-                extract_from: o.extract_from.clone(),
+                extract_from: resolve_output(&o.extract_from),
                 conditions,
             }
         })
@@ -585,7 +644,11 @@ async fn build_unit_test(
             inputs,
             transforms,
             checks,
-            no_outputs_from: definition.no_outputs_from.clone(),
+            no_outputs_from: definition
+                .no_outputs_from
+                .iter()
+                .map(resolve_output)
+                .collect(),
             globals: config.global.clone(),
         })
     }
@@ -1060,6 +1123,36 @@ mod tests {
         assert_eq!(tests[0].run().1, Vec::<String>::new());
     }
 
+    #[tokio::test]
+    async fn test_route_unmatched() {
+        let config: ConfigBuilder = toml::from_str(indoc! {r#"
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "route"
+              [transforms.foo.route.first]
+                type = "check_fields"
+                "message.eq" = "test swimlane 1"
+
+            [[tests]]
+              name = "unmatched route test"
+              no_outputs_from = ["foo.first"]
+
+              [tests.input]
+                insert_at = "foo"
+                value = "test swimlane 2"
+
+              [[tests.outputs]]
+                extract_from = "foo._unmatched"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "message.equals" = "test swimlane 2"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+    }
+
     #[tokio::test]
     async fn test_fail_no_outputs() {
         let config: ConfigBuilder = toml::from_str(indoc! {r#"
@@ -138,26 +138,60 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Checks that the sinks with `reroute_dropped` enabled do send the events
+/// they drop to their `dropped` output.
+pub fn check_reroute_dropped(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let errors: Vec<_> = config
+        .sinks
+        .iter()
+        .filter(|(_, sink)| sink.reroute_dropped() && !sink.inner.can_reroute_dropped())
+        .map(|(key, sink)| {
+            format!(
+                "Sink \"{}\": `reroute_dropped` is not supported by the `{}` sink with this configuration",
+                key,
+                sink.inner.sink_type()
+            )
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub fn warnings(config: &Config) -> Vec<String> {
     let mut warnings = vec![];
 
-    let source_names = config.sources.keys().map(|name| ("source", name.clone()));
-    let transform_names = config
-        .transforms
+    let source_names = config
+        .sources
         .keys()
-        .map(|name| ("transform", name.clone()));
+        .map(|name| ("source", name.clone(), vec![OutputId::from(name)]));
+    let transform_names = config.transforms.iter().map(|(name, transform)| {
+        // Transforms without a primary output, like `route`, are consumed
+        // through their named outputs instead.
+        let ids = if transform.inner.has_primary_output() {
+            vec![OutputId::from(name)]
+        } else {
+            transform
+                .inner
+                .named_outputs()
+                .into_iter()
+                .map(|output| OutputId::from((name, output)))
+                .collect()
+        };
+        ("transform", name.clone(), ids)
+    });
 
     // TODO: maybe warn about no consumers for named outputs as well?
-    for (input_type, name) in transform_names.chain(source_names) {
-        let id = OutputId::from(&name);
+    for (input_type, name, ids) in transform_names.chain(source_names) {
+        let consumes = |inputs: &[OutputId]| ids.iter().any(|id| inputs.contains(id));
         if !config
             .transforms
             .iter()
-            .any(|(_, transform)| transform.inputs.contains(&id))
-            && !config
-                .sinks
-                .iter()
-                .any(|(_, sink)| sink.inputs.contains(&id))
+            .any(|(_, transform)| consumes(&transform.inputs))
+            && !config.sinks.iter().any(|(_, sink)| consumes(&sink.inputs))
         {
             warnings.push(format!(
                 "{} \"{}\" has no consumers",
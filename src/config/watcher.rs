@@ -29,13 +29,34 @@ const RETRY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 pub fn spawn_thread<'a>(
     config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
     delay: impl Into<Option<Duration>>,
+) -> Result<(), Error> {
+    spawn_thread_with_handler(config_paths, delay, false, || {
+        raise_sighup();
+        true
+    })
+}
+
+/// Calls `on_change` when files on config_paths change, with the same guarantees as
+/// `spawn_thread`. Directories are watched recursively if `recursive` is set. The thread
+/// stops once `on_change` returns false.
+#[cfg(unix)]
+pub fn spawn_thread_with_handler<'a>(
+    config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
+    delay: impl Into<Option<Duration>>,
+    recursive: bool,
+    mut on_change: impl FnMut() -> bool + Send + 'static,
 ) -> Result<(), Error> {
     let config_paths: Vec<_> = config_paths.into_iter().cloned().collect();
     let delay = delay.into().unwrap_or(CONFIG_WATCH_DELAY);
+    let mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
 
     // Create watcher now so not to miss any changes happening between
     // returning from this function and the thread starting.
-    let mut watcher = Some(create_watcher(&config_paths)?);
+    let mut watcher = Some(create_watcher(&config_paths, mode)?);
 
     info!("Watching configuration files.");
 
@@ -49,14 +70,16 @@ pub fn spawn_thread<'a>(
                     while let Ok(..) = receiver.recv_timeout(delay) {}
 
                     // We need to read paths to resolve any inode changes that may have happened.
-                    // And we need to do it before raising sighup to avoid missing any change.
-                    if let Err(error) = add_paths(&mut watcher, &config_paths) {
+                    // And we need to do it before notifying to avoid missing any change.
+                    if let Err(error) = add_paths(&mut watcher, &config_paths, mode) {
                         error!(message = "Failed to read files to watch.", %error);
                         break;
                     }
 
                     info!("Configuration file changed.");
-                    raise_sighup();
+                    if !on_change() {
+                        return;
+                    }
                 } else {
                     debug!(message = "Ignoring event.", event = ?event)
                 }
@@ -65,16 +88,18 @@ pub fn spawn_thread<'a>(
 
         thread::sleep(RETRY_TIMEOUT);
 
-        watcher = create_watcher(&config_paths)
+        watcher = create_watcher(&config_paths, mode)
             .map_err(|error| error!(message = "Failed to create file watcher.", %error))
             .ok();
 
         if watcher.is_some() {
             // Config files could have changed while we weren't watching,
-            // so for a good measure raise SIGHUP and let reload logic
+            // so for a good measure notify and let reload logic
             // determine if anything changed.
             info!("Speculating that configuration files have changed.");
-            raise_sighup();
+            if !on_change() {
+                return;
+            }
         }
     });
 
@@ -90,6 +115,17 @@ pub fn spawn_thread<'a>(
     Err("Reloading config on Windows isn't currently supported. Related issue https://github.com/timberio/vector/issues/938 .".into())
 }
 
+#[cfg(windows)]
+/// Errors on Windows.
+pub fn spawn_thread_with_handler<'a>(
+    _config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
+    _delay: impl Into<Option<Duration>>,
+    _recursive: bool,
+    _on_change: impl FnMut() -> bool + Send + 'static,
+) -> Result<(), Error> {
+    Err("Reloading config on Windows isn't currently supported. Related issue https://github.com/timberio/vector/issues/938 .".into())
+}
+
 #[cfg(unix)]
 fn raise_sighup() {
     use nix::sys::signal;
@@ -101,18 +137,23 @@ fn raise_sighup() {
 #[cfg(unix)]
 fn create_watcher(
     config_paths: &[PathBuf],
+    mode: RecursiveMode,
 ) -> Result<(RecommendedWatcher, Receiver<RawEvent>), Error> {
     info!("Creating configuration file watcher.");
     let (sender, receiver) = channel();
     let mut watcher = raw_watcher(sender)?;
-    add_paths(&mut watcher, config_paths)?;
+    add_paths(&mut watcher, config_paths, mode)?;
     Ok((watcher, receiver))
 }
 
 #[cfg(unix)]
-fn add_paths(watcher: &mut RecommendedWatcher, config_paths: &[PathBuf]) -> Result<(), Error> {
+fn add_paths(
+    watcher: &mut RecommendedWatcher,
+    config_paths: &[PathBuf],
+    mode: RecursiveMode,
+) -> Result<(), Error> {
     for path in config_paths {
-        watcher.watch(path, RecursiveMode::NonRecursive)?;
+        watcher.watch(path, mode)?;
     }
     Ok(())
 }
@@ -0,0 +1,168 @@
+//! Rerouting of events that a component would otherwise drop.
+//!
+//! Components supporting it expose a `reroute_dropped` option. When enabled,
+//! events that fail to be processed are sent to the `<component>.dropped`
+//! output instead of being discarded, where any downstream component can pick
+//! them up, e.g. to archive them.
+//!
+//! Each rerouted event is annotated with the reason it has been dropped for and
+//! the error message, while the topology adds the details of the component the
+//! event has been dropped by.
+
+use crate::{config::ComponentKey, event::Event, internal_events::DroppedOutputFull};
+use futures::channel::mpsc;
+use std::sync::{Arc, Mutex};
+
+/// Name of the output that dropped events are rerouted to.
+pub const DROPPED_OUTPUT: &str = "dropped";
+
+/// Number of events dropped by a sink that can wait to be forwarded to its
+/// `dropped` output.
+const DROPPED_CAPACITY: usize = 1000;
+
+/// Prefix of the fields (or tags, for metrics) describing why an event has
+/// been dropped.
+const METADATA_PREFIX: &str = "metadata.dropped";
+
+fn insert(event: &mut Event, key: &str, value: String) {
+    let key = format!("{}.{}", METADATA_PREFIX, key);
+    match event {
+        Event::Log(log) => {
+            log.insert(key, value);
+        }
+        Event::Metric(metric) => {
+            metric.insert_tag(key, value);
+        }
+        Event::Trace(trace) => {
+            trace.insert(key, value);
+        }
+    }
+}
+
+/// Annotates `event` with the `reason` it has been dropped for, and the
+/// `message` of the error causing it.
+pub fn annotate(event: &mut Event, reason: &str, message: impl ToString) {
+    insert(event, "reason", reason.to_owned());
+    insert(event, "message", message.to_string());
+}
+
+/// Annotates `event` with the details of the component it has been dropped by.
+pub(crate) fn annotate_component(
+    event: &mut Event,
+    key: &ComponentKey,
+    component_type: &str,
+    component_kind: &str,
+) {
+    insert(event, "component_id", key.to_string());
+    insert(event, "component_type", component_type.to_owned());
+    insert(event, "component_kind", component_kind.to_owned());
+}
+
+/// The named outputs of a component, depending on its `reroute_dropped`
+/// option.
+pub(crate) fn named_outputs(reroute_dropped: bool) -> Vec<String> {
+    if reroute_dropped {
+        vec![DROPPED_OUTPUT.to_owned()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Sends events dropped by a sink to its `dropped` output.
+///
+/// Sinks drop events deep inside of their encoding and request building, so
+/// sending never waits for downstream components to catch up. Instead, events
+/// are discarded, and counted, while the `dropped` output is full.
+#[derive(Clone, Debug)]
+pub struct DroppedSender {
+    // A single sender is shared by all clones, as each sender of a bounded
+    // channel is guaranteed a slot of its own.
+    inner: Arc<Mutex<mpsc::Sender<Event>>>,
+}
+
+impl DroppedSender {
+    pub(crate) fn new() -> (Self, mpsc::Receiver<Event>) {
+        Self::with_capacity(DROPPED_CAPACITY)
+    }
+
+    fn with_capacity(capacity: usize) -> (Self, mpsc::Receiver<Event>) {
+        let (inner, rx) = mpsc::channel(capacity);
+        let inner = Arc::new(Mutex::new(inner));
+        (Self { inner }, rx)
+    }
+
+    /// Annotates `event` like [`annotate`] and sends it to the `dropped`
+    /// output, or discards it if the output is full.
+    pub fn send(&self, mut event: Event, reason: &str, message: impl ToString) {
+        annotate(&mut event, reason, message);
+        if let Err(error) = self.inner.lock().unwrap().try_send(event) {
+            // The receiving end is only gone once the topology is shutting
+            // down.
+            if error.is_full() {
+                emit!(&DroppedOutputFull);
+            }
+        }
+    }
+
+    /// Stops accepting events, so that the `dropped` output finishes once the
+    /// events sent so far have been forwarded.
+    pub(crate) fn close(&self) {
+        self.inner.lock().unwrap().close_channel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn annotates_logs() {
+        let mut event = Event::from("foo");
+        annotate(&mut event, "error", "failed");
+        annotate_component(
+            &mut event,
+            &ComponentKey::from("parser"),
+            "remap",
+            "transform",
+        );
+
+        let log = event.as_log();
+        assert_eq!(log["metadata.dropped.reason"], "error".into());
+        assert_eq!(log["metadata.dropped.message"], "failed".into());
+        assert_eq!(log["metadata.dropped.component_id"], "parser".into());
+        assert_eq!(log["metadata.dropped.component_type"], "remap".into());
+        assert_eq!(log["metadata.dropped.component_kind"], "transform".into());
+    }
+
+    #[test]
+    fn annotates_metrics() {
+        let mut event = Event::Metric(Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        annotate(&mut event, "error", "failed");
+
+        let tags = event.as_metric().tags().unwrap();
+        assert_eq!(tags["metadata.dropped.reason"], "error");
+        assert_eq!(tags["metadata.dropped.message"], "failed");
+    }
+
+    #[test]
+    fn discards_while_full() {
+        let (dropped, mut rx) = DroppedSender::with_capacity(1);
+        let clone = dropped.clone();
+        for _ in 0..5 {
+            clone.send(Event::from("foo"), "error", "failed");
+        }
+        dropped.close();
+
+        let mut count = 0;
+        while let Ok(Some(_)) = rx.try_next() {
+            count += 1;
+        }
+        // The capacity of the channel, plus the slot of its single sender.
+        assert_eq!(count, 2);
+    }
+}
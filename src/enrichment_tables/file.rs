@@ -1,11 +1,12 @@
 use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription};
 use bytes::Bytes;
-use enrichment::{Case, Condition, IndexHandle, Table};
+use enrichment::{Case, Condition, IndexHandle, IndexKind, Table};
 use serde::{Deserialize, Serialize};
 use shared::{conversion::Conversion, datetime::TimeZone};
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::hash::Hasher;
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::time::SystemTime;
 use tracing::trace;
@@ -20,6 +21,10 @@ enum Encoding {
         #[serde(default = "default_delimiter")]
         delimiter: char,
     },
+    /// One JSON object per line, the keys of the objects being the columns.
+    JsonLines,
+    /// A SQLite database, the rows being the result of the query.
+    Sqlite { query: String },
 }
 
 impl Default for Encoding {
@@ -49,6 +54,25 @@ const fn default_delimiter() -> char {
 }
 
 impl FileConfig {
+    /// Applies the schema to a value that already has a type, such as the values of JSON
+    /// objects or SQLite columns.
+    ///
+    /// Only strings are converted according to the schema, the other values are kept as-is.
+    fn parse_typed_column(
+        &self,
+        timezone: TimeZone,
+        column: &str,
+        row: usize,
+        value: Value,
+    ) -> Result<Value, String> {
+        match value {
+            Value::Bytes(bytes) => {
+                self.parse_column(timezone, column, row, &String::from_utf8_lossy(&bytes))
+            }
+            value => Ok(value),
+        }
+    }
+
     fn parse_column(
         &self,
         timezone: TimeZone,
@@ -110,11 +134,47 @@ impl FileConfig {
         &self,
         timezone: TimeZone,
     ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>, SystemTime)> {
-        let Encoding::Csv {
-            include_headers,
-            delimiter,
-        } = self.file.encoding;
+        let (headers, data) = match &self.file.encoding {
+            Encoding::Csv {
+                include_headers,
+                delimiter,
+            } => self.load_csv(timezone, *include_headers, *delimiter)?,
+            Encoding::JsonLines => self.load_json_lines(timezone)?,
+            Encoding::Sqlite { query } => self.load_sqlite(timezone, query)?,
+        };
+
+        trace!(
+            "Loaded enrichment file {} with headers {:?}.",
+            self.file.path.to_str().unwrap_or("path with invalid utf"),
+            headers
+        );
+
+        let modified = self.modified()?;
+
+        Ok((headers, data, modified))
+    }
 
+    /// The last time the data file has been modified. Writes to SQLite
+    /// databases in WAL mode only modify the `-wal` file, until they are
+    /// checkpointed into the database itself.
+    fn modified(&self) -> std::io::Result<SystemTime> {
+        let modified = fs::metadata(&self.file.path)?.modified()?;
+        if let Encoding::Sqlite { .. } = self.file.encoding {
+            let mut wal = self.file.path.clone().into_os_string();
+            wal.push("-wal");
+            if let Ok(wal_modified) = fs::metadata(wal).and_then(|metadata| metadata.modified()) {
+                return Ok(modified.max(wal_modified));
+            }
+        }
+        Ok(modified)
+    }
+
+    fn load_csv(
+        &self,
+        timezone: TimeZone,
+        include_headers: bool,
+        delimiter: char,
+    ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
         let mut reader = csv::ReaderBuilder::new()
             .has_headers(include_headers)
             .delimiter(delimiter as u8)
@@ -146,15 +206,96 @@ impl FileConfig {
             })
             .collect::<crate::Result<Vec<_>>>()?;
 
-        trace!(
-            "Loaded enrichment file {} with headers {:?}.",
-            self.file.path.to_str().unwrap_or("path with invalid utf"),
-            headers
-        );
+        Ok((headers, data))
+    }
 
-        let modified = fs::metadata(&self.file.path)?.modified()?;
+    /// Loads a file holding a JSON object per line.
+    ///
+    /// The headers are all the keys found in the objects, a row holds null for the keys missing
+    /// from its object.
+    fn load_json_lines(&self, timezone: TimeZone) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
+        let file = fs::read_to_string(&self.file.path)?;
 
-        Ok((headers, data, modified))
+        let objects = file
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                serde_json::from_str::<BTreeMap<String, Value>>(line)
+                    .map_err(|error| format!("unable to parse line {}: {}", idx + 1, error))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut headers = objects
+            .iter()
+            .flat_map(|object| object.keys())
+            .cloned()
+            .collect::<Vec<_>>();
+        headers.sort();
+        headers.dedup();
+
+        let data = objects
+            .into_iter()
+            .enumerate()
+            .map(|(row, mut object)| {
+                headers
+                    .iter()
+                    .map(|header| match object.remove(header) {
+                        Some(value) => self.parse_typed_column(timezone, header, row, value),
+                        None => Ok(Value::Null),
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((headers, data))
+    }
+
+    /// Loads the rows returned by the query from a SQLite database.
+    ///
+    /// The headers are the names of the columns of the query.
+    fn load_sqlite(
+        &self,
+        timezone: TimeZone,
+        query: &str,
+    ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
+        use rusqlite::{types::ValueRef, Connection, OpenFlags};
+
+        let connection = Connection::open_with_flags(
+            &self.file.path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        let mut statement = connection.prepare(query)?;
+
+        let headers = statement
+            .column_names()
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        let mut rows = statement.query([])?;
+        let mut data = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let values = headers
+                .iter()
+                .enumerate()
+                .map(|(idx, header)| {
+                    let value = match row.get_ref(idx)? {
+                        ValueRef::Null => Value::Null,
+                        ValueRef::Integer(int) => Value::from(int),
+                        ValueRef::Real(float) => Value::from(float),
+                        ValueRef::Text(text) | ValueRef::Blob(text) => Value::from(text),
+                    };
+
+                    Ok(self.parse_typed_column(timezone, header, data.len(), value)?)
+                })
+                .collect::<crate::Result<Vec<_>>>()?;
+
+            data.push(values);
+        }
+
+        Ok((headers, data))
     }
 }
 
@@ -195,6 +336,7 @@ pub struct File {
         Vec<usize>,
         HashMap<u64, Vec<usize>, hash_hasher::HashBuildHasher>,
     )>,
+    field_indexes: Vec<(usize, FieldIndex)>,
 }
 
 impl File {
@@ -212,6 +354,7 @@ impl File {
             data,
             headers,
             indexes: Vec::new(),
+            field_indexes: Vec::new(),
         }
     }
 
@@ -242,9 +385,56 @@ impl File {
                     _ => false,
                 },
             },
+            Condition::CidrContains { field, ip } => match self.column_index(field) {
+                None => false,
+                Some(idx) => match &row[idx] {
+                    Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                        .ok()
+                        .and_then(parse_cidr)
+                        .map_or(false, |network| cidr_contains(network, *ip)),
+                    _ => false,
+                },
+            },
+            Condition::LongestPrefix { field, value } => match self.column_index(field) {
+                None => false,
+                Some(idx) => match (case, &row[idx]) {
+                    (Case::Insensitive, Value::Bytes(bytes)) => match std::str::from_utf8(bytes) {
+                        Ok(prefix) => value.to_lowercase().starts_with(&prefix.to_lowercase()),
+                        Err(_) => false,
+                    },
+                    (Case::Sensitive, Value::Bytes(bytes)) => value.as_bytes().starts_with(bytes),
+                    _ => false,
+                },
+            },
+            Condition::BetweenNumbers { field, from, to } => match self.column_index(field) {
+                None => false,
+                Some(idx) => match as_number(&row[idx]) {
+                    Some(number) => *from <= number && number <= *to,
+                    None => false,
+                },
+            },
         })
     }
 
+    /// Only keeps the rows holding the longest prefix, for each of the `LongestPrefix`
+    /// conditions.
+    fn retain_longest_prefixes(&self, condition: &[Condition], rows: &mut Vec<&Vec<Value>>) {
+        for condition in condition {
+            if let Condition::LongestPrefix { field, .. } = condition {
+                if let Some(idx) = self.column_index(field) {
+                    let length = |row: &Vec<Value>| match &row[idx] {
+                        Value::Bytes(bytes) => bytes.len(),
+                        _ => 0,
+                    };
+
+                    if let Some(longest) = rows.iter().map(|row| length(row)).max() {
+                        rows.retain(|row| length(row) == longest);
+                    }
+                }
+            }
+        }
+    }
+
     fn add_columns(&self, select: Option<&[String]>, row: &[Value]) -> BTreeMap<String, Value> {
         self.headers
             .iter()
@@ -333,17 +523,70 @@ impl File {
         case: Case,
         condition: &'a [Condition<'a>],
         select: Option<&'a [String]>,
-    ) -> impl Iterator<Item = BTreeMap<String, Value>> + 'a
+    ) -> Vec<BTreeMap<String, Value>>
     where
         I: Iterator<Item = &'a Vec<Value>> + 'a,
     {
-        data.filter_map(move |row| {
-            if self.row_equals(case, condition, &*row) {
-                Some(self.add_columns(select, &*row))
-            } else {
-                None
+        let mut rows = data
+            .filter(|row| self.row_equals(case, condition, row))
+            .collect::<Vec<_>>();
+
+        self.retain_longest_prefixes(condition, &mut rows);
+
+        rows.into_iter()
+            .map(|row| self.add_columns(select, row))
+            .collect()
+    }
+
+    /// Searches the field indexes for the rows that can match the given condition.
+    ///
+    /// When several conditions have a field index, the smallest set of rows is returned. Returns
+    /// `None` if none of the conditions have a field index.
+    fn field_indexed(&self, condition: &[Condition]) -> Option<Vec<usize>> {
+        condition
+            .iter()
+            .filter_map(|condition| {
+                let kind = condition.index_kind()?;
+                let idx = self.column_index(condition.field())?;
+                let (_, index) = self
+                    .field_indexes
+                    .iter()
+                    .find(|(column, index)| *column == idx && index.kind() == kind)?;
+
+                index.search(condition)
+            })
+            .min_by_key(|rows| rows.len())
+    }
+
+    /// Returns the rows that can match the given condition, using the given index and the
+    /// field indexes to avoid scanning all of the data.
+    ///
+    /// Returns `None` if the given index has no rows for the condition.
+    fn candidates<'a>(
+        &'a self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        index: Option<IndexHandle>,
+    ) -> Result<Option<Rows<'a>>, String> {
+        let indexed = match index {
+            Some(handle) => match self.indexed(case, condition, handle)? {
+                Some(indexed) => Some(indexed),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+
+        Ok(Some(match (indexed, self.field_indexed(condition)) {
+            (Some(indexed), Some(field_indexed)) if indexed.len() <= field_indexed.len() => {
+                Box::new(indexed.iter().map(move |idx| &self.data[*idx]))
             }
-        })
+            (_, Some(field_indexed)) => {
+                Box::new(field_indexed.into_iter().map(move |idx| &self.data[idx]))
+            }
+            (Some(indexed), None) => Box::new(indexed.iter().map(move |idx| &self.data[*idx])),
+            // No index can be used so we need to do a Sequential Scan.
+            (None, None) => Box::new(self.data.iter()),
+        }))
     }
 
     fn indexed<'a>(
@@ -396,6 +639,196 @@ fn hash_value(hasher: &mut seahash::SeaHasher, case: Case, value: &Value) -> Res
     Ok(())
 }
 
+/// The rows of the data to search through.
+type Rows<'a> = Box<dyn Iterator<Item = &'a Vec<Value>> + 'a>;
+
+/// The rows grouped by the network address of their CIDR range.
+type Networks = HashMap<u128, Vec<usize>>;
+
+/// An index over a single field, to search it with conditions other than `Equals`.
+///
+/// Searching the index returns the rows that can match the condition, which still need to be
+/// checked against it.
+#[derive(Clone, Debug)]
+enum FieldIndex {
+    /// The rows grouped by the network address of their CIDR range, for each IP version and
+    /// prefix length found in the field. The most specific prefix lengths come first.
+    Cidr(Vec<((bool, u8), Networks)>),
+    /// The rows grouped by their lowercased value, along with the distinct lengths of these
+    /// values, longest first.
+    Prefix {
+        rows: HashMap<String, Vec<usize>>,
+        lengths: Vec<usize>,
+    },
+    /// The rows holding a number, sorted by that number.
+    Range(Vec<(f64, usize)>),
+}
+
+impl FieldIndex {
+    fn new<'a, I>(kind: IndexKind, values: I) -> Self
+    where
+        I: Iterator<Item = &'a Value>,
+    {
+        match kind {
+            IndexKind::Cidr => {
+                let mut networks = HashMap::<_, HashMap<_, Vec<_>>>::new();
+
+                for (idx, value) in values.enumerate() {
+                    let network = match value {
+                        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok().and_then(parse_cidr),
+                        _ => None,
+                    };
+
+                    if let Some((ip, bits)) = network {
+                        let (address, width) = ip_to_bits(ip);
+                        networks
+                            .entry((width == 128, bits))
+                            .or_default()
+                            .entry(network_prefix(address, width, bits))
+                            .or_default()
+                            .push(idx);
+                    }
+                }
+
+                let mut networks = networks.into_iter().collect::<Vec<_>>();
+                networks.sort_by(|((_, bits1), _), ((_, bits2), _)| bits2.cmp(bits1));
+
+                Self::Cidr(networks)
+            }
+            IndexKind::Prefix => {
+                let mut rows = HashMap::<_, Vec<_>>::new();
+
+                for (idx, value) in values.enumerate() {
+                    if let Value::Bytes(bytes) = value {
+                        if let Ok(prefix) = std::str::from_utf8(bytes) {
+                            rows.entry(prefix.to_lowercase()).or_default().push(idx);
+                        }
+                    }
+                }
+
+                let mut lengths = rows.keys().map(String::len).collect::<Vec<_>>();
+                lengths.sort_unstable_by(|a, b| b.cmp(a));
+                lengths.dedup();
+
+                Self::Prefix { rows, lengths }
+            }
+            IndexKind::Range => {
+                let mut numbers = values
+                    .enumerate()
+                    .filter_map(|(idx, value)| Some((as_number(value)?, idx)))
+                    .filter(|(number, _)| !number.is_nan())
+                    .collect::<Vec<_>>();
+
+                numbers.sort_by(|(a, _), (b, _)| a.partial_cmp(b).expect("numbers can't be NaN"));
+
+                Self::Range(numbers)
+            }
+        }
+    }
+
+    fn kind(&self) -> IndexKind {
+        match self {
+            Self::Cidr(_) => IndexKind::Cidr,
+            Self::Prefix { .. } => IndexKind::Prefix,
+            Self::Range(_) => IndexKind::Range,
+        }
+    }
+
+    /// Returns the rows that can match the condition, in the order they are found in the data,
+    /// or `None` if the index can't be used for the condition.
+    fn search(&self, condition: &Condition) -> Option<Vec<usize>> {
+        let mut rows = match (self, condition) {
+            (Self::Cidr(networks), Condition::CidrContains { ip, .. }) => {
+                let (address, width) = ip_to_bits(*ip);
+
+                networks
+                    .iter()
+                    .filter(|((v6, _), _)| *v6 == (width == 128))
+                    .filter_map(|((_, bits), rows)| {
+                        rows.get(&network_prefix(address, width, *bits))
+                    })
+                    .flatten()
+                    .copied()
+                    .collect::<Vec<_>>()
+            }
+            // The index is case insensitive, the rows matching only when ignoring the case are
+            // filtered out when checking the condition.
+            (Self::Prefix { rows, lengths }, Condition::LongestPrefix { value, .. }) => {
+                let value = value.to_lowercase();
+
+                lengths
+                    .iter()
+                    .filter(|length| value.is_char_boundary(**length))
+                    .filter_map(|length| rows.get(&value[..*length]))
+                    .flatten()
+                    .copied()
+                    .collect::<Vec<_>>()
+            }
+            (Self::Range(numbers), Condition::BetweenNumbers { from, to, .. }) => {
+                let start = numbers.partition_point(|(number, _)| number < from);
+                let end = numbers.partition_point(|(number, _)| number <= to);
+
+                numbers[start..end]
+                    .iter()
+                    .map(|(_, idx)| *idx)
+                    .collect::<Vec<_>>()
+            }
+            _ => return None,
+        };
+
+        rows.sort_unstable();
+
+        Some(rows)
+    }
+}
+
+/// Parses a CIDR network range, such as `10.0.0.0/8`, into its address and prefix length.
+/// A single IP address is parsed as a network range holding only that address.
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u8)> {
+    let mut split = cidr.trim().splitn(2, '/');
+    let ip = split.next()?.parse::<IpAddr>().ok()?;
+    let width = ip_to_bits(ip).1;
+
+    let bits = match split.next() {
+        Some(bits) => bits.parse::<u8>().ok().filter(|bits| *bits <= width)?,
+        None => width,
+    };
+
+    Some((ip, bits))
+}
+
+/// Is the IP address within the given CIDR network range?
+fn cidr_contains((network, bits): (IpAddr, u8), ip: IpAddr) -> bool {
+    let (network, network_width) = ip_to_bits(network);
+    let (address, width) = ip_to_bits(ip);
+
+    network_width == width
+        && network_prefix(network, width, bits) == network_prefix(address, width, bits)
+}
+
+/// Returns the IP address as a number, along with the number of bits of the address.
+fn ip_to_bits(ip: IpAddr) -> (u128, u8) {
+    match ip {
+        IpAddr::V4(ip) => (u128::from(u32::from(ip)), 32),
+        IpAddr::V6(ip) => (u128::from(ip), 128),
+    }
+}
+
+/// Returns the first `bits` bits of an address of the given width.
+fn network_prefix(address: u128, width: u8, bits: u8) -> u128 {
+    address
+        .checked_shr(u32::from(width - bits))
+        .unwrap_or_default()
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(int) => Some(*int as f64),
+        Value::Float(float) => Some(float.into_inner()),
+        _ => None,
+    }
+}
+
 /// Returns an error if the iterator doesn't yield exactly one result.
 fn single_or_err<I, T>(mut iter: T) -> Result<I, String>
 where
@@ -419,22 +852,12 @@ impl Table for File {
         select: Option<&'a [String]>,
         index: Option<IndexHandle>,
     ) -> Result<BTreeMap<String, Value>, String> {
-        match index {
-            None => {
-                // No index has been passed so we need to do a Sequential Scan.
-                single_or_err(self.sequential(self.data.iter(), case, condition, select))
-            }
-            Some(handle) => {
-                let result = self
-                    .indexed(case, condition, handle)?
-                    .ok_or_else(|| "no rows found in index".to_string())?
-                    .iter()
-                    .map(|idx| &self.data[*idx]);
+        let result = self
+            .candidates(case, condition, index)?
+            .ok_or_else(|| "no rows found in index".to_string())?;
 
-                // Perform a sequential scan over the indexed result.
-                single_or_err(self.sequential(result, case, condition, select))
-            }
-        }
+        // Perform a sequential scan over the candidate rows.
+        single_or_err(self.sequential(result, case, condition, select).into_iter())
     }
 
     fn find_table_rows<'a>(
@@ -444,26 +867,10 @@ impl Table for File {
         select: Option<&'a [String]>,
         index: Option<IndexHandle>,
     ) -> Result<Vec<BTreeMap<String, Value>>, String> {
-        match index {
-            None => {
-                // No index has been passed so we need to do a Sequential Scan.
-                Ok(self
-                    .sequential(self.data.iter(), case, condition, select)
-                    .collect())
-            }
-            Some(handle) => {
-                // Perform a sequential scan over the indexed result.
-                Ok(self
-                    .sequential(
-                        self.indexed(case, condition, handle)?
-                            .iter()
-                            .flat_map(|results| results.iter().map(|idx| &self.data[*idx])),
-                        case,
-                        condition,
-                        select,
-                    )
-                    .collect())
-            }
+        match self.candidates(case, condition, index)? {
+            // Perform a sequential scan over the candidate rows.
+            Some(result) => Ok(self.sequential(result, case, condition, select)),
+            None => Ok(Vec::new()),
         }
     }
 
@@ -504,11 +911,34 @@ impl Table for File {
             .collect::<Vec<_>>()
     }
 
+    fn add_field_index(&mut self, field: &str, kind: IndexKind) -> Result<(), String> {
+        let idx = self
+            .column_index(field)
+            .ok_or_else(|| format!("field(s) '{}' missing from dataset", field))?;
+
+        if !self
+            .field_indexes
+            .iter()
+            .any(|(column, index)| *column == idx && index.kind() == kind)
+        {
+            let index = FieldIndex::new(kind, self.data.iter().map(|row| &row[idx]));
+            self.field_indexes.push((idx, index));
+        }
+
+        Ok(())
+    }
+
+    /// Returns a list of the fields that have a field index.
+    fn field_indexes(&self) -> Vec<(String, IndexKind)> {
+        self.field_indexes
+            .iter()
+            .map(|(idx, index)| (self.headers[*idx].clone(), index.kind()))
+            .collect()
+    }
+
     /// Checks the modified timestamp of the data file to see if data has changed.
     fn needs_reload(&self) -> bool {
-        matches!(fs::metadata(&self.config.file.path)
-            .and_then(|metadata| metadata.modified()),
-            Ok(modified) if modified > self.last_modified)
+        matches!(self.config.modified(), Ok(modified) if modified > self.last_modified)
     }
 }
 
@@ -594,6 +1024,151 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_json_lines() {
+        let path = crate::test_util::temp_file();
+        fs::write(
+            &path,
+            r#"{"name": "zip", "count": 3, "date": "2020-03-05"}
+
+{"name": "zup", "date": "2021-03-05", "tags": ["a"]}
+"#,
+        )
+        .unwrap();
+
+        let config = FileConfig {
+            file: FileC {
+                path,
+                encoding: Encoding::JsonLines,
+            },
+            schema: vec![("date".to_string(), "date".to_string())]
+                .into_iter()
+                .collect(),
+        };
+
+        let (headers, data, _) = config.load_file(Default::default()).unwrap();
+
+        assert_eq!(vec!["count", "date", "name", "tags"], headers);
+        assert_eq!(
+            vec![
+                vec![
+                    Value::from(3),
+                    Value::from(chrono::Utc.ymd(2020, 3, 5).and_hms(0, 0, 0)),
+                    Value::from("zip"),
+                    Value::Null,
+                ],
+                vec![
+                    Value::Null,
+                    Value::from(chrono::Utc.ymd(2021, 3, 5).and_hms(0, 0, 0)),
+                    Value::from("zup"),
+                    Value::from(vec![Value::from("a")]),
+                ],
+            ],
+            data
+        );
+    }
+
+    #[test]
+    fn load_json_lines_invalid() {
+        let path = crate::test_util::temp_file();
+        fs::write(&path, "{\"name\": \"zip\"}\nnot json\n").unwrap();
+
+        let config = FileConfig {
+            file: FileC {
+                path,
+                encoding: Encoding::JsonLines,
+            },
+            schema: HashMap::new(),
+        };
+
+        let error = config.load_file(Default::default()).unwrap_err();
+        assert!(error.to_string().starts_with("unable to parse line 2"));
+    }
+
+    #[test]
+    fn load_sqlite() {
+        let path = crate::test_util::temp_file();
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE teams (name TEXT, members INTEGER, created TEXT, ratio REAL);
+                 INSERT INTO teams VALUES ('infra', 4, '2020-03-05', 0.5);
+                 INSERT INTO teams VALUES ('payments', NULL, '2021-03-05', 1.5);",
+            )
+            .unwrap();
+        drop(connection);
+
+        let config = FileConfig {
+            file: FileC {
+                path,
+                encoding: Encoding::Sqlite {
+                    query: "SELECT name, members, created AS date, ratio FROM teams ORDER BY name"
+                        .to_string(),
+                },
+            },
+            schema: vec![("date".to_string(), "date".to_string())]
+                .into_iter()
+                .collect(),
+        };
+
+        let (headers, data, _) = config.load_file(Default::default()).unwrap();
+
+        assert_eq!(vec!["name", "members", "date", "ratio"], headers);
+        assert_eq!(
+            vec![
+                vec![
+                    Value::from("infra"),
+                    Value::from(4),
+                    Value::from(chrono::Utc.ymd(2020, 3, 5).and_hms(0, 0, 0)),
+                    Value::from(0.5),
+                ],
+                vec![
+                    Value::from("payments"),
+                    Value::Null,
+                    Value::from(chrono::Utc.ymd(2021, 3, 5).and_hms(0, 0, 0)),
+                    Value::from(1.5),
+                ],
+            ],
+            data
+        );
+    }
+
+    #[test]
+    fn sqlite_wal_needs_reload() {
+        let path = crate::test_util::temp_file();
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        connection
+            .query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE teams (name TEXT);
+                 INSERT INTO teams VALUES ('infra');",
+            )
+            .unwrap();
+
+        let config = FileConfig {
+            file: FileC {
+                path,
+                encoding: Encoding::Sqlite {
+                    query: "SELECT name FROM teams".to_string(),
+                },
+            },
+            schema: HashMap::new(),
+        };
+        let (headers, data, modified) = config.load_file(Default::default()).unwrap();
+        let file = File::new(config, Default::default(), modified, data, headers);
+        assert!(!file.needs_reload());
+
+        // The connection is kept open, so that the write isn't checkpointed
+        // into the database.
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        connection
+            .execute("INSERT INTO teams VALUES ('payments')", [])
+            .unwrap();
+        assert!(file.needs_reload());
+    }
+
     #[test]
     fn seahash() {
         // Ensure we can separate fields to create a distinct hash.
@@ -957,4 +1532,226 @@ mod tests {
             file.find_table_row(Case::Sensitive, &[condition], None, Some(handle))
         );
     }
+
+    fn networks() -> File {
+        File::new(
+            Default::default(),
+            shared::datetime::TimeZone::Local,
+            SystemTime::now(),
+            vec![
+                vec!["10.0.0.0/8".into(), "infra".into()],
+                vec!["10.1.0.0/16".into(), "payments".into()],
+                vec!["192.168.1.12".into(), "office".into()],
+                vec!["2001:db8::/32".into(), "infra".into()],
+                vec!["not a network".into(), "nobody".into()],
+            ],
+            vec!["network".to_string(), "team".to_string()],
+        )
+    }
+
+    #[test]
+    fn finds_rows_with_cidr() {
+        let mut file = networks();
+
+        let condition = [Condition::CidrContains {
+            field: "network",
+            ip: "10.1.2.3".parse().unwrap(),
+        }];
+
+        let expected = Ok(vec![
+            btreemap! {
+                "network" => "10.0.0.0/8",
+                "team" => "infra",
+            },
+            btreemap! {
+                "network" => "10.1.0.0/16",
+                "team" => "payments",
+            },
+        ]);
+
+        assert_eq!(
+            expected,
+            file.find_table_rows(Case::Sensitive, &condition, None, None)
+        );
+
+        file.add_field_index("network", IndexKind::Cidr).unwrap();
+
+        assert_eq!(
+            expected,
+            file.find_table_rows(Case::Sensitive, &condition, None, None)
+        );
+
+        for (ip, team) in [
+            ("192.168.1.12", Ok("office")),
+            ("192.168.1.13", Err("no rows found".to_string())),
+            ("2001:db8:1::1", Ok("infra")),
+            ("2001:db9::1", Err("no rows found".to_string())),
+        ] {
+            let condition = Condition::CidrContains {
+                field: "network",
+                ip: ip.parse().unwrap(),
+            };
+
+            assert_eq!(
+                team.map(Value::from),
+                file.find_table_row(
+                    Case::Sensitive,
+                    &[condition],
+                    Some(&["team".to_string()]),
+                    None
+                )
+                .map(|row| row["team"].clone())
+            );
+        }
+    }
+
+    #[test]
+    fn finds_row_with_cidr_and_index() {
+        let mut file = networks();
+
+        let handle = file.add_index(Case::Sensitive, &["team"]).unwrap();
+        file.add_field_index("network", IndexKind::Cidr).unwrap();
+
+        let conditions = [
+            Condition::Equals {
+                field: "team",
+                value: "payments".into(),
+            },
+            Condition::CidrContains {
+                field: "network",
+                ip: "10.1.2.3".parse().unwrap(),
+            },
+        ];
+
+        assert_eq!(
+            Ok(btreemap! {
+                "network" => "10.1.0.0/16",
+                "team" => "payments",
+            }),
+            file.find_table_row(Case::Sensitive, &conditions, None, Some(handle))
+        );
+    }
+
+    #[test]
+    fn finds_row_with_longest_prefix() {
+        let mut file = File::new(
+            Default::default(),
+            shared::datetime::TimeZone::Local,
+            SystemTime::now(),
+            vec![
+                vec!["/api".into(), "backend".into()],
+                vec!["/api/v1".into(), "legacy".into()],
+                vec!["/API/V2".into(), "platform".into()],
+                vec!["/".into(), "frontend".into()],
+            ],
+            vec!["path".to_string(), "team".to_string()],
+        );
+
+        let find = |file: &File, case, path: &str| {
+            let condition = Condition::LongestPrefix {
+                field: "path",
+                value: path.to_string(),
+            };
+
+            file.find_table_row(case, &[condition], Some(&["team".to_string()]), None)
+                .map(|row| row["team"].clone())
+        };
+
+        for indexed in [false, true] {
+            if indexed {
+                file.add_field_index("path", IndexKind::Prefix).unwrap();
+            }
+
+            assert_eq!(
+                Ok(Value::from("legacy")),
+                find(&file, Case::Sensitive, "/api/v1/users")
+            );
+            assert_eq!(
+                Ok(Value::from("backend")),
+                find(&file, Case::Sensitive, "/api/v2/users")
+            );
+            assert_eq!(
+                Ok(Value::from("platform")),
+                find(&file, Case::Insensitive, "/api/v2/users")
+            );
+            assert_eq!(
+                Ok(Value::from("frontend")),
+                find(&file, Case::Sensitive, "/index.html")
+            );
+            assert_eq!(
+                Err("no rows found".to_string()),
+                find(&file, Case::Sensitive, "index.html")
+            );
+        }
+    }
+
+    #[test]
+    fn finds_rows_between_numbers() {
+        let mut file = File::new(
+            Default::default(),
+            shared::datetime::TimeZone::Local,
+            SystemTime::now(),
+            vec![
+                vec!["zip".into(), 3.into()],
+                vec!["zup".into(), 10.into()],
+                vec!["zirp".into(), Value::from(15.5)],
+                vec!["zurp".into(), "not a number".into()],
+                vec!["zorp".into(), 20.into()],
+            ],
+            vec!["field1".to_string(), "field2".to_string()],
+        );
+
+        let condition = [Condition::BetweenNumbers {
+            field: "field2",
+            from: 10.0,
+            to: 20.0,
+        }];
+
+        let expected = Ok(vec![
+            btreemap! {
+                "field1" => "zup",
+            },
+            btreemap! {
+                "field1" => "zirp",
+            },
+            btreemap! {
+                "field1" => "zorp",
+            },
+        ]);
+        let select = ["field1".to_string()];
+
+        assert_eq!(
+            expected,
+            file.find_table_rows(Case::Sensitive, &condition, Some(&select), None)
+        );
+
+        file.add_field_index("field2", IndexKind::Range).unwrap();
+
+        assert_eq!(
+            expected,
+            file.find_table_rows(Case::Sensitive, &condition, Some(&select), None)
+        );
+    }
+
+    #[test]
+    fn duplicate_field_indexes() {
+        let mut file = networks();
+
+        file.add_field_index("network", IndexKind::Cidr).unwrap();
+        file.add_field_index("network", IndexKind::Cidr).unwrap();
+        file.add_field_index("team", IndexKind::Prefix).unwrap();
+
+        assert_eq!(
+            vec![
+                ("network".to_string(), IndexKind::Cidr),
+                ("team".to_string(), IndexKind::Prefix),
+            ],
+            file.field_indexes()
+        );
+
+        assert_eq!(
+            Err("field(s) 'apples' missing from dataset".to_string()),
+            file.add_field_index("apples", IndexKind::Range)
+        );
+    }
 }
@@ -0,0 +1,480 @@
+use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription};
+use enrichment::{Case, Condition, IndexHandle, Table};
+use maxminddb::{
+    geoip2::{City, ConnectionType, Isp},
+    Reader,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use vrl::Value;
+
+// MaxMind GeoIP database files have a type field we can use to recognize specific
+// products. If it is something other than ASN, ISP or ConnectionType we assume
+// a City database.
+const ASN_DATABASE_TYPE: &str = "GeoLite2-ASN";
+const ISP_DATABASE_TYPE: &str = "GeoIP2-ISP";
+const CONNECTION_TYPE_DATABASE_TYPE: &str = "GeoIP2-Connection-Type";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum DatabaseKind {
+    Asn,
+    Isp,
+    ConnectionType,
+    City,
+}
+
+impl From<&str> for DatabaseKind {
+    fn from(v: &str) -> Self {
+        match v {
+            ASN_DATABASE_TYPE => Self::Asn,
+            ISP_DATABASE_TYPE => Self::Isp,
+            CONNECTION_TYPE_DATABASE_TYPE => Self::ConnectionType,
+            _ => Self::City,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GeoipConfig {
+    path: PathBuf,
+    #[serde(default = "default_locale")]
+    locale: String,
+}
+
+// The locale used to pick the localized names (city, country, region) from
+// City databases.
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+impl Default for GeoipConfig {
+    fn default() -> Self {
+        Self {
+            path: "/path/to/GeoLite2-City.mmdb".into(),
+            locale: default_locale(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "geoip")]
+impl EnrichmentTableConfig for GeoipConfig {
+    async fn build(
+        &self,
+        _globals: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(Geoip::new(self.clone())?))
+    }
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<GeoipConfig>("geoip")
+}
+
+impl_generate_config_from_default!(GeoipConfig);
+
+/// An enrichment table backed by a MaxMind GeoIP database.
+///
+/// The table is searched with a single equality condition holding the IP
+/// address to look up, the field name of the condition is ignored.
+#[derive(Clone)]
+pub struct Geoip {
+    config: GeoipConfig,
+    dbreader: Arc<Reader<Vec<u8>>>,
+    dbkind: DatabaseKind,
+    last_modified: SystemTime,
+}
+
+impl Geoip {
+    pub fn new(config: GeoipConfig) -> crate::Result<Self> {
+        let dbreader = Arc::new(Reader::open_readfile(&config.path)?);
+        let dbkind = DatabaseKind::from(dbreader.metadata.database_type.as_str());
+
+        let last_modified = fs::metadata(&config.path)?.modified()?;
+
+        Ok(Self {
+            config,
+            dbreader,
+            dbkind,
+            last_modified,
+        })
+    }
+
+    fn lookup(&self, ip: IpAddr, select: Option<&[String]>) -> Option<BTreeMap<String, Value>> {
+        let mut map = BTreeMap::new();
+        let mut add_field = |key: &str, value: Option<Value>| {
+            if select
+                .map(|fields| fields.iter().any(|field| field == key))
+                // If no select is passed, we assume all fields are included
+                .unwrap_or(true)
+            {
+                map.insert(key.to_string(), value.unwrap_or(Value::Null));
+            }
+        };
+
+        match self.dbkind {
+            DatabaseKind::Asn | DatabaseKind::Isp => {
+                let data = self.dbreader.lookup::<Isp>(ip).ok()?;
+
+                add_field(
+                    "autonomous_system_number",
+                    data.autonomous_system_number.map(Into::into),
+                );
+                add_field(
+                    "autonomous_system_organization",
+                    data.autonomous_system_organization.map(Into::into),
+                );
+                add_field("isp", data.isp.map(Into::into));
+                add_field("organization", data.organization.map(Into::into));
+            }
+            DatabaseKind::ConnectionType => {
+                let data = self.dbreader.lookup::<ConnectionType>(ip).ok()?;
+
+                add_field("connection_type", data.connection_type.map(Into::into));
+            }
+            DatabaseKind::City => {
+                let data = self.dbreader.lookup::<City>(ip).ok()?;
+                let locale = self.config.locale.as_str();
+
+                add_field(
+                    "city_name",
+                    data.city
+                        .and_then(|city| city.names)
+                        .and_then(|names| names.get(locale).copied())
+                        .map(Into::into),
+                );
+
+                let continent_code = data.continent.and_then(|continent| continent.code);
+                add_field("continent_code", continent_code.map(Into::into));
+
+                let country = data.country;
+                add_field(
+                    "country_code",
+                    country
+                        .as_ref()
+                        .and_then(|country| country.iso_code)
+                        .map(Into::into),
+                );
+                add_field(
+                    "country_name",
+                    country
+                        .and_then(|country| country.names)
+                        .and_then(|names| names.get(locale).copied())
+                        .map(Into::into),
+                );
+
+                let location = data.location;
+                add_field(
+                    "timezone",
+                    location
+                        .as_ref()
+                        .and_then(|location| location.time_zone)
+                        .map(Into::into),
+                );
+                add_field(
+                    "latitude",
+                    location
+                        .as_ref()
+                        .and_then(|location| location.latitude)
+                        .map(Into::into),
+                );
+                add_field(
+                    "longitude",
+                    location
+                        .as_ref()
+                        .and_then(|location| location.longitude)
+                        .map(Into::into),
+                );
+                add_field(
+                    "metro_code",
+                    location
+                        .and_then(|location| location.metro_code)
+                        .map(Into::into),
+                );
+
+                // The most specific subdivision is the last one.
+                let subdivision = data
+                    .subdivisions
+                    .as_ref()
+                    .and_then(|subdivisions| subdivisions.last());
+                add_field(
+                    "region_code",
+                    subdivision
+                        .and_then(|subdivision| subdivision.iso_code)
+                        .map(Into::into),
+                );
+                add_field(
+                    "region_name",
+                    subdivision
+                        .and_then(|subdivision| subdivision.names.as_ref())
+                        .and_then(|names| names.get(locale).copied())
+                        .map(Into::into),
+                );
+
+                add_field(
+                    "postal_code",
+                    data.postal.and_then(|postal| postal.code).map(Into::into),
+                );
+            }
+        }
+
+        Some(map)
+    }
+}
+
+impl Table for Geoip {
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    ///
+    /// # Errors
+    /// Errors if no rows, or more than 1 row is found.
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_table_rows(case, condition, select, index)?;
+
+        match rows.pop() {
+            Some(row) if rows.is_empty() => Ok(row),
+            Some(_) => Err("more than one row found".to_string()),
+            None => Err("IP not found".to_string()),
+        }
+    }
+
+    /// Search the enrichment table data with the given condition.
+    /// All conditions must match (AND).
+    /// Can return multiple matched records
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        match condition.get(0) {
+            Some(_) if condition.len() > 1 => Err("only one condition is allowed".to_string()),
+            Some(Condition::Equals { value, .. }) => {
+                let ip = value
+                    .try_bytes_utf8_lossy()
+                    .map_err(|_| "IP address must be a string".to_string())?
+                    .parse::<IpAddr>()
+                    .map_err(|_| "invalid IP address".to_string())?;
+
+                Ok(self
+                    .lookup(ip, select)
+                    .map(|values| vec![values])
+                    .unwrap_or_default())
+            }
+            Some(_) => Err("only equality condition is allowed".to_string()),
+            None => Err("IP condition must be specified".to_string()),
+        }
+    }
+
+    /// Hints to the enrichment table what data is going to be searched to allow it to index the
+    /// data in advance.
+    ///
+    /// # Errors
+    /// Errors if the fields are not in the table.
+    fn add_index(&mut self, _: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        match fields.len() {
+            0 => Err("IP field is required".to_string()),
+            1 => Ok(IndexHandle(0)),
+            _ => Err("only one field is allowed".to_string()),
+        }
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Checks the modified timestamp of the database file to see if data has changed.
+    fn needs_reload(&self) -> bool {
+        matches!(fs::metadata(&self.config.path)
+            .and_then(|metadata| metadata.modified()),
+            Ok(modified) if modified > self.last_modified)
+    }
+}
+
+impl std::fmt::Debug for Geoip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Geoip {} database {}",
+            self.config.locale,
+            self.config.path.display()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GeoipConfig>();
+    }
+
+    #[test]
+    fn city_lookup() {
+        let values = find("2.125.160.216", "tests/data/GeoIP2-City-Test.mmdb").unwrap();
+
+        assert_eq!(
+            values,
+            btreemap! {
+                "city_name" => "Boxford",
+                "country_code" => "GB",
+                "continent_code" => "EU",
+                "country_name" => "United Kingdom",
+                "region_code" => "WBK",
+                "region_name" => "West Berkshire",
+                "timezone" => "Europe/London",
+                "latitude" => 51.75,
+                "longitude" => -1.25,
+                "postal_code" => "OX1",
+                "metro_code" => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn city_partial_lookup() {
+        let values = find_select(
+            "2.125.160.216",
+            "tests/data/GeoIP2-City-Test.mmdb",
+            Some(&["latitude".to_string(), "longitude".to_string()]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            values,
+            btreemap! {
+                "latitude" => 51.75,
+                "longitude" => -1.25,
+            }
+        );
+    }
+
+    #[test]
+    fn city_lookup_partial_results() {
+        let values = find("67.43.156.9", "tests/data/GeoIP2-City-Test.mmdb").unwrap();
+
+        assert_eq!(
+            values,
+            btreemap! {
+                "city_name" => Value::Null,
+                "country_code" => "BT",
+                "country_name" => "Bhutan",
+                "continent_code" => "AS",
+                "region_code" => Value::Null,
+                "region_name" => Value::Null,
+                "timezone" => "Asia/Thimphu",
+                "latitude" => 27.5,
+                "longitude" => 90.5,
+                "postal_code" => Value::Null,
+                "metro_code" => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn city_lookup_no_results() {
+        let values = find("10.1.12.1", "tests/data/GeoIP2-City-Test.mmdb");
+
+        assert!(values.is_none());
+    }
+
+    #[test]
+    fn isp_lookup() {
+        let values = find("208.192.1.2", "tests/data/GeoIP2-ISP-Test.mmdb").unwrap();
+
+        assert_eq!(
+            values,
+            btreemap! {
+                "autonomous_system_number" => 701,
+                "autonomous_system_organization" => "MCI Communications Services, Inc. d/b/a Verizon Business",
+                "isp" => "Verizon Business",
+                "organization" => "Verizon Business",
+            }
+        );
+    }
+
+    #[test]
+    fn asn_lookup() {
+        let values = find("2600:7000::1", "tests/data/GeoLite2-ASN-Test.mmdb").unwrap();
+
+        assert_eq!(
+            values,
+            btreemap! {
+                "autonomous_system_number" => 6939,
+                "autonomous_system_organization" => "Hurricane Electric, Inc.",
+                "isp" => Value::Null,
+                "organization" => Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn asn_lookup_no_results() {
+        let values = find("10.1.12.1", "tests/data/GeoLite2-ASN-Test.mmdb");
+
+        assert!(values.is_none());
+    }
+
+    #[test]
+    fn invalid_ip() {
+        let geoip = Geoip::new(GeoipConfig {
+            path: "tests/data/GeoIP2-City-Test.mmdb".into(),
+            locale: default_locale(),
+        })
+        .unwrap();
+
+        let condition = Condition::Equals {
+            field: "ip",
+            value: "not an ip".into(),
+        };
+
+        assert_eq!(
+            Err("invalid IP address".to_string()),
+            geoip.find_table_row(Case::Insensitive, &[condition], None, None)
+        );
+    }
+
+    fn find(ip: &str, database: &str) -> Option<BTreeMap<String, Value>> {
+        find_select(ip, database, None)
+    }
+
+    fn find_select(
+        ip: &str,
+        database: &str,
+        select: Option<&[String]>,
+    ) -> Option<BTreeMap<String, Value>> {
+        Geoip::new(GeoipConfig {
+            path: database.into(),
+            locale: default_locale(),
+        })
+        .unwrap()
+        .find_table_rows(
+            Case::Insensitive,
+            &[Condition::Equals {
+                field: "ip",
+                value: ip.into(),
+            }],
+            select,
+            None,
+        )
+        .unwrap()
+        .pop()
+    }
+}
@@ -0,0 +1,21 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub(crate) struct DroppedOutputFull;
+
+impl InternalEvent for DroppedOutputFull {
+    fn emit_logs(&self) {
+        warn!(
+            message = "The `dropped` output is full; discarding event.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1,
+              "reason" => "dropped_output_full");
+    }
+}
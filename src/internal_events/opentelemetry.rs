@@ -0,0 +1,56 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct OpenTelemetryEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+    pub protocol: &'static str,
+}
+
+impl InternalEvent for OpenTelemetryEventsReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received events.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            protocol = %self.protocol,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_received_events_total", self.count as u64,
+            "protocol" => self.protocol,
+        );
+        counter!(
+            "component_received_event_bytes_total", self.byte_size as u64,
+            "protocol" => self.protocol,
+        );
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct OpenTelemetryUnsupportedEventDropped {
+    pub kind: &'static str,
+}
+
+impl InternalEvent for OpenTelemetryUnsupportedEventDropped {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event has no OTLP representation; dropping event.",
+            kind = %self.kind,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "events_discarded_total", 1,
+            "reason" => "unsupported",
+            "kind" => self.kind,
+        );
+    }
+}
@@ -28,6 +28,7 @@ pub mod conditions;
 pub mod dns;
 #[cfg(feature = "docker")]
 pub mod docker;
+pub mod dropped;
 pub mod expiring_hash_map;
 pub mod generate;
 #[macro_use]
@@ -54,6 +55,8 @@ pub(crate) mod proto;
 pub mod providers;
 #[cfg(feature = "rusoto_core")]
 pub mod rusoto;
+#[cfg(feature = "secrets")]
+pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
 pub mod service;
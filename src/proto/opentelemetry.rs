@@ -0,0 +1,63 @@
+//! Generated bindings for the OpenTelemetry protocol (OTLP).
+//!
+//! The module tree mirrors the protobuf package hierarchy, as the generated code
+//! refers to messages from other packages through relative `super::` paths.
+#![allow(clippy::clone_on_ref_ptr)]
+
+// Fields used to carry OTLP log record properties on Vector log events.
+pub const ATTRIBUTES_KEY: &str = "attributes";
+pub const RESOURCES_KEY: &str = "resources";
+pub const SCOPE_KEY: &str = "scope";
+pub const SEVERITY_TEXT_KEY: &str = "severity_text";
+pub const SEVERITY_NUMBER_KEY: &str = "severity_number";
+pub const TRACE_ID_KEY: &str = "trace_id";
+pub const SPAN_ID_KEY: &str = "span_id";
+pub const FLAGS_KEY: &str = "flags";
+pub const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
+pub const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
+
+pub mod common {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.common.v1");
+    }
+}
+
+pub mod resource {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.resource.v1");
+    }
+}
+
+pub mod logs {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.logs.v1");
+    }
+}
+
+pub mod metrics {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.metrics.v1");
+    }
+}
+
+pub mod collector {
+    pub mod logs {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.logs.v1");
+
+            pub use logs_service_client::LogsServiceClient as Client;
+            pub use logs_service_server::{LogsService as Service, LogsServiceServer as Server};
+        }
+    }
+
+    pub mod metrics {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.metrics.v1");
+
+            pub use metrics_service_client::MetricsServiceClient as Client;
+            pub use metrics_service_server::{
+                MetricsService as Service, MetricsServiceServer as Server,
+            };
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use super::Result;
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+        watcher,
+    },
+    signal,
+};
+use async_stream::stream;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct DirectoryConfig {
+    path: Option<PathBuf>,
+    watch: bool,
+}
+
+impl Default for DirectoryConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            watch: true,
+        }
+    }
+}
+
+/// Loads the config directory, logging any warnings.
+fn load_directory(path: &Path) -> Result {
+    info!(message = "Loading configuration directory.", path = ?path);
+
+    let (config_builder, warnings) = config::load_builder_from_dir(path)?;
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    Ok(config_builder)
+}
+
+/// Reloads the config directory whenever a file in it changes, returning a stream of
+/// `ConfigBuilder`.
+fn watch_directory(
+    path: PathBuf,
+) -> std::result::Result<impl Stream<Item = signal::SignalTo>, Vec<String>> {
+    // Changes happening while a reload is pending are folded into it.
+    let (tx, mut rx) = mpsc::channel(1);
+    watcher::spawn_thread_with_handler(std::iter::once(&path), None, true, move || {
+        !matches!(tx.try_send(()), Err(mpsc::error::TrySendError::Closed(_)))
+    })
+    .map_err(|error| vec![format!("Unable to watch {:?}: {}.", path, error)])?;
+
+    Ok(stream! {
+        while rx.recv().await.is_some() {
+            match load_directory(&path) {
+                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
+                Err(errors) => {
+                    for error in errors {
+                        error!(message = "Configuration directory is invalid.", %error, path = ?path);
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "directory")]
+impl ProviderConfig for DirectoryConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| vec!["Path is required for the `directory` provider.".to_owned()])?;
+
+        let config_builder = load_directory(&path)?;
+
+        // Reload on changes to the directory.
+        if self.watch {
+            signal_handler.add(watch_directory(path)?);
+        }
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "directory"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<DirectoryConfig>("directory")
+}
+
+impl_generate_config_from_default!(DirectoryConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ComponentKey;
+    use indoc::indoc;
+    use std::fs;
+
+    fn write_directory(path: &Path) {
+        fs::create_dir(path.join("sources")).unwrap();
+        fs::create_dir(path.join("sinks")).unwrap();
+        fs::write(
+            path.join("global.toml"),
+            indoc! {r#"
+                [log_schema]
+                  host_key = "this"
+            "#},
+        )
+        .unwrap();
+        fs::write(
+            path.join("sources").join("in.toml"),
+            indoc! {r#"
+                type = "stdin"
+            "#},
+        )
+        .unwrap();
+        fs::write(
+            path.join("sinks").join("out.yaml"),
+            indoc! {r#"
+                type: console
+                inputs: ["in"]
+                encoding: json
+            "#},
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn loads_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        write_directory(dir.path());
+
+        let (mut signal_handler, _) = signal::SignalHandler::new();
+        let mut provider = DirectoryConfig {
+            path: Some(dir.path().to_owned()),
+            watch: false,
+        };
+        let config = provider
+            .build(&mut signal_handler)
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!("this", config.global.log_schema.host_key().to_string());
+        assert!(config.sources.contains_key(&ComponentKey::from("in")));
+        assert!(config.sinks.contains_key(&ComponentKey::from("out")));
+    }
+
+    #[tokio::test]
+    async fn invalid_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        write_directory(dir.path());
+        fs::create_dir(dir.path().join("unknown")).unwrap();
+
+        let (mut signal_handler, _) = signal::SignalHandler::new();
+        let mut provider = DirectoryConfig {
+            path: Some(dir.path().to_owned()),
+            watch: false,
+        };
+
+        assert!(provider.build(&mut signal_handler).await.is_err());
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))] // https://github.com/timberio/vector/issues/5000
+    #[tokio::test]
+    async fn reloads_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        write_directory(dir.path());
+
+        let (mut signal_handler, mut signal_rx) = signal::SignalHandler::new();
+        let mut provider = DirectoryConfig {
+            path: Some(dir.path().to_owned()),
+            watch: true,
+        };
+        provider.build(&mut signal_handler).await.unwrap();
+
+        fs::write(
+            dir.path().join("sources").join("other.toml"),
+            indoc! {r#"
+                type = "stdin"
+            "#},
+        )
+        .unwrap();
+
+        let signal = tokio::time::timeout(std::time::Duration::from_secs(15), signal_rx.recv())
+            .await
+            .expect("Test timed out");
+        match signal {
+            Some(signal::SignalTo::ReloadFromConfigBuilder(config_builder)) => {
+                assert!(config_builder
+                    .sources
+                    .contains_key(&ComponentKey::from("other")));
+            }
+            _ => panic!("Expected a config reload"),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use super::Result;
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+        Format,
+    },
+    signal,
+};
+use async_stream::stream;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use tokio::{process::Command, time};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct ExecConfig {
+    command: Vec<String>,
+    format: Option<Format>,
+    poll_interval_secs: Option<u64>,
+    timeout_secs: u64,
+}
+
+impl Default for ExecConfig {
+    fn default() -> Self {
+        Self {
+            command: Vec::new(),
+            format: None,
+            poll_interval_secs: None,
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// Runs the command, returning its standard output.
+async fn run_command(
+    command: &[String],
+    timeout_secs: u64,
+) -> std::result::Result<Vec<u8>, String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| "Command is required for the `exec` provider.".to_owned())?;
+
+    info!(message = "Attempting to retrieve configuration.", command = ?command);
+
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = time::timeout(time::Duration::from_secs(timeout_secs), output)
+        .await
+        .map_err(|_| format!("Command timed out after {} seconds.", timeout_secs))?
+        .map_err(|error| format!("Unable to run command {:?}: {}.", program, error))?;
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(format!(
+            "Command failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ))
+    }
+}
+
+/// Loads the output of the command as a `ConfigBuilder`.
+fn output_to_config_builder(output: &[u8], format: Option<Format>) -> Result {
+    let (config_builder, warnings) = config::load(output, format)?;
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    Ok(config_builder)
+}
+
+/// Runs the command after/every `poll_interval_secs`, returning a stream of `ConfigBuilder`
+/// whenever its output changes.
+fn poll_command(
+    poll_interval_secs: u64,
+    command: Vec<String>,
+    format: Option<Format>,
+    timeout_secs: u64,
+    mut last_output: Vec<u8>,
+) -> impl Stream<Item = signal::SignalTo> {
+    let duration = time::Duration::from_secs(poll_interval_secs);
+    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            match run_command(&command, timeout_secs).await {
+                Ok(output) if output == last_output => {
+                    debug!(message = "Configuration is unchanged.", command = ?command);
+                }
+                Ok(output) => match output_to_config_builder(&output, format) {
+                    Ok(config_builder) => {
+                        last_output = output;
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                    Err(errors) => {
+                        for error in errors {
+                            error!(message = "Configuration is invalid.", %error, command = ?command);
+                        }
+                    }
+                },
+                Err(error) => {
+                    error!(message = "Failed to retrieve configuration.", %error, command = ?command);
+                }
+            };
+
+            info!(
+                message = "Exec provider is waiting.",
+                poll_interval_secs = ?poll_interval_secs,
+                command = ?command);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl ProviderConfig for ExecConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let output = run_command(&self.command, self.timeout_secs)
+            .await
+            .map_err(|error| vec![error])?;
+        let config_builder = output_to_config_builder(&output, self.format)?;
+
+        // Poll for changes to the command output.
+        if let Some(poll_interval_secs) = self.poll_interval_secs {
+            signal_handler.add(poll_command(
+                poll_interval_secs,
+                self.command.clone(),
+                self.format,
+                self.timeout_secs,
+                output,
+            ));
+        }
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<ExecConfig>("exec")
+}
+
+impl_generate_config_from_default!(ExecConfig);
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::config::ComponentKey;
+
+    fn provider(script: &str, poll_interval_secs: Option<u64>) -> ExecConfig {
+        ExecConfig {
+            command: vec!["sh".into(), "-c".into(), script.into()],
+            format: Some(Format::Json),
+            poll_interval_secs,
+            timeout_secs: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn loads_command_output() {
+        let script = r#"echo '{"sources": {"in": {"type": "stdin"}}, "sinks": {"out": {"type": "console", "inputs": ["in"], "encoding": "json"}}}'"#;
+
+        let (mut signal_handler, _) = signal::SignalHandler::new();
+        let config = provider(script, None)
+            .build(&mut signal_handler)
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(config.sources.contains_key(&ComponentKey::from("in")));
+        assert!(config.sinks.contains_key(&ComponentKey::from("out")));
+    }
+
+    #[tokio::test]
+    async fn command_failure() {
+        let (mut signal_handler, _) = signal::SignalHandler::new();
+        let errors = provider("echo oops >&2; exit 1", None)
+            .build(&mut signal_handler)
+            .await
+            .unwrap_err();
+
+        assert!(errors[0].contains("oops"));
+    }
+
+    #[tokio::test]
+    async fn command_timeout() {
+        let mut provider = provider("sleep 10", None);
+        provider.timeout_secs = 1;
+
+        let (mut signal_handler, _) = signal::SignalHandler::new();
+        let errors = provider.build(&mut signal_handler).await.unwrap_err();
+
+        assert!(errors[0].contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn reloads_on_changed_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let counter = dir.path().join("counter");
+        // Outputs a different source id on every run.
+        let script = format!(
+            r#"echo x >> {path:?}; echo "{{\"sources\": {{\"in_$(wc -l < {path:?} | tr -d ' ')\": {{\"type\": \"stdin\"}}}}}}""#,
+            path = counter
+        );
+
+        let (mut signal_handler, mut signal_rx) = signal::SignalHandler::new();
+        let config_builder = provider(&script, Some(1))
+            .build(&mut signal_handler)
+            .await
+            .unwrap();
+        assert!(config_builder
+            .sources
+            .contains_key(&ComponentKey::from("in_1")));
+
+        let signal = time::timeout(time::Duration::from_secs(5), signal_rx.recv())
+            .await
+            .expect("Test timed out");
+        match signal {
+            Some(signal::SignalTo::ReloadFromConfigBuilder(config_builder)) => {
+                assert!(config_builder
+                    .sources
+                    .contains_key(&ComponentKey::from("in_2")));
+            }
+            _ => panic!("Expected a config reload"),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod directory;
+pub mod exec;
 pub mod http;
 
 use super::config::ConfigBuilder;
@@ -0,0 +1,174 @@
+use crate::{
+    config::{secret::SecretBackend, ProxyConfig},
+    rusoto::{self, AwsAuthentication, RegionOrEndpoint},
+};
+use rusoto_secretsmanager::{GetSecretValueRequest, SecretsManager, SecretsManagerClient};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    thread,
+};
+
+/// Reads the secrets from a single AWS Secrets Manager secret, whose value is a JSON object of
+/// keys to secret values.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AwsSecretsManagerBackend {
+    pub secret_id: String,
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub proxy: ProxyConfig,
+}
+
+#[typetag::serde(name = "aws_secrets_manager")]
+impl SecretBackend for AwsSecretsManagerBackend {
+    fn retrieve(&mut self, keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        // Configs are loaded from synchronous code which may itself be running on a runtime, so
+        // the request gets a runtime of its own on a separate thread.
+        let backend = self.clone();
+        let secret_string = thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(backend.get_secret_string())
+        })
+        .join()
+        .map_err(|_| "secret request panicked")??;
+
+        let mut values: HashMap<String, String> =
+            serde_json::from_str(&secret_string).map_err(|error| {
+                format!(
+                    "secret {:?} is not a JSON object of strings: {}",
+                    self.secret_id, error
+                )
+            })?;
+
+        keys.into_iter()
+            .map(|key| -> crate::Result<(String, String)> {
+                match values.remove(&key) {
+                    Some(value) => Ok((key, value)),
+                    None => Err(
+                        format!("key {:?} missing from secret {:?}", key, self.secret_id).into(),
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+impl AwsSecretsManagerBackend {
+    fn create_client(&self) -> crate::Result<SecretsManagerClient> {
+        let region = (&self.region).try_into()?;
+        let client = rusoto::client(&self.proxy)?;
+        let creds = self.auth.build(&region, None)?;
+
+        Ok(SecretsManagerClient::new_with(client, creds, region))
+    }
+
+    async fn get_secret_string(&self) -> crate::Result<String> {
+        let response = self
+            .create_client()?
+            .get_secret_value(GetSecretValueRequest {
+                secret_id: self.secret_id.clone(),
+                ..Default::default()
+            })
+            .await?;
+
+        response
+            .secret_string
+            .ok_or_else(|| format!("secret {:?} has no string value", self.secret_id).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::next_addr;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    };
+    use std::convert::Infallible;
+
+    async fn handle(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let target = request
+            .headers()
+            .get("x-amz-target")
+            .and_then(|target| target.to_str().ok());
+        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let response = match (target, body["SecretId"].as_str()) {
+            (Some("secretsmanager.GetSecretValue"), Some("vector")) => Response::new(Body::from(
+                serde_json::json!({
+                    "ARN": "arn:aws:secretsmanager:us-east-1:123456789012:secret:vector",
+                    "Name": "vector",
+                    "SecretString": r#"{"username": "admin", "password": "hunter2"}"#,
+                })
+                .to_string(),
+            )),
+            _ => Response::builder()
+                .status(400)
+                .body(Body::from(
+                    r#"{"__type": "ResourceNotFoundException", "message": "not found"}"#,
+                ))
+                .unwrap(),
+        };
+
+        Ok(response)
+    }
+
+    fn backend(endpoint: String, secret_id: &str) -> AwsSecretsManagerBackend {
+        AwsSecretsManagerBackend {
+            secret_id: secret_id.into(),
+            region: RegionOrEndpoint::with_endpoint(endpoint),
+            auth: AwsAuthentication::Static {
+                access_key_id: "dummy".into(),
+                secret_access_key: "dummy".into(),
+            },
+            proxy: Default::default(),
+        }
+    }
+
+    fn keys(keys: &[&str]) -> HashSet<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn retrieves_secrets() {
+        let addr = next_addr();
+        let service = make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(handle)) });
+        tokio::spawn(Server::bind(&addr).serve(service));
+        let endpoint = format!("http://{}", addr);
+
+        let mut found = backend(endpoint.clone(), "vector");
+        let secrets =
+            tokio::task::spawn_blocking(move || found.retrieve(keys(&["username", "password"])))
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(secrets["username"], "admin");
+        assert_eq!(secrets["password"], "hunter2");
+
+        let mut missing_key = backend(endpoint.clone(), "vector");
+        let result = tokio::task::spawn_blocking(move || missing_key.retrieve(keys(&["token"])))
+            .await
+            .unwrap();
+        assert!(result.is_err());
+
+        let mut missing_secret = backend(endpoint, "unknown");
+        let result =
+            tokio::task::spawn_blocking(move || missing_secret.retrieve(keys(&["username"])))
+                .await
+                .unwrap();
+        assert!(result.is_err());
+    }
+}
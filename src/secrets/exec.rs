@@ -0,0 +1,196 @@
+use crate::config::secret::SecretBackend;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+/// Runs a helper command which is sent the requested keys as JSON on its standard input, and
+/// answers with a JSON object of `{"<key>": {"value": ..., "error": ...}}` on its standard output.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExecBackend {
+    pub command: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+#[derive(Serialize)]
+struct ExecRequest<'a> {
+    version: &'static str,
+    secrets: &'a HashSet<String>,
+}
+
+#[derive(Deserialize)]
+struct ExecSecret {
+    value: Option<String>,
+    error: Option<String>,
+}
+
+#[typetag::serde(name = "exec")]
+impl SecretBackend for ExecBackend {
+    fn retrieve(&mut self, keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        let request = serde_json::to_vec(&ExecRequest {
+            version: "1.0",
+            secrets: &keys,
+        })?;
+        let output = self.run(&request)?;
+
+        let mut response: HashMap<String, ExecSecret> = serde_json::from_slice(&output)
+            .map_err(|error| format!("invalid output from secret command: {}", error))?;
+
+        keys.into_iter()
+            .map(|key| -> crate::Result<(String, String)> {
+                match response.remove(&key) {
+                    Some(ExecSecret {
+                        error: Some(error), ..
+                    }) => Err(format!("secret {:?} errored: {}", key, error).into()),
+                    Some(ExecSecret {
+                        value: Some(value), ..
+                    }) => Ok((key, value)),
+                    _ => Err(format!("secret {:?} missing from command output", key).into()),
+                }
+            })
+            .collect()
+    }
+}
+
+impl ExecBackend {
+    /// Runs the command with the given input, returning its standard output.
+    fn run(&self, input: &[u8]) -> crate::Result<Vec<u8>> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or("the secret command must not be empty")?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("unable to run secret command {:?}: {}", program, error))?;
+
+        // The pipes are drained in their own threads so a large output can't block the command.
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let stdout = thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let stderr = thread::spawn(move || {
+            let mut output = String::new();
+            stderr.read_to_string(&mut output).map(|_| output)
+        });
+
+        // Dropping stdin closes it, signalling the end of the request.
+        if let Some(mut stdin) = child.stdin.take() {
+            // A command not reading its input is only an error if it fails to answer.
+            let _ = stdin.write_all(input);
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(self.timeout_secs);
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "secret command timed out after {} seconds",
+                    self.timeout_secs
+                )
+                .into());
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        let stdout = stdout.join().expect("stdout reader panicked")?;
+        let stderr = stderr.join().expect("stderr reader panicked")?;
+
+        if status.success() {
+            Ok(stdout)
+        } else {
+            Err(format!(
+                "secret command failed with {}: {}",
+                status,
+                stderr.trim_end()
+            )
+            .into())
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn backend(script: &str, timeout_secs: u64) -> ExecBackend {
+        ExecBackend {
+            command: vec!["sh".into(), "-c".into(), script.into()],
+            timeout_secs,
+        }
+    }
+
+    fn keys(keys: &[&str]) -> HashSet<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn retrieves_secrets() {
+        let script = r#"
+            request=$(cat)
+            case "$request" in
+                *'"version":"1.0"'*) ;;
+                *) exit 1 ;;
+            esac
+            echo '{"first": {"value": "one", "error": null}, "second": {"value": "two"}}'
+        "#;
+
+        let secrets = backend(script, 5)
+            .retrieve(keys(&["first", "second"]))
+            .unwrap();
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(secrets["first"], "one");
+        assert_eq!(secrets["second"], "two");
+    }
+
+    #[test]
+    fn secret_error() {
+        let script = r#"cat > /dev/null; echo '{"first": {"value": null, "error": "denied"}}'"#;
+
+        let error = backend(script, 5).retrieve(keys(&["first"])).unwrap_err();
+        assert!(error.to_string().contains("denied"));
+    }
+
+    #[test]
+    fn missing_secret() {
+        let script = r#"cat > /dev/null; echo '{}'"#;
+
+        assert!(backend(script, 5).retrieve(keys(&["first"])).is_err());
+    }
+
+    #[test]
+    fn command_failure() {
+        let script = "cat > /dev/null; echo oops >&2; exit 3";
+
+        let error = backend(script, 5).retrieve(keys(&["first"])).unwrap_err();
+        assert!(error.to_string().contains("oops"));
+    }
+
+    #[test]
+    fn command_timeout() {
+        let error = backend("sleep 10", 1)
+            .retrieve(keys(&["first"]))
+            .unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+    }
+}
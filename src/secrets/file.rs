@@ -0,0 +1,79 @@
+use crate::config::secret::SecretBackend;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Reads each secret from the file of the same name in a directory, as is common with
+/// orchestrators mounting secrets as files.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FileBackend {
+    pub path: PathBuf,
+}
+
+#[typetag::serde(name = "file")]
+impl SecretBackend for FileBackend {
+    fn retrieve(&mut self, keys: HashSet<String>) -> crate::Result<HashMap<String, String>> {
+        keys.into_iter()
+            .map(|key| -> crate::Result<(String, String)> {
+                // Keys are plain file names, they can't point outside of the directory.
+                let mut components = Path::new(&key).components();
+                match (components.next(), components.next()) {
+                    (Some(Component::Normal(_)), None) => {}
+                    _ => return Err(format!("invalid secret file name {:?}", key).into()),
+                }
+
+                let value = fs::read_to_string(self.path.join(&key))
+                    .map_err(|error| format!("unable to read secret {:?}: {}", key, error))?;
+                let value = value.trim_end_matches(&['\r', '\n'][..]).to_owned();
+
+                Ok((key, value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retrieve(path: &Path, keys: &[&str]) -> crate::Result<HashMap<String, String>> {
+        FileBackend {
+            path: path.to_owned(),
+        }
+        .retrieve(keys.iter().map(|key| key.to_string()).collect())
+    }
+
+    #[test]
+    fn reads_secret_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("password"), "hunter2\n").unwrap();
+        fs::write(dir.path().join("token.txt"), "abc def").unwrap();
+
+        let secrets = retrieve(dir.path(), &["password", "token.txt"]).unwrap();
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(secrets["password"], "hunter2");
+        assert_eq!(secrets["token.txt"], "abc def");
+    }
+
+    #[test]
+    fn missing_secret_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(retrieve(dir.path(), &["missing"]).is_err());
+    }
+
+    #[test]
+    fn rejects_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("nested").join("secret"), "value").unwrap();
+
+        assert!(retrieve(dir.path(), &["nested/secret"]).is_err());
+        assert!(retrieve(&dir.path().join("nested"), &["../nested"]).is_err());
+        assert!(retrieve(dir.path(), &["/etc/passwd"]).is_err());
+    }
+}
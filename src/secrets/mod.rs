@@ -0,0 +1,4 @@
+#[cfg(feature = "secrets-aws_secrets_manager")]
+pub mod aws_secrets_manager;
+pub mod exec;
+pub mod file;
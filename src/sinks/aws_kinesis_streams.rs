@@ -131,6 +131,7 @@ impl KinesisSinkConfig {
         let client = rusoto::client(proxy)?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
+        self.compression.ensure_none_or_gzip()?;
         let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
         Ok(KinesisClient::new_with_client(client, region))
     }
@@ -1,3 +1,4 @@
+use crate::codecs::{FramingEncoderConfig, NewlineDelimitedEncoderConfig, SerializerConfig};
 use crate::config::SinkContext;
 use crate::sinks::s3_common::sink::S3Sink;
 use crate::sinks::util::encoding::StandardEncodings;
@@ -13,8 +14,8 @@ use crate::{
             service::S3Service,
         },
         util::{
-            encoding::EncodingConfig, BatchConfig, Compression, Concurrency, ServiceBuilderExt,
-            TowerRequestConfig,
+            encoding::{build_codecs_encoder, resolve_encoding, EncodingConfig},
+            BatchConfig, Compression, Concurrency, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -49,7 +50,11 @@ pub struct S3SinkConfig {
     pub options: S3Options,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<StandardEncodings>,
+    /// Optional when `serializer` is configured, in which case only its field rules apply.
+    #[serde(default)]
+    pub encoding: Option<EncodingConfig<StandardEncodings>>,
+    pub framing: Option<Box<dyn FramingEncoderConfig>>,
+    pub serializer: Option<Box<dyn SerializerConfig>>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(default)]
@@ -72,7 +77,9 @@ impl GenerateConfig for S3SinkConfig {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
-            encoding: StandardEncodings::Text.into(),
+            encoding: Some(StandardEncodings::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -100,6 +107,10 @@ impl SinkConfig for S3SinkConfig {
     fn sink_type(&self) -> &'static str {
         "aws_s3"
     }
+
+    fn can_reroute_dropped(&self) -> bool {
+        self.serializer.is_some()
+    }
 }
 
 impl S3SinkConfig {
@@ -138,6 +149,13 @@ impl S3SinkConfig {
         let filename_append_uuid = self
             .filename_append_uuid
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
+        let encoder = build_codecs_encoder(&self.framing, &self.serializer, || {
+            Box::new(NewlineDelimitedEncoderConfig::new())
+        })?
+        .map(|encoder| encoder.with_dropped(cx.dropped()));
+        let encoding = resolve_encoding(self.encoding.clone(), &encoder, || {
+            StandardEncodings::Text.into()
+        })?;
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
@@ -145,7 +163,8 @@ impl S3SinkConfig {
             filename_extension: self.filename_extension.clone(),
             filename_time_format,
             filename_append_uuid,
-            encoding: self.encoding.clone(),
+            encoding,
+            encoder,
             compression: self.compression,
         };
 
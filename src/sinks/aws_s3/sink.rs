@@ -1,12 +1,13 @@
 use std::io;
 
 use crate::{
+    codecs,
     event::Event,
     sinks::{
         s3_common::{config::S3Options, service::S3Metadata, service::S3Request},
         util::{
-            encoding::{EncodingConfig, StandardEncodings},
-            Compression, RequestBuilder,
+            encoding::{Encoder, EncodingConfig, EncodingConfiguration, StandardEncodings},
+            Compression, Compressor, RequestBuilder,
         },
     },
 };
@@ -23,6 +24,7 @@ pub struct S3RequestOptions {
     pub filename_extension: Option<String>,
     pub api_options: S3Options,
     pub encoding: EncodingConfig<StandardEncodings>,
+    pub encoder: Option<codecs::Encoder>,
     pub compression: Compression,
 }
 
@@ -55,6 +57,23 @@ impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
         (metadata, events)
     }
 
+    fn encode_events(&self, mut events: Self::Events) -> Result<Self::Payload, Self::Error> {
+        let mut compressor = Compressor::from(self.compression);
+        match &self.encoder {
+            Some(encoder) => {
+                for event in events.iter_mut() {
+                    self.encoding.apply_rules(event);
+                }
+                let _ = encoder.encode_input(events, &mut compressor)?;
+            }
+            None => {
+                let _ = self.encoding.encode_input(events, &mut compressor)?;
+            }
+        }
+
+        Ok(compressor.into_inner().into())
+    }
+
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
         let filename = {
             let formatted_ts = Utc::now().format(self.filename_time_format.as_str());
@@ -272,7 +272,9 @@ mod integration_tests {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".to_owned()),
-            encoding: StandardEncodings::Text.into(),
+            encoding: Some(StandardEncodings::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Compression::None,
             batch: BatchConfig {
                 max_events: Some(batch_size),
@@ -250,6 +250,9 @@ impl Compression {
         match self {
             Self::None => "text/plain",
             Self::Gzip(_) => "application/gzip",
+            Self::Zstd(_) => "application/zstd",
+            Self::Snappy => "application/x-snappy-framed",
+            Self::Lz4 => "application/x-lz4",
         }
     }
 }
@@ -469,6 +472,28 @@ mod tests {
         assert_eq!(request.content_type, "application/gzip");
     }
 
+    #[test]
+    fn azure_blob_build_request_with_zstd_compression() {
+        let partition = PartitionInnerBuffer::new(vec![0u8; 10], Bytes::from("blob"));
+        let compression = Compression::zstd_default();
+        let container_name = String::from("logs");
+        let blob_time_format = String::from("");
+        let blob_append_uuid = false;
+
+        let request = build_request(
+            partition,
+            compression,
+            container_name,
+            blob_time_format,
+            blob_append_uuid,
+        );
+
+        assert_eq!(request.container_name, "logs".to_string());
+        assert_eq!(request.blob_name, "blob.log.zst".to_string());
+        assert_eq!(request.content_encoding, Some("zstd"));
+        assert_eq!(request.content_type, "application/zstd");
+    }
+
     #[test]
     fn azure_blob_build_request_with_time_format() {
         let partition = PartitionInnerBuffer::new(vec![0u8; 10], Bytes::from("blob"));
@@ -63,6 +63,8 @@ impl SinkConfig for ClickhouseConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        self.compression.ensure_none_or_gzip()?;
+
         let batch = BatchSettings::default()
             .bytes(10_000_000)
             .timeout(1)
@@ -1,14 +1,19 @@
 use crate::{
     buffers::Acker,
+    codecs::{self, FramingEncoderConfig, NewlineDelimitedEncoderConfig, SerializerConfig},
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::{ConsoleEventProcessed, ConsoleFieldNotFound},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{
+            build_codecs_encoder, encode_with_codecs, resolve_encoding, EncodingConfig,
+            EncodingConfiguration,
+        },
         StreamSink,
     },
 };
 use async_trait::async_trait;
+use bytes::Bytes;
 use futures::{
     future,
     stream::{BoxStream, StreamExt},
@@ -32,7 +37,11 @@ pub enum Target {
 pub struct ConsoleSinkConfig {
     #[serde(default)]
     pub target: Target,
-    pub encoding: EncodingConfig<Encoding>,
+    /// Optional when `serializer` is configured, in which case only its field rules apply.
+    #[serde(default)]
+    pub encoding: Option<EncodingConfig<Encoding>>,
+    pub framing: Option<Box<dyn FramingEncoderConfig>>,
+    pub serializer: Option<Box<dyn SerializerConfig>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
@@ -50,7 +59,9 @@ impl GenerateConfig for ConsoleSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             target: Target::Stdout,
-            encoding: Encoding::Json.into(),
+            encoding: Some(Encoding::Json.into()),
+            framing: None,
+            serializer: None,
         })
         .unwrap()
     }
@@ -63,7 +74,11 @@ impl SinkConfig for ConsoleSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let encoding = self.encoding.clone();
+        let encoder = build_codecs_encoder(&self.framing, &self.serializer, || {
+            Box::new(NewlineDelimitedEncoderConfig::new())
+        })?
+        .map(|encoder| encoder.with_dropped(cx.dropped()));
+        let encoding = resolve_encoding(self.encoding.clone(), &encoder, || Encoding::Text.into())?;
 
         let output: Box<dyn io::AsyncWrite + Send + Sync + Unpin> = match self.target {
             Target::Stdout => Box::new(io::stdout()),
@@ -74,6 +89,7 @@ impl SinkConfig for ConsoleSinkConfig {
             acker: cx.acker(),
             output,
             encoding,
+            encoder,
         };
 
         Ok((
@@ -89,6 +105,10 @@ impl SinkConfig for ConsoleSinkConfig {
     fn sink_type(&self) -> &'static str {
         "console"
     }
+
+    fn can_reroute_dropped(&self) -> bool {
+        self.serializer.is_some()
+    }
 }
 
 fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option<String> {
@@ -121,6 +141,12 @@ fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option
                 .ok(),
             Encoding::Text => Some(format!("{}", metric)),
         },
+        // Spans have no natural single-line text form, so they are always written as JSON.
+        Event::Trace(trace) => serde_json::to_string(&trace)
+            .map_err(|error| {
+                error!(message = "Error encoding json.", %error);
+            })
+            .ok(),
     }
 }
 
@@ -128,6 +154,7 @@ struct WriterSink {
     acker: Acker,
     output: Box<dyn io::AsyncWrite + Send + Sync + Unpin>,
     encoding: EncodingConfig<Encoding>,
+    encoder: Option<codecs::Encoder>,
 }
 
 #[async_trait]
@@ -135,9 +162,15 @@ impl StreamSink for WriterSink {
     async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
         while let Some(event) = input.next().await {
             self.acker.ack(1);
-            if let Some(mut buf) = encode_event(event, &self.encoding) {
-                buf.push('\n');
-                if let Err(error) = self.output.write_all(buf.as_bytes()).await {
+            let buf = match &self.encoder {
+                Some(encoder) => encode_with_codecs(event, &self.encoding, encoder),
+                None => encode_event(event, &self.encoding).map(|mut buf| {
+                    buf.push('\n');
+                    Bytes::from(buf)
+                }),
+            };
+            if let Some(buf) = buf {
+                if let Err(error) = self.output.write_all(&buf).await {
                     // Error when writing to stdout/stderr is likely irrecoverable,
                     // so stop the sink.
                     error!(message = "Error writing to output. Stopping sink.", %error);
@@ -100,6 +100,9 @@ impl DatadogLogsConfig {
         client: HttpClient,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
+        let compression = self.compression.unwrap_or_default();
+        compression.ensure_none_or_gzip()?;
+
         let default_api_key: Arc<str> = Arc::from(self.default_api_key.clone().as_str());
         let request_limits = self.request.unwrap_with(&DEFAULT_REQUEST_LIMITS);
 
@@ -122,7 +125,7 @@ impl DatadogLogsConfig {
             ));
         let sink = LogSinkBuilder::new(service, cx, default_api_key, batch)
             .encoding(self.encoding.clone())
-            .compression(self.compression.unwrap_or_default())
+            .compression(compression)
             .build();
 
         Ok(VectorSink::Stream(Box::new(sink)))
@@ -43,6 +43,8 @@ pub struct ElasticSearchCommon {
 
 impl ElasticSearchCommon {
     pub fn parse_config(config: &ElasticSearchConfig) -> crate::Result<Self> {
+        config.compression.ensure_none_or_gzip()?;
+
         // Test the configured host, but ignore the result
         let uri = format!("{}/_test", &config.endpoint);
         let uri = uri.parse::<Uri>().with_context(|| InvalidHost {
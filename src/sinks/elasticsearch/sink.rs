@@ -59,6 +59,7 @@ impl ElasticSearchSink {
                 future::ready(Some(match event {
                     Event::Metric(metric) => metric_to_log.transform_one(metric),
                     Event::Log(log) => Some(log),
+                    Event::Trace(trace) => Some(trace.into()),
                 }))
             })
             .filter_map(|x| async move { x })
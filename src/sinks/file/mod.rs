@@ -1,16 +1,20 @@
 use crate::expiring_hash_map::ExpiringHashMap;
 use crate::{
     buffers::Acker,
+    codecs::{self, FramingEncoderConfig, NewlineDelimitedEncoderConfig, SerializerConfig},
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::{EventsSent, FileBytesSent, FileOpen, TemplateRenderingFailed},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{
+            build_codecs_encoder, encode_with_codecs, resolve_encoding, EncodingConfig,
+            EncodingConfiguration,
+        },
         StreamSink,
     },
     template::Template,
 };
-use async_compression::tokio::write::GzipEncoder;
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{
@@ -19,7 +23,10 @@ use futures::{
     FutureExt,
 };
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
 use vector_core::ByteSizeOf;
 
 use tokio::{
@@ -35,7 +42,11 @@ use std::convert::TryFrom;
 pub struct FileSinkConfig {
     pub path: Template,
     pub idle_timeout_secs: Option<u64>,
-    pub encoding: EncodingConfig<Encoding>,
+    /// Optional when `serializer` is configured, in which case only its field rules apply.
+    #[serde(default)]
+    pub encoding: Option<EncodingConfig<Encoding>>,
+    pub framing: Option<Box<dyn FramingEncoderConfig>>,
+    pub serializer: Option<Box<dyn SerializerConfig>>,
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -52,7 +63,9 @@ impl GenerateConfig for FileSinkConfig {
         toml::Value::try_from(Self {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Default::default(),
         })
         .unwrap()
@@ -70,6 +83,10 @@ pub enum Encoding {
 #[serde(rename_all = "snake_case")]
 pub enum Compression {
     Gzip,
+    Zstd,
+    /// Snappy in its framing format, which unlike the raw format can be streamed.
+    Snappy,
+    Lz4,
     None,
 }
 
@@ -82,6 +99,9 @@ impl Default for Compression {
 enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
+    Zstd(ZstdEncoder<File>),
+    Snappy(SyncEncoder<snap::write::FrameEncoder<Vec<u8>>>),
+    Lz4(SyncEncoder<lz4_flex::frame::FrameEncoder<Vec<u8>>>),
 }
 
 impl OutFile {
@@ -89,6 +109,15 @@ impl OutFile {
         match compression {
             Compression::None => OutFile::Regular(file),
             Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
+            Compression::Zstd => OutFile::Zstd(ZstdEncoder::new(file)),
+            Compression::Snappy => OutFile::Snappy(SyncEncoder::new(
+                file,
+                snap::write::FrameEncoder::new(Vec::new()),
+            )),
+            Compression::Lz4 => OutFile::Lz4(SyncEncoder::new(
+                file,
+                lz4_flex::frame::FrameEncoder::new(Vec::new()),
+            )),
         }
     }
 
@@ -96,6 +125,9 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.sync_all().await,
             OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
+            OutFile::Zstd(zstd) => zstd.get_mut().sync_all().await,
+            OutFile::Snappy(snappy) => snappy.file.sync_all().await,
+            OutFile::Lz4(lz4) => lz4.file.sync_all().await,
         }
     }
 
@@ -103,6 +135,9 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.shutdown().await,
             OutFile::Gzip(gzip) => gzip.shutdown().await,
+            OutFile::Zstd(zstd) => zstd.shutdown().await,
+            OutFile::Snappy(snappy) => snappy.shutdown().await,
+            OutFile::Lz4(lz4) => lz4.shutdown().await,
         }
     }
 
@@ -110,6 +145,9 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
             OutFile::Gzip(gzip) => gzip.write_all(src).await,
+            OutFile::Zstd(zstd) => zstd.write_all(src).await,
+            OutFile::Snappy(snappy) => snappy.write_all(src).await,
+            OutFile::Lz4(lz4) => lz4.write_all(src).await,
         }
     }
 
@@ -121,6 +159,78 @@ impl OutFile {
     }
 }
 
+/// A synchronous encoder writing its frames to an in-memory buffer.
+trait FrameEncoder: Write {
+    /// The frames which have been completed so far.
+    fn frames(&mut self) -> &mut Vec<u8>;
+
+    /// Completes the last frame, returning the remaining output.
+    fn finish(self) -> io::Result<Vec<u8>>;
+}
+
+impl FrameEncoder for snap::write::FrameEncoder<Vec<u8>> {
+    fn frames(&mut self) -> &mut Vec<u8> {
+        self.get_mut()
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        self.into_inner()
+            .map_err(|error| io::Error::new(error.error().kind(), error.error().to_string()))
+    }
+}
+
+impl FrameEncoder for lz4_flex::frame::FrameEncoder<Vec<u8>> {
+    fn frames(&mut self) -> &mut Vec<u8> {
+        self.get_mut()
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        lz4_flex::frame::FrameEncoder::finish(self)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+}
+
+/// There are no async encoders for snappy and LZ4, so the data is compressed
+/// in memory and the completed frames are written out to the file as soon as
+/// they become available.
+struct SyncEncoder<E> {
+    file: File,
+    encoder: Option<E>,
+}
+
+impl<E: FrameEncoder> SyncEncoder<E> {
+    fn new(file: File, encoder: E) -> Self {
+        Self {
+            file,
+            encoder: Some(encoder),
+        }
+    }
+
+    async fn write_all(&mut self, src: &[u8]) -> Result<(), std::io::Error> {
+        let encoder = self.encoder.as_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "write to a file that has been shut down",
+            )
+        })?;
+        encoder.write_all(src)?;
+
+        let frames = encoder.frames();
+        if !frames.is_empty() {
+            self.file.write_all(frames).await?;
+            frames.clear();
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), std::io::Error> {
+        if let Some(encoder) = self.encoder.take() {
+            self.file.write_all(&encoder.finish()?).await?;
+        }
+        self.file.shutdown().await
+    }
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "file")]
 impl SinkConfig for FileSinkConfig {
@@ -128,7 +238,10 @@ impl SinkConfig for FileSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let sink = FileSink::new(self, cx.acker());
+        let mut sink = FileSink::new(self, cx.acker())?;
+        sink.encoder = sink
+            .encoder
+            .map(|encoder| encoder.with_dropped(cx.dropped()));
         Ok((
             super::VectorSink::Stream(Box::new(sink)),
             future::ok(()).boxed(),
@@ -142,6 +255,10 @@ impl SinkConfig for FileSinkConfig {
     fn sink_type(&self) -> &'static str {
         "file"
     }
+
+    fn can_reroute_dropped(&self) -> bool {
+        self.serializer.is_some()
+    }
 }
 
 #[derive(Debug)]
@@ -149,21 +266,29 @@ pub struct FileSink {
     acker: Acker,
     path: Template,
     encoding: EncodingConfig<Encoding>,
+    encoder: Option<codecs::Encoder>,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
 }
 
 impl FileSink {
-    pub fn new(config: &FileSinkConfig, acker: Acker) -> Self {
-        Self {
+    pub fn new(config: &FileSinkConfig, acker: Acker) -> crate::Result<Self> {
+        let encoder = build_codecs_encoder(&config.framing, &config.serializer, || {
+            Box::new(NewlineDelimitedEncoderConfig::new())
+        })?;
+        let encoding =
+            resolve_encoding(config.encoding.clone(), &encoder, || Encoding::Text.into())?;
+
+        Ok(Self {
             acker,
             path: config.path.clone(),
-            encoding: config.encoding.clone(),
+            encoding,
+            encoder,
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
-        }
+        })
     }
 
     /// Uses pass the `event` to `self.path` template to obtain the file path
@@ -291,7 +416,18 @@ impl FileSink {
 
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.size_of();
-        match write_event_to_file(file, event, &self.encoding).await {
+        let buf = match &self.encoder {
+            Some(encoder) => match encode_with_codecs(event, &self.encoding, encoder) {
+                Some(buf) => buf,
+                None => return,
+            },
+            None => {
+                let mut buf = encode_event(&self.encoding, event);
+                buf.push(b'\n');
+                Bytes::from(buf)
+            }
+        };
+        match write_to_file(file, &buf).await {
             Ok(byte_size) => {
                 emit!(&EventsSent {
                     count: 1,
@@ -335,14 +471,8 @@ pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Ve
     }
 }
 
-async fn write_event_to_file(
-    file: &mut OutFile,
-    event: Event,
-    encoding: &EncodingConfig<Encoding>,
-) -> Result<usize, std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
-    file.write_all(&buf[..]).await.map(|()| buf.len())
+async fn write_to_file(file: &mut OutFile, buf: &[u8]) -> Result<usize, std::io::Error> {
+    file.write_all(buf).await.map(|()| buf.len())
 }
 
 #[async_trait]
@@ -360,8 +490,8 @@ mod tests {
     use super::*;
     use crate::test_util::{
         components::{self, FILE_SINK_TAGS, SINK_TESTS},
-        lines_from_file, lines_from_gzip_file, random_events_with_stream, random_lines_with_stream,
-        temp_dir, temp_file, trace_init,
+        lines_from_file, lines_from_gzip_file, lines_from_zst_file, random_events_with_stream,
+        random_lines_with_stream, temp_dir, temp_file, trace_init,
     };
     use futures::{stream, SinkExt};
     use pretty_assertions::assert_eq;
@@ -382,11 +512,13 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (input, _events) = random_lines_with_stream(100, 64, None);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -399,6 +531,38 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_with_codecs() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config: FileSinkConfig = toml::from_str(&format!(
+            r#"
+            path = "{}"
+            encoding.codec = "text"
+            framing.method = "character_delimited"
+            framing.character_delimited.delimiter = "\n"
+            serializer.codec = "csv"
+            serializer.csv.fields = ["message", "missing"]
+            "#,
+            template.to_str().unwrap()
+        ))
+        .unwrap();
+
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
+        let (input, _events) = random_lines_with_stream(100, 64, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+
+        let output = lines_from_file(template);
+        assert_eq!(input.len(), output.len());
+        for (input, output) in input.into_iter().zip(output) {
+            assert_eq!(format!("{},", input), output);
+        }
+    }
+
     #[tokio::test]
     async fn single_partition_gzip() {
         components::init_test();
@@ -409,11 +573,13 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Compression::Gzip,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (input, _) = random_lines_with_stream(100, 64, None);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -426,6 +592,64 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_zstd() {
+        components::init_test();
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
+            compression: Compression::Zstd,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+        SINK_TESTS.assert(&FILE_SINK_TAGS);
+
+        let output = lines_from_zst_file(template);
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    async fn single_partition_snappy() {
+        components::init_test();
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
+            compression: Compression::Snappy,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+        SINK_TESTS.assert(&FILE_SINK_TAGS);
+
+        let file = std::fs::File::open(template).unwrap();
+        let output =
+            std::io::BufRead::lines(std::io::BufReader::new(snap::read::FrameDecoder::new(file)))
+                .map(Result::unwrap)
+                .collect::<Vec<_>>();
+        assert_eq!(input, output);
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         components::init_test();
@@ -441,11 +665,13 @@ mod tests {
         let config = FileSinkConfig {
             path: template.try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
 
         let (mut input, _events) = random_events_with_stream(32, 8, None);
         input[0].as_mut_log().insert("date", "2019-26-07");
@@ -522,11 +748,13 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: Some(1),
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (mut input, _events) = random_lines_with_stream(10, 64, None);
 
         let (mut tx, rx) = futures::channel::mpsc::channel(0);
@@ -0,0 +1,797 @@
+//! Sink sending logs to Fluentd or Fluent Bit aggregators with the Forward protocol.
+//!
+//! Events are batched by tag and sent as `PackedForward` messages, whose entries can be
+//! compressed with gzip. When `require_ack_response` is set, each message carries a `chunk` id
+//! the server acknowledges, and events are only marked as delivered once it did.
+//!
+//! Messages are sent one at a time, each only once the previous one was acknowledged, so with
+//! acknowledgements the throughput is bound to one batch per round trip to the server.
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, EventStatus, Finalizable, Value},
+    internal_events::TemplateRenderingFailed,
+    sinks::{
+        util::{
+            encoding::{EncodingConfigWithDefault, EncodingConfiguration},
+            tcp::{TcpConnector, TcpSinkConfig},
+            BatchConfig, BatchSettings, SinkBuilderExt, StreamSink,
+        },
+        Healthcheck, VectorSink,
+    },
+    tcp::TcpKeepaliveConfig,
+    template::Template,
+    tls::{MaybeTlsStream, TlsConfig},
+};
+use async_trait::async_trait;
+use bytes::{Buf, BytesMut};
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use openssl::sha::Sha512;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    convert::TryFrom,
+    io::{self, Cursor, Write},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::{sleep, timeout},
+};
+use vector_core::{buffers::Acker, partition::Partitioner, stream::BatcherSettings};
+
+const DEFAULT_BATCH_SETTINGS: BatchSettings<()> = BatchSettings::const_default()
+    .bytes(1_048_576)
+    .events(1_000)
+    .timeout(1);
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSinkConfig {
+    address: String,
+    tag: Template,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    encoding: EncodingConfigWithDefault<Encoding>,
+    #[serde(default)]
+    compression: FluentCompression,
+    #[serde(default)]
+    batch: BatchConfig,
+    /// Whether to wait for the server to acknowledge each message before
+    /// marking its events as delivered.
+    #[serde(default)]
+    require_ack_response: bool,
+    #[serde(default = "default_ack_response_timeout_secs")]
+    ack_response_timeout_secs: u64,
+    auth: Option<FluentAuthConfig>,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsConfig>,
+    send_buffer_bytes: Option<usize>,
+}
+
+const fn default_ack_response_timeout_secs() -> u64 {
+    60
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Encoding {
+    #[derivative(Default)]
+    Default,
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum FluentCompression {
+    #[derivative(Default)]
+    None,
+    Gzip,
+}
+
+/// Shared key authentication, as done by the `security` section of Fluentd's
+/// `in_forward` plugin.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FluentAuthConfig {
+    shared_key: String,
+    /// The hostname sent to the server, which defaults to the hostname of the
+    /// machine.
+    self_hostname: Option<String>,
+    /// The credentials used when the server also requires user authentication.
+    username: Option<String>,
+    password: Option<String>,
+}
+
+inventory::submit! {
+    SinkDescription::new::<FluentSinkConfig>("fluent")
+}
+
+impl GenerateConfig for FluentSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:24224"
+            tag = "vector.{{ host }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SinkConfig for FluentSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = TcpSinkConfig::new(
+            self.address.clone(),
+            self.keepalive,
+            self.tls.clone(),
+            self.send_buffer_bytes,
+        )
+        .build_connector()?;
+        let batch_settings = DEFAULT_BATCH_SETTINGS
+            .parse_config(self.batch)?
+            .into_batcher_settings()?;
+
+        let sink = FluentSink {
+            connector: connector.clone(),
+            acker: cx.acker(),
+            tag: self.tag.clone(),
+            encoding: self.encoding.clone(),
+            compression: self.compression,
+            auth: self.auth.clone(),
+            ack_response_timeout: if self.require_ack_response {
+                Some(Duration::from_secs(self.ack_response_timeout_secs))
+            } else {
+                None
+            },
+            batch_settings,
+        };
+
+        let healthcheck = async move { connector.healthcheck().await }.boxed();
+
+        Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "fluent"
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum FluentError {
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Connection closed by the server."))]
+    ConnectionClosed,
+    #[snafu(display("Invalid response from the server: {}", reason))]
+    InvalidResponse { reason: String },
+    #[snafu(display("Authentication failed: {}", reason))]
+    AuthenticationFailed { reason: String },
+    #[snafu(display("Timed out waiting for the acknowledgement of chunk {:?}.", chunk))]
+    AckTimeout { chunk: String },
+}
+
+/// Partitions events by their rendered tag.
+struct TagPartitioner(Template);
+
+impl Partitioner for TagPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(&TemplateRenderingFailed {
+                    error,
+                    field: Some("tag"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+struct FluentSink {
+    connector: TcpConnector,
+    acker: Acker,
+    tag: Template,
+    encoding: EncodingConfigWithDefault<Encoding>,
+    compression: FluentCompression,
+    auth: Option<FluentAuthConfig>,
+    /// How long to wait for acknowledgements, if they're required.
+    ack_response_timeout: Option<Duration>,
+    batch_settings: BatcherSettings,
+}
+
+impl FluentSink {
+    /// Connects to the server, authenticating if configured to.
+    async fn connect(&self) -> Connection {
+        let mut backoff = TcpConnector::fresh_backoff();
+        loop {
+            let mut connection = Connection::new(self.connector.connect_backoff().await);
+            match &self.auth {
+                None => return connection,
+                Some(auth) => match connection.handshake(auth).await {
+                    Ok(()) => return connection,
+                    Err(error) => {
+                        error!(message = "Fluent handshake failed.", %error);
+                        sleep(backoff.next().unwrap()).await;
+                    }
+                },
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        connection: &mut Connection,
+        message: &[u8],
+        chunk: Option<&str>,
+    ) -> Result<(), FluentError> {
+        connection.stream.write_all(message).await.context(Io)?;
+        connection.stream.flush().await.context(Io)?;
+
+        if let (Some(chunk), Some(ack_response_timeout)) = (chunk, self.ack_response_timeout) {
+            let response = timeout(ack_response_timeout, connection.read_value())
+                .await
+                .map_err(|_| FluentError::AckTimeout {
+                    chunk: chunk.to_owned(),
+                })??;
+            let ack = response
+                .as_map()
+                .and_then(|entries| map_get(entries, "ack"))
+                .and_then(rmpv::Value::as_str);
+            if ack != Some(chunk) {
+                return Err(FluentError::InvalidResponse {
+                    reason: format!("expected the acknowledgement of chunk {:?}", chunk),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StreamSink for FluentSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoding = self.encoding.clone();
+        let mut batches = input
+            .map(move |mut event| {
+                encoding.apply_rules(&mut event);
+                event
+            })
+            .batched(TagPartitioner(self.tag.clone()), self.batch_settings);
+
+        let mut connection = None;
+        while let Some((tag, mut events)) = batches.next().await {
+            let count = events.len();
+            let finalizers = events.take_finalizers();
+            let tag = match tag {
+                Some(tag) => tag,
+                None => {
+                    finalizers.update_status(EventStatus::Errored);
+                    self.acker.ack(count);
+                    continue;
+                }
+            };
+
+            let chunk = self
+                .ack_response_timeout
+                .map(|_| base64::encode(rand::random::<[u8; 16]>()));
+            let message = encode_message(&tag, events, self.compression, chunk.as_deref());
+
+            // Messages are sent until they're acknowledged, on a new
+            // connection after each failure.
+            let mut backoff = TcpConnector::fresh_backoff();
+            loop {
+                let mut current = match connection.take() {
+                    Some(current) => current,
+                    None => self.connect().await,
+                };
+                match self.send(&mut current, &message, chunk.as_deref()).await {
+                    Ok(()) => {
+                        connection = Some(current);
+                        break;
+                    }
+                    Err(error) => {
+                        error!(message = "Failed sending events to Fluent server; retrying.", %error);
+                        sleep(backoff.next().unwrap()).await;
+                    }
+                }
+            }
+
+            finalizers.update_status(EventStatus::Delivered);
+            self.acker.ack(count);
+        }
+
+        Ok(())
+    }
+}
+
+/// A connection to the server, buffering the bytes received until a whole
+/// MessagePack value is read.
+struct Connection {
+    stream: MaybeTlsStream<TcpStream>,
+    buffer: BytesMut,
+}
+
+impl Connection {
+    fn new(stream: MaybeTlsStream<TcpStream>) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+        }
+    }
+
+    async fn read_value(&mut self) -> Result<rmpv::Value, FluentError> {
+        loop {
+            if !self.buffer.is_empty() {
+                let mut cursor = Cursor::new(&self.buffer[..]);
+                match rmpv::decode::read_value(&mut cursor) {
+                    Ok(value) => {
+                        let position = cursor.position() as usize;
+                        self.buffer.advance(position);
+                        return Ok(value);
+                    }
+                    Err(rmpv::decode::Error::InvalidMarkerRead(error))
+                    | Err(rmpv::decode::Error::InvalidDataRead(error))
+                        if error.kind() == io::ErrorKind::UnexpectedEof => {}
+                    Err(error) => {
+                        return Err(FluentError::InvalidResponse {
+                            reason: error.to_string(),
+                        })
+                    }
+                }
+            }
+
+            if self.stream.read_buf(&mut self.buffer).await.context(Io)? == 0 {
+                return Err(FluentError::ConnectionClosed);
+            }
+        }
+    }
+
+    async fn write_value(&mut self, value: &rmpv::Value) -> Result<(), FluentError> {
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, value).expect("writing to a Vec can't fail");
+        self.stream.write_all(&buf).await.context(Io)?;
+        self.stream.flush().await.context(Io)
+    }
+
+    /// Authenticates with the shared key, answering the `HELO` of the server
+    /// with a `PING` and checking its `PONG`.
+    async fn handshake(&mut self, auth: &FluentAuthConfig) -> Result<(), FluentError> {
+        let helo = self.read_value().await?;
+        let options = match helo.as_array().map(Vec::as_slice) {
+            Some([kind, rmpv::Value::Map(options)]) if kind.as_str() == Some("HELO") => options,
+            _ => {
+                return Err(FluentError::InvalidResponse {
+                    reason: format!("expected HELO, got {}", helo),
+                })
+            }
+        };
+        let nonce = map_get(options, "nonce")
+            .and_then(value_bytes)
+            .ok_or_else(|| FluentError::InvalidResponse {
+                reason: "HELO without nonce".into(),
+            })?;
+        let user_auth_salt = map_get(options, "auth")
+            .and_then(value_bytes)
+            .unwrap_or_default();
+
+        let hostname = auth
+            .self_hostname
+            .clone()
+            .unwrap_or_else(|| crate::get_hostname().unwrap_or_default());
+        let salt = hex::encode(rand::random::<[u8; 16]>());
+        let digest = hex_sha512(&[
+            salt.as_bytes(),
+            hostname.as_bytes(),
+            &nonce,
+            auth.shared_key.as_bytes(),
+        ]);
+        let (username, password_digest) = if user_auth_salt.is_empty() {
+            (String::new(), String::new())
+        } else {
+            match (&auth.username, &auth.password) {
+                (Some(username), Some(password)) => (
+                    username.clone(),
+                    hex_sha512(&[&user_auth_salt, username.as_bytes(), password.as_bytes()]),
+                ),
+                _ => {
+                    return Err(FluentError::AuthenticationFailed {
+                        reason: "the server requires a username and password".into(),
+                    })
+                }
+            }
+        };
+
+        self.write_value(&rmpv::Value::Array(vec![
+            "PING".into(),
+            hostname.into(),
+            salt.clone().into(),
+            digest.into(),
+            username.into(),
+            password_digest.into(),
+        ]))
+        .await?;
+
+        let pong = self.read_value().await?;
+        match pong.as_array().map(Vec::as_slice) {
+            Some([kind, authenticated, reason, server_hostname, server_digest])
+                if kind.as_str() == Some("PONG") =>
+            {
+                if authenticated.as_bool() != Some(true) {
+                    return Err(FluentError::AuthenticationFailed {
+                        reason: reason.as_str().unwrap_or_default().to_owned(),
+                    });
+                }
+                let expected = hex_sha512(&[
+                    salt.as_bytes(),
+                    server_hostname.as_str().unwrap_or_default().as_bytes(),
+                    &nonce,
+                    auth.shared_key.as_bytes(),
+                ]);
+                if server_digest.as_str() != Some(expected.as_str()) {
+                    return Err(FluentError::AuthenticationFailed {
+                        reason: "the server's shared key digest doesn't match".into(),
+                    });
+                }
+                Ok(())
+            }
+            _ => Err(FluentError::InvalidResponse {
+                reason: format!("expected PONG, got {}", pong),
+            }),
+        }
+    }
+}
+
+fn map_get<'a>(entries: &'a [(rmpv::Value, rmpv::Value)], key: &str) -> Option<&'a rmpv::Value> {
+    entries
+        .iter()
+        .find(|(name, _)| name.as_str() == Some(key))
+        .map(|(_, value)| value)
+}
+
+fn value_bytes(value: &rmpv::Value) -> Option<Vec<u8>> {
+    match value {
+        rmpv::Value::String(string) => Some(string.as_bytes().to_vec()),
+        rmpv::Value::Binary(bytes) => Some(bytes.clone()),
+        _ => None,
+    }
+}
+
+fn hex_sha512(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finish())
+}
+
+/// Encodes the events as a `PackedForward` message.
+fn encode_message(
+    tag: &str,
+    events: Vec<Event>,
+    compression: FluentCompression,
+    chunk: Option<&str>,
+) -> Vec<u8> {
+    let size = events.len() as u64;
+    let mut entries = Vec::new();
+    for event in events {
+        let mut log = event.into_log();
+        let timestamp = match log.remove(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => timestamp,
+            Some(value) => {
+                log.insert(log_schema().timestamp_key(), value);
+                Utc::now()
+            }
+            None => Utc::now(),
+        };
+        let entry = rmpv::Value::Array(vec![
+            event_time(timestamp),
+            to_msgpack(Value::Map(log.into_parts().0)),
+        ]);
+        rmpv::encode::write_value(&mut entries, &entry).expect("writing to a Vec can't fail");
+    }
+
+    let mut options = vec![("size".into(), size.into())];
+    let entries = match compression {
+        FluentCompression::None => entries,
+        FluentCompression::Gzip => {
+            options.push(("compressed".into(), "gzip".into()));
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder
+                .write_all(&entries)
+                .expect("writing to a Vec can't fail");
+            encoder.finish().expect("writing to a Vec can't fail")
+        }
+    };
+    if let Some(chunk) = chunk {
+        options.push(("chunk".into(), chunk.into()));
+    }
+
+    let message = rmpv::Value::Array(vec![
+        tag.into(),
+        rmpv::Value::Binary(entries),
+        rmpv::Value::Map(options),
+    ]);
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &message).expect("writing to a Vec can't fail");
+    buf
+}
+
+/// Encodes the timestamp as the `EventTime` extension, which keeps nanoseconds, or as integer
+/// seconds if it's out of the range of the extension's unsigned 32 bits seconds.
+fn event_time(timestamp: DateTime<Utc>) -> rmpv::Value {
+    match u32::try_from(timestamp.timestamp()) {
+        Ok(seconds) => {
+            let mut bytes = Vec::with_capacity(8);
+            bytes.extend_from_slice(&seconds.to_be_bytes());
+            bytes.extend_from_slice(&timestamp.timestamp_subsec_nanos().to_be_bytes());
+            rmpv::Value::Ext(0, bytes)
+        }
+        Err(_) => timestamp.timestamp().into(),
+    }
+}
+
+fn to_msgpack(value: Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => string.into(),
+            Err(error) => rmpv::Value::Binary(error.into_bytes()),
+        },
+        Value::Integer(int) => int.into(),
+        Value::Float(float) => float.into(),
+        Value::Boolean(boolean) => boolean.into(),
+        Value::Timestamp(timestamp) => timestamp.to_rfc3339().into(),
+        Value::Map(fields) => rmpv::Value::Map(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.into(), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(values) => rmpv::Value::Array(values.into_iter().map(to_msgpack).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{BatchNotifier, BatchStatus, LogEvent},
+        sinks::util::test::load_sink,
+        test_util::{next_addr, random_lines_with_stream},
+    };
+    use chrono::TimeZone;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentSinkConfig>();
+    }
+
+    /// Reads a message from the client, returning its tag, its records and
+    /// its options.
+    async fn read_message(
+        connection: &mut Connection,
+    ) -> (String, Vec<rmpv::Value>, Vec<(rmpv::Value, rmpv::Value)>) {
+        let message = connection.read_value().await.unwrap();
+        let mut message = match message {
+            rmpv::Value::Array(message) => message,
+            message => panic!("unexpected message {}", message),
+        };
+        let options = match message.pop() {
+            Some(rmpv::Value::Map(options)) => options,
+            options => panic!("unexpected options {:?}", options),
+        };
+        let mut entries = match message.pop() {
+            Some(rmpv::Value::Binary(entries)) => entries,
+            entries => panic!("unexpected entries {:?}", entries),
+        };
+        if map_get(&options, "compressed").and_then(rmpv::Value::as_str) == Some("gzip") {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(&entries[..])
+                .read_to_end(&mut decompressed)
+                .unwrap();
+            entries = decompressed;
+        }
+        let tag = message.pop().unwrap().as_str().unwrap().to_owned();
+
+        let mut records = Vec::new();
+        let mut entries = &entries[..];
+        while !entries.is_empty() {
+            let entry = rmpv::decode::read_value(&mut entries).unwrap();
+            match entry {
+                rmpv::Value::Array(mut entry) => {
+                    assert!(matches!(entry[0], rmpv::Value::Ext(0, _)));
+                    records.push(entry.remove(1));
+                }
+                entry => panic!("unexpected entry {}", entry),
+            }
+        }
+
+        (tag, records, options)
+    }
+
+    fn messages(records: &[rmpv::Value]) -> Vec<String> {
+        records
+            .iter()
+            .map(|record| {
+                let fields = record.as_map().unwrap();
+                map_get(fields, "message")
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encodes_gzip_compressed_entries() {
+        let timestamps = vec![
+            Utc.timestamp(1_441_588_984, 42),
+            Utc.timestamp(-1, 0),
+            Utc.timestamp(i64::from(u32::MAX) + 1, 0),
+        ];
+        let events = timestamps
+            .iter()
+            .map(|&timestamp| {
+                let mut log = LogEvent::from("message");
+                log.insert(log_schema().timestamp_key(), timestamp);
+                Event::from(log)
+            })
+            .collect();
+        let message = encode_message("tag", events, FluentCompression::Gzip, None);
+
+        let message = rmpv::decode::read_value(&mut &message[..]).unwrap();
+        let message = message.as_array().unwrap();
+        assert_eq!(message[0].as_str(), Some("tag"));
+        let options = message[2].as_map().unwrap();
+        assert_eq!(
+            map_get(options, "compressed").and_then(rmpv::Value::as_str),
+            Some("gzip")
+        );
+        assert_eq!(
+            map_get(options, "size").and_then(rmpv::Value::as_u64),
+            Some(3)
+        );
+
+        let mut entries = Vec::new();
+        GzDecoder::new(message[1].as_slice().unwrap())
+            .read_to_end(&mut entries)
+            .unwrap();
+        let mut entries = &entries[..];
+        let mut times = Vec::new();
+        while !entries.is_empty() {
+            let entry = rmpv::decode::read_value(&mut entries).unwrap();
+            let entry = entry.as_array().unwrap();
+            assert_eq!(messages(&entry[1..]), vec!["message"]);
+            times.push(entry[0].clone());
+        }
+
+        // Timestamps out of the range of `EventTime` fall back to integer seconds.
+        assert_eq!(
+            times,
+            vec![
+                rmpv::Value::Ext(0, vec![0x55, 0xec, 0xe6, 0xf8, 0, 0, 0, 42]),
+                rmpv::Value::from(-1),
+                rmpv::Value::from(i64::from(u32::MAX) + 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_acknowledged_packed_forward_messages() {
+        let addr = next_addr();
+        let (sink, _) = load_sink::<FluentSinkConfig>(&format!(
+            r#"
+            address = "{}"
+            tag = "vector.test"
+            compression = "gzip"
+            require_ack_response = true
+            "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(10, 3, Some(batch));
+        let pump = tokio::spawn(sink.run(events));
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut connection = Connection::new(MaybeTlsStream::Raw(stream));
+        let (tag, records, options) = read_message(&mut connection).await;
+        assert_eq!(tag, "vector.test");
+        assert_eq!(messages(&records), lines);
+
+        // The events are only delivered once the chunk is acknowledged.
+        assert!(receiver.try_recv().is_err());
+        let chunk = map_get(&options, "chunk").unwrap().clone();
+        connection
+            .write_value(&rmpv::Value::Map(vec![("ack".into(), chunk)]))
+            .await
+            .unwrap();
+
+        pump.await.unwrap().unwrap();
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn authenticates_with_shared_key() {
+        let addr = next_addr();
+        let (sink, _) = load_sink::<FluentSinkConfig>(&format!(
+            r#"
+            address = "{}"
+            tag = "vector.{{{{ message }}}}"
+            auth.shared_key = "secret"
+            auth.self_hostname = "client"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let (lines, events) = random_lines_with_stream(10, 1, None);
+        let pump = tokio::spawn(sink.run(events));
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut connection = Connection::new(MaybeTlsStream::Raw(stream));
+        connection
+            .write_value(&rmpv::Value::Array(vec![
+                "HELO".into(),
+                rmpv::Value::Map(vec![
+                    ("nonce".into(), "nonce".into()),
+                    ("auth".into(), "".into()),
+                    ("keepalive".into(), true.into()),
+                ]),
+            ]))
+            .await
+            .unwrap();
+
+        let ping = connection.read_value().await.unwrap();
+        let ping = ping.as_array().unwrap();
+        assert_eq!(ping[0].as_str(), Some("PING"));
+        assert_eq!(ping[1].as_str(), Some("client"));
+        let salt = ping[2].as_str().unwrap().to_owned();
+        assert_eq!(
+            ping[3].as_str(),
+            Some(hex_sha512(&[salt.as_bytes(), b"client", b"nonce", b"secret"]).as_str())
+        );
+
+        connection
+            .write_value(&rmpv::Value::Array(vec![
+                "PONG".into(),
+                true.into(),
+                "".into(),
+                "server".into(),
+                hex_sha512(&[salt.as_bytes(), b"server", b"nonce", b"secret"]).into(),
+            ]))
+            .await
+            .unwrap();
+
+        let (tag, records, _) = read_message(&mut connection).await;
+        assert_eq!(tag, format!("vector.{}", lines[0]));
+        assert_eq!(messages(&records), lines);
+
+        pump.await.unwrap().unwrap();
+    }
+}
@@ -561,6 +561,12 @@ mod tests {
         );
         assert_eq!(req.key, "key/date.log.gz".to_string());
 
+        let req = RequestWrapper::new(
+            buf.clone(),
+            request_settings(None, false, Compression::zstd_default()),
+        );
+        assert_eq!(req.key, "key/date.log.zst".to_string());
+
         let req = RequestWrapper::new(
             buf,
             request_settings(None, true, Compression::gzip_default()),
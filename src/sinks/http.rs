@@ -1,16 +1,19 @@
 use crate::{
+    codecs::{self, FramingEncoderConfig, NewlineDelimitedEncoderConfig, SerializerConfig},
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
     internal_events::{HttpEventEncoded, HttpEventMissingMessage},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{
+            build_codecs_encoder, encode_with_codecs, resolve_encoding, EncodingConfig,
+            EncodingConfiguration,
+        },
         http::{BatchedHttpSink, HttpSink, RequestConfig},
-        BatchConfig, BatchSettings, Buffer, Compression, TowerRequestConfig, UriSerde,
+        BatchConfig, BatchSettings, Buffer, Compression, Compressor, TowerRequestConfig, UriSerde,
     },
     tls::{TlsOptions, TlsSettings},
 };
-use flate2::write::GzEncoder;
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
@@ -46,7 +49,12 @@ pub struct HttpSinkConfig {
     pub headers: Option<IndexMap<String, String>>,
     #[serde(default)]
     pub compression: Compression,
-    pub encoding: EncodingConfig<Encoding>,
+    /// Optional when `serializer` is configured, in which case only its field
+    /// rules apply.
+    #[serde(default)]
+    pub encoding: Option<EncodingConfig<Encoding>>,
+    pub framing: Option<Box<dyn FramingEncoderConfig>>,
+    pub serializer: Option<Box<dyn SerializerConfig>>,
     #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
@@ -63,7 +71,9 @@ fn default_config(e: Encoding) -> HttpSinkConfig {
         headers: Default::default(),
         compression: Default::default(),
         batch: Default::default(),
-        encoding: e.into(),
+        encoding: Some(e.into()),
+        framing: Default::default(),
+        serializer: Default::default(),
         request: Default::default(),
         tls: Default::default(),
     }
@@ -146,8 +156,18 @@ impl SinkConfig for HttpSinkConfig {
             .request
             .tower
             .unwrap_with(&TowerRequestConfig::default());
+        let encoder = build_codecs_encoder(&config.framing, &config.serializer, || {
+            Box::new(NewlineDelimitedEncoderConfig::new())
+        })?
+        .map(|encoder| encoder.with_dropped(cx.dropped()));
+        let encoding =
+            resolve_encoding(config.encoding.clone(), &encoder, || Encoding::Text.into())?;
         let sink = BatchedHttpSink::new(
-            config,
+            HttpLogsSink {
+                config,
+                encoding,
+                encoder,
+            },
             Buffer::new(batch.size, Compression::None),
             request,
             batch.timeout,
@@ -168,14 +188,36 @@ impl SinkConfig for HttpSinkConfig {
     fn sink_type(&self) -> &'static str {
         "http"
     }
+
+    fn can_reroute_dropped(&self) -> bool {
+        self.serializer.is_some()
+    }
+}
+
+#[derive(Clone)]
+struct HttpLogsSink {
+    config: HttpSinkConfig,
+    /// The `encoding` of the config, defaulted when `serializer` has been
+    /// configured.
+    encoding: EncodingConfig<Encoding>,
+    /// Replaces `encoding.codec` when `serializer` has been configured.
+    encoder: Option<codecs::Encoder>,
 }
 
 #[async_trait::async_trait]
-impl HttpSink for HttpSinkConfig {
+impl HttpSink for HttpLogsSink {
     type Input = Vec<u8>;
     type Output = Vec<u8>;
 
     fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
+        if let Some(encoder) = &self.encoder {
+            let body = encode_with_codecs(event, &self.encoding, encoder)?.to_vec();
+            emit!(&HttpEventEncoded {
+                byte_size: body.len(),
+            });
+            return Some(body);
+        }
+
         self.encoding.apply_rules(&mut event);
         let event = event.into_log();
 
@@ -216,7 +258,8 @@ impl HttpSink for HttpSinkConfig {
     }
 
     async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
-        let method = match &self.method.clone().unwrap_or(HttpMethod::Post) {
+        let config = &self.config;
+        let method = match &config.method.clone().unwrap_or(HttpMethod::Post) {
             HttpMethod::Get => Method::GET,
             HttpMethod::Head => Method::HEAD,
             HttpMethod::Post => Method::POST,
@@ -226,42 +269,50 @@ impl HttpSink for HttpSinkConfig {
             HttpMethod::Trace => Method::TRACE,
             HttpMethod::Patch => Method::PATCH,
         };
-        let uri: Uri = self.uri.uri.clone();
-
-        let ct = match self.encoding.codec() {
-            Encoding::Text => "text/plain",
-            Encoding::Ndjson => "application/x-ndjson",
-            Encoding::Json => {
-                body.insert(0, b'[');
-                body.pop(); // remove trailing comma from last record
-                body.push(b']');
-                "application/json"
-            }
+        let uri: Uri = config.uri.uri.clone();
+
+        let mut builder = Request::builder().method(method).uri(uri);
+
+        let content_type = match &self.encoder {
+            Some(encoder) => encoder.content_type(),
+            None => match self.encoding.codec() {
+                Encoding::Text => "text/plain",
+                Encoding::Ndjson => "application/x-ndjson",
+                Encoding::Json => {
+                    body.insert(0, b'[');
+                    body.pop(); // remove trailing comma from last record
+                    body.push(b']');
+                    "application/json"
+                }
+            },
         };
+        // A content type configured in `request.headers` takes precedence.
+        if !config
+            .request
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("Content-Type"))
+        {
+            builder = builder.header("Content-Type", content_type);
+        }
 
-        let mut builder = Request::builder()
-            .method(method)
-            .uri(uri)
-            .header("Content-Type", ct);
-
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
+        if let Some(content_encoding) = config.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
 
-                let mut w = GzEncoder::new(Vec::new(), level);
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail");
-            }
-            Compression::None => {}
+            let mut compressor = Compressor::from(config.compression);
+            compressor
+                .write_all(&body)
+                .expect("Writing to Vec can't fail");
+            body = compressor.into_inner();
         }
 
-        for (header, value) in self.request.headers.iter() {
+        for (header, value) in config.request.headers.iter() {
             builder = builder.header(header.as_str(), value.as_str());
         }
 
         let mut request = builder.body(body).unwrap();
 
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = &config.auth {
             auth.apply(&mut request);
         }
 
@@ -335,21 +386,51 @@ mod tests {
         let encoding = EncodingConfig::from(Encoding::Text);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Text);
-        config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap();
+        let config = default_config(Encoding::Text);
+        let sink = HttpLogsSink {
+            config,
+            encoding,
+            encoder: None,
+        };
+        let bytes = sink.encode_event(event).unwrap();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
 
+    #[test]
+    fn http_encode_event_with_codecs() {
+        let config = default_config(Encoding::Text);
+        let mut encoding = EncodingConfig::from(Encoding::Text);
+        encoding.except_fields = Some(vec!["timestamp".into()]);
+        let encoder = build_codecs_encoder(
+            &None,
+            &Some(Box::new(codecs::LogfmtSerializerConfig::new())),
+            || Box::new(NewlineDelimitedEncoderConfig::new()),
+        )
+        .unwrap();
+        let sink = HttpLogsSink {
+            config,
+            encoding,
+            encoder,
+        };
+
+        let bytes = sink.encode_event(Event::from("hello world")).unwrap();
+
+        assert_eq!(bytes, Vec::from("message=\"hello world\"\n"));
+    }
+
     #[test]
     fn http_encode_event_json() {
         let encoding = EncodingConfig::from(Encoding::Ndjson);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Json);
-        config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap();
+        let config = default_config(Encoding::Json);
+        let sink = HttpLogsSink {
+            config,
+            encoding,
+            encoder: None,
+        };
+        let bytes = sink.encode_event(event).unwrap();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -363,6 +444,23 @@ mod tests {
         assert_eq!(output.message, "hello world".to_string());
     }
 
+    #[tokio::test]
+    async fn http_build_request_zstd() {
+        let mut config = default_config(Encoding::Text);
+        config.compression = Compression::zstd_default();
+        let sink = HttpLogsSink {
+            config,
+            encoding: EncodingConfig::from(Encoding::Text),
+            encoder: None,
+        };
+
+        let request = sink.build_request(b"hello world\n".to_vec()).await.unwrap();
+
+        assert_eq!(request.headers()["Content-Encoding"], "zstd");
+        let body = zstd::stream::decode_all(&request.body()[..]).unwrap();
+        assert_eq!(body, b"hello world\n");
+    }
+
     #[test]
     fn http_validates_normal_headers() {
         let config = r#"
@@ -484,6 +582,84 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn http_happy_path_with_codecs() {
+        run_sink(
+            r#"
+        serializer.codec = "json"
+    "#,
+            |parts| {
+                assert_eq!(Method::POST, parts.method);
+                assert_eq!(
+                    Some("application/x-ndjson"),
+                    parts
+                        .headers
+                        .get("Content-Type")
+                        .map(|v| v.to_str().unwrap())
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn http_json_content_type_without_newline_framing() {
+        run_sink(
+            r#"
+        framing.method = "bytes"
+        serializer.codec = "json"
+    "#,
+            |parts| {
+                assert_eq!(Method::POST, parts.method);
+                assert_eq!(
+                    Some("application/json"),
+                    parts
+                        .headers
+                        .get("Content-Type")
+                        .map(|v| v.to_str().unwrap())
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn http_content_type_from_request_headers() {
+        run_sink(
+            r#"
+        serializer.codec = "json"
+        [request.headers]
+        content-type = "application/json"
+    "#,
+            |parts| {
+                let content_types = parts
+                    .headers
+                    .get_all("Content-Type")
+                    .iter()
+                    .map(|v| v.to_str().unwrap())
+                    .collect::<Vec<_>>();
+                assert_eq!(content_types, vec!["application/json"]);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn http_encoding_optional_with_serializer() {
+        let config: HttpSinkConfig = toml::from_str(
+            r#"
+            uri = "http://127.0.0.1:9000/frames"
+            serializer.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_ok());
+
+        let config: HttpSinkConfig =
+            toml::from_str(r#"uri = "http://127.0.0.1:9000/frames""#).unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
     #[tokio::test]
     async fn retries_on_no_connection() {
         let num_lines = 10;
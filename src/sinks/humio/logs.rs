@@ -1,7 +1,9 @@
 use super::{host_key, Encoding};
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    sinks::splunk_hec::logs::HecSinkLogsConfig,
+    sinks::splunk_hec::{
+        acknowledgements::HecClientAcknowledgementsConfig, logs::HecSinkLogsConfig,
+    },
     sinks::util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
     sinks::{Healthcheck, VectorSink},
     template::Template,
@@ -92,6 +94,11 @@ impl HumioLogsConfig {
             batch: self.batch,
             request: self.request,
             tls: self.tls.clone(),
+            // Humio doesn't implement indexer acknowledgements.
+            acknowledgements: HecClientAcknowledgementsConfig {
+                indexer_acknowledgements_enabled: false,
+                ..Default::default()
+            },
         }
     }
 }
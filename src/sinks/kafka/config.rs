@@ -1,3 +1,4 @@
+use crate::codecs::{FramingEncoderConfig, SerializerConfig};
 use crate::config::{DataType, GenerateConfig, SinkConfig, SinkContext};
 use crate::kafka::{KafkaAuthConfig, KafkaCompression};
 use crate::serde::to_string;
@@ -17,7 +18,11 @@ pub(crate) struct KafkaSinkConfig {
     pub bootstrap_servers: String,
     pub topic: String,
     pub key_field: Option<String>,
-    pub encoding: EncodingConfig<StandardEncodings>,
+    /// Optional when `serializer` is configured, in which case only its field rules apply.
+    #[serde(default)]
+    pub encoding: Option<EncodingConfig<StandardEncodings>>,
+    pub framing: Option<Box<dyn FramingEncoderConfig>>,
+    pub serializer: Option<Box<dyn SerializerConfig>>,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
     pub batch: BatchConfig,
@@ -141,7 +146,9 @@ impl GenerateConfig for KafkaSinkConfig {
             bootstrap_servers: "10.14.22.123:9092,10.14.23.332:9092".to_owned(),
             topic: "topic-1234".to_owned(),
             key_field: Some("user_id".to_owned()),
-            encoding: StandardEncodings::Json.into(),
+            encoding: Some(StandardEncodings::Json.into()),
+            framing: None,
+            serializer: None,
             batch: Default::default(),
             compression: KafkaCompression::None,
             auth: Default::default(),
@@ -158,7 +165,7 @@ impl GenerateConfig for KafkaSinkConfig {
 #[typetag::serde(name = "kafka")]
 impl SinkConfig for KafkaSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone(), cx.acker())?;
+        let sink = KafkaSink::new(self.clone(), cx.acker(), cx.dropped())?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::Stream(Box::new(sink)), hc))
     }
@@ -170,6 +177,10 @@ impl SinkConfig for KafkaSinkConfig {
     fn sink_type(&self) -> &'static str {
         "kafka"
     }
+
+    fn can_reroute_dropped(&self) -> bool {
+        self.serializer.is_some()
+    }
 }
 
 #[cfg(test)]
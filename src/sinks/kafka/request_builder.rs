@@ -1,7 +1,10 @@
+use crate::codecs;
 use crate::event::{Event, Finalizable, Value};
 use crate::internal_events::KafkaHeaderExtractionFailed;
 use crate::sinks::kafka::service::{KafkaRequest, KafkaRequestMetadata};
-use crate::sinks::util::encoding::{Encoder, EncodingConfig, StandardEncodings};
+use crate::sinks::util::encoding::{
+    encode_with_codecs, Encoder, EncodingConfig, StandardEncodings,
+};
 use crate::template::Template;
 use bytes::Bytes;
 use rdkafka::message::OwnedHeaders;
@@ -12,6 +15,7 @@ pub struct KafkaRequestBuilder {
     pub headers_field: Option<String>,
     pub topic_template: Template,
     pub encoder: EncodingConfig<StandardEncodings>,
+    pub codecs_encoder: Option<codecs::Encoder>,
     pub log_schema: &'static LogSchema,
 }
 
@@ -25,8 +29,14 @@ impl KafkaRequestBuilder {
             headers: get_headers(&event, &self.headers_field),
             topic,
         };
-        let mut body = vec![];
-        self.encoder.encode_input(event, &mut body).ok()?;
+        let body = match &self.codecs_encoder {
+            Some(encoder) => encode_with_codecs(event, &self.encoder, encoder)?.to_vec(),
+            None => {
+                let mut body = vec![];
+                self.encoder.encode_input(event, &mut body).ok()?;
+                body
+            }
+        };
 
         Some(KafkaRequest { body, metadata })
     }
@@ -39,6 +49,7 @@ fn get_key(event: &Event, key_field: &Option<String>) -> Option<Bytes> {
             .tags()
             .and_then(|tags| tags.get(key_field))
             .map(|value| value.clone().into()),
+        Event::Trace(trace) => trace.get(key_field).map(|value| value.as_bytes()),
     })
 }
 
@@ -49,6 +60,7 @@ fn get_timestamp_millis(event: &Event, log_schema: &'static LogSchema) -> Option
             .and_then(|v| v.as_timestamp())
             .copied(),
         Event::Metric(metric) => metric.timestamp(),
+        Event::Trace(trace) => trace.start_timestamp().copied(),
     }
     .map(|ts| ts.timestamp_millis())
 }
@@ -86,9 +98,13 @@ fn get_headers(event: &Event, headers_field: &Option<String>) -> Option<OwnedHea
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::codecs::{
+        BytesEncoderConfig, EncodingConfig as CodecsConfig, LogfmtSerializerConfig,
+    };
     use bytes::Bytes;
     use rdkafka::message::Headers;
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, convert::TryFrom};
+    use vector_core::config::log_schema;
 
     #[test]
     fn kafka_get_headers() {
@@ -106,4 +122,30 @@ mod tests {
         assert_eq!(headers.get(1).unwrap().0, "b-key");
         assert_eq!(headers.get(1).unwrap().1, "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_encode_with_codecs() {
+        let encoder = CodecsConfig::new(
+            Box::new(BytesEncoderConfig::new()),
+            Box::new(LogfmtSerializerConfig::new()),
+        )
+        .build()
+        .unwrap();
+        let mut encoding = EncodingConfig::from(StandardEncodings::Text);
+        encoding.except_fields = Some(vec![log_schema().timestamp_key().into()]);
+        let request_builder = KafkaRequestBuilder {
+            key_field: None,
+            headers_field: None,
+            topic_template: Template::try_from("topic").unwrap(),
+            encoder: encoding,
+            codecs_encoder: Some(encoder),
+            log_schema: log_schema(),
+        };
+
+        let request = request_builder
+            .build_request(Event::from("hello world"))
+            .unwrap();
+
+        assert_eq!(request.body, b"message=\"hello world\"");
+    }
 }
@@ -1,11 +1,15 @@
 use super::config::KafkaRole;
 use super::config::KafkaSinkConfig;
+use crate::codecs::{self, BytesEncoderConfig};
+use crate::dropped::DroppedSender;
 use crate::event::Event;
 use crate::kafka::KafkaStatisticsContext;
 use crate::sinks::kafka::config::QUEUED_MIN_MESSAGES;
 use crate::sinks::kafka::request_builder::KafkaRequestBuilder;
 use crate::sinks::kafka::service::KafkaService;
-use crate::sinks::util::encoding::{EncodingConfig, StandardEncodings};
+use crate::sinks::util::encoding::{
+    build_codecs_encoder, resolve_encoding, EncodingConfig, StandardEncodings,
+};
 use crate::sinks::util::{builder::SinkBuilderExt, StreamSink};
 use crate::template::{Template, TemplateParseError};
 use async_trait::async_trait;
@@ -33,6 +37,7 @@ pub enum BuildError {
 
 pub struct KafkaSink {
     encoding: EncodingConfig<StandardEncodings>,
+    encoder: Option<codecs::Encoder>,
     acker: Acker,
     service: KafkaService,
     topic: Template,
@@ -50,13 +55,26 @@ pub fn create_producer(
 }
 
 impl KafkaSink {
-    pub(crate) fn new(config: KafkaSinkConfig, acker: Acker) -> crate::Result<Self> {
+    pub(crate) fn new(
+        config: KafkaSinkConfig,
+        acker: Acker,
+        dropped: Option<DroppedSender>,
+    ) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
+        // Every event is sent as its own Kafka message, hence no framing is
+        // needed by default.
+        let encoder = build_codecs_encoder(&config.framing, &config.serializer, || {
+            Box::new(BytesEncoderConfig::new())
+        })?
+        .map(|encoder| encoder.with_dropped(dropped));
+        let encoding =
+            resolve_encoding(config.encoding, &encoder, || StandardEncodings::Text.into())?;
 
         Ok(KafkaSink {
             headers_field: config.headers_field,
-            encoding: config.encoding,
+            encoding,
+            encoder,
             acker,
             service: KafkaService::new(producer),
             topic: Template::try_from(config.topic).context(TopicTemplate)?,
@@ -72,6 +90,7 @@ impl KafkaSink {
             headers_field: self.headers_field,
             topic_template: self.topic,
             encoder: self.encoding,
+            codecs_encoder: self.encoder,
             log_schema: log_schema(),
         };
         let sink = input
@@ -34,7 +34,9 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
-            encoding: EncodingConfig::from(StandardEncodings::Text),
+            encoding: Some(EncodingConfig::from(StandardEncodings::Text)),
+            framing: None,
+            serializer: None,
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -86,7 +88,9 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".to_string(),
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
-            encoding: StandardEncodings::Text.into(),
+            encoding: Some(StandardEncodings::Text.into()),
+            framing: None,
+            serializer: None,
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -102,7 +106,7 @@ mod integration_test {
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
         config.clone().to_rdkafka(KafkaRole::Producer)?;
         self::sink::healthcheck(config.clone()).await?;
-        KafkaSink::new(config, acker)
+        KafkaSink::new(config, acker, None)
     }
 
     #[tokio::test]
@@ -237,7 +241,9 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
-            encoding: EncodingConfig::from(StandardEncodings::Text),
+            encoding: Some(EncodingConfig::from(StandardEncodings::Text)),
+            framing: None,
+            serializer: None,
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
@@ -249,7 +255,7 @@ mod integration_test {
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
         println!("Topic name generated in test: {:?}", topic);
         let (acker, ack_counter) = Acker::new_for_testing();
-        let sink = Box::new(KafkaSink::new(config, acker).unwrap());
+        let sink = Box::new(KafkaSink::new(config, acker, None).unwrap());
 
         let num_events = 1000;
         let (batch, mut receiver) = BatchNotifier::new_with_receiver();
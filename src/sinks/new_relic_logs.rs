@@ -119,6 +119,8 @@ impl NewRelicLogsConfig {
     }
 
     fn create_config(&self) -> crate::Result<HttpSinkConfig> {
+        self.compression.ensure_none_or_gzip()?;
+
         let mut headers: IndexMap<String, String> = IndexMap::new();
         if let Some(license_key) = &self.license_key {
             headers.insert("X-License-Key".to_owned(), license_key.clone());
@@ -153,7 +155,9 @@ impl NewRelicLogsConfig {
             auth: None,
             headers: None,
             compression: self.compression,
-            encoding: EncodingConfig::<Encoding>::from(self.encoding.clone()).into_encoding(),
+            encoding: Some(EncodingConfig::<Encoding>::from(self.encoding.clone()).into_encoding()),
+            framing: None,
+            serializer: None,
             batch,
             request,
             tls: None,
@@ -209,7 +213,10 @@ mod tests {
             "https://log-api.newrelic.com/log/v1".to_string()
         );
         assert_eq!(http_config.method, Some(HttpMethod::Post));
-        assert_eq!(http_config.encoding.codec(), &Encoding::Json.into());
+        assert_eq!(
+            http_config.encoding.as_ref().unwrap().codec(),
+            &Encoding::Json.into()
+        );
         assert_eq!(http_config.batch.max_bytes, Some(MAX_PAYLOAD_SIZE));
         assert_eq!(http_config.request.tower.concurrency, Concurrency::None);
         assert_eq!(
@@ -240,7 +247,10 @@ mod tests {
             "https://log-api.eu.newrelic.com/log/v1".to_string()
         );
         assert_eq!(http_config.method, Some(HttpMethod::Post));
-        assert_eq!(http_config.encoding.codec(), &Encoding::Json.into());
+        assert_eq!(
+            http_config.encoding.as_ref().unwrap().codec(),
+            &Encoding::Json.into()
+        );
         assert_eq!(http_config.batch.max_bytes, Some(MAX_PAYLOAD_SIZE));
         assert_eq!(
             http_config.request.tower.concurrency,
@@ -278,7 +288,10 @@ mod tests {
             "https://log-api.eu.newrelic.com/log/v1".to_string()
         );
         assert_eq!(http_config.method, Some(HttpMethod::Post));
-        assert_eq!(http_config.encoding.codec(), &Encoding::Json.into());
+        assert_eq!(
+            http_config.encoding.as_ref().unwrap().codec(),
+            &Encoding::Json.into()
+        );
         assert_eq!(http_config.batch.max_bytes, Some(838860));
         assert_eq!(
             http_config.request.tower.concurrency,
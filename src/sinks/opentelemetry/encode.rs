@@ -0,0 +1,681 @@
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event, LogEvent, Value,
+    },
+    internal_events::OpenTelemetryUnsupportedEventDropped,
+    proto::opentelemetry::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{
+            any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric, number_data_point, summary_data_point::ValueAtQuantile, AggregationTemporality,
+            Gauge, Histogram, HistogramDataPoint, Metric as OtlpMetric, NumberDataPoint,
+            ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY,
+        RESOURCES_KEY, SCOPE_KEY, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY,
+        TRACE_ID_KEY,
+    },
+    sinks::util::{EncodedEvent, EncodedLength},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use prost::Message;
+use std::{collections::BTreeMap, convert::TryFrom};
+use vector_core::ByteSizeOf;
+
+/// A single event, encoded into the OTLP message for its resource.
+#[derive(Clone, Debug)]
+pub enum OtlpItem {
+    Logs(ResourceLogs),
+    Metrics(ResourceMetrics),
+}
+
+/// The OTLP signals, each exported with its own request.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Signal {
+    Logs,
+    Metrics,
+}
+
+impl OtlpItem {
+    pub const fn signal(&self) -> Signal {
+        match self {
+            Self::Logs(_) => Signal::Logs,
+            Self::Metrics(_) => Signal::Metrics,
+        }
+    }
+}
+
+impl EncodedLength for OtlpItem {
+    fn encoded_length(&self) -> usize {
+        match self {
+            Self::Logs(logs) => logs.encoded_len(),
+            Self::Metrics(metrics) => metrics.encoded_len(),
+        }
+    }
+}
+
+pub fn encode_event(mut event: Event) -> Option<EncodedEvent<OtlpItem>> {
+    let byte_size = event.size_of();
+    let finalizers = event.metadata_mut().take_finalizers();
+
+    let item = match event {
+        Event::Log(log) => OtlpItem::Logs(encode_log(log)),
+        Event::Metric(metric) => match encode_metric(metric) {
+            Some(metrics) => OtlpItem::Metrics(metrics),
+            None => {
+                emit!(&OpenTelemetryUnsupportedEventDropped { kind: "metric" });
+                return None;
+            }
+        },
+        Event::Trace(_) => {
+            emit!(&OpenTelemetryUnsupportedEventDropped { kind: "trace" });
+            return None;
+        }
+    };
+
+    Some(EncodedEvent {
+        item,
+        finalizers,
+        byte_size,
+    })
+}
+
+/// An export request for a single signal.
+#[derive(Debug)]
+pub enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+}
+
+/// Builds the export request of a batch of items of the given signal.
+///
+/// Consecutive items sharing the same resource and scope are merged so that
+/// the resource and scope are only sent once. Batches are partitioned by
+/// signal, so items of other signals are never expected here.
+pub fn build_request(signal: Signal, items: Vec<OtlpItem>) -> ExportRequest {
+    match signal {
+        Signal::Logs => {
+            let mut resource_logs = Vec::new();
+            for item in items {
+                if let OtlpItem::Logs(logs) = item {
+                    merge_logs(&mut resource_logs, logs);
+                }
+            }
+            ExportRequest::Logs(ExportLogsServiceRequest { resource_logs })
+        }
+        Signal::Metrics => {
+            let mut resource_metrics = Vec::new();
+            for item in items {
+                if let OtlpItem::Metrics(metrics) = item {
+                    merge_metrics(&mut resource_metrics, metrics);
+                }
+            }
+            ExportRequest::Metrics(ExportMetricsServiceRequest { resource_metrics })
+        }
+    }
+}
+
+fn merge_logs(merged: &mut Vec<ResourceLogs>, mut logs: ResourceLogs) {
+    if let Some(last) = merged.last_mut() {
+        if last.resource == logs.resource {
+            for scope_logs in logs.scope_logs.drain(..) {
+                match last.scope_logs.last_mut() {
+                    Some(last_scope) if last_scope.scope == scope_logs.scope => {
+                        last_scope.log_records.extend(scope_logs.log_records)
+                    }
+                    _ => last.scope_logs.push(scope_logs),
+                }
+            }
+            return;
+        }
+    }
+    merged.push(logs);
+}
+
+fn merge_metrics(merged: &mut Vec<ResourceMetrics>, mut metrics: ResourceMetrics) {
+    if let Some(last) = merged.last_mut() {
+        if last.resource == metrics.resource {
+            for scope_metrics in metrics.scope_metrics.drain(..) {
+                match last.scope_metrics.last_mut() {
+                    Some(last_scope) if last_scope.scope == scope_metrics.scope => {
+                        last_scope.metrics.extend(scope_metrics.metrics)
+                    }
+                    _ => last.scope_metrics.push(scope_metrics),
+                }
+            }
+            return;
+        }
+    }
+    merged.push(metrics);
+}
+
+fn encode_log(mut log: LogEvent) -> ResourceLogs {
+    let body = log
+        .remove(log_schema().message_key())
+        .map(value_into_any_value);
+    let time_unix_nano = log
+        .remove(log_schema().timestamp_key())
+        .and_then(|value| value.as_timestamp().map(timestamp_into_nanos))
+        .unwrap_or_else(|| timestamp_into_nanos(&Utc::now()));
+    log.remove(log_schema().source_type_key());
+
+    let observed_time_unix_nano = log
+        .remove(OBSERVED_TIMESTAMP_KEY)
+        .and_then(|value| value.as_timestamp().map(timestamp_into_nanos))
+        .unwrap_or(0);
+    let resource = match log.remove(RESOURCES_KEY) {
+        Some(Value::Map(attributes)) => Some(Resource {
+            attributes: map_into_key_values(attributes),
+            dropped_attributes_count: 0,
+        }),
+        _ => None,
+    };
+    let scope = match log.remove(SCOPE_KEY) {
+        Some(Value::Map(scope)) => Some(map_into_scope(scope)),
+        _ => None,
+    };
+    let severity_text = log
+        .remove(SEVERITY_TEXT_KEY)
+        .map(|value| value.to_string_lossy())
+        .unwrap_or_default();
+    let severity_number = log
+        .remove(SEVERITY_NUMBER_KEY)
+        .and_then(|value| integer(&value))
+        .and_then(|number| i32::try_from(number).ok())
+        .unwrap_or(0);
+    let trace_id = log
+        .remove(TRACE_ID_KEY)
+        .and_then(|value| hex::decode(value.as_bytes()).ok())
+        .unwrap_or_default();
+    let span_id = log
+        .remove(SPAN_ID_KEY)
+        .and_then(|value| hex::decode(value.as_bytes()).ok())
+        .unwrap_or_default();
+    let flags = log
+        .remove(FLAGS_KEY)
+        .and_then(|value| integer(&value))
+        .and_then(|flags| u32::try_from(flags).ok())
+        .unwrap_or(0);
+    let dropped_attributes_count = log
+        .remove(DROPPED_ATTRIBUTES_COUNT_KEY)
+        .and_then(|value| integer(&value))
+        .and_then(|count| u32::try_from(count).ok())
+        .unwrap_or(0);
+
+    let mut attributes = match log.remove(ATTRIBUTES_KEY) {
+        Some(Value::Map(attributes)) => attributes,
+        Some(value) => vec![(ATTRIBUTES_KEY.to_string(), value)]
+            .into_iter()
+            .collect(),
+        None => BTreeMap::new(),
+    };
+    // Whatever is left on the event has no OTLP counterpart, so it is sent
+    // along as attributes rather than being dropped.
+    let (fields, _) = log.into_parts();
+    for (key, value) in fields {
+        attributes.entry(key).or_insert(value);
+    }
+
+    ResourceLogs {
+        resource,
+        scope_logs: vec![ScopeLogs {
+            scope,
+            log_records: vec![LogRecord {
+                time_unix_nano,
+                observed_time_unix_nano,
+                severity_number,
+                severity_text,
+                body,
+                attributes: map_into_key_values(attributes),
+                dropped_attributes_count,
+                flags,
+                trace_id,
+                span_id,
+            }],
+            schema_url: String::new(),
+        }],
+        schema_url: String::new(),
+    }
+}
+
+fn encode_metric(metric: Metric) -> Option<ResourceMetrics> {
+    let (series, data, _) = metric.into_parts();
+
+    let name = match series.name.namespace {
+        Some(namespace) => format!("{}.{}", namespace, series.name.name),
+        None => series.name.name,
+    };
+    let (resource, scope, attributes) = split_tags(series.tags.unwrap_or_default());
+    let time_unix_nano = timestamp_into_nanos(&data.timestamp.unwrap_or_else(Utc::now));
+    let temporality = match data.kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+
+    let data = match data.value {
+        MetricValue::Counter { value } => metric::Data::Sum(Sum {
+            data_points: vec![number_point(attributes, time_unix_nano, value)],
+            aggregation_temporality: temporality,
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => match data.kind {
+            MetricKind::Absolute => metric::Data::Gauge(Gauge {
+                data_points: vec![number_point(attributes, time_unix_nano, value)],
+            }),
+            MetricKind::Incremental => metric::Data::Sum(Sum {
+                data_points: vec![number_point(attributes, time_unix_nano, value)],
+                aggregation_temporality: temporality,
+                is_monotonic: false,
+            }),
+        },
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let mut explicit_bounds = Vec::with_capacity(buckets.len());
+            let mut bucket_counts = Vec::with_capacity(buckets.len() + 1);
+            for bucket in &buckets {
+                if bucket.upper_limit.is_finite() {
+                    explicit_bounds.push(bucket.upper_limit);
+                }
+                bucket_counts.push(u64::from(bucket.count));
+            }
+            // OTLP always has one more bucket than bounds, the last one being
+            // unbounded. It's implicit in the count of Vector histograms, so
+            // it holds what the other buckets don't.
+            if bucket_counts.len() == explicit_bounds.len() {
+                let bounded = bucket_counts.iter().sum::<u64>();
+                bucket_counts.push(u64::from(count).saturating_sub(bounded));
+            }
+
+            metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes,
+                    start_time_unix_nano: 0,
+                    time_unix_nano,
+                    count: u64::from(count),
+                    sum,
+                    bucket_counts,
+                    explicit_bounds,
+                    flags: 0,
+                }],
+                aggregation_temporality: temporality,
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => metric::Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes,
+                start_time_unix_nano: 0,
+                time_unix_nano,
+                count: u64::from(count),
+                sum,
+                quantile_values: quantiles
+                    .iter()
+                    .map(|quantile| ValueAtQuantile {
+                        quantile: quantile.upper_limit,
+                        value: quantile.value,
+                    })
+                    .collect(),
+                flags: 0,
+            }],
+        }),
+        MetricValue::Set { .. } | MetricValue::Distribution { .. } => return None,
+    };
+
+    Some(ResourceMetrics {
+        resource,
+        scope_metrics: vec![ScopeMetrics {
+            scope,
+            metrics: vec![OtlpMetric {
+                name,
+                description: String::new(),
+                unit: String::new(),
+                data: Some(data),
+            }],
+            schema_url: String::new(),
+        }],
+        schema_url: String::new(),
+    })
+}
+
+fn number_point(attributes: Vec<KeyValue>, time_unix_nano: u64, value: f64) -> NumberDataPoint {
+    NumberDataPoint {
+        attributes,
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        flags: 0,
+    }
+}
+
+/// Splits metric tags back into resource attributes, scope and data point
+/// attributes, following the naming used by the `opentelemetry` source.
+fn split_tags(
+    tags: MetricTags,
+) -> (
+    Option<Resource>,
+    Option<InstrumentationScope>,
+    Vec<KeyValue>,
+) {
+    let resources_prefix = format!("{}.", RESOURCES_KEY);
+    let scope_attributes_prefix = format!("{}.{}.", SCOPE_KEY, ATTRIBUTES_KEY);
+    let scope_name = format!("{}.name", SCOPE_KEY);
+    let scope_version = format!("{}.version", SCOPE_KEY);
+
+    let mut resource = Vec::new();
+    let mut scope: Option<InstrumentationScope> = None;
+    let mut attributes = Vec::new();
+
+    for (key, value) in tags {
+        if let Some(key) = key.strip_prefix(&resources_prefix) {
+            resource.push(string_key_value(key.to_string(), value));
+        } else if let Some(key) = key.strip_prefix(&scope_attributes_prefix) {
+            scope
+                .get_or_insert_with(Default::default)
+                .attributes
+                .push(string_key_value(key.to_string(), value));
+        } else if key == scope_name {
+            scope.get_or_insert_with(Default::default).name = value;
+        } else if key == scope_version {
+            scope.get_or_insert_with(Default::default).version = value;
+        } else {
+            attributes.push(string_key_value(key, value));
+        }
+    }
+
+    let resource = (!resource.is_empty()).then(|| Resource {
+        attributes: resource,
+        dropped_attributes_count: 0,
+    });
+
+    (resource, scope, attributes)
+}
+
+fn map_into_scope(mut map: BTreeMap<String, Value>) -> InstrumentationScope {
+    InstrumentationScope {
+        name: map
+            .remove("name")
+            .map(|value| value.to_string_lossy())
+            .unwrap_or_default(),
+        version: map
+            .remove("version")
+            .map(|value| value.to_string_lossy())
+            .unwrap_or_default(),
+        attributes: match map.remove(ATTRIBUTES_KEY) {
+            Some(Value::Map(attributes)) => map_into_key_values(attributes),
+            _ => Vec::new(),
+        },
+        dropped_attributes_count: 0,
+    }
+}
+
+fn map_into_key_values(map: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    map.into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(value_into_any_value(value)),
+        })
+        .collect()
+}
+
+fn string_key_value(key: String, value: String) -> KeyValue {
+    KeyValue {
+        key,
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+fn value_into_any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => Some(any_value::Value::StringValue(
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )),
+        Value::Integer(value) => Some(any_value::Value::IntValue(value)),
+        Value::Float(value) => Some(any_value::Value::DoubleValue(value)),
+        Value::Boolean(value) => Some(any_value::Value::BoolValue(value)),
+        Value::Timestamp(timestamp) => Some(any_value::Value::StringValue(
+            timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        )),
+        Value::Map(map) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: map_into_key_values(map),
+        })),
+        Value::Array(values) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: values.into_iter().map(value_into_any_value).collect(),
+        })),
+        Value::Null => None,
+    };
+
+    AnyValue { value }
+}
+
+const fn integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn timestamp_into_nanos(timestamp: &DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_nanos()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::Bucket;
+    use chrono::TimeZone;
+
+    fn timestamp() -> DateTime<Utc> {
+        Utc.ymd(2021, 10, 1).and_hms(12, 0, 0)
+    }
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        })
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let mut log = LogEvent::from("hello world");
+        log.insert(log_schema().timestamp_key(), timestamp());
+        log.insert(log_schema().source_type_key(), "opentelemetry");
+        log.insert("resources.service", "checkout");
+        log.insert("scope.name", "tracer");
+        log.insert(SEVERITY_TEXT_KEY, "INFO");
+        log.insert(SEVERITY_NUMBER_KEY, 9);
+        log.insert(TRACE_ID_KEY, "4bf92f35");
+        log.insert("host", "localhost");
+
+        let item = encode_event(log.into()).unwrap().item;
+        let resource_logs = match item {
+            OtlpItem::Logs(logs) => logs,
+            OtlpItem::Metrics(_) => panic!("expected logs"),
+        };
+
+        assert_eq!(
+            resource_logs.resource.unwrap().attributes,
+            vec![string_key_value("service".into(), "checkout".into())]
+        );
+        let scope_logs = &resource_logs.scope_logs[0];
+        assert_eq!(scope_logs.scope.as_ref().unwrap().name, "tracer");
+
+        let record = &scope_logs.log_records[0];
+        assert_eq!(record.body, string_value("hello world"));
+        assert_eq!(record.time_unix_nano, 1_633_089_600_000_000_000);
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(record.trace_id, vec![0x4b, 0xf9, 0x2f, 0x35]);
+        assert!(record.span_id.is_empty());
+        assert_eq!(
+            record.attributes,
+            vec![string_key_value("host".into(), "localhost".into())]
+        );
+    }
+
+    #[test]
+    fn encodes_histograms() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1,
+                    },
+                    Bucket {
+                        upper_limit: 5.0,
+                        count: 2,
+                    },
+                ],
+                count: 3,
+                sum: 6.5,
+            },
+        )
+        .with_namespace(Some("http"))
+        .with_tags(Some(
+            vec![
+                ("method".to_string(), "GET".to_string()),
+                ("resources.service".to_string(), "checkout".to_string()),
+                ("scope.version".to_string(), "1.2.3".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+        .with_timestamp(Some(timestamp()));
+
+        let resource_metrics = match encode_event(metric.into()).unwrap().item {
+            OtlpItem::Metrics(metrics) => metrics,
+            OtlpItem::Logs(_) => panic!("expected metrics"),
+        };
+
+        assert_eq!(
+            resource_metrics.resource.unwrap().attributes,
+            vec![string_key_value("service".into(), "checkout".into())]
+        );
+        let scope_metrics = &resource_metrics.scope_metrics[0];
+        assert_eq!(scope_metrics.scope.as_ref().unwrap().version, "1.2.3");
+
+        let metric = &scope_metrics.metrics[0];
+        assert_eq!(metric.name, "http.latency");
+        assert_eq!(
+            metric.data,
+            Some(metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: vec![string_key_value("method".into(), "GET".into())],
+                    start_time_unix_nano: 0,
+                    time_unix_nano: 1_633_089_600_000_000_000,
+                    count: 3,
+                    sum: 6.5,
+                    bucket_counts: vec![1, 2, 0],
+                    explicit_bounds: vec![1.0, 5.0],
+                    flags: 0,
+                }],
+                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            }))
+        );
+    }
+
+    #[test]
+    fn encodes_histogram_overflow_bucket() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![Bucket {
+                    upper_limit: 1.0,
+                    count: 1,
+                }],
+                count: 4,
+                sum: 12.0,
+            },
+        );
+
+        let resource_metrics = match encode_event(metric.into()).unwrap().item {
+            OtlpItem::Metrics(metrics) => metrics,
+            OtlpItem::Logs(_) => panic!("expected metrics"),
+        };
+        match &resource_metrics.scope_metrics[0].metrics[0].data {
+            Some(metric::Data::Histogram(histogram)) => {
+                let point = &histogram.data_points[0];
+                // Observations over the last bound are in the unbounded bucket.
+                assert_eq!(point.bucket_counts, vec![1, 3]);
+                assert_eq!(point.bucket_counts.iter().sum::<u64>(), point.count);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+    }
+
+    #[test]
+    fn encodes_incremental_counters_as_delta_sums() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        )
+        .with_timestamp(Some(timestamp()));
+
+        let resource_metrics = match encode_event(metric.into()).unwrap().item {
+            OtlpItem::Metrics(metrics) => metrics,
+            OtlpItem::Logs(_) => panic!("expected metrics"),
+        };
+
+        assert!(resource_metrics.resource.is_none());
+        assert_eq!(
+            resource_metrics.scope_metrics[0].metrics[0].data,
+            Some(metric::Data::Sum(Sum {
+                data_points: vec![number_point(Vec::new(), 1_633_089_600_000_000_000, 2.0)],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+                is_monotonic: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn drops_unsupported_metrics() {
+        let metric = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: vec!["alice".into()].into_iter().collect(),
+            },
+        );
+
+        assert!(encode_event(metric.into()).is_none());
+    }
+
+    #[test]
+    fn merges_items_sharing_a_resource() {
+        let items = vec!["one", "two"]
+            .into_iter()
+            .map(|message| {
+                let mut log = LogEvent::from(message);
+                log.insert("resources.service", "checkout");
+                encode_event(log.into()).unwrap().item
+            })
+            .collect();
+
+        let logs = match build_request(Signal::Logs, items) {
+            ExportRequest::Logs(logs) => logs,
+            ExportRequest::Metrics(_) => panic!("expected logs"),
+        };
+
+        assert_eq!(logs.resource_logs.len(), 1);
+        assert_eq!(logs.resource_logs[0].scope_logs.len(), 1);
+        assert_eq!(logs.resource_logs[0].scope_logs[0].log_records.len(), 2);
+    }
+}
@@ -0,0 +1,293 @@
+mod encode;
+mod service;
+
+use crate::{
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    http::HttpClient,
+    proto::opentelemetry::collector::{logs::v1 as logs, metrics::v1 as metrics},
+    sinks::{
+        util::{
+            grpc::{new_client, with_default_scheme, HyperSvc},
+            BatchConfig, BatchSettings, PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer,
+            ServiceBuilderExt, TowerRequestConfig, VecBuffer,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use http::Uri;
+use serde::{Deserialize, Serialize};
+use service::{OpenTelemetryRetryLogic, OpenTelemetryService};
+use tower::ServiceBuilder;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpenTelemetrySinkConfig {
+    endpoint: String,
+    #[serde(default)]
+    protocol: Protocol,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    Grpc,
+    Http,
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        Self::Grpc
+    }
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpenTelemetrySinkConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpenTelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://127.0.0.1:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpenTelemetrySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let endpoint = with_default_scheme(&self.endpoint, tls.is_tls())?;
+
+        let service = match self.protocol {
+            Protocol::Grpc => {
+                let client = new_client(&tls)?;
+                OpenTelemetryService::Grpc {
+                    logs: logs::Client::new(HyperSvc::new(endpoint.clone(), client.clone())),
+                    metrics: metrics::Client::new(HyperSvc::new(endpoint, client)),
+                }
+            }
+            Protocol::Http => OpenTelemetryService::Http {
+                client: HttpClient::new(tls, cx.proxy())?,
+                logs_uri: http_uri(&endpoint, "logs")?,
+                metrics_uri: http_uri(&endpoint, "metrics")?,
+            },
+        };
+
+        let healthcheck = healthcheck(service.clone()).boxed();
+
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch = BatchSettings::default()
+            .events(1000)
+            .timeout(1)
+            .parse_config(self.batch)?;
+
+        let svc = ServiceBuilder::new()
+            .settings(request, OpenTelemetryRetryLogic)
+            .service(service);
+
+        // Batches are partitioned by signal so that logs and metrics are
+        // exported, and retried, with separate requests.
+        let buffer = PartitionBuffer::new(VecBuffer::new(batch.size));
+        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+            .sink_map_err(|error| error!(message = "Fatal OpenTelemetry sink error.", %error))
+            .with_flat_map(move |event| {
+                let event = encode::encode_event(event).map(|event| {
+                    event.map(|item| {
+                        let signal = item.signal();
+                        PartitionInnerBuffer::new(item, signal)
+                    })
+                });
+                stream::iter(event).map(Ok)
+            });
+
+        Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
+
+/// OTLP/HTTP exposes one path per signal below the configured endpoint.
+fn http_uri(endpoint: &Uri, signal: &str) -> crate::Result<Uri> {
+    let uri = format!(
+        "{}/v1/{}",
+        endpoint.to_string().trim_end_matches('/'),
+        signal
+    );
+    Ok(uri.parse::<Uri>()?)
+}
+
+/// OTLP has no dedicated health endpoint, but an empty export request is
+/// valid and cheap for the receiver to process.
+async fn healthcheck(service: OpenTelemetryService) -> crate::Result<()> {
+    service.export_logs(Default::default()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{Event, Metric, MetricKind, MetricValue},
+        proto::opentelemetry::collector::logs::v1::ExportLogsServiceRequest,
+        sinks::util::test::{build_test_server, build_test_server_generic},
+        test_util::{next_addr, random_lines_with_stream},
+    };
+    use bytes::Bytes;
+    use futures::channel::mpsc;
+    use http::request::Parts;
+    use prost::Message;
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    // one byte for the compression flag plus four bytes for the length
+    const GRPC_HEADER_SIZE: usize = 5;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpenTelemetrySinkConfig>();
+    }
+
+    #[test]
+    fn http_uris_are_relative_to_the_endpoint() {
+        let endpoint: Uri = "http://localhost:4318/otlp/".parse().unwrap();
+        assert_eq!(
+            http_uri(&endpoint, "logs").unwrap().to_string(),
+            "http://localhost:4318/otlp/v1/logs"
+        );
+    }
+
+    async fn received_records(
+        rx: mpsc::Receiver<(Parts, Bytes)>,
+        header_size: usize,
+        assert_parts: impl Fn(&Parts),
+    ) -> usize {
+        rx.map(|(parts, body)| {
+            assert_parts(&parts);
+            let request = ExportLogsServiceRequest::decode(body.slice(header_size..)).unwrap();
+            request
+                .resource_logs
+                .iter()
+                .flat_map(|logs| logs.scope_logs.iter())
+                .map(|scope| scope.log_records.len())
+                .sum::<usize>()
+        })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .sum()
+    }
+
+    #[tokio::test]
+    async fn delivers_logs_over_grpc() {
+        let address = next_addr();
+        let config: OpenTelemetrySinkConfig =
+            toml::from_str(&format!(r#"endpoint = "http://{}""#, address)).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server_generic(address, || {
+            hyper::Response::builder()
+                .header("grpc-status", "0")
+                .header("content-type", "application/grpc")
+                // An uncompressed, empty `ExportLogsServiceResponse`.
+                .body(hyper::Body::from(vec![0u8; GRPC_HEADER_SIZE]))
+                .unwrap()
+        });
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_, events) = random_lines_with_stream(8, 10, Some(batch));
+        sink.run(events).await.unwrap();
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        let records = received_records(rx, GRPC_HEADER_SIZE, |parts| {
+            assert_eq!(
+                parts.uri.path(),
+                "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+            );
+        })
+        .await;
+        assert_eq!(records, 10);
+    }
+
+    #[tokio::test]
+    async fn delivers_logs_over_http() {
+        let address = next_addr();
+        let config: OpenTelemetrySinkConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "http://{}"
+            protocol = "http"
+            "#,
+            address
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(address);
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_, events) = random_lines_with_stream(8, 10, Some(batch));
+        sink.run(events).await.unwrap();
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        let records = received_records(rx, 0, |parts| {
+            assert_eq!(parts.uri.path(), "/v1/logs");
+            assert_eq!(
+                parts.headers.get("content-type").unwrap(),
+                "application/x-protobuf"
+            );
+        })
+        .await;
+        assert_eq!(records, 10);
+    }
+
+    #[tokio::test]
+    async fn exports_logs_and_metrics_with_separate_requests() {
+        let address = next_addr();
+        let config: OpenTelemetrySinkConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "http://{}"
+            protocol = "http"
+            "#,
+            address
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(address);
+        tokio::spawn(server);
+
+        let events = vec![
+            Event::from("hello"),
+            Event::Metric(Metric::new(
+                "requests",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 1.0 },
+            )),
+            Event::from("world"),
+        ];
+        sink.run(stream::iter(events)).await.unwrap();
+        drop(trigger);
+
+        let mut paths = rx
+            .map(|(parts, _)| parts.uri.path().to_string())
+            .collect::<Vec<_>>()
+            .await;
+        paths.sort();
+        assert_eq!(paths, vec!["/v1/logs", "/v1/metrics"]);
+    }
+}
@@ -0,0 +1,156 @@
+use super::encode::{build_request, ExportRequest, OtlpItem, Signal};
+use crate::{
+    http::{HttpClient, HttpError},
+    proto::opentelemetry::collector::{
+        logs::v1::{self as logs, ExportLogsServiceRequest},
+        metrics::v1::{self as metrics, ExportMetricsServiceRequest},
+    },
+    sinks::util::{grpc::HyperSvc, retries::RetryLogic, PartitionInnerBuffer},
+};
+use futures::future::BoxFuture;
+use http::{header::CONTENT_TYPE, StatusCode, Uri};
+use hyper::Body;
+use prost::Message;
+use snafu::Snafu;
+use std::task::{Context, Poll};
+use tonic::IntoRequest;
+
+const CONTENT_TYPE_PROTOBUF: &str = "application/x-protobuf";
+
+#[derive(Clone)]
+pub enum OpenTelemetryService {
+    Grpc {
+        logs: logs::Client<HyperSvc>,
+        metrics: metrics::Client<HyperSvc>,
+    },
+    Http {
+        client: HttpClient,
+        logs_uri: Uri,
+        metrics_uri: Uri,
+    },
+}
+
+impl OpenTelemetryService {
+    pub async fn export_logs(&self, request: ExportLogsServiceRequest) -> Result<(), Error> {
+        match self {
+            Self::Grpc { logs, .. } => logs
+                .clone()
+                .export(request.into_request())
+                .await
+                .map(|_| ())
+                .map_err(|source| Error::GrpcRequest { source }),
+            Self::Http {
+                client, logs_uri, ..
+            } => send_http(client, logs_uri, request.encode_to_vec()).await,
+        }
+    }
+
+    pub async fn export_metrics(&self, request: ExportMetricsServiceRequest) -> Result<(), Error> {
+        match self {
+            Self::Grpc { metrics, .. } => metrics
+                .clone()
+                .export(request.into_request())
+                .await
+                .map(|_| ())
+                .map_err(|source| Error::GrpcRequest { source }),
+            Self::Http {
+                client,
+                metrics_uri,
+                ..
+            } => send_http(client, metrics_uri, request.encode_to_vec()).await,
+        }
+    }
+}
+
+async fn send_http(client: &HttpClient, uri: &Uri, body: Vec<u8>) -> Result<(), Error> {
+    let request = http::Request::post(uri)
+        .header(CONTENT_TYPE, CONTENT_TYPE_PROTOBUF)
+        .body(Body::from(body))
+        .map_err(|source| Error::BuildRequest { source })?;
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|source| Error::HttpRequest { source })?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(Error::HttpStatus { status })
+    }
+}
+
+impl tower::Service<PartitionInnerBuffer<Vec<OtlpItem>, Signal>> for OpenTelemetryService {
+    type Response = ();
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, batch: PartitionInnerBuffer<Vec<OtlpItem>, Signal>) -> Self::Future {
+        let service = self.clone();
+        let (items, signal) = batch.into_parts();
+
+        Box::pin(async move {
+            match build_request(signal, items) {
+                ExportRequest::Logs(request) => service.export_logs(request).await,
+                ExportRequest::Metrics(request) => service.export_metrics(request).await,
+            }
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Request failed: {}", source))]
+    GrpcRequest { source: tonic::Status },
+
+    #[snafu(display("Request failed: {}", source))]
+    HttpRequest { source: HttpError },
+
+    #[snafu(display("Failed to build request: {}", source))]
+    BuildRequest { source: http::Error },
+
+    #[snafu(display("Server responded with {}", status))]
+    HttpStatus { status: StatusCode },
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenTelemetryRetryLogic;
+
+impl RetryLogic for OpenTelemetryRetryLogic {
+    type Error = Error;
+    type Response = ();
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match error {
+            // Retryable codes as defined by the OTLP specification
+            //
+            // <https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/protocol/otlp.md#failures>
+            Error::GrpcRequest { source } => matches!(
+                source.code(),
+                Cancelled
+                    | DeadlineExceeded
+                    | ResourceExhausted
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+            ),
+            Error::HttpRequest { .. } => true,
+            Error::BuildRequest { .. } => false,
+            Error::HttpStatus { status } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+        }
+    }
+}
@@ -1,9 +1,16 @@
 #[cfg(unix)]
 use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
+    codecs::{
+        BytesEncoderConfig, FramingEncoderConfig, NewlineDelimitedEncoderConfig, SerializerConfig,
+    },
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     sinks::util::{
-        encode_log, encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig, Encoding,
+        encode_log,
+        encoding::{build_codecs_encoder, encode_with_codecs, resolve_encoding, EncodingConfig},
+        tcp::TcpSinkConfig,
+        udp::UdpSinkConfig,
+        Encoding,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -14,7 +21,11 @@ use serde::{Deserialize, Serialize};
 pub struct SocketSinkConfig {
     #[serde(flatten)]
     pub mode: Mode,
-    pub encoding: EncodingConfig<Encoding>,
+    /// Optional when `serializer` is configured, in which case only its field rules apply.
+    #[serde(default)]
+    pub encoding: Option<EncodingConfig<Encoding>>,
+    pub framing: Option<Box<dyn FramingEncoderConfig>>,
+    pub serializer: Option<Box<dyn SerializerConfig>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -43,7 +54,12 @@ impl GenerateConfig for SocketSinkConfig {
 
 impl SocketSinkConfig {
     pub const fn new(mode: Mode, encoding: EncodingConfig<Encoding>) -> Self {
-        SocketSinkConfig { mode, encoding }
+        SocketSinkConfig {
+            mode,
+            encoding: Some(encoding),
+            framing: None,
+            serializer: None,
+        }
     }
 
     pub fn make_basic_tcp_config(address: String) -> Self {
@@ -61,8 +77,16 @@ impl SinkConfig for SocketSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let encoding = self.encoding.clone();
-        let encode_event = move |event| encode_log(event, &encoding);
+        let encoder = build_codecs_encoder(&self.framing, &self.serializer, || match self.mode {
+            Mode::Udp(_) => Box::new(BytesEncoderConfig::new()),
+            _ => Box::new(NewlineDelimitedEncoderConfig::new()),
+        })?
+        .map(|encoder| encoder.with_dropped(cx.dropped()));
+        let encoding = resolve_encoding(self.encoding.clone(), &encoder, || Encoding::Text.into())?;
+        let encode_event = move |event| match &encoder {
+            Some(encoder) => encode_with_codecs(event, &encoding, encoder),
+            None => encode_log(event, &encoding),
+        };
         match &self.mode {
             Mode::Tcp(config) => config.build(cx, encode_event),
             Mode::Udp(config) => config.build(cx, encode_event),
@@ -78,6 +102,10 @@ impl SinkConfig for SocketSinkConfig {
     fn sink_type(&self) -> &'static str {
         "socket"
     }
+
+    fn can_reroute_dropped(&self) -> bool {
+        self.serializer.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -111,7 +139,9 @@ mod test {
 
         let config = SocketSinkConfig {
             mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: Some(Encoding::Json.into()),
+            framing: None,
+            serializer: None,
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -153,7 +183,9 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: Some(Encoding::Json.into()),
+            framing: None,
+            serializer: None,
         };
 
         let context = SinkContext::new_test();
@@ -176,6 +208,63 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_stream_with_codecs() {
+        trace_init();
+
+        let addr = next_addr();
+        let config: SocketSinkConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            mode = "tcp"
+            encoding.codec = "text"
+            encoding.except_fields = ["timestamp"]
+            serializer.codec = "logfmt"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let mut receiver = CountReceiver::receive_lines(addr);
+
+        let (lines, events) = random_lines_with_stream(10, 100, None);
+        sink.run(events).await.unwrap();
+
+        // Wait for output to connect
+        receiver.connected().await;
+
+        let output = receiver.await;
+        assert_eq!(lines.len(), output.len());
+        for (source, received) in lines.iter().zip(output) {
+            assert_eq!(format!("message={}", source), received);
+        }
+    }
+
+    #[tokio::test]
+    async fn encoding_optional_with_serializer() {
+        let config: SocketSinkConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:9000"
+            mode = "udp"
+            serializer.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_ok());
+
+        let config: SocketSinkConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:9000"
+            mode = "udp"
+            "#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
     // This is a test that checks that we properly receive all events in the
     // case of a proper server side write side shutdown.
     //
@@ -224,7 +313,9 @@ mod test {
                 }),
                 None,
             )),
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -340,7 +431,9 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         };
 
         let context = SinkContext::new_test();
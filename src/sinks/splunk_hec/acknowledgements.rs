@@ -0,0 +1,231 @@
+//! Indexer acknowledgements, with which Splunk confirms that the events of a request were
+//! indexed. Responses to requests sent on a channel carry an `ackId`, whose status is then
+//! polled on the `/services/collector/ack` endpoint until the indexer acknowledges it.
+
+use crate::{
+    event::EventStatus,
+    http::HttpClient,
+    sinks::util::sink::{ServiceLogic, StdServiceLogic},
+};
+use bytes::Bytes;
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::oneshot, time};
+
+pub const CHANNEL_HEADER: &str = "X-Splunk-Request-Channel";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HecClientAcknowledgementsConfig {
+    pub indexer_acknowledgements_enabled: bool,
+    pub query_interval_secs: u64,
+    pub retry_limit: u8,
+}
+
+impl Default for HecClientAcknowledgementsConfig {
+    fn default() -> Self {
+        Self {
+            indexer_acknowledgements_enabled: true,
+            query_interval_secs: 10,
+            retry_limit: 30,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum AckQueryError {
+    #[snafu(display("Unexpected status: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+}
+
+#[derive(Serialize)]
+struct HecAckQuery<'a> {
+    acks: &'a [u64],
+}
+
+#[derive(Deserialize)]
+struct HecAckQueryResponse {
+    acks: HashMap<u64, bool>,
+}
+
+#[derive(Deserialize)]
+struct HecResponse {
+    #[serde(rename = "ackId")]
+    ack_id: Option<u64>,
+}
+
+struct PendingAck {
+    queries: u8,
+    tx: oneshot::Sender<bool>,
+}
+
+/// Polls the status of the `ackId`s received on a channel.
+pub struct HecAckClient {
+    uri: Uri,
+    token: String,
+    channel: String,
+    client: HttpClient,
+    retry_limit: u8,
+    pending: Mutex<HashMap<u64, PendingAck>>,
+}
+
+impl HecAckClient {
+    /// Creates the client, polling until it's dropped.
+    pub fn spawn(
+        uri: Uri,
+        token: String,
+        channel: String,
+        client: HttpClient,
+        config: &HecClientAcknowledgementsConfig,
+    ) -> Arc<Self> {
+        let ack_client = Arc::new(Self {
+            uri,
+            token,
+            channel,
+            client,
+            retry_limit: config.retry_limit,
+            pending: Mutex::new(HashMap::new()),
+        });
+
+        let weak = Arc::downgrade(&ack_client);
+        let period = Duration::from_secs(config.query_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut interval = time::interval_at(time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                match weak.upgrade() {
+                    Some(ack_client) => ack_client.query().await,
+                    None => break,
+                }
+            }
+        });
+
+        ack_client
+    }
+
+    /// Waits for the indexer to acknowledge `ack_id`, returning `false` if it wasn't
+    /// acknowledged within the retry limit.
+    pub async fn wait(&self, ack_id: u64) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .expect("poisoned lock")
+            .insert(ack_id, PendingAck { queries: 0, tx });
+        rx.await.unwrap_or(false)
+    }
+
+    async fn query(&self) {
+        let ack_ids = self
+            .pending
+            .lock()
+            .expect("poisoned lock")
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        if ack_ids.is_empty() {
+            return;
+        }
+
+        let acks = self.send_query(&ack_ids).await.unwrap_or_else(|error| {
+            warn!(message = "Failed to query indexer acknowledgements.", %error);
+            HashMap::new()
+        });
+
+        let mut pending = self.pending.lock().expect("poisoned lock");
+        for ack_id in ack_ids {
+            if acks.get(&ack_id).copied().unwrap_or(false) {
+                if let Some(ack) = pending.remove(&ack_id) {
+                    let _ = ack.tx.send(true);
+                }
+            } else if let Some(ack) = pending.get_mut(&ack_id) {
+                ack.queries = ack.queries.saturating_add(1);
+                if ack.queries >= self.retry_limit {
+                    if let Some(ack) = pending.remove(&ack_id) {
+                        let _ = ack.tx.send(false);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send_query(&self, ack_ids: &[u64]) -> crate::Result<HashMap<u64, bool>> {
+        let body = serde_json::to_vec(&HecAckQuery { acks: ack_ids })?;
+        let request = Request::post(self.uri.clone())
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Splunk {}", self.token))
+            .header(CHANNEL_HEADER, self.channel.as_str())
+            .body(Body::from(body))?;
+
+        let response = self.client.send(request).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AckQueryError::UnexpectedStatus { status }.into());
+        }
+
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        let response = serde_json::from_slice::<HecAckQueryResponse>(&body)?;
+        Ok(response.acks)
+    }
+}
+
+/// Resolves the status of requests by waiting for their indexer acknowledgement, if the
+/// response carries an `ackId`. Otherwise the response status alone decides.
+#[derive(Clone, Default)]
+pub struct HecServiceLogic {
+    ack_client: Option<Arc<HecAckClient>>,
+}
+
+impl HecServiceLogic {
+    pub fn new(ack_client: Option<Arc<HecAckClient>>) -> Self {
+        Self { ack_client }
+    }
+}
+
+impl ServiceLogic for HecServiceLogic {
+    type Response = http::Response<Bytes>;
+
+    fn result_status(&self, result: crate::Result<Self::Response>) -> EventStatus {
+        StdServiceLogic::default().result_status(result)
+    }
+
+    fn resolve_status(
+        &self,
+        result: crate::Result<Self::Response>,
+    ) -> BoxFuture<'static, EventStatus> {
+        let ack_id = match (&self.ack_client, &result) {
+            (Some(_), Ok(response)) if response.status().is_success() => {
+                serde_json::from_slice::<HecResponse>(response.body())
+                    .ok()
+                    .and_then(|response| response.ack_id)
+            }
+            _ => None,
+        };
+
+        match (&self.ack_client, ack_id) {
+            (Some(ack_client), Some(ack_id)) => {
+                let ack_client = Arc::clone(ack_client);
+                async move {
+                    if ack_client.wait(ack_id).await {
+                        EventStatus::Delivered
+                    } else {
+                        warn!(message = "Events were not acknowledged by the indexer.", %ack_id);
+                        EventStatus::Errored
+                    }
+                }
+                .boxed()
+            }
+            _ => future::ready(self.result_status(result)).boxed(),
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use super::acknowledgements::{
+    HecAckClient, HecClientAcknowledgementsConfig, HecServiceLogic, CHANNEL_HEADER,
+};
 use crate::{
     buffers::Acker,
     config::ProxyConfig,
+    event::Event,
     http::HttpClient,
     sinks,
     sinks::util::buffer::Compression,
-    sinks::util::http::BatchedHttpSink,
-    sinks::util::http::HttpSink,
+    sinks::util::http::{BatchedHttpSink, HttpRetryLogic, HttpSink},
     sinks::util::service::TowerRequestConfig,
     sinks::util::{BatchConfig, BatchSettings, Buffer},
     sinks::UriParseError,
@@ -13,10 +16,11 @@ use crate::{
     tls::{TlsOptions, TlsSettings},
 };
 use futures::{FutureExt, SinkExt};
-use http::{Request, StatusCode, Uri};
+use http::{HeaderValue, Request, StatusCode, Uri};
 use hyper::Body;
 use snafu::{ResultExt, Snafu};
 use std::convert::TryFrom;
+use uuid::Uuid;
 
 #[derive(Debug, Snafu)]
 enum HealthcheckError {
@@ -40,6 +44,7 @@ pub fn build_sink<T>(
     batch_config: BatchConfig,
     compression: Compression,
     acker: Acker,
+    acknowledgements: &HecClientAcknowledgementsConfig,
     endpoint: &str,
     token: &str,
 ) -> crate::Result<(VectorSink, Healthcheck)>
@@ -47,6 +52,7 @@ where
     T: HttpSink<Input = Vec<u8>, Output = Vec<u8>>,
 {
     validate_host(endpoint)?;
+    compression.ensure_none_or_gzip()?;
 
     let batch_settings = BatchSettings::default()
         .bytes(1_000_000)
@@ -56,13 +62,31 @@ where
     let tls_settings = TlsSettings::from_options(tls_options)?;
     let client = HttpClient::new(tls_settings, proxy_config)?;
 
-    let sink = BatchedHttpSink::new(
-        sink,
+    // Indexer acknowledgements are tracked per channel, so requests are all sent on one.
+    let (channel, ack_client) = if acknowledgements.indexer_acknowledgements_enabled {
+        let channel = Uuid::new_v4().to_string();
+        let uri = build_uri(endpoint, "/services/collector/ack").context(UriParseError)?;
+        let ack_client = HecAckClient::spawn(
+            uri,
+            token.to_string(),
+            channel.clone(),
+            client.clone(),
+            acknowledgements,
+        );
+        (Some(channel), Some(ack_client))
+    } else {
+        (None, None)
+    };
+
+    let sink = BatchedHttpSink::with_logic(
+        ChannelSink { sink, channel },
         Buffer::new(batch_settings.size, compression),
+        HttpRetryLogic,
         request_settings,
         batch_settings.timeout,
         client.clone(),
         acker,
+        HecServiceLogic::new(ack_client),
     )
     .sink_map_err(|error| error!(message = "Fatal splunk_hec sink error.", %error));
 
@@ -71,6 +95,32 @@ where
     Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
 }
 
+/// Sends the requests of the wrapped sink on a channel, if one is set.
+struct ChannelSink<T> {
+    sink: T,
+    channel: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl<T: HttpSink> HttpSink for ChannelSink<T> {
+    type Input = T::Input;
+    type Output = T::Output;
+
+    fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        self.sink.encode_event(event)
+    }
+
+    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Vec<u8>>> {
+        let mut request = self.sink.build_request(events).await?;
+        if let Some(channel) = &self.channel {
+            request
+                .headers_mut()
+                .insert(CHANNEL_HEADER, HeaderValue::from_str(channel)?);
+        }
+        Ok(request)
+    }
+}
+
 pub async fn build_request(
     endpoint: &str,
     token: &str,
@@ -124,10 +174,9 @@ fn validate_host(host: &str) -> crate::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::event::Event;
-    use http::HeaderValue;
+    use crate::event::{BatchNotifier, BatchStatus};
     use std::path::PathBuf;
-    use wiremock::matchers::{body_string, header, method, path};
+    use wiremock::matchers::{body_string, header, header_exists, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
@@ -231,6 +280,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             &mock_server.uri(),
             "token",
         )
@@ -242,6 +292,79 @@ mod tests {
         sink.flush().await.unwrap();
     }
 
+    async fn send_with_indexer_acknowledgements(acknowledged: bool) -> BatchStatus {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/stub-path"))
+            .and(header_exists(CHANNEL_HEADER))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"text":"Success","code":0,"ackId":0}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/services/collector/ack"))
+            .and(header("Authorization", "Splunk token"))
+            .and(header_exists(CHANNEL_HEADER))
+            .and(body_string(r#"{"acks":[0]}"#))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(format!(r#"{{"acks":{{"0":{}}}}}"#, acknowledged)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let (sink, _) = build_sink(
+            StubSink {
+                endpoint: mock_server.uri(),
+            },
+            &TowerRequestConfig::default(),
+            &None,
+            &ProxyConfig::default(),
+            BatchConfig::default(),
+            Compression::None,
+            Acker::Null,
+            &HecClientAcknowledgementsConfig {
+                indexer_acknowledgements_enabled: true,
+                query_interval_secs: 1,
+                retry_limit: 1,
+            },
+            &mock_server.uri(),
+            "token",
+        )
+        .unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut sink = sink.into_sink();
+
+        sink.send(Event::from("test event").with_batch_notifier(&batch))
+            .await
+            .unwrap();
+        drop(batch);
+        sink.flush().await.unwrap();
+
+        receiver.await
+    }
+
+    #[tokio::test]
+    async fn test_build_sink_indexer_acknowledgement_delivers_events() {
+        assert_eq!(
+            send_with_indexer_acknowledgements(true).await,
+            BatchStatus::Delivered
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_sink_missing_indexer_acknowledgement_errors_events() {
+        assert_eq!(
+            send_with_indexer_acknowledgements(false).await,
+            BatchStatus::Errored
+        );
+    }
+
     #[tokio::test]
     async fn test_build_sink_healthcheck_200_response_returns_ok() {
         let mock_server = MockServer::start().await;
@@ -261,6 +384,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             &mock_server.uri(),
             "token",
         )
@@ -288,6 +412,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             &mock_server.uri(),
             "token",
         )
@@ -318,6 +443,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             &mock_server.uri(),
             "token",
         )
@@ -348,6 +474,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             &mock_server.uri(),
             "token",
         )
@@ -371,6 +498,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             non_listening_uri,
             "token",
         )
@@ -393,6 +521,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             invalid_uri,
             "token",
         )
@@ -414,6 +543,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             invalid_uri,
             "token",
         )
@@ -445,6 +575,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             "http://localhost:36448",
             "token",
         )
@@ -476,6 +607,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             "http://localhost:36448",
             "token",
         )
@@ -504,6 +636,7 @@ mod tests {
             BatchConfig::default(),
             Compression::None,
             Acker::Null,
+            &HecClientAcknowledgementsConfig::default(),
             "http://localhost:36448",
             "token",
         )
@@ -2,7 +2,7 @@ use crate::{
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::{Event, LogEvent, Value},
     internal_events::{SplunkEventEncodeError, SplunkEventSent},
-    sinks::splunk_hec::conn,
+    sinks::splunk_hec::{acknowledgements::HecClientAcknowledgementsConfig, conn},
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
         http::HttpSink,
@@ -38,6 +38,8 @@ pub struct HecSinkLogsConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
     pub tls: Option<TlsOptions>,
+    #[serde(default)]
+    pub acknowledgements: HecClientAcknowledgementsConfig,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Derivative)]
@@ -74,6 +76,7 @@ impl GenerateConfig for HecSinkLogsConfig {
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             tls: None,
+            acknowledgements: HecClientAcknowledgementsConfig::default(),
         })
         .unwrap()
     }
@@ -91,6 +94,7 @@ impl SinkConfig for HecSinkLogsConfig {
             self.batch,
             self.compression,
             cx.acker(),
+            &self.acknowledgements,
             &self.endpoint,
             &self.token,
         )
@@ -646,6 +650,7 @@ mod integration_tests {
             },
             request: TowerRequestConfig::default(),
             tls: None,
+            acknowledgements: Default::default(),
         }
     }
 }
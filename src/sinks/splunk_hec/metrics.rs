@@ -3,7 +3,7 @@ use crate::{
     event::{Event, Metric, MetricValue},
     internal_events::SplunkInvalidMetricReceived,
     internal_events::{SplunkEventEncodeError, SplunkEventSent},
-    sinks::splunk_hec::conn,
+    sinks::splunk_hec::{acknowledgements::HecClientAcknowledgementsConfig, conn},
     sinks::util::{encode_namespace, http::HttpSink, BatchConfig, Compression, TowerRequestConfig},
     sinks::{Healthcheck, VectorSink},
     template::Template,
@@ -34,6 +34,8 @@ pub struct HecSinkMetricsConfig {
     #[serde(default)]
     pub request: TowerRequestConfig,
     pub tls: Option<TlsOptions>,
+    #[serde(default)]
+    pub acknowledgements: HecClientAcknowledgementsConfig,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -106,6 +108,7 @@ impl GenerateConfig for HecSinkMetricsConfig {
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             tls: None,
+            acknowledgements: HecClientAcknowledgementsConfig::default(),
         })
         .unwrap()
     }
@@ -123,6 +126,7 @@ impl SinkConfig for HecSinkMetricsConfig {
             self.batch,
             self.compression,
             cx.acker(),
+            &self.acknowledgements,
             &self.endpoint,
             &self.token,
         )
@@ -705,6 +709,7 @@ mod integration_tests {
             },
             request: TowerRequestConfig::default(),
             tls: None,
+            acknowledgements: Default::default(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::{event::EventRef, internal_events::TemplateRenderingFailed, template::Template};
 
+pub mod acknowledgements;
 mod conn;
 pub mod logs;
 pub mod metrics;
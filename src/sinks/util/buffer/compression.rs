@@ -7,12 +7,39 @@ pub const GZIP_FAST: u32 = 1;
 pub const GZIP_DEFAULT: u32 = 6;
 pub const GZIP_BEST: u32 = 9;
 
+#[cfg(feature = "sinks-utils-compression")]
+pub const ZSTD_FAST: i32 = 1;
+#[cfg(feature = "sinks-utils-compression")]
+pub const ZSTD_DEFAULT: i32 = 3;
+#[cfg(feature = "sinks-utils-compression")]
+pub const ZSTD_BEST: i32 = 21;
+
+/// The algorithms other than `none` and `gzip` require the
+/// `sinks-utils-compression` feature, which the sinks supporting them enable.
+#[cfg(feature = "sinks-utils-compression")]
+const ALGORITHMS: &[&str] = &["none", "gzip", "zstd", "snappy", "lz4"];
+#[cfg(feature = "sinks-utils-compression")]
+const EXPECTED_ALGORITHMS: &str = r#""none", "gzip", "zstd", "snappy" or "lz4""#;
+#[cfg(not(feature = "sinks-utils-compression"))]
+const ALGORITHMS: &[&str] = &["none", "gzip"];
+#[cfg(not(feature = "sinks-utils-compression"))]
+const EXPECTED_ALGORITHMS: &str = r#""none" or "gzip""#;
+
 #[derive(Debug, Derivative, Copy, Clone, Eq, PartialEq)]
 #[derivative(Default)]
 pub enum Compression {
     #[derivative(Default)]
     None,
     Gzip(flate2::Compression),
+    #[cfg(feature = "sinks-utils-compression")]
+    Zstd(i32),
+    /// Snappy in its framing format, which unlike the raw format can be
+    /// streamed. Sent with the `x-snappy-framed` content encoding.
+    #[cfg(feature = "sinks-utils-compression")]
+    Snappy,
+    /// LZ4 in its frame format.
+    #[cfg(feature = "sinks-utils-compression")]
+    Lz4,
 }
 
 impl Compression {
@@ -23,10 +50,51 @@ impl Compression {
         Compression::Gzip(flate2::Compression::new(6))
     }
 
+    #[cfg(feature = "sinks-utils-compression")]
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(ZSTD_DEFAULT)
+    }
+
     pub const fn content_encoding(&self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Zstd(_) => Some("zstd"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Snappy => Some("x-snappy-framed"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Lz4 => Some("lz4"),
+        }
+    }
+
+    pub const fn algorithm(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip(_) => "gzip",
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Zstd(_) => "zstd",
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Snappy => "snappy",
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Lz4 => "lz4",
+        }
+    }
+
+    /// Rejects the algorithms other than `none` and `gzip`.
+    ///
+    /// Only the object storage, `file` and `http` sinks support `zstd`, `snappy` and `lz4`, the
+    /// services of the other sinks don't accept them as `Content-Encoding`, and rusoto can't
+    /// apply them for the AWS sinks.
+    pub fn ensure_none_or_gzip(&self) -> crate::Result<()> {
+        match self {
+            Self::None | Self::Gzip(_) => Ok(()),
+            #[cfg(feature = "sinks-utils-compression")]
+            _ => Err(format!(
+                "Compression algorithm `{}` is not supported by this sink, only `none` and `gzip` are.",
+                self.algorithm()
+            )
+            .into()),
         }
     }
 
@@ -34,6 +102,12 @@ impl Compression {
         match self {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Zstd(_) => "log.zst",
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Snappy => "log.snappy",
+            #[cfg(feature = "sinks-utils-compression")]
+            Self::Lz4 => "log.lz4",
         }
     }
 }
@@ -42,6 +116,12 @@ impl fmt::Display for Compression {
         match *self {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.level()),
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Zstd(level) => write!(f, "zstd({})", level),
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Snappy => write!(f, "snappy"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Lz4 => write!(f, "lz4"),
         }
     }
 }
@@ -50,10 +130,16 @@ impl fmt::Display for Compression {
 impl From<Compression> for rusoto_core::encoding::ContentEncoding {
     fn from(compression: Compression) -> Self {
         match compression {
-            Compression::None => rusoto_core::encoding::ContentEncoding::Identity,
             Compression::Gzip(level) => {
                 rusoto_core::encoding::ContentEncoding::Gzip(None, level.level())
             }
+            // Rusoto can only compress with gzip itself, the AWS sinks reject
+            // the other algorithms with `ensure_none_or_gzip`.
+            Compression::None => rusoto_core::encoding::ContentEncoding::Identity,
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Zstd(_) | Compression::Snappy | Compression::Lz4 => {
+                rusoto_core::encoding::ContentEncoding::Identity
+            }
         }
     }
 }
@@ -79,9 +165,15 @@ impl<'de> de::Deserialize<'de> for Compression {
                 match s {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
+                    #[cfg(feature = "sinks-utils-compression")]
+                    "zstd" => Ok(Compression::zstd_default()),
+                    #[cfg(feature = "sinks-utils-compression")]
+                    "snappy" => Ok(Compression::Snappy),
+                    #[cfg(feature = "sinks-utils-compression")]
+                    "lz4" => Ok(Compression::Lz4),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip""#,
+                        &EXPECTED_ALGORITHMS,
                     )),
                 }
             }
@@ -105,30 +197,10 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
+                            // The valid levels depend on the algorithm, which
+                            // isn't necessarily known yet.
                             level = Some(match map.next_value::<Value>()? {
-                                Value::Number(level) => match level.as_u64() {
-                                    Some(value) if value <= 9 => {
-                                        flate2::Compression::new(value as u32)
-                                    }
-                                    Some(_) | None => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Other(&level.to_string()),
-                                            &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                                        ))
-                                    }
-                                },
-                                Value::String(level) => match level.as_str() {
-                                    "none" => flate2::Compression::none(),
-                                    "fast" => flate2::Compression::fast(),
-                                    "default" => flate2::Compression::default(),
-                                    "best" => flate2::Compression::best(),
-                                    level => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Str(level),
-                                            &r#""none", "fast", "best" or "default""#,
-                                        ))
-                                    }
-                                },
+                                value @ Value::Number(_) | value @ Value::String(_) => value,
                                 value => {
                                     return Err(de::Error::invalid_type(
                                         de::Unexpected::Other(&value.to_string()),
@@ -142,14 +214,21 @@ impl<'de> de::Deserialize<'de> for Compression {
                 }
 
                 match algorithm.ok_or_else(|| de::Error::missing_field("algorithm"))? {
-                    "none" => match level {
-                        Some(_) => Err(de::Error::unknown_field("level", &[])),
-                        None => Ok(Compression::None),
-                    },
-                    "gzip" => Ok(Compression::Gzip(
-                        level.unwrap_or_else(flate2::Compression::default),
-                    )),
-                    algorithm => Err(de::Error::unknown_variant(algorithm, &["none", "gzip"])),
+                    "none" => without_level(level, Compression::None),
+                    "gzip" => Ok(Compression::Gzip(match level {
+                        Some(level) => gzip_level(level)?,
+                        None => flate2::Compression::default(),
+                    })),
+                    #[cfg(feature = "sinks-utils-compression")]
+                    "zstd" => Ok(Compression::Zstd(match level {
+                        Some(level) => zstd_level(level)?,
+                        None => ZSTD_DEFAULT,
+                    })),
+                    #[cfg(feature = "sinks-utils-compression")]
+                    "snappy" => without_level(level, Compression::Snappy),
+                    #[cfg(feature = "sinks-utils-compression")]
+                    "lz4" => without_level(level, Compression::Lz4),
+                    algorithm => Err(de::Error::unknown_variant(algorithm, ALGORITHMS)),
                 }
             }
         }
@@ -158,6 +237,64 @@ impl<'de> de::Deserialize<'de> for Compression {
     }
 }
 
+fn without_level<E: de::Error>(
+    level: Option<Value>,
+    compression: Compression,
+) -> Result<Compression, E> {
+    match level {
+        Some(_) => Err(de::Error::unknown_field("level", &[])),
+        None => Ok(compression),
+    }
+}
+
+fn gzip_level<E: de::Error>(level: Value) -> Result<flate2::Compression, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if value <= 9 => Ok(flate2::Compression::new(value as u32)),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+            )),
+        },
+        Value::String(level) => match level.as_str() {
+            "none" => Ok(flate2::Compression::none()),
+            "fast" => Ok(flate2::Compression::fast()),
+            "default" => Ok(flate2::Compression::default()),
+            "best" => Ok(flate2::Compression::best()),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""none", "fast", "best" or "default""#,
+            )),
+        },
+        _ => unreachable!("level is checked to be a number or a string"),
+    }
+}
+
+#[cfg(feature = "sinks-utils-compression")]
+fn zstd_level<E: de::Error>(level: Value) -> Result<i32, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if (ZSTD_FAST as u64..=ZSTD_BEST as u64).contains(&value) => {
+                Ok(value as i32)
+            }
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"an integer between 1 and 21",
+            )),
+        },
+        Value::String(level) => match level.as_str() {
+            "fast" => Ok(ZSTD_FAST),
+            "default" => Ok(ZSTD_DEFAULT),
+            "best" => Ok(ZSTD_BEST),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""fast", "best" or "default""#,
+            )),
+        },
+        _ => unreachable!("level is checked to be a number or a string"),
+    }
+}
+
 impl ser::Serialize for Compression {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -180,12 +317,26 @@ impl ser::Serialize for Compression {
                     level => map.serialize_entry("level", &level)?,
                 };
             }
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Zstd(level) => {
+                map.serialize_entry("algorithm", "zstd")?;
+                match *level {
+                    ZSTD_FAST => map.serialize_entry("level", "fast")?,
+                    ZSTD_DEFAULT => {}
+                    ZSTD_BEST => map.serialize_entry("level", "best")?,
+                    level => map.serialize_entry("level", &level)?,
+                };
+            }
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Snappy => map.serialize_entry("algorithm", "snappy")?,
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Lz4 => map.serialize_entry("algorithm", "lz4")?,
         };
         map.end()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sinks-utils-compression"))]
 mod test {
     use super::Compression;
 
@@ -206,6 +357,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": 8}"#,
                 Compression::Gzip(flate2::Compression::new(8)),
             ),
+            (r#""zstd""#, Compression::Zstd(3)),
+            (
+                r#"{"algorithm": "zstd", "level": "best"}"#,
+                Compression::Zstd(21),
+            ),
+            (r#"{"algorithm": "zstd", "level": 7}"#, Compression::Zstd(7)),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "lz4"}"#, Compression::Lz4),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -219,11 +378,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zstd", "snappy" or "lz4" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected `none` or `gzip` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zstd`, `snappy`, `lz4` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -245,6 +404,18 @@ mod test {
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
                 r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
             ),
+            (
+                r#"{"algorithm": "zstd", "level": 22}"#,
+                r#"invalid value: 22, expected an integer between 1 and 21 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": "none"}"#,
+                r#"invalid value: string "none", expected "fast", "best" or "default" at line 1 column 38"#,
+            ),
+            (
+                r#"{"algorithm": "snappy", "level": 1}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 35"#,
+            ),
         ];
         for (source, result) in fixtures_invalid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(source);
@@ -252,4 +423,36 @@ mod test {
             assert_eq!(error.to_string().as_str(), *result);
         }
     }
+
+    #[test]
+    fn none_or_gzip() {
+        assert!(Compression::None.ensure_none_or_gzip().is_ok());
+        assert!(Compression::gzip_default().ensure_none_or_gzip().is_ok());
+        assert_eq!(
+            Compression::zstd_default()
+                .ensure_none_or_gzip()
+                .unwrap_err()
+                .to_string(),
+            "Compression algorithm `zstd` is not supported by this sink, only `none` and `gzip` are."
+        );
+        assert!(Compression::Snappy.ensure_none_or_gzip().is_err());
+        assert!(Compression::Lz4.ensure_none_or_gzip().is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        for compression in [
+            Compression::None,
+            Compression::gzip_default(),
+            Compression::Gzip(flate2::Compression::fast()),
+            Compression::zstd_default(),
+            Compression::Zstd(12),
+            Compression::Snappy,
+            Compression::Lz4,
+        ] {
+            let serialized = serde_json::to_string(&compression).unwrap();
+            let deserialized: Compression = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, compression);
+        }
+    }
 }
@@ -1,7 +1,9 @@
-use super::batch::{
-    err_event_too_large, Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult,
+use super::{
+    batch::{
+        err_event_too_large, Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult,
+    },
+    Compressor,
 };
-use flate2::write::GzEncoder;
 use std::io::Write;
 
 pub mod compression;
@@ -17,19 +19,13 @@ pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
 pub struct Buffer {
-    inner: Option<InnerBuffer>,
+    inner: Option<Compressor>,
     num_items: usize,
     num_bytes: usize,
     settings: BatchSize<Self>,
     compression: Compression,
 }
 
-#[derive(Debug)]
-pub enum InnerBuffer {
-    Plain(Vec<u8>),
-    Gzip(GzEncoder<Vec<u8>>),
-}
-
 impl Buffer {
     pub const fn new(settings: BatchSize<Self>, compression: Compression) -> Self {
         Self {
@@ -41,38 +37,22 @@ impl Buffer {
         }
     }
 
-    fn buffer(&mut self) -> &mut InnerBuffer {
+    fn buffer(&mut self) -> &mut Compressor {
         let bytes = self.settings.bytes;
         let compression = self.compression;
-        self.inner.get_or_insert_with(|| {
-            let buffer = Vec::with_capacity(bytes);
-            match compression {
-                Compression::None => InnerBuffer::Plain(buffer),
-                Compression::Gzip(level) => InnerBuffer::Gzip(GzEncoder::new(buffer, level)),
-            }
-        })
+        self.inner
+            .get_or_insert_with(|| Compressor::with_capacity(compression, bytes))
     }
 
     pub fn push(&mut self, input: &[u8]) {
         self.num_items += 1;
-        match self.buffer() {
-            InnerBuffer::Plain(inner) => {
-                inner.extend_from_slice(input);
-            }
-            InnerBuffer::Gzip(inner) => {
-                inner.write_all(input).unwrap();
-            }
-        }
+        self.buffer().write_all(input).unwrap();
     }
 
     pub fn is_empty(&self) -> bool {
-        self.inner
-            .as_ref()
-            .map(|inner| match inner {
-                InnerBuffer::Plain(inner) => inner.is_empty(),
-                InnerBuffer::Gzip(inner) => inner.get_ref().is_empty(),
-            })
-            .unwrap_or(true)
+        // Some of the compressors hold on to their input for a while, so the
+        // compressed buffer can still be empty after the first push.
+        self.num_items == 0
     }
 }
 
@@ -115,10 +95,7 @@ impl Batch for Buffer {
 
     fn finish(self) -> Self::Output {
         match self.inner {
-            Some(InnerBuffer::Plain(inner)) => inner,
-            Some(InnerBuffer::Gzip(inner)) => inner
-                .finish()
-                .expect("This can't fail because the inner writer is a Vec"),
+            Some(inner) => inner.into_inner(),
             None => Vec::new(),
         }
     }
@@ -199,4 +176,56 @@ mod test {
         .take(100_000)
         .flatten()));
     }
+
+    #[tokio::test]
+    #[cfg(feature = "sinks-utils-compression")]
+    async fn zstd() {
+        let (acker, _) = Acker::new_for_testing();
+        let sent_requests = Arc::new(Mutex::new(Vec::new()));
+
+        let svc = tower::service_fn(|req| {
+            let sent_requests = Arc::clone(&sent_requests);
+            sent_requests.lock().unwrap().push(req);
+            future::ok::<_, std::io::Error>(())
+        });
+        let batch_size = BatchSettings::default().bytes(100_000).events(1_000).size;
+        let timeout = Duration::from_secs(0);
+
+        let buffered = BatchSink::new(
+            svc,
+            Buffer::new(batch_size, Compression::zstd_default()),
+            timeout,
+            acker,
+        );
+
+        let input = std::iter::repeat(b"It's going down, I'm yelling timber".to_vec()).take(10_000);
+
+        let _ = buffered
+            .sink_map_err(drop)
+            .send_all(&mut stream::iter(input).map(|item| Ok(EncodedEvent::new(item, 0))))
+            .await
+            .unwrap();
+
+        let output = Arc::try_unwrap(sent_requests)
+            .unwrap()
+            .into_inner()
+            .unwrap();
+
+        assert!(output.len() > 1);
+
+        let decompressed = output.into_iter().flat_map(|batch: Vec<u8>| {
+            let mut decompressed = vec![];
+            zstd::stream::read::Decoder::new(batch.as_slice())
+                .unwrap()
+                .read_to_end(&mut decompressed)
+                .unwrap();
+            decompressed
+        });
+
+        assert!(decompressed.eq(std::iter::repeat(
+            b"It's going down, I'm yelling timber".to_vec()
+        )
+        .take(10_000)
+        .flatten()));
+    }
 }
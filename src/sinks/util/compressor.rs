@@ -1,4 +1,4 @@
-use std::io;
+use std::{fmt, io};
 
 use flate2::write::GzEncoder;
 
@@ -7,14 +7,29 @@ use super::Compression;
 enum Writer {
     Plain(Vec<u8>),
     Gzip(GzEncoder<Vec<u8>>),
+    #[cfg(feature = "sinks-utils-compression")]
+    Zstd(zstd::stream::write::Encoder<Vec<u8>>),
+    #[cfg(feature = "sinks-utils-compression")]
+    Snappy(snap::write::FrameEncoder<Vec<u8>>),
+    #[cfg(feature = "sinks-utils-compression")]
+    Lz4(lz4_flex::frame::FrameEncoder<Vec<u8>>),
 }
 
-impl From<Compression> for Writer {
-    fn from(compression: Compression) -> Self {
-        let buffer = Vec::with_capacity(1_024);
+impl Writer {
+    fn new(compression: Compression, capacity: usize) -> Self {
+        let buffer = Vec::with_capacity(capacity);
         match compression {
             Compression::None => Writer::Plain(buffer),
             Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(buffer, level)),
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Zstd(level) => Writer::Zstd(
+                zstd::stream::write::Encoder::new(buffer, level)
+                    .expect("zstd encoder should not fail to initialize"),
+            ),
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Snappy => Writer::Snappy(snap::write::FrameEncoder::new(buffer)),
+            #[cfg(feature = "sinks-utils-compression")]
+            Compression::Lz4 => Writer::Lz4(lz4_flex::frame::FrameEncoder::new(buffer)),
         }
     }
 }
@@ -24,6 +39,12 @@ impl io::Write for Writer {
         match self {
             Writer::Plain(inner_buf) => inner_buf.write(buf),
             Writer::Gzip(writer) => writer.write(buf),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Zstd(writer) => writer.write(buf),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Snappy(writer) => writer.write(buf),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Lz4(writer) => writer.write(buf),
         }
     }
 
@@ -31,6 +52,12 @@ impl io::Write for Writer {
         match self {
             Writer::Plain(_) => Ok(()),
             Writer::Gzip(writer) => writer.flush(),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Snappy(writer) => writer.flush(),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Zstd(writer) => writer.flush(),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Lz4(writer) => writer.flush(),
         }
     }
 }
@@ -39,10 +66,36 @@ impl io::Write for Writer {
 ///
 /// Users can acquire a `Compressor` via [`Compressor::from`] based on the desired compression scheme.
 pub struct Compressor {
+    compression: Compression,
     inner: Writer,
 }
 
 impl Compressor {
+    /// Creates a compressor whose internal buffer is preallocated to hold `capacity` bytes.
+    pub fn with_capacity(compression: Compression, capacity: usize) -> Self {
+        Compressor {
+            compression,
+            inner: Writer::new(compression, capacity),
+        }
+    }
+
+    /// Gets a reference to the internal buffer used by the compressor.
+    ///
+    /// Depending on the compression scheme, not all of the written data may have made it into the
+    /// buffer yet.
+    pub fn get_ref(&self) -> &[u8] {
+        match &self.inner {
+            Writer::Plain(buf) => buf,
+            Writer::Gzip(writer) => writer.get_ref(),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Snappy(writer) => writer.get_ref(),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Zstd(writer) => writer.get_ref(),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Lz4(writer) => writer.get_ref(),
+        }
+    }
+
     /// Consumes the compressor, returning the internal buffer used by the compressor.
     pub fn into_inner(self) -> Vec<u8> {
         match self.inner {
@@ -50,6 +103,18 @@ impl Compressor {
             Writer::Gzip(writer) => writer
                 .finish()
                 .expect("gzip writer should not fail to finish"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Zstd(writer) => writer
+                .finish()
+                .expect("zstd writer should not fail to finish"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Snappy(writer) => writer
+                .into_inner()
+                .expect("snappy writer should not fail to finish"),
+            #[cfg(feature = "sinks-utils-compression")]
+            Writer::Lz4(writer) => writer
+                .finish()
+                .expect("lz4 writer should not fail to finish"),
         }
     }
 }
@@ -64,10 +129,66 @@ impl io::Write for Compressor {
     }
 }
 
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressor")
+            .field("compression", &self.compression)
+            .field("len", &self.get_ref().len())
+            .finish()
+    }
+}
+
 impl From<Compression> for Compressor {
     fn from(compression: Compression) -> Self {
-        Compressor {
-            inner: compression.into(),
+        Compressor::with_capacity(compression, 1_024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    const INPUT: &[u8] = b"It's going down, I'm yelling timber, You better move, you better dance";
+
+    fn compress(compression: Compression) -> Vec<u8> {
+        let mut compressor = Compressor::from(compression);
+        for _ in 0..100 {
+            compressor.write_all(INPUT).unwrap();
         }
+        compressor.into_inner()
+    }
+
+    fn assert_decompresses(mut decompressed: impl Read) {
+        let mut output = Vec::new();
+        decompressed.read_to_end(&mut output).unwrap();
+        assert_eq!(output, INPUT.repeat(100));
+    }
+
+    #[test]
+    fn compresses_gzip() {
+        let compressed = compress(Compression::gzip_default());
+        assert_decompresses(flate2::read::GzDecoder::new(&compressed[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "sinks-utils-compression")]
+    fn compresses_zstd() {
+        let compressed = compress(Compression::zstd_default());
+        assert_decompresses(zstd::stream::read::Decoder::new(&compressed[..]).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "sinks-utils-compression")]
+    fn compresses_snappy() {
+        let compressed = compress(Compression::Snappy);
+        assert_decompresses(snap::read::FrameDecoder::new(&compressed[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "sinks-utils-compression")]
+    fn compresses_lz4() {
+        let compressed = compress(Compression::Lz4);
+        assert_decompresses(lz4_flex::frame::FrameDecoder::new(&compressed[..]));
     }
 }
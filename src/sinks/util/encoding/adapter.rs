@@ -0,0 +1,171 @@
+//! Glue between the sink specific `encoding` configurations and the
+//! [`codecs`](crate::codecs) encoding framework.
+//!
+//! Sinks that support the framework expose optional `framing` and `serializer`
+//! options next to their `encoding` option. When a serializer is configured it
+//! replaces `encoding.codec`, while the field rules of `encoding` (`only_fields`,
+//! `except_fields` and `timestamp_format`) still apply. The `encoding` option
+//! is optional in that case, and no field rules apply when it is left out.
+
+use super::{Encoder, EncodingConfiguration};
+use crate::{
+    codecs::{self, FramingEncoderConfig, SerializerConfig},
+    event::Event,
+};
+use bytes::{Bytes, BytesMut};
+use std::io;
+
+/// Builds the encoder configured through the `framing` and `serializer`
+/// options of a sink.
+///
+/// Returns `None` if no serializer has been configured, in which case the sink
+/// should keep using its `encoding.codec`. The framing defaults to
+/// `default_framing` since the appropriate framing depends on the transport.
+pub fn build_codecs_encoder(
+    framing: &Option<Box<dyn FramingEncoderConfig>>,
+    serializer: &Option<Box<dyn SerializerConfig>>,
+    default_framing: impl FnOnce() -> Box<dyn FramingEncoderConfig>,
+) -> crate::Result<Option<codecs::Encoder>> {
+    match (framing, serializer) {
+        (framing, Some(serializer)) => {
+            let framing = framing.clone().unwrap_or_else(default_framing);
+            codecs::EncodingConfig::new(framing, serializer.clone())
+                .build()
+                .map(Some)
+        }
+        (Some(_), None) => Err("Using `framing` requires `serializer` to be configured.".into()),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Resolves the `encoding` option of a sink against its codecs `encoder`.
+///
+/// `encoding` is only required when no serializer has been configured. When it
+/// is left out next to a serializer, `default` provides a configuration that
+/// applies no field rules, since its codec is replaced by the serializer.
+pub fn resolve_encoding<E>(
+    encoding: Option<E>,
+    encoder: &Option<codecs::Encoder>,
+    default: impl FnOnce() -> E,
+) -> crate::Result<E> {
+    match (encoding, encoder) {
+        (Some(encoding), _) => Ok(encoding),
+        (None, Some(_)) => Ok(default()),
+        (None, None) => {
+            Err("Missing `encoding`, required unless `serializer` is configured.".into())
+        }
+    }
+}
+
+/// Applies the field rules of `encoding` and encodes the event into a single
+/// frame with `encoder`.
+///
+/// Errors are reported by the encoder itself, in which case the event is
+/// dropped by returning `None`.
+pub fn encode_with_codecs(
+    mut event: Event,
+    encoding: &impl EncodingConfiguration,
+    encoder: &codecs::Encoder,
+) -> Option<Bytes> {
+    encoding.apply_rules(&mut event);
+
+    let mut buffer = BytesMut::new();
+    tokio_util::codec::Encoder::encode(&mut encoder.clone(), event, &mut buffer).ok()?;
+    Some(buffer.freeze())
+}
+
+impl Encoder<Event> for codecs::Encoder {
+    fn encode_input(&self, event: Event, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let mut buffer = BytesMut::new();
+        tokio_util::codec::Encoder::encode(&mut self.clone(), event, &mut buffer)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        writer.write_all(&buffer)?;
+        Ok(buffer.len())
+    }
+}
+
+impl Encoder<Vec<Event>> for codecs::Encoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let mut written = 0;
+        for event in events {
+            written += self.encode_input(event, writer)?;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codecs::{JsonSerializerConfig, NewlineDelimitedEncoderConfig, RawMessageSerializerConfig},
+        sinks::util::encoding::{EncodingConfig, StandardEncodings},
+    };
+
+    fn encoder(
+        framing: Option<Box<dyn FramingEncoderConfig>>,
+        serializer: Option<Box<dyn SerializerConfig>>,
+    ) -> crate::Result<Option<codecs::Encoder>> {
+        build_codecs_encoder(&framing, &serializer, || {
+            Box::new(NewlineDelimitedEncoderConfig::new())
+        })
+    }
+
+    #[test]
+    fn no_encoder_without_serializer() {
+        assert!(encoder(None, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn framing_requires_serializer() {
+        assert!(encoder(Some(Box::new(NewlineDelimitedEncoderConfig::new())), None).is_err());
+    }
+
+    #[test]
+    fn encoding_optional_with_serializer() {
+        let serializer = encoder(None, Some(Box::new(JsonSerializerConfig::new()))).unwrap();
+
+        let encoding: EncodingConfig<StandardEncodings> =
+            resolve_encoding(None, &serializer, || StandardEncodings::Text.into()).unwrap();
+        assert_eq!(encoding.codec, StandardEncodings::Text);
+        assert!(
+            resolve_encoding::<EncodingConfig<StandardEncodings>>(None, &None, || {
+                StandardEncodings::Text.into()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn applies_encoding_rules() {
+        let encoder = encoder(None, Some(Box::new(JsonSerializerConfig::new())))
+            .unwrap()
+            .unwrap();
+        let encoding: EncodingConfig<StandardEncodings> = toml::from_str(
+            r#"
+            codec = "json"
+            only_fields = ["message"]
+            "#,
+        )
+        .unwrap();
+
+        let bytes = encode_with_codecs(Event::from("foo"), &encoding, &encoder).unwrap();
+
+        assert_eq!(bytes, "{\"message\":\"foo\"}\n");
+    }
+
+    #[test]
+    fn encodes_batches() {
+        let encoder = encoder(None, Some(Box::new(RawMessageSerializerConfig::new())))
+            .unwrap()
+            .unwrap();
+
+        let mut writer = Vec::new();
+        let written = encoder
+            .encode_input(vec![Event::from("foo"), Event::from("bar")], &mut writer)
+            .unwrap();
+
+        assert_eq!(written, 8);
+        assert_eq!(writer, b"foo\nbar\n");
+    }
+}
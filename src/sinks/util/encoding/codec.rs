@@ -158,6 +158,9 @@ impl Encoder<Event> for StandardJsonEncoding {
             Event::Metric(metric) => as_tracked_write(writer, &metric, |writer, item| {
                 serde_json::to_writer(writer, item)
             }),
+            Event::Trace(trace) => as_tracked_write(writer, &trace, |writer, item| {
+                serde_json::to_writer(writer, item)
+            }),
         }
     }
 }
@@ -165,8 +168,8 @@ impl Encoder<Event> for StandardJsonEncoding {
 /// Standard implementation for encoding events as text.
 ///
 /// If given a log event, the value used in the field matching the global lob schema's "message" key
-/// will be written out, otherwise an empty string will be written.  If anything other than a log
-/// event is given, the encoder will panic.
+/// will be written out, otherwise an empty string will be written.  Metrics are written using their
+/// `Display` implementation, and traces, which have no natural text form, are written as JSON.
 ///
 /// Each event is delimited with a newline character.
 pub struct StandardTextEncoding;
@@ -185,6 +188,9 @@ impl Encoder<Event> for StandardTextEncoding {
                 let message = metric.to_string().into_bytes();
                 writer.write_all(&message).map(|()| message.len())
             }
+            Event::Trace(trace) => as_tracked_write(writer, &trace, |writer, item| {
+                serde_json::to_writer(writer, item)
+            }),
         }
     }
 }
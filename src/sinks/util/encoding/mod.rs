@@ -57,6 +57,12 @@
 //! distinct types! Having [`EncodingConfigWithDefault`] is a relatively straightforward way to
 //! accomplish this without a bunch of magic.  [`EncodingConfigFixed`] goes a step further and
 //! provides a way to force a codec, disallowing an override from being specified.
+#[cfg(feature = "codecs")]
+mod adapter;
+
+#[cfg(feature = "codecs")]
+pub use adapter::{build_codecs_encoder, encode_with_codecs, resolve_encoding};
+
 mod codec;
 
 pub use codec::{StandardEncodings, StandardJsonEncoding, StandardTextEncoding};
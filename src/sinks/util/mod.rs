@@ -4,6 +4,8 @@ pub mod buffer;
 pub mod builder;
 pub mod compressor;
 pub mod encoding;
+#[cfg(any(feature = "sinks-vector", feature = "sinks-opentelemetry"))]
+pub mod grpc;
 pub mod http;
 pub mod request_builder;
 pub mod retries;
@@ -39,7 +39,10 @@ use super::{
 };
 use crate::{buffers::Acker, event::EventStatus, internal_events::EventsSent};
 use futures::{
-    future::BoxFuture, ready, stream::FuturesUnordered, FutureExt, Sink, Stream, TryFutureExt,
+    future::{self, BoxFuture},
+    ready,
+    stream::FuturesUnordered,
+    FutureExt, Sink, Stream, TryFutureExt,
 };
 use pin_project::pin_project;
 use std::{
@@ -513,8 +516,8 @@ where
         self.service
             .call(items)
             .err_into()
-            .map(move |result| {
-                let status = logic.result_status(result);
+            .then(move |result| logic.resolve_status(result))
+            .map(move |status| {
                 finalizers.update_status(status);
                 if status == EventStatus::Delivered {
                     emit!(&EventsSent { count, byte_size });
@@ -573,6 +576,16 @@ where
 pub trait ServiceLogic: Clone {
     type Response: Response;
     fn result_status(&self, result: crate::Result<Self::Response>) -> EventStatus;
+
+    /// Resolves the status of the events sent in a request, once it completed. Services
+    /// confirming delivery separately from their responses can wait for the confirmation here,
+    /// outside of the request's timeout and retries.
+    fn resolve_status(
+        &self,
+        result: crate::Result<Self::Response>,
+    ) -> BoxFuture<'static, EventStatus> {
+        future::ready(self.result_status(result)).boxed()
+    }
 }
 
 #[derive(Derivative)]
@@ -3,21 +3,20 @@ use crate::{
     event::{proto::EventWrapper, Event},
     proto::vector as proto,
     sinks::util::{
-        retries::RetryLogic, BatchConfig, BatchSettings, BatchSink, EncodedEvent, EncodedLength,
-        ServiceBuilderExt, TowerRequestConfig, VecBuffer,
+        grpc::{new_client, with_default_scheme, HyperSvc},
+        retries::RetryLogic,
+        BatchConfig, BatchSettings, BatchSink, EncodedEvent, EncodedLength, ServiceBuilderExt,
+        TowerRequestConfig, VecBuffer,
     },
     sinks::{Healthcheck, VectorSink},
-    tls::{tls_connector_builder, MaybeTlsSettings, TlsConfig},
+    tls::{MaybeTlsSettings, TlsConfig},
 };
 use futures::{future::BoxFuture, stream, SinkExt, StreamExt, TryFutureExt};
-use http::uri::Uri;
-use hyper::client::HttpConnector;
-use hyper_openssl::HttpsConnector;
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use std::task::{Context, Poll};
-use tonic::{body::BoxBody, IntoRequest};
+use tonic::IntoRequest;
 use tower::ServiceBuilder;
 use vector_core::ByteSizeOf;
 
@@ -50,90 +49,6 @@ fn default_config(address: &str) -> VectorConfig {
     }
 }
 
-/// grpc doesn't like an address without a scheme, so we default to http or https if one isn't
-/// specified in the address.
-fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
-    let uri: Uri = address.parse()?;
-    if uri.scheme().is_none() {
-        // Default the scheme to http or https.
-        let mut parts = uri.into_parts();
-
-        parts.scheme = if tls {
-            Some(
-                "https"
-                    .parse()
-                    .unwrap_or_else(|_| unreachable!("https should be valid")),
-            )
-        } else {
-            Some(
-                "http"
-                    .parse()
-                    .unwrap_or_else(|_| unreachable!("http should be valid")),
-            )
-        };
-
-        if parts.path_and_query.is_none() {
-            parts.path_and_query = Some(
-                "/".parse()
-                    .unwrap_or_else(|_| unreachable!("root should be valid")),
-            );
-        }
-        Ok(Uri::from_parts(parts)?)
-    } else {
-        Ok(uri)
-    }
-}
-
-fn new_client(
-    tls_settings: &MaybeTlsSettings,
-) -> crate::Result<hyper::Client<HttpsConnector<HttpConnector>, BoxBody>> {
-    let mut http = HttpConnector::new();
-    http.enforce_http(false);
-
-    let tls = tls_connector_builder(tls_settings)?;
-    let mut https = HttpsConnector::with_connector(http, tls)?;
-
-    let settings = tls_settings.tls().cloned();
-    https.set_callback(move |c, _uri| {
-        if let Some(settings) = &settings {
-            settings.apply_connect_configuration(c);
-        }
-
-        Ok(())
-    });
-
-    Ok(hyper::Client::builder().http2_only(true).build(https))
-}
-
-#[derive(Clone)]
-struct HyperSvc {
-    uri: Uri,
-    client: hyper::Client<HttpsConnector<HttpConnector>, BoxBody>,
-}
-
-impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
-    type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
-    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
-
-    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
-    }
-
-    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
-        let uri = Uri::builder()
-            .scheme(self.uri.scheme().unwrap().clone())
-            .authority(self.uri.authority().unwrap().clone())
-            .path_and_query(req.uri().path_and_query().unwrap().clone())
-            .build()
-            .unwrap();
-
-        *req.uri_mut() = uri;
-
-        Box::pin(self.client.request(req))
-    }
-}
-
 impl VectorConfig {
     pub(crate) async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
@@ -147,13 +62,10 @@ impl VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = proto::Client::new(HyperSvc {
-            uri: healthcheck_uri,
-            client: client.clone(),
-        });
+        let healthcheck_client = proto::Client::new(HyperSvc::new(healthcheck_uri, client.clone()));
 
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck.clone());
-        let client = proto::Client::new(HyperSvc { uri, client });
+        let client = proto::Client::new(HyperSvc::new(uri, client));
         let request = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch = BatchSettings::default()
             .events(1000)
@@ -389,18 +301,6 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
     }
 
-    #[test]
-    fn test_with_default_scheme() {
-        assert_eq!(
-            with_default_scheme("0.0.0.0", false).unwrap().to_string(),
-            "http://0.0.0.0/"
-        );
-        assert_eq!(
-            with_default_scheme("0.0.0.0", true).unwrap().to_string(),
-            "https://0.0.0.0/"
-        );
-    }
-
     async fn get_received(
         rx: mpsc::Receiver<(Parts, Bytes)>,
         assert_parts: impl Fn(Parts),
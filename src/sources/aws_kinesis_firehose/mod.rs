@@ -76,7 +76,7 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding.output_type()
     }
 
     fn source_type(&self) -> &'static str {
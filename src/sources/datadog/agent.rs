@@ -5,24 +5,22 @@ use crate::{
         SourceDescription,
     },
     event::Event,
-    internal_events::HttpDecompressError,
     serde::{default_decoding, default_framing_message_based},
     sources::{
         self,
-        util::{ErrorMessage, TcpError},
+        util::{decode, ErrorMessage, TcpError},
     },
     tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use chrono::Utc;
-use flate2::read::{DeflateDecoder, MultiGzDecoder};
 use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
 use http::StatusCode;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
-use std::{io::Read, net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc};
 use tokio_util::codec::Decoder;
 use vector_core::event::{BatchNotifier, BatchStatus};
 use warp::{
@@ -48,6 +46,8 @@ pub struct DatadogAgentConfig {
     framing: Box<dyn FramingConfig>,
     #[serde(default = "default_decoding")]
     decoding: Box<dyn ParserConfig>,
+    #[serde(default)]
+    max_decompressed_bytes: Option<usize>,
 }
 
 inventory::submit! {
@@ -68,6 +68,7 @@ impl GenerateConfig for DatadogAgentConfig {
             store_api_key: true,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            max_decompressed_bytes: None,
         })
         .unwrap()
     }
@@ -78,7 +79,8 @@ impl GenerateConfig for DatadogAgentConfig {
 impl SourceConfig for DatadogAgentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
         let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
-        let source = DatadogAgentSource::new(self.store_api_key, decoder);
+        let source =
+            DatadogAgentSource::new(self.store_api_key, decoder, self.max_decompressed_bytes);
 
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         let listener = tls.bind(&self.address).await?;
@@ -110,7 +112,7 @@ impl SourceConfig for DatadogAgentConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding.output_type()
     }
 
     fn source_type(&self) -> &'static str {
@@ -129,10 +131,15 @@ struct DatadogAgentSource {
     log_schema_timestamp_key: &'static str,
     log_schema_source_type_key: &'static str,
     decoder: codecs::Decoder,
+    max_decompressed_bytes: Option<usize>,
 }
 
 impl DatadogAgentSource {
-    fn new(store_api_key: bool, decoder: codecs::Decoder) -> Self {
+    fn new(
+        store_api_key: bool,
+        decoder: codecs::Decoder,
+        max_decompressed_bytes: Option<usize>,
+    ) -> Self {
         Self {
             store_api_key,
             api_key_matcher: Regex::new(r"^/v1/input/(?P<api_key>[[:alnum:]]{32})/??")
@@ -140,6 +147,7 @@ impl DatadogAgentSource {
             log_schema_source_type_key: log_schema().source_type_key(),
             log_schema_timestamp_key: log_schema().timestamp_key(),
             decoder,
+            max_decompressed_bytes,
         }
     }
 
@@ -224,7 +232,7 @@ impl DatadogAgentSource {
                         None
                     };
 
-                    let events = decode(&encoding_header, body)
+                    let events = decode(&encoding_header, body, self.max_decompressed_bytes)
                         .and_then(|body| self.decode_body(body, token));
                     Self::handle_request(events, acknowledgements, out.clone())
                 },
@@ -300,49 +308,6 @@ impl DatadogAgentSource {
     }
 }
 
-fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
-    if let Some(encodings) = header {
-        for encoding in encodings.rsplit(',').map(str::trim) {
-            body = match encoding {
-                "identity" => body,
-                "gzip" | "x-gzip" => {
-                    let mut decoded = Vec::new();
-                    MultiGzDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                "deflate" | "x-deflate" => {
-                    let mut decoded = Vec::new();
-                    DeflateDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                encoding => {
-                    return Err(ErrorMessage::new(
-                        StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                        format!("Unsupported encoding {}", encoding),
-                    ))
-                }
-            }
-        }
-    }
-
-    Ok(body)
-}
-
-fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMessage {
-    emit!(&HttpDecompressError {
-        encoding,
-        error: &error
-    });
-    ErrorMessage::new(
-        StatusCode::UNPROCESSABLE_ENTITY,
-        format!("Failed decompressing payload with {} decoder.", encoding),
-    )
-}
-
 // https://github.com/DataDog/datadog-agent/blob/a33248c2bc125920a9577af1e16f12298875a4ad/pkg/logs/processor/json.go#L23-L49
 #[derive(Deserialize, Clone, Serialize, Debug)]
 #[serde(deny_unknown_fields)]
@@ -373,7 +338,7 @@ mod tests {
     use http::HeaderMap;
     use pretty_assertions::assert_eq;
     use quickcheck::{Arbitrary, Gen, QuickCheck, TestResult};
-    use std::net::SocketAddr;
+    use std::{io::Write, net::SocketAddr};
 
     impl Arbitrary for LogMsg {
         fn arbitrary(g: &mut Gen) -> Self {
@@ -401,7 +366,7 @@ mod tests {
 
             let decoder =
                 codecs::Decoder::new(Box::new(BytesCodec::new()), Box::new(BytesParser::new()));
-            let source = DatadogAgentSource::new(true, decoder);
+            let source = DatadogAgentSource::new(true, decoder, None);
             let events = source.decode_body(body, api_key).unwrap();
             assert_eq!(events.len(), msgs.len());
             for (msg, event) in msgs.into_iter().zip(events.into_iter()) {
@@ -442,6 +407,7 @@ mod tests {
                 store_api_key,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
+                max_decompressed_bytes: None,
             }
             .build(context)
             .await
@@ -517,6 +483,47 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn snappy_framed_payload() {
+        trace_init();
+        let (rx, addr) = source(EventStatus::Delivered, true, true).await;
+
+        let body = serde_json::to_vec(&[LogMsg {
+            message: Bytes::from("foo"),
+            timestamp: 123,
+            hostname: Bytes::from("festeburg"),
+            status: Bytes::from("notice"),
+            service: Bytes::from("vector"),
+            ddsource: Bytes::from("curl"),
+            ddtags: Bytes::from("one,two,three"),
+        }])
+        .unwrap();
+        let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+        encoder.write_all(&body).unwrap();
+        let body = encoder.into_inner().unwrap();
+
+        let mut events = spawn_collect_n(
+            async move {
+                let status = reqwest::Client::new()
+                    .post(&format!("http://{}/api/v2/logs", addr))
+                    .header("content-encoding", "x-snappy-framed")
+                    .body(body)
+                    .send()
+                    .await
+                    .unwrap()
+                    .status();
+                assert_eq!(200, status.as_u16());
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let event = events.remove(0);
+        assert_eq!(event.as_log()["message"], "foo".into());
+        assert_eq!(event.as_log()["hostname"], "festeburg".into());
+    }
+
     #[tokio::test]
     async fn full_payload_v2() {
         trace_init();
@@ -209,7 +209,7 @@ impl SourceConfig for ExecConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding.output_type()
     }
 
     fn source_type(&self) -> &'static str {
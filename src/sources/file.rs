@@ -318,7 +318,7 @@ pub fn file_source(
     let shutdown = shutdown.shared();
     let finalizer = acknowledgements.then(|| {
         let checkpoints = checkpointer.view();
-        OrderedFinalizer::new(shutdown.clone(), move |entry: FinalizerEntry| {
+        OrderedFinalizer::new(shutdown.clone(), move |_status, entry: FinalizerEntry| {
             checkpoints.update(entry.file_id, entry.offset)
         })
     });
@@ -1504,13 +1504,30 @@ mod tests {
         let dir = tempdir().unwrap();
         let config = file::FileConfig {
             include: vec![PathBuf::from("tests/data/gzipped.log")],
-            // TODO: remove this once files are fingerprinted after decompression
-            //
-            // Currently, this needs to be smaller than the total size of the compressed file
-            // because the fingerprinter tries to read until a newline, which it's not going to see
-            // in the compressed data, or this number of bytes. If it hits EOF before that, it
-            // can't return a fingerprint because the value would change once more data is written.
-            max_line_bytes: 100,
+            ..test_default_file_config(&dir)
+        };
+
+        let received = run_file_source(&config, false, NoAcks, sleep_500_millis()).await;
+
+        let received = extract_messages_value(received);
+
+        assert_eq!(
+            received,
+            vec![
+                "this is a simple file".into(),
+                "i have been compressed".into(),
+                "in order to make me smaller".into(),
+                "but you can still read me".into(),
+                "hooray".into(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zstd_compressed_file() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![PathBuf::from("tests/data/zstd-compressed.log")],
             ..test_default_file_config(&dir)
         };
 
@@ -13,11 +13,8 @@ use bytes::{Buf, Bytes, BytesMut};
 use flate2::read::MultiGzDecoder;
 use rmp_serde::{decode, Deserializer};
 use serde::{Deserialize, Serialize};
-use smallvec::{smallvec, SmallVec};
-use std::{
-    collections::VecDeque,
-    io::{self, Read},
-};
+use smallvec::SmallVec;
+use std::io::{self, Read};
 use tokio_util::codec::Decoder;
 
 use crate::sources::fluent::message::{
@@ -63,6 +60,7 @@ impl SourceConfig for FluentConfig {
             tls,
             self.receive_buffer_bytes,
             cx.shutdown,
+            cx.acknowledgements,
             cx.out,
         )
     }
@@ -85,7 +83,7 @@ struct FluentSource;
 
 impl TcpSource for FluentSource {
     type Error = DecodeError;
-    type Item = FluentFrame;
+    type Item = DecodedFluentMessage;
     type Decoder = FluentDecoder;
 
     fn decoder(&self) -> Self::Decoder {
@@ -101,6 +99,22 @@ impl TcpSource for FluentSource {
             }
         }
     }
+
+    /// Acknowledges the chunk of messages requesting it, as `{"ack": <chunk>}`.
+    fn build_ack(&self, message: &DecodedFluentMessage) -> Bytes {
+        match &message.chunk {
+            Some(chunk) => {
+                let ack = rmpv::Value::Map(vec![(
+                    rmpv::Value::from("ack"),
+                    rmpv::Value::from(chunk.as_str()),
+                )]);
+                let mut buf = Vec::new();
+                rmpv::encode::write_value(&mut buf, &ack).expect("writing to a Vec can't fail");
+                buf.into()
+            }
+            None => Bytes::new(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -150,68 +164,38 @@ impl From<decode::Error> for DecodeError {
 }
 
 #[derive(Debug)]
-struct FluentDecoder {
-    // unread frames from previous fluent message
-    unread_frames: VecDeque<(FluentFrame, usize)>,
-}
+struct FluentDecoder;
 
 impl FluentDecoder {
-    fn new() -> Self {
-        FluentDecoder {
-            unread_frames: VecDeque::new(),
-        }
-    }
-
-    fn handle_message(
-        &mut self,
-        message: FluentMessage,
-        byte_size: usize,
-    ) -> Result<(), DecodeError> {
-        match message {
-            FluentMessage::Message(tag, timestamp, record)
-            | FluentMessage::MessageWithOptions(tag, timestamp, record, ..) => {
-                self.unread_frames.push_back((
-                    FluentFrame {
-                        tag,
-                        timestamp,
-                        record,
-                    },
-                    byte_size,
-                ));
-                Ok(())
-            }
-            FluentMessage::Forward(tag, entries)
-            | FluentMessage::ForwardWithOptions(tag, entries, ..) => {
-                self.unread_frames.extend(entries.into_iter().map(
-                    |FluentEntry(timestamp, record)| {
-                        (
-                            FluentFrame {
-                                tag: tag.clone(),
-                                timestamp,
-                                record,
-                            },
-                            byte_size,
-                        )
-                    },
-                ));
-                Ok(())
+    const fn new() -> Self {
+        FluentDecoder
+    }
+
+    /// Returns the frames of the message, or `None` for heartbeats.
+    fn handle_message(message: FluentMessage) -> Result<Option<DecodedFluentMessage>, DecodeError> {
+        let (frames, options) = match message {
+            FluentMessage::Message(tag, timestamp, record) => (
+                vec![FluentFrame {
+                    tag,
+                    timestamp,
+                    record,
+                }],
+                None,
+            ),
+            FluentMessage::MessageWithOptions(tag, timestamp, record, options) => (
+                vec![FluentFrame {
+                    tag,
+                    timestamp,
+                    record,
+                }],
+                Some(options),
+            ),
+            FluentMessage::Forward(tag, entries) => (Self::forward_frames(tag, entries), None),
+            FluentMessage::ForwardWithOptions(tag, entries, options) => {
+                (Self::forward_frames(tag, entries), Some(options))
             }
             FluentMessage::PackedForward(tag, bin) => {
-                let mut buf = BytesMut::from(&bin[..]);
-
-                let mut decoder = FluentEntryStreamDecoder;
-
-                while let Some(FluentEntry(timestamp, record)) = decoder.decode(&mut buf)? {
-                    self.unread_frames.push_back((
-                        FluentFrame {
-                            tag: tag.clone(),
-                            timestamp,
-                            record,
-                        },
-                        byte_size,
-                    ));
-                }
-                Ok(())
+                (Self::packed_forward_frames(tag, bin.into_vec())?, None)
             }
             FluentMessage::PackedForwardWithOptions(tag, bin, options) => {
                 let buf = match options.compressed.as_deref() {
@@ -226,80 +210,100 @@ impl FluentDecoder {
                     Some(s) => Err(DecodeError::UnknownCompression(s.to_owned())),
                 }?;
 
-                let mut buf = BytesMut::from(&buf[..]);
-
-                let mut decoder = FluentEntryStreamDecoder;
-
-                while let Some(FluentEntry(timestamp, record)) = decoder.decode(&mut buf)? {
-                    self.unread_frames.push_back((
-                        FluentFrame {
-                            tag: tag.clone(),
-                            timestamp,
-                            record,
-                        },
-                        byte_size,
-                    ));
-                }
-                Ok(())
+                (Self::packed_forward_frames(tag, buf)?, Some(options))
             }
-            FluentMessage::Heartbeat(rmpv::Value::Nil) => Ok(()),
-            FluentMessage::Heartbeat(value) => Err(DecodeError::UnexpectedValue(value)),
+            FluentMessage::Heartbeat(rmpv::Value::Nil) => return Ok(None),
+            FluentMessage::Heartbeat(value) => return Err(DecodeError::UnexpectedValue(value)),
+        };
+
+        Ok(Some(DecodedFluentMessage {
+            frames,
+            chunk: options.and_then(|options| options.chunk),
+        }))
+    }
+
+    fn forward_frames(tag: FluentTag, entries: Vec<FluentEntry>) -> Vec<FluentFrame> {
+        entries
+            .into_iter()
+            .map(|FluentEntry(timestamp, record)| FluentFrame {
+                tag: tag.clone(),
+                timestamp,
+                record,
+            })
+            .collect()
+    }
+
+    fn packed_forward_frames(
+        tag: FluentTag,
+        buf: Vec<u8>,
+    ) -> Result<Vec<FluentFrame>, DecodeError> {
+        let mut buf = BytesMut::from(&buf[..]);
+
+        let mut decoder = FluentEntryStreamDecoder;
+
+        let mut frames = Vec::new();
+        while let Some(FluentEntry(timestamp, record)) = decoder.decode(&mut buf)? {
+            frames.push(FluentFrame {
+                tag: tag.clone(),
+                timestamp,
+                record,
+            });
         }
+        Ok(frames)
     }
 }
 
 impl Decoder for FluentDecoder {
-    type Item = (FluentFrame, usize);
+    type Item = (DecodedFluentMessage, usize);
     type Error = DecodeError;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(item) = self.unread_frames.pop_front() {
-            return Ok(Some(item));
-        }
-
-        if src.is_empty() {
-            return Ok(None);
-        }
+        loop {
+            if src.is_empty() {
+                return Ok(None);
+            }
 
-        let (byte_size, res) = {
-            let mut des = Deserializer::new(io::Cursor::new(&src[..]));
+            let (byte_size, res) = {
+                let mut des = Deserializer::new(io::Cursor::new(&src[..]));
 
-            let res = Deserialize::deserialize(&mut des).map_err(DecodeError::Decode);
+                let res = Deserialize::deserialize(&mut des).map_err(DecodeError::Decode);
 
-            // check for unexpected EOF to indicate that we need more data
-            match res {
-                // can use or-patterns in 1.53
-                // https://github.com/rust-lang/rust/pull/79278
-                Err(DecodeError::Decode(decode::Error::InvalidDataRead(ref custom))) => {
-                    if custom.kind() == io::ErrorKind::UnexpectedEof {
-                        return Ok(None);
+                // check for unexpected EOF to indicate that we need more data
+                match res {
+                    // can use or-patterns in 1.53
+                    // https://github.com/rust-lang/rust/pull/79278
+                    Err(DecodeError::Decode(decode::Error::InvalidDataRead(ref custom))) => {
+                        if custom.kind() == io::ErrorKind::UnexpectedEof {
+                            return Ok(None);
+                        }
                     }
-                }
-                Err(DecodeError::Decode(decode::Error::InvalidMarkerRead(ref custom))) => {
-                    if custom.kind() == io::ErrorKind::UnexpectedEof {
-                        return Ok(None);
+                    Err(DecodeError::Decode(decode::Error::InvalidMarkerRead(ref custom))) => {
+                        if custom.kind() == io::ErrorKind::UnexpectedEof {
+                            return Ok(None);
+                        }
                     }
+                    _ => {}
                 }
-                _ => {}
-            }
 
-            (des.position() as usize, res)
-        };
+                (des.position() as usize, res)
+            };
 
-        src.advance(byte_size);
+            src.advance(byte_size);
 
-        res.and_then(|message| {
-            self.handle_message(message, byte_size)
-                .map(|_| self.unread_frames.pop_front())
-        })
-        .map_err(|error| {
-            let base64_encoded_message = base64::encode(&src);
-            emit!(&FluentMessageDecodeError {
-                error: &error,
-                base64_encoded_message
-            });
-            error
-        })
+            let message = res.and_then(Self::handle_message).map_err(|error| {
+                let base64_encoded_message = base64::encode(&src);
+                emit!(&FluentMessageDecodeError {
+                    error: &error,
+                    base64_encoded_message
+                });
+                error
+            })?;
+
+            // Heartbeats carry no frames, so keep on decoding past them.
+            if let Some(message) = message {
+                return Ok(Some((message, byte_size)));
+            }
+        }
     }
 }
 
@@ -340,6 +344,20 @@ impl Decoder for FluentEntryStreamDecoder {
     }
 }
 
+/// A decoded fluent message, with the chunk to acknowledge once its frames are processed,
+/// if the client requested acknowledgements.
+#[derive(Debug, PartialEq)]
+struct DecodedFluentMessage {
+    frames: Vec<FluentFrame>,
+    chunk: Option<String>,
+}
+
+impl From<DecodedFluentMessage> for SmallVec<[Event; 1]> {
+    fn from(message: DecodedFluentMessage) -> Self {
+        message.frames.into_iter().map(Event::from).collect()
+    }
+}
+
 /// Normalized fluent message.
 #[derive(Debug, PartialEq)]
 struct FluentFrame {
@@ -354,12 +372,6 @@ impl From<FluentFrame> for Event {
     }
 }
 
-impl From<FluentFrame> for SmallVec<[Event; 1]> {
-    fn from(frame: FluentFrame) -> Self {
-        smallvec![frame.into()]
-    }
-}
-
 impl From<FluentFrame> for LogEvent {
     fn from(frame: FluentFrame) -> LogEvent {
         let FluentFrame {
@@ -380,7 +392,10 @@ impl From<FluentFrame> for LogEvent {
 
 #[cfg(test)]
 mod tests {
-    use crate::sources::fluent::{DecodeError, FluentConfig, FluentDecoder};
+    use crate::sources::{
+        fluent::{DecodeError, FluentConfig, FluentDecoder, FluentSource},
+        util::TcpSource,
+    };
     use bytes::BytesMut;
     use chrono::DateTime;
     use shared::{assert_event_data_eq, btreemap};
@@ -672,14 +687,61 @@ mod tests {
         assert_eq!(got[2].1, expected[2].1);
     }
 
+    #[test]
+    fn acknowledges_chunks() {
+        //[
+        //  "tag.name",
+        //  [[1441588984, {"message": "foo"}], [1441588985, {"message": "bar"}]],
+        //  {"chunk": "p8n9gmxTQVC8/nh2wlKKeQ=="}
+        //]
+        let entry = |timestamp: u32, message: &str| {
+            rmpv::Value::Array(vec![
+                timestamp.into(),
+                rmpv::Value::Map(vec![("message".into(), message.into())]),
+            ])
+        };
+        let message = rmpv::Value::Array(vec![
+            "tag.name".into(),
+            rmpv::Value::Array(vec![entry(1441588984, "foo"), entry(1441588985, "bar")]),
+            rmpv::Value::Map(vec![("chunk".into(), "p8n9gmxTQVC8/nh2wlKKeQ==".into())]),
+        ]);
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, &message).unwrap();
+
+        let mut buf = BytesMut::from(&buf[..]);
+        let (message, _) = FluentDecoder::new().decode(&mut buf).unwrap().unwrap();
+        assert_eq!(message.frames.len(), 2);
+        assert_eq!(message.chunk.as_deref(), Some("p8n9gmxTQVC8/nh2wlKKeQ=="));
+
+        let ack = FluentSource.build_ack(&message);
+        assert_eq!(
+            rmpv::decode::read_value(&mut &ack[..]).unwrap(),
+            rmpv::Value::Map(vec![("ack".into(), "p8n9gmxTQVC8/nh2wlKKeQ==".into())])
+        );
+
+        // Messages without a chunk aren't acknowledged.
+        let message: Vec<u8> = vec![
+            147, 168, 116, 97, 103, 46, 110, 97, 109, 101, 206, 85, 236, 230, 248, 129, 167, 109,
+            101, 115, 115, 97, 103, 101, 163, 98, 97, 114,
+        ];
+        let mut buf = BytesMut::from(&message[..]);
+        let (message, _) = FluentDecoder::new().decode(&mut buf).unwrap().unwrap();
+        assert!(FluentSource.build_ack(&message).is_empty());
+    }
+
     fn decode_all(message: Vec<u8>) -> Result<Vec<(LogEvent, usize)>, DecodeError> {
         let mut buf = BytesMut::from(&message[..]);
 
         let mut decoder = FluentDecoder::new();
 
         let mut frames = vec![];
-        while let Some((frame, byte_size)) = decoder.decode(&mut buf)? {
-            frames.push((LogEvent::from(frame), byte_size))
+        while let Some((message, byte_size)) = decoder.decode(&mut buf)? {
+            frames.extend(
+                message
+                    .frames
+                    .into_iter()
+                    .map(|frame| (LogEvent::from(frame), byte_size)),
+            );
         }
         Ok(frames)
     }
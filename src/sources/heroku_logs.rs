@@ -37,6 +37,8 @@ pub struct LogplexConfig {
     framing: Box<dyn FramingConfig>,
     #[serde(default = "default_decoding")]
     decoding: Box<dyn ParserConfig>,
+    #[serde(default)]
+    max_decompressed_bytes: Option<usize>,
 }
 
 inventory::submit! {
@@ -56,6 +58,7 @@ impl GenerateConfig for LogplexConfig {
             auth: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            max_decompressed_bytes: None,
         })
         .unwrap()
     }
@@ -65,6 +68,7 @@ impl GenerateConfig for LogplexConfig {
 struct LogplexSource {
     query_parameters: Vec<String>,
     decoder: codecs::Decoder,
+    max_decompressed_bytes: Option<usize>,
 }
 
 impl HttpSource for LogplexSource {
@@ -79,6 +83,10 @@ impl HttpSource for LogplexSource {
         add_query_parameters(&mut events, &self.query_parameters, query_parameters);
         Ok(events)
     }
+
+    fn max_decompressed_bytes(&self) -> Option<usize> {
+        self.max_decompressed_bytes
+    }
 }
 
 #[async_trait::async_trait]
@@ -89,6 +97,7 @@ impl SourceConfig for LogplexConfig {
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
             decoder,
+            max_decompressed_bytes: self.max_decompressed_bytes,
         };
         source.run(self.address, "events", true, &self.tls, &self.auth, cx)
     }
@@ -299,6 +308,7 @@ mod tests {
                 auth,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
+                max_decompressed_bytes: None,
             }
             .build(context)
             .await
@@ -42,6 +42,8 @@ pub struct SimpleHttpConfig {
     path_key: String,
     framing: Option<Box<dyn FramingConfig>>,
     decoding: Option<Box<dyn ParserConfig>>,
+    #[serde(default)]
+    max_decompressed_bytes: Option<usize>,
 }
 
 inventory::submit! {
@@ -62,6 +64,7 @@ impl GenerateConfig for SimpleHttpConfig {
             strict_path: true,
             framing: Some(default_framing_stream_based()),
             decoding: Some(default_decoding()),
+            max_decompressed_bytes: None,
         })
         .unwrap()
     }
@@ -81,8 +84,10 @@ struct SimpleHttpSource {
     query_parameters: Vec<String>,
     path_key: String,
     decoder: codecs::Decoder,
+    max_decompressed_bytes: Option<usize>,
 }
 
+#[async_trait::async_trait]
 impl HttpSource for SimpleHttpSource {
     fn build_events(
         &self,
@@ -117,14 +122,22 @@ impl HttpSource for SimpleHttpSource {
 
         let now = Utc::now();
         for event in &mut events {
-            let log = event.as_mut_log();
-
-            log.try_insert(log_schema().source_type_key(), Bytes::from("http"));
-            log.try_insert(log_schema().timestamp_key(), now);
+            if let Event::Log(log) = event {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("http"));
+                log.try_insert(log_schema().timestamp_key(), now);
+            }
         }
 
         Ok(events)
     }
+
+    fn max_decompressed_bytes(&self) -> Option<usize> {
+        self.max_decompressed_bytes
+    }
+
+    async fn resolve(&self, body: &[u8]) {
+        self.decoder.resolve(body).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -173,6 +186,7 @@ impl SourceConfig for SimpleHttpConfig {
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
             decoder,
+            max_decompressed_bytes: self.max_decompressed_bytes,
         };
         source.run(
             self.address,
@@ -185,7 +199,10 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        match (&self.encoding, &self.decoding) {
+            (None, Some(decoding)) => decoding.output_type(),
+            _ => DataType::Log,
+        }
     }
 
     fn source_type(&self) -> &'static str {
@@ -199,9 +216,9 @@ impl SourceConfig for SimpleHttpConfig {
 
 fn add_path(events: &mut [Event], key: &str, path: &str) {
     for event in events.iter_mut() {
-        event
-            .as_mut_log()
-            .try_insert(key, Value::from(path.to_string()));
+        if let Event::Log(log) = event {
+            log.try_insert(key, Value::from(path.to_string()));
+        }
     }
 }
 
@@ -210,10 +227,12 @@ fn add_headers(events: &mut [Event], headers_config: &[String], headers: HeaderM
         let value = headers.get(header_name).map(HeaderValue::as_bytes);
 
         for event in events.iter_mut() {
-            event.as_mut_log().try_insert_flat(
-                header_name as &str,
-                Value::from(value.map(Bytes::copy_from_slice)),
-            );
+            if let Event::Log(log) = event {
+                log.try_insert_flat(
+                    header_name as &str,
+                    Value::from(value.map(Bytes::copy_from_slice)),
+                );
+            }
         }
     }
 }
@@ -275,6 +294,7 @@ mod tests {
                 path,
                 framing,
                 decoding,
+                max_decompressed_bytes: None,
             }
             .build(context)
             .await
@@ -778,6 +798,62 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_zstd_gzip_headers() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"test body").unwrap();
+        let body = zstd::stream::encode_all(&encoder.finish().unwrap()[..], 0).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.append("Content-Encoding", "gzip".parse().unwrap());
+        headers.append("Content-Encoding", "zstd".parse().unwrap());
+
+        let (rx, addr) = source(
+            vec![],
+            vec![],
+            "http_path",
+            "/",
+            true,
+            EventStatus::Delivered,
+            true,
+            None,
+            None,
+        )
+        .await;
+
+        let mut events = spawn_ok_collect_n(send_bytes(addr, body, headers), rx, 1).await;
+
+        let event = events.remove(0);
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn http_max_decompressed_bytes() {
+        let body = zstd::stream::encode_all(&[b'a'; 1024][..], 0).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Encoding", "zstd".parse().unwrap());
+
+        let (sender, _rx) = Pipeline::new_test();
+        let address = next_addr();
+        let mut config: SimpleHttpConfig =
+            toml::from_str(&format!(r#"address = "{}""#, address)).unwrap();
+        config.max_decompressed_bytes = Some(512);
+        tokio::spawn(async move {
+            config
+                .build(SourceContext::new_test(sender))
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        assert_eq!(413, send_bytes(address, body, headers).await);
+    }
+
     #[tokio::test]
     async fn http_path() {
         let (rx, addr) = source(
@@ -1,7 +1,8 @@
+use super::util::finalizer::OrderedFinalizer;
 use crate::{
     codecs::{BoxedFramingError, CharacterDelimitedCodec},
     config::{log_schema, DataType, SourceConfig, SourceContext, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, Value},
     internal_events::{JournaldEventReceived, JournaldInvalidRecord},
     shutdown::ShutdownSignal,
     Pipeline,
@@ -24,6 +25,10 @@ use std::{
     iter::FromIterator,
     process::Stdio,
     str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 use tokio_util::codec::FramedRead;
@@ -32,6 +37,7 @@ use tokio::{
     fs::{File, OpenOptions},
     io::{self, AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
     process::Command,
+    sync::{mpsc, Mutex},
     time::sleep,
 };
 
@@ -183,6 +189,7 @@ impl SourceConfig for JournaldConfig {
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
+                acknowledgements: cx.acknowledgements,
                 out: cx.out,
             }
             .run_shutdown(cx.shutdown, start),
@@ -204,6 +211,7 @@ struct JournaldSource {
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
+    acknowledgements: bool,
     out: Pipeline,
 }
 
@@ -213,7 +221,7 @@ impl JournaldSource {
         shutdown: ShutdownSignal,
         start_journalctl: StartJournalctlFn,
     ) -> Result<(), ()> {
-        let mut checkpointer = Checkpointer::new(self.checkpoint_path.clone())
+        let checkpointer = Checkpointer::new(self.checkpoint_path.clone())
             .await
             .map_err(|error| {
                 error!(
@@ -234,27 +242,32 @@ impl JournaldSource {
             }
         };
 
+        let checkpointer = Arc::new(Mutex::new(checkpointer));
+        let finalizer = Finalizer::new(
+            self.acknowledgements,
+            Arc::clone(&checkpointer),
+            shutdown.clone(),
+        );
+
         let mut on_stop = None;
-        let run = Box::pin(self.run(
-            &mut checkpointer,
-            &mut cursor,
-            &mut on_stop,
-            start_journalctl,
-        ));
+        let run = Box::pin(self.run(&finalizer, &mut cursor, &mut on_stop, start_journalctl));
         future::select(run, shutdown).await;
 
         if let Some(stop) = on_stop {
             stop();
         }
 
-        Self::save_checkpoint(&mut checkpointer, &cursor).await;
+        // With acknowledgements, only the cursors of delivered records are checkpointed.
+        if let (Finalizer::Sync(_), Some(cursor)) = (&finalizer, &cursor) {
+            save_checkpoint(&mut *checkpointer.lock().await, cursor).await;
+        }
 
         Ok(())
     }
 
     async fn run<'a>(
         mut self,
-        checkpointer: &'a mut Checkpointer,
+        finalizer: &'a Finalizer,
         cursor: &'a mut Option<String>,
         on_stop: &'a mut Option<StopJournalctlFn>,
         start_journalctl: StartJournalctlFn,
@@ -264,7 +277,7 @@ impl JournaldSource {
             match start_journalctl(&*cursor) {
                 Ok((stream, stop)) => {
                     *on_stop = Some(stop);
-                    let should_restart = self.run_stream(stream, checkpointer, cursor).await;
+                    let should_restart = self.run_stream(stream, finalizer, cursor).await;
                     if let Some(stop) = on_stop.take() {
                         stop();
                     }
@@ -288,17 +301,21 @@ impl JournaldSource {
     async fn run_stream<'a>(
         &'a mut self,
         mut stream: BoxStream<'static, Result<Bytes, BoxedFramingError>>,
-        checkpointer: &'a mut Checkpointer,
+        finalizer: &'a Finalizer,
         cursor: &'a mut Option<String>,
     ) -> bool {
         loop {
             let mut saw_record = false;
+            let mut stopped = false;
+            let (batch, receiver) =
+                BatchNotifier::maybe_new_with_receiver(finalizer.acknowledges());
 
             for _ in 0..self.batch_size {
                 let bytes = match stream.next().await {
                     None => {
                         warn!("Journalctl process stopped.");
-                        return true;
+                        stopped = true;
+                        break;
                     }
                     Some(Ok(text)) => text,
                     Some(Err(error)) => {
@@ -334,7 +351,12 @@ impl JournaldSource {
                     byte_size: bytes.len()
                 });
 
-                match self.out.send(create_event(record)).await {
+                let mut event = create_event(record);
+                if let Some(batch) = &batch {
+                    event.add_batch_notifier(Arc::clone(batch));
+                }
+
+                match self.out.send(event).await {
                     Ok(_) => {}
                     Err(error) => {
                         error!(message = "Could not send journald log.", %error);
@@ -344,25 +366,95 @@ impl JournaldSource {
                 }
             }
 
+            drop(batch);
             if saw_record {
-                Self::save_checkpoint(checkpointer, &*cursor).await;
+                if let Some(cursor) = &*cursor {
+                    finalizer.finalize(cursor.clone(), receiver).await;
+                }
+            }
+
+            if stopped {
+                return true;
             }
         }
     }
+}
 
-    async fn save_checkpoint(checkpointer: &mut Checkpointer, cursor: &Option<String>) {
-        if let Some(cursor) = cursor {
-            if let Err(error) = checkpointer.set(cursor).await {
-                error!(
-                    message = "Could not set journald checkpoint.",
-                    %error,
-                    filename = ?checkpointer.filename,
-                );
+type SharedCheckpointer = Arc<Mutex<Checkpointer>>;
+
+/// Checkpoints the cursor of each batch of records. With acknowledgements, the cursor is
+/// only checkpointed once all of the batch's events were delivered, and the checkpoint
+/// stops advancing after a batch failed, so that its records are read again on restart.
+enum Finalizer {
+    Sync(SharedCheckpointer),
+    Async(OrderedFinalizer<String>),
+}
+
+impl Finalizer {
+    fn new(
+        acknowledgements: bool,
+        checkpointer: SharedCheckpointer,
+        shutdown: ShutdownSignal,
+    ) -> Self {
+        if acknowledgements {
+            // Checkpoints are saved in their own task, in the order the batches were read.
+            let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+            tokio::spawn(async move {
+                while let Some(cursor) = receiver.recv().await {
+                    save_checkpoint(&mut *checkpointer.lock().await, &cursor).await;
+                }
+            });
+
+            let failed = AtomicBool::new(false);
+            Self::Async(OrderedFinalizer::new(
+                shutdown.shared(),
+                move |status, cursor: String| match status {
+                    BatchStatus::Delivered if !failed.load(Ordering::Relaxed) => {
+                        let _ = sender.send(cursor);
+                    }
+                    BatchStatus::Delivered => debug!(
+                        message = "Not checkpointing journald records after undelivered ones.",
+                        %cursor,
+                    ),
+                    _ => {
+                        failed.store(true, Ordering::Relaxed);
+                        error!(
+                            message = "Journald records were not delivered, not checkpointing them or any later records.",
+                            %cursor,
+                        );
+                    }
+                },
+            ))
+        } else {
+            Self::Sync(checkpointer)
+        }
+    }
+
+    fn acknowledges(&self) -> bool {
+        matches!(self, Self::Async(_))
+    }
+
+    async fn finalize(&self, cursor: String, receiver: Option<BatchStatusReceiver>) {
+        match (self, receiver) {
+            (Self::Sync(checkpointer), _) => {
+                save_checkpoint(&mut *checkpointer.lock().await, &cursor).await
             }
+            (Self::Async(finalizer), Some(receiver)) => finalizer.add(cursor, receiver),
+            (Self::Async(_), None) => unreachable!("Batches have receivers with acknowledgements."),
         }
     }
 }
 
+async fn save_checkpoint(checkpointer: &mut Checkpointer, cursor: &str) {
+    if let Err(error) = checkpointer.set(cursor).await {
+        error!(
+            message = "Could not set journald checkpoint.",
+            %error,
+            filename = ?checkpointer.filename,
+        );
+    }
+}
+
 /// A function that starts journalctl process.
 /// Return a stream of output splitted by '\n', and a `StopJournalctlFn`.
 ///
@@ -649,6 +741,7 @@ mod checkpointer_tests {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::event::EventStatus;
     use futures::Stream;
     use std::pin::Pin;
     use std::{
@@ -656,7 +749,10 @@ mod tests {
         task::{Context, Poll},
     };
     use tempfile::tempdir;
-    use tokio::time::{sleep, timeout, Duration};
+    use tokio::{
+        fs::read_to_string,
+        time::{sleep, timeout, Duration},
+    };
 
     const FAKE_JOURNAL: &str = r#"{"_SYSTEMD_UNIT":"sysinit.target","MESSAGE":"System Initialization","__CURSOR":"1","_SOURCE_REALTIME_TIMESTAMP":"1578529839140001","PRIORITY":"6"}
 {"_SYSTEMD_UNIT":"unit.service","MESSAGE":"unit message","__CURSOR":"2","_SOURCE_REALTIME_TIMESTAMP":"1578529839140002","PRIORITY":"7"}
@@ -752,6 +848,7 @@ mod tests {
             checkpoint_path,
             batch_size: DEFAULT_BATCH_SIZE,
             remap_priority: true,
+            acknowledgements: false,
             out: tx,
         }
         .run_shutdown(
@@ -766,6 +863,61 @@ mod tests {
         timeout(Duration::from_secs(1), rx.collect()).await.unwrap()
     }
 
+    #[tokio::test]
+    async fn checkpoints_delivered_records() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+
+        let tempdir = tempdir().unwrap();
+        let checkpoint_path = tempdir.path().join(CHECKPOINT_FILENAME);
+
+        let source = JournaldSource {
+            include_matches: HashMap::new(),
+            exclude_matches: HashMap::new(),
+            checkpoint_path: checkpoint_path.clone(),
+            batch_size: 3,
+            remap_priority: false,
+            acknowledgements: true,
+            out: tx,
+        }
+        .run_shutdown(
+            shutdown,
+            Box::new(|checkpoint| Ok(FakeJournal::new(checkpoint))),
+        );
+        tokio::spawn(source);
+
+        let checkpoint = || async { read_to_string(&checkpoint_path).await.unwrap() };
+
+        // Nothing is checkpointed before the events are delivered.
+        let mut events = Vec::new();
+        for _ in 0..8 {
+            events.push(rx.next().await.unwrap());
+        }
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(checkpoint().await, "");
+
+        // Delivered batches are checkpointed.
+        let mut errored = events.split_off(3);
+        drop(events);
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(checkpoint().await, "3\n");
+
+        // Errored batches aren't checkpointed.
+        let rest = errored.split_off(3);
+        for event in errored {
+            event.metadata().update_status(EventStatus::Errored);
+        }
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(checkpoint().await, "3\n");
+
+        // Nor are the batches delivered after an errored one.
+        drop(rest);
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(checkpoint().await, "3\n");
+
+        drop(trigger);
+    }
+
     fn create_unit_matches<S: Into<String>>(units: Vec<S>) -> Matches {
         let units: HashSet<String> = units.into_iter().map(Into::into).collect();
         let mut map = HashMap::new();
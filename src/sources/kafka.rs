@@ -2,7 +2,7 @@ use super::util::finalizer::OrderedFinalizer;
 use crate::{
     codecs::{self, DecodingConfig, FramingConfig, ParserConfig},
     config::{log_schema, DataType, SourceConfig, SourceContext, SourceDescription},
-    event::{BatchNotifier, Event, Value},
+    event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed},
     kafka::{KafkaAuthConfig, KafkaStatisticsContext},
     serde::{default_decoding, default_framing_message_based},
@@ -12,7 +12,7 @@ use crate::{
 };
 use bytes::Bytes;
 use chrono::{TimeZone, Utc};
-use futures::{FutureExt, SinkExt, StreamExt, TryStreamExt};
+use futures::{stream, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use futures_util::future::ready;
 use rdkafka::{
     config::ClientConfig,
@@ -142,7 +142,7 @@ impl SourceConfig for KafkaSourceConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding.output_type()
     }
 
     fn source_type(&self) -> &'static str {
@@ -220,23 +220,27 @@ async fn kafka_source(
                 let offset_key = &offset_key;
                 let headers_key = &headers_key;
 
+                decoder.resolve(payload).await;
                 let payload = Cursor::new(Bytes::copy_from_slice(payload));
 
                 let mut stream = FramedRead::new(payload, decoder.clone())
                     .map(|input| match input {
+                        // A frame can decode to any number of events, e.g. none for
+                        // a partial GELF message.
                         Ok((mut events, _)) => {
-                            let mut event = events.pop().expect("event must exist");
-                            if let Event::Log(ref mut log) = event {
-                                log.try_insert(schema.source_type_key(), Bytes::from("kafka"));
-                                log.try_insert(schema.timestamp_key(), timestamp);
-                                log.try_insert(key_field, msg_key.clone());
-                                log.try_insert(topic_key, Value::from(msg_topic.clone()));
-                                log.try_insert(partition_key, Value::from(msg_partition));
-                                log.try_insert(offset_key, Value::from(msg_offset));
-                                log.try_insert(headers_key, Value::from(headers_map.clone()));
+                            for event in &mut events {
+                                if let Event::Log(ref mut log) = event {
+                                    log.try_insert(schema.source_type_key(), Bytes::from("kafka"));
+                                    log.try_insert(schema.timestamp_key(), timestamp);
+                                    log.try_insert(key_field, msg_key.clone());
+                                    log.try_insert(topic_key, Value::from(msg_topic.clone()));
+                                    log.try_insert(partition_key, Value::from(msg_partition));
+                                    log.try_insert(offset_key, Value::from(msg_offset));
+                                    log.try_insert(headers_key, Value::from(headers_map.clone()));
+                                }
                             }
 
-                            Some(Some(Ok(event)))
+                            Some(Some(events))
                         }
                         Err(e) => {
                             // Error is logged by `crate::codecs::Decoder`, no further handling
@@ -249,7 +253,8 @@ async fn kafka_source(
                         }
                     })
                     .take_while(|x| ready(x.is_some()))
-                    .filter_map(|x| ready(x.expect("should have inner value")));
+                    .filter_map(|x| ready(x.expect("should have inner value")))
+                    .flat_map(|events| stream::iter(events.into_iter().map(Ok)));
 
                 match &mut finalizer {
                     Some(finalizer) => {
@@ -300,8 +305,10 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
     }
 }
 
-fn mark_done(consumer: Arc<StreamConsumer<KafkaStatisticsContext>>) -> impl Fn(FinalizerEntry) {
-    move |entry| {
+fn mark_done(
+    consumer: Arc<StreamConsumer<KafkaStatisticsContext>>,
+) -> impl Fn(BatchStatus, FinalizerEntry) {
+    move |_status, entry| {
         if let Err(error) = consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
             emit!(&KafkaOffsetUpdateFailed { error });
         }
@@ -61,6 +61,7 @@ impl SourceConfig for LogstashConfig {
             tls,
             self.receive_buffer_bytes,
             cx.shutdown,
+            cx.acknowledgements,
             cx.out,
         )
     }
@@ -323,8 +324,8 @@ impl Decoder for LogstashDecoder {
                     }
                 }
                 // The window size indicates how many events the writer will send before waiting
-                // for acks. As we forward events as we get them, and ack as they are receieved, we
-                // do not need to keep track of this.
+                // for acks. As we ack the frames we read once their events are forwarded, or
+                // delivered with acknowledgements, we do not need to keep track of this.
                 //
                 // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#window-size-frame-type
                 LogstashDecoderReadState::ReadFrame(_protocol, LogstashFrameType::WindowSize) => {
@@ -514,11 +515,73 @@ impl From<LogstashEventFrame> for SmallVec<[Event; 1]> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{next_addr, wait_for_tcp},
+        Pipeline,
+    };
+    use futures::StreamExt;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        time::{timeout, Duration},
+    };
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<LogstashConfig>();
     }
+
+    async fn send_with_acknowledgements(status: EventStatus) -> Vec<u8> {
+        let (tx, mut rx) = Pipeline::new_test_finalize(status);
+        let address = next_addr();
+        let mut cx = SourceContext::new_test(tx);
+        cx.acknowledgements = true;
+        let source = LogstashConfig {
+            address: address.into(),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+        }
+        .build(cx)
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let payload = br#"{"message":"hello"}"#;
+        let mut frames = vec![b'2', b'W', 0, 0, 0, 1, b'2', b'J'];
+        frames.extend(&1u32.to_be_bytes());
+        frames.extend(&(payload.len() as u32).to_be_bytes());
+        frames.extend(&payload[..]);
+
+        let mut socket = TcpStream::connect(address).await.unwrap();
+        socket.write_all(&frames).await.unwrap();
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(event.as_log()["message"], "hello".into());
+        drop(event);
+
+        let mut ack = vec![0; 6];
+        let read = timeout(Duration::from_secs(5), socket.read(&mut ack))
+            .await
+            .unwrap()
+            .unwrap();
+        ack.truncate(read);
+        ack
+    }
+
+    #[tokio::test]
+    async fn acknowledges_delivered_frames() {
+        let ack = send_with_acknowledgements(EventStatus::Delivered).await;
+        assert_eq!(ack, vec![b'2', b'A', 0, 0, 0, 1]);
+    }
+
+    #[tokio::test]
+    async fn closes_connection_without_acknowledging_errored_frames() {
+        let ack = send_with_acknowledgements(EventStatus::Errored).await;
+        assert!(ack.is_empty());
+    }
 }
 
 #[cfg(all(test, feature = "logstash-integration-tests"))]
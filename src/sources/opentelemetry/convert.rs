@@ -0,0 +1,585 @@
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+        Event, LogEvent, Value,
+    },
+    proto::opentelemetry::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+        logs::v1::LogRecord,
+        metrics::v1::{
+            metric, number_data_point, AggregationTemporality, HistogramDataPoint, NumberDataPoint,
+            SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY,
+        RESOURCES_KEY, SCOPE_KEY, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY,
+        TRACE_ID_KEY,
+    },
+};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+pub const SOURCE_NAME: &str = "opentelemetry";
+
+/// Flattens an OTLP logs export request into one log event per log record.
+pub fn logs_into_events(request: ExportLogsServiceRequest) -> Vec<Event> {
+    let now = Utc::now();
+
+    request
+        .resource_logs
+        .into_iter()
+        .flat_map(|resource_logs| {
+            let resources = resource_attributes(resource_logs.resource);
+            resource_logs
+                .scope_logs
+                .into_iter()
+                .flat_map(move |scope_logs| {
+                    let resources = resources.clone();
+                    let scope = scope_logs.scope.map(scope_into_value);
+                    scope_logs.log_records.into_iter().map(move |record| {
+                        record_into_event(record, resources.clone(), scope.clone(), now)
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Flattens an OTLP metrics export request into one metric event per data point.
+pub fn metrics_into_events(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    for resource_metrics in request.resource_metrics {
+        let mut base_tags = MetricTags::new();
+        for (key, value) in resource_attributes(resource_metrics.resource) {
+            base_tags.insert(
+                format!("{}.{}", RESOURCES_KEY, key),
+                value.to_string_lossy(),
+            );
+        }
+
+        for scope_metrics in resource_metrics.scope_metrics {
+            let mut tags = base_tags.clone();
+            if let Some(scope) = scope_metrics.scope {
+                insert_scope_tags(&mut tags, scope);
+            }
+
+            for metric in scope_metrics.metrics {
+                let name = metric.name;
+                match metric.data {
+                    Some(metric::Data::Gauge(gauge)) => {
+                        events.extend(gauge.data_points.into_iter().filter_map(|point| {
+                            number_into_metric(&name, &tags, point, MetricKind::Absolute, |value| {
+                                MetricValue::Gauge { value }
+                            })
+                        }))
+                    }
+                    Some(metric::Data::Sum(sum)) => {
+                        let kind = temporality_into_kind(sum.aggregation_temporality);
+                        let is_monotonic = sum.is_monotonic;
+                        events.extend(sum.data_points.into_iter().filter_map(|point| {
+                            number_into_metric(&name, &tags, point, kind, |value| {
+                                if is_monotonic {
+                                    MetricValue::Counter { value }
+                                } else {
+                                    MetricValue::Gauge { value }
+                                }
+                            })
+                        }))
+                    }
+                    Some(metric::Data::Histogram(histogram)) => {
+                        let kind = temporality_into_kind(histogram.aggregation_temporality);
+                        events.extend(
+                            histogram
+                                .data_points
+                                .into_iter()
+                                .map(|point| histogram_into_metric(&name, &tags, point, kind)),
+                        )
+                    }
+                    Some(metric::Data::Summary(summary)) => events.extend(
+                        summary
+                            .data_points
+                            .into_iter()
+                            .map(|point| summary_into_metric(&name, &tags, point)),
+                    ),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    events
+}
+
+fn record_into_event(
+    record: LogRecord,
+    resources: BTreeMap<String, Value>,
+    scope: Option<Value>,
+    now: DateTime<Utc>,
+) -> Event {
+    let mut log = LogEvent::default();
+
+    if let Some(body) = record.body {
+        log.insert(log_schema().message_key(), any_value_into_value(body));
+    }
+
+    let observed_timestamp = nanos_into_timestamp(record.observed_time_unix_nano);
+    let timestamp = nanos_into_timestamp(record.time_unix_nano)
+        .or(observed_timestamp)
+        .unwrap_or(now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    if let Some(observed_timestamp) = observed_timestamp {
+        log.insert_flat(OBSERVED_TIMESTAMP_KEY, observed_timestamp);
+    }
+
+    let attributes = key_values_into_map(record.attributes);
+    if !attributes.is_empty() {
+        log.insert_flat(ATTRIBUTES_KEY, attributes);
+    }
+    if !resources.is_empty() {
+        log.insert_flat(RESOURCES_KEY, resources);
+    }
+    if let Some(scope) = scope {
+        log.insert_flat(SCOPE_KEY, scope);
+    }
+
+    if !record.severity_text.is_empty() {
+        log.insert_flat(SEVERITY_TEXT_KEY, record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert_flat(SEVERITY_NUMBER_KEY, record.severity_number);
+    }
+    if !record.trace_id.is_empty() {
+        log.insert_flat(TRACE_ID_KEY, hex::encode(record.trace_id));
+    }
+    if !record.span_id.is_empty() {
+        log.insert_flat(SPAN_ID_KEY, hex::encode(record.span_id));
+    }
+    if record.flags != 0 {
+        log.insert_flat(FLAGS_KEY, record.flags);
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert_flat(
+            DROPPED_ATTRIBUTES_COUNT_KEY,
+            record.dropped_attributes_count,
+        );
+    }
+
+    log.insert(log_schema().source_type_key(), Bytes::from(SOURCE_NAME));
+
+    log.into()
+}
+
+fn number_into_metric(
+    name: &str,
+    tags: &MetricTags,
+    point: NumberDataPoint,
+    kind: MetricKind,
+    into_value: impl Fn(f64) -> MetricValue,
+) -> Option<Event> {
+    let value = match point.value? {
+        number_data_point::Value::AsDouble(value) => value,
+        number_data_point::Value::AsInt(value) => value as f64,
+    };
+
+    Some(
+        Metric::new(name, kind, into_value(value))
+            .with_tags(Some(point_tags(tags, point.attributes)))
+            .with_timestamp(nanos_into_timestamp(point.time_unix_nano))
+            .into(),
+    )
+}
+
+fn histogram_into_metric(
+    name: &str,
+    tags: &MetricTags,
+    point: HistogramDataPoint,
+    kind: MetricKind,
+) -> Event {
+    // OTLP buckets are not cumulative, like `MetricValue::AggregatedHistogram`
+    // ones. Their last bucket has no upper bound, and is left implicit in the
+    // count of the histogram, as sinks add the `+Inf` bucket themselves.
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(&point.bucket_counts)
+        .map(|(&upper_limit, &count)| Bucket {
+            upper_limit,
+            count: saturating_u32(count),
+        })
+        .collect();
+
+    Metric::new(
+        name,
+        kind,
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+    )
+    .with_tags(Some(point_tags(tags, point.attributes)))
+    .with_timestamp(nanos_into_timestamp(point.time_unix_nano))
+    .into()
+}
+
+fn summary_into_metric(name: &str, tags: &MetricTags, point: SummaryDataPoint) -> Event {
+    let quantiles = point
+        .quantile_values
+        .iter()
+        .map(|quantile| Quantile {
+            upper_limit: quantile.quantile,
+            value: quantile.value,
+        })
+        .collect();
+
+    Metric::new(
+        name,
+        MetricKind::Absolute,
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+    )
+    .with_tags(Some(point_tags(tags, point.attributes)))
+    .with_timestamp(nanos_into_timestamp(point.time_unix_nano))
+    .into()
+}
+
+const fn temporality_into_kind(temporality: i32) -> MetricKind {
+    if temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn point_tags(tags: &MetricTags, attributes: Vec<KeyValue>) -> MetricTags {
+    let mut tags = tags.clone();
+    for (key, value) in key_values_into_map(attributes) {
+        tags.insert(key, value.to_string_lossy());
+    }
+    tags
+}
+
+fn insert_scope_tags(tags: &mut MetricTags, scope: InstrumentationScope) {
+    if !scope.name.is_empty() {
+        tags.insert(format!("{}.name", SCOPE_KEY), scope.name);
+    }
+    if !scope.version.is_empty() {
+        tags.insert(format!("{}.version", SCOPE_KEY), scope.version);
+    }
+    for (key, value) in key_values_into_map(scope.attributes) {
+        tags.insert(
+            format!("{}.{}.{}", SCOPE_KEY, ATTRIBUTES_KEY, key),
+            value.to_string_lossy(),
+        );
+    }
+}
+
+fn scope_into_value(scope: InstrumentationScope) -> Value {
+    let mut map = BTreeMap::new();
+    if !scope.name.is_empty() {
+        map.insert("name".to_string(), Value::from(scope.name));
+    }
+    if !scope.version.is_empty() {
+        map.insert("version".to_string(), Value::from(scope.version));
+    }
+    let attributes = key_values_into_map(scope.attributes);
+    if !attributes.is_empty() {
+        map.insert(ATTRIBUTES_KEY.to_string(), Value::from(attributes));
+    }
+    Value::from(map)
+}
+
+fn resource_attributes(resource: Option<Resource>) -> BTreeMap<String, Value> {
+    resource
+        .map(|resource| key_values_into_map(resource.attributes))
+        .unwrap_or_default()
+}
+
+fn key_values_into_map(key_values: Vec<KeyValue>) -> BTreeMap<String, Value> {
+    key_values
+        .into_iter()
+        .map(|kv| {
+            let value = kv.value.map(any_value_into_value).unwrap_or(Value::Null);
+            (kv.key, value)
+        })
+        .collect()
+}
+
+fn any_value_into_value(value: AnyValue) -> Value {
+    match value.value {
+        Some(any_value::Value::StringValue(value)) => Value::from(value),
+        Some(any_value::Value::BoolValue(value)) => Value::from(value),
+        Some(any_value::Value::IntValue(value)) => Value::from(value),
+        Some(any_value::Value::DoubleValue(value)) => Value::from(value),
+        Some(any_value::Value::BytesValue(value)) => Value::from(Bytes::from(value)),
+        Some(any_value::Value::ArrayValue(array)) => Value::from(
+            array
+                .values
+                .into_iter()
+                .map(any_value_into_value)
+                .collect::<Vec<_>>(),
+        ),
+        Some(any_value::Value::KvlistValue(list)) => Value::from(key_values_into_map(list.values)),
+        None => Value::Null,
+    }
+}
+
+fn nanos_into_timestamp(nanos: u64) -> Option<DateTime<Utc>> {
+    (nanos != 0)
+        .then(|| i64::try_from(nanos).ok())
+        .flatten()
+        .map(|nanos| Utc.timestamp_nanos(nanos))
+}
+
+fn saturating_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::{
+        logs::v1::{ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            summary_data_point::ValueAtQuantile, Gauge, Histogram, Metric as OtlpMetric,
+            ResourceMetrics, ScopeMetrics, Sum, Summary,
+        },
+    };
+    use shared::btreemap;
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        })
+    }
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: string_value(value),
+        }
+    }
+
+    fn resource() -> Option<Resource> {
+        Some(Resource {
+            attributes: vec![key_value("service.name", "checkout")],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn scope() -> Option<InstrumentationScope> {
+        Some(InstrumentationScope {
+            name: "tracer".into(),
+            version: "1.2.3".into(),
+            attributes: Vec::new(),
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn metrics_request(data: metric::Data) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: resource(),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: scope(),
+                    metrics: vec![OtlpMetric {
+                        name: "requests".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(data),
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    fn expected_tags() -> MetricTags {
+        vec![
+            ("method".to_string(), "GET".to_string()),
+            ("resources.service.name".to_string(), "checkout".to_string()),
+            ("scope.name".to_string(), "tracer".to_string()),
+            ("scope.version".to_string(), "1.2.3".to_string()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn converts_log_records() {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: resource(),
+                scope_logs: vec![ScopeLogs {
+                    scope: scope(),
+                    log_records: vec![LogRecord {
+                        time_unix_nano: 1_633_089_600_000_000_000,
+                        observed_time_unix_nano: 0,
+                        severity_number: 9,
+                        severity_text: "INFO".into(),
+                        body: string_value("hello world"),
+                        attributes: vec![key_value("http.method", "GET")],
+                        dropped_attributes_count: 0,
+                        flags: 1,
+                        trace_id: vec![0x4b, 0xf9, 0x2f, 0x35],
+                        span_id: vec![0x00, 0xf0, 0x67, 0xaa],
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+
+        let events = logs_into_events(request);
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "hello world".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.ymd(2021, 10, 1).and_hms(12, 0, 0).into()
+        );
+        assert_eq!(log[log_schema().source_type_key()], SOURCE_NAME.into());
+        assert_eq!(
+            log[ATTRIBUTES_KEY],
+            Value::Map(btreemap! { "http.method" => "GET" })
+        );
+        assert_eq!(
+            log[RESOURCES_KEY],
+            Value::Map(btreemap! { "service.name" => "checkout" })
+        );
+        assert_eq!(log["scope.name"], "tracer".into());
+        assert_eq!(log["scope.version"], "1.2.3".into());
+        assert_eq!(log[SEVERITY_TEXT_KEY], "INFO".into());
+        assert_eq!(log[SEVERITY_NUMBER_KEY], 9.into());
+        assert_eq!(log[TRACE_ID_KEY], "4bf92f35".into());
+        assert_eq!(log[SPAN_ID_KEY], "00f067aa".into());
+        assert_eq!(log[FLAGS_KEY], 1.into());
+        assert!(log.get(OBSERVED_TIMESTAMP_KEY).is_none());
+        assert!(log.get(DROPPED_ATTRIBUTES_COUNT_KEY).is_none());
+    }
+
+    #[test]
+    fn converts_gauges_and_sums() {
+        let point = NumberDataPoint {
+            attributes: vec![key_value("method", "GET")],
+            start_time_unix_nano: 0,
+            time_unix_nano: 1_633_089_600_000_000_000,
+            value: Some(number_data_point::Value::AsInt(42)),
+            flags: 0,
+        };
+
+        let gauge = metrics_into_events(metrics_request(metric::Data::Gauge(Gauge {
+            data_points: vec![point.clone()],
+        })));
+        assert_eq!(
+            gauge[0].as_metric(),
+            &Metric::new(
+                "requests",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 42.0 }
+            )
+            .with_tags(Some(expected_tags()))
+            .with_timestamp(Some(Utc.ymd(2021, 10, 1).and_hms(12, 0, 0)))
+        );
+
+        let counter = metrics_into_events(metrics_request(metric::Data::Sum(Sum {
+            data_points: vec![point.clone()],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+            is_monotonic: true,
+        })));
+        assert_eq!(counter[0].as_metric().kind(), MetricKind::Incremental);
+        assert_eq!(
+            counter[0].as_metric().value(),
+            &MetricValue::Counter { value: 42.0 }
+        );
+
+        let up_down = metrics_into_events(metrics_request(metric::Data::Sum(Sum {
+            data_points: vec![point],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            is_monotonic: false,
+        })));
+        assert_eq!(up_down[0].as_metric().kind(), MetricKind::Absolute);
+        assert_eq!(
+            up_down[0].as_metric().value(),
+            &MetricValue::Gauge { value: 42.0 }
+        );
+    }
+
+    #[test]
+    fn converts_histograms() {
+        let events = metrics_into_events(metrics_request(metric::Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                attributes: vec![key_value("method", "GET")],
+                start_time_unix_nano: 0,
+                time_unix_nano: 0,
+                count: 6,
+                sum: 12.5,
+                bucket_counts: vec![1, 2, 3],
+                explicit_bounds: vec![1.0, 5.0],
+                flags: 0,
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+        })));
+
+        assert_eq!(
+            events[0].as_metric(),
+            &Metric::new(
+                "requests",
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vec![
+                        Bucket {
+                            upper_limit: 1.0,
+                            count: 1
+                        },
+                        Bucket {
+                            upper_limit: 5.0,
+                            count: 2
+                        },
+                    ],
+                    count: 6,
+                    sum: 12.5,
+                }
+            )
+            .with_tags(Some(expected_tags()))
+        );
+    }
+
+    #[test]
+    fn converts_summaries() {
+        let events = metrics_into_events(metrics_request(metric::Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: vec![key_value("method", "GET")],
+                start_time_unix_nano: 0,
+                time_unix_nano: 0,
+                count: 3,
+                sum: 6.0,
+                quantile_values: vec![ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 2.0,
+                }],
+                flags: 0,
+            }],
+        })));
+
+        assert_eq!(
+            events[0].as_metric().value(),
+            &MetricValue::AggregatedSummary {
+                quantiles: vec![Quantile {
+                    upper_limit: 0.5,
+                    value: 2.0
+                }],
+                count: 3,
+                sum: 6.0,
+            }
+        );
+    }
+}
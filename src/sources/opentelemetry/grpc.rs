@@ -0,0 +1,100 @@
+use super::convert;
+use crate::{
+    internal_events::OpenTelemetryEventsReceived,
+    proto::opentelemetry::collector::{logs::v1 as logs, metrics::v1 as metrics},
+    shutdown::{ShutdownSignal, ShutdownSignalToken},
+    sources::util::grpc::handle_batch_status,
+    tls::MaybeTlsSettings,
+    Pipeline,
+};
+use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
+use std::{net::SocketAddr, sync::Arc};
+use tonic::{transport::Server, Request, Response, Status};
+use vector_core::{
+    event::{BatchNotifier, Event},
+    ByteSizeOf,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct Service {
+    pub(super) pipeline: Pipeline,
+    pub(super) acknowledgements: bool,
+}
+
+impl Service {
+    async fn handle_events(&self, mut events: Vec<Event>) -> Result<(), Status> {
+        emit!(&OpenTelemetryEventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+            protocol: "grpc",
+        });
+
+        let receiver = self.acknowledgements.then(|| {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            for event in &mut events {
+                event.add_batch_notifier(Arc::clone(&batch));
+            }
+
+            receiver
+        });
+
+        self.pipeline
+            .clone()
+            .send_all(&mut futures::stream::iter(events).map(Ok))
+            .map_err(|err| Status::unavailable(err.to_string()))
+            .and_then(|_| handle_batch_status(receiver))
+            .await
+    }
+}
+
+#[tonic::async_trait]
+impl logs::Service for Service {
+    async fn export(
+        &self,
+        request: Request<logs::ExportLogsServiceRequest>,
+    ) -> Result<Response<logs::ExportLogsServiceResponse>, Status> {
+        self.handle_events(convert::logs_into_events(request.into_inner()))
+            .await?;
+
+        Ok(Response::new(logs::ExportLogsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl metrics::Service for Service {
+    async fn export(
+        &self,
+        request: Request<metrics::ExportMetricsServiceRequest>,
+    ) -> Result<Response<metrics::ExportMetricsServiceResponse>, Status> {
+        self.handle_events(convert::metrics_into_events(request.into_inner()))
+            .await?;
+
+        Ok(Response::new(metrics::ExportMetricsServiceResponse {}))
+    }
+}
+
+pub(super) async fn run(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    service: Service,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let _span = crate::trace::current_span();
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream();
+
+    info!(message = "Building gRPC server.", address = %address);
+
+    Server::builder()
+        .add_service(logs::Server::new(service.clone()))
+        .add_service(metrics::Server::new(service))
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use super::convert;
+use crate::{
+    event::Event,
+    proto::opentelemetry::collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+    },
+    sources::util::{ErrorMessage, HttpSource},
+};
+use bytes::Bytes;
+use http::StatusCode;
+use prost::Message;
+use std::collections::HashMap;
+use warp::http::HeaderMap;
+
+pub(super) const PATH_PREFIX: &str = "v1";
+const LOGS_PATH: &str = "/v1/logs";
+const METRICS_PATH: &str = "/v1/metrics";
+const CONTENT_TYPE: &str = "application/x-protobuf";
+
+#[derive(Clone)]
+pub(super) struct OpenTelemetryHttpSource;
+
+impl HttpSource for OpenTelemetryHttpSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        if let Some(content_type) = header_map.get("content-type") {
+            if content_type.as_bytes() != CONTENT_TYPE.as_bytes() {
+                return Err(ErrorMessage::new(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    format!("Unsupported content type, expected {:?}", CONTENT_TYPE),
+                ));
+            }
+        }
+
+        match path {
+            LOGS_PATH => ExportLogsServiceRequest::decode(body)
+                .map(convert::logs_into_events)
+                .map_err(decode_error),
+            METRICS_PATH => ExportMetricsServiceRequest::decode(body)
+                .map(convert::metrics_into_events)
+                .map_err(decode_error),
+            _ => Err(ErrorMessage::new(
+                StatusCode::NOT_FOUND,
+                "Not found".to_string(),
+            )),
+        }
+    }
+}
+
+fn decode_error(error: prost::DecodeError) -> ErrorMessage {
+    ErrorMessage::new(
+        StatusCode::BAD_REQUEST,
+        format!("Could not decode request: {}", error),
+    )
+}
@@ -0,0 +1,218 @@
+mod convert;
+mod grpc;
+mod http;
+
+use crate::{
+    config::{DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceDescription},
+    sources::util::HttpSource,
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+use futures::{future, FutureExt, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpenTelemetryConfig {
+    grpc: GrpcConfig,
+    http: HttpConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct GrpcConfig {
+    address: SocketAddr,
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct HttpConfig {
+    address: SocketAddr,
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpenTelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpenTelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: GrpcConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+                tls: None,
+            },
+            http: HttpConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: None,
+            },
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpenTelemetryConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let grpc_tls_settings = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
+        let grpc_service = grpc::Service {
+            pipeline: cx.out.clone(),
+            acknowledgements: cx.acknowledgements,
+        };
+        let grpc_source = grpc::run(
+            self.grpc.address,
+            grpc_tls_settings,
+            grpc_service,
+            cx.shutdown.clone(),
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
+
+        let http_source = http::OpenTelemetryHttpSource.run(
+            self.http.address,
+            http::PATH_PREFIX,
+            false,
+            &self.http.tls,
+            &None,
+            cx,
+        )?;
+
+        Ok(future::try_join(grpc_source, http_source)
+            .map_ok(|_| ())
+            .boxed())
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            Resource::tcp(self.grpc.address),
+            Resource::tcp(self.http.address),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::Event,
+        proto::opentelemetry::{
+            collector::logs::v1::{
+                logs_service_client::LogsServiceClient, ExportLogsServiceRequest,
+            },
+            common::v1::{any_value, AnyValue},
+            logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        },
+        test_util::{self, next_addr, wait_for_tcp},
+        Pipeline,
+    };
+    use futures::Stream;
+    use prost::Message;
+
+    #[test]
+    fn generate_config() {
+        test_util::test_generate_config::<OpenTelemetryConfig>();
+    }
+
+    async fn source() -> (impl Stream<Item = Event> + Unpin, SocketAddr, SocketAddr) {
+        let (tx, rx) = Pipeline::new_test();
+        let grpc_address = next_addr();
+        let http_address = next_addr();
+        let config = OpenTelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_address,
+                tls: None,
+            },
+            http: HttpConfig {
+                address: http_address,
+                tls: None,
+            },
+        };
+        let source = config.build(SourceContext::new_test(tx)).await.unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(grpc_address).await;
+        wait_for_tcp(http_address).await;
+        (rx, grpc_address, http_address)
+    }
+
+    fn logs_request(message: &str) -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: None,
+                scope_logs: vec![ScopeLogs {
+                    scope: None,
+                    log_records: vec![LogRecord {
+                        body: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue(message.into())),
+                        }),
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_grpc() {
+        let (rx, grpc_address, _) = source().await;
+
+        let mut client = LogsServiceClient::connect(format!("http://{}", grpc_address))
+            .await
+            .unwrap();
+        client.export(logs_request("over grpc")).await.unwrap();
+
+        let events = test_util::collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "over grpc".into());
+        assert_eq!(log[log_schema().source_type_key()], "opentelemetry".into());
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_http() {
+        let (rx, _, http_address) = source().await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/v1/logs", http_address))
+            .header("content-type", "application/x-protobuf")
+            .body(logs_request("over http").encode_to_vec())
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, 200);
+
+        let events = test_util::collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "over http".into());
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_http_paths() {
+        let (_rx, _, http_address) = source().await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/v1/traces", http_address))
+            .header("content-type", "application/x-protobuf")
+            .body(Vec::new())
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, 404);
+    }
+}
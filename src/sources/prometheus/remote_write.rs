@@ -5,7 +5,7 @@ use crate::{
     internal_events::PrometheusRemoteWriteParseError,
     sources::{
         self,
-        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{content_encoding, decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
     },
     tls::TlsConfig,
 };
@@ -25,6 +25,9 @@ struct PrometheusRemoteWriteConfig {
     tls: Option<TlsConfig>,
 
     auth: Option<HttpSourceAuthConfig>,
+
+    #[serde(default)]
+    max_decompressed_bytes: Option<usize>,
 }
 
 inventory::submit! {
@@ -37,6 +40,7 @@ impl GenerateConfig for PrometheusRemoteWriteConfig {
             address: "127.0.0.1:9090".parse().unwrap(),
             tls: None,
             auth: None,
+            max_decompressed_bytes: None,
         })
         .unwrap()
     }
@@ -46,7 +50,9 @@ impl GenerateConfig for PrometheusRemoteWriteConfig {
 #[typetag::serde(name = "prometheus_remote_write")]
 impl SourceConfig for PrometheusRemoteWriteConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
-        let source = RemoteWriteSource;
+        let source = RemoteWriteSource {
+            max_decompressed_bytes: self.max_decompressed_bytes,
+        };
         source.run(self.address, "", true, &self.tls, &self.auth, cx)
     }
 
@@ -60,7 +66,9 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
 }
 
 #[derive(Clone)]
-struct RemoteWriteSource;
+struct RemoteWriteSource {
+    max_decompressed_bytes: Option<usize>,
+}
 
 impl RemoteWriteSource {
     fn decode_body(&self, body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
@@ -90,18 +98,27 @@ impl HttpSource for RemoteWriteSource {
         _query_parameters: HashMap<String, String>,
         _full_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        // If `Content-Encoding` header isn't `snappy` HttpSource won't decode it for us
+        // If `Content-Encoding` header doesn't list `snappy` HttpSource won't decode it for us
         // se we need to.
-        if header_map
-            .get("Content-Encoding")
-            .map(|header| header.as_ref())
-            != Some(b"snappy")
-        {
-            body = decode(&Some("snappy".to_string()), body)?;
+        let snappy_encoded = content_encoding(&header_map).map_or(false, |encodings| {
+            encodings
+                .split(',')
+                .any(|encoding| encoding.trim() == "snappy")
+        });
+        if !snappy_encoded {
+            body = decode(
+                &Some("snappy".to_string()),
+                body,
+                self.max_decompressed_bytes,
+            )?;
         }
         let events = self.decode_body(body)?;
         Ok(events)
     }
+
+    fn max_decompressed_bytes(&self) -> Option<usize> {
+        self.max_decompressed_bytes
+    }
 }
 
 #[cfg(test)]
@@ -145,6 +162,7 @@ mod test {
             address,
             auth: None,
             tls: tls.clone(),
+            max_decompressed_bytes: None,
         };
         let source = source.build(SourceContext::new_test(tx)).await.unwrap();
         tokio::spawn(source);
@@ -236,6 +254,7 @@ mod integration_tests {
             address: PROMETHEUS_RECEIVE_ADDRESS.parse().unwrap(),
             auth: None,
             tls: None,
+            max_decompressed_bytes: None,
         };
 
         let (tx, rx) = Pipeline::new_test();
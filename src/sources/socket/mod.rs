@@ -109,6 +109,7 @@ impl SourceConfig for SocketConfig {
                     tls,
                     config.receive_buffer_bytes(),
                     cx.shutdown,
+                    false,
                     cx.out,
                 )
             }
@@ -185,7 +186,12 @@ impl SourceConfig for SocketConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        match &self.mode {
+            Mode::Tcp(config) => config.decoding().output_type(),
+            Mode::Udp(config) => config.decoding().output_type(),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) | Mode::UnixStream(config) => config.decoding.output_type(),
+        }
     }
 
     fn source_type(&self) -> &'static str {
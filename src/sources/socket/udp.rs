@@ -96,6 +96,7 @@ pub fn udp(
 
                     let payload = buf.split_to(byte_size);
 
+                    decoder.resolve(&payload).await;
                     let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
 
                     loop {
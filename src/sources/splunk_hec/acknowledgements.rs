@@ -0,0 +1,324 @@
+//! Indexer acknowledgements, with which clients learn that the events they sent were delivered.
+//! Requests sent on a channel are given an `ackId`, resolved once all of their events were
+//! delivered by the sinks, and which clients then query on the `/services/collector/ack`
+//! endpoint.
+
+use super::ApiError;
+use crate::event::{BatchStatus, BatchStatusReceiver};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::time;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HecAcknowledgementsConfig {
+    /// Maximum number of pending acks, over all channels
+    pub max_pending_acks: u64,
+    /// Maximum number of channels clients can open
+    pub max_number_of_ack_channels: u64,
+    /// Maximum number of pending acks of a single channel
+    pub max_pending_acks_per_channel: u64,
+    /// Whether to drop channels, along with their pending acks, once they're idle
+    pub ack_idle_cleanup: bool,
+    /// Time after which a channel without any request is idle
+    pub max_idle_time_secs: u64,
+}
+
+impl Default for HecAcknowledgementsConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_acks: 10_000_000,
+            max_number_of_ack_channels: 1_000_000,
+            max_pending_acks_per_channel: 1_000_000,
+            ack_idle_cleanup: true,
+            max_idle_time_secs: 300,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct HecAckStatusRequest {
+    pub acks: Vec<u64>,
+}
+
+#[derive(Serialize)]
+pub struct HecAckStatusResponse {
+    pub acks: HashMap<u64, bool>,
+}
+
+struct Channel {
+    next_ack_id: u64,
+    /// Ack ids not queried as acknowledged yet, with whether their events were delivered.
+    statuses: HashMap<u64, bool>,
+    last_used: Instant,
+}
+
+impl Channel {
+    fn new() -> Self {
+        Self {
+            next_ack_id: 0,
+            statuses: HashMap::new(),
+            last_used: Instant::now(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    channels: HashMap<String, Channel>,
+    pending_acks: u64,
+}
+
+/// Tracks the ack ids of the requests received on each channel.
+pub struct IndexerAcknowledgement {
+    config: HecAcknowledgementsConfig,
+    state: Mutex<State>,
+}
+
+impl IndexerAcknowledgement {
+    pub fn new(config: HecAcknowledgementsConfig) -> Arc<Self> {
+        let idx_ack = Arc::new(Self {
+            config,
+            state: Mutex::new(State::default()),
+        });
+
+        if idx_ack.config.ack_idle_cleanup {
+            let weak = Arc::downgrade(&idx_ack);
+            let max_idle_time = Duration::from_secs(idx_ack.config.max_idle_time_secs.max(1));
+            tokio::spawn(async move {
+                let mut interval = time::interval(max_idle_time / 2);
+                loop {
+                    interval.tick().await;
+                    match weak.upgrade() {
+                        Some(idx_ack) => idx_ack.remove_idle_channels(max_idle_time),
+                        None => break,
+                    }
+                }
+            });
+        }
+
+        idx_ack
+    }
+
+    /// Creates an ack id for the events of a request received on the channel. It's
+    /// acknowledged once the events are delivered. The id is created before the events are
+    /// forwarded, so that a request is never rejected once its events were sent on, and must
+    /// be released if forwarding them fails.
+    pub fn get_ack_id(
+        self: &Arc<Self>,
+        channel_id: &str,
+        receiver: BatchStatusReceiver,
+    ) -> Result<u64, ApiError> {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if state.pending_acks >= self.config.max_pending_acks
+            || (!state.channels.contains_key(channel_id)
+                && state.channels.len() as u64 >= self.config.max_number_of_ack_channels)
+        {
+            return Err(ApiError::ServiceUnavailable);
+        }
+
+        let channel = state
+            .channels
+            .entry(channel_id.to_owned())
+            .or_insert_with(Channel::new);
+        if channel.statuses.len() as u64 >= self.config.max_pending_acks_per_channel {
+            return Err(ApiError::ServiceUnavailable);
+        }
+
+        let ack_id = channel.next_ack_id;
+        channel.next_ack_id += 1;
+        channel.statuses.insert(ack_id, false);
+        channel.last_used = Instant::now();
+        state.pending_acks += 1;
+
+        let idx_ack = Arc::clone(self);
+        let channel_id = channel_id.to_owned();
+        tokio::spawn(async move {
+            let status = receiver.await;
+            idx_ack.resolve(&channel_id, ack_id, status == BatchStatus::Delivered);
+        });
+
+        Ok(ack_id)
+    }
+
+    /// Returns whether the ack ids were acknowledged. Acknowledged ids are only reported once.
+    pub fn get_acks_status(
+        &self,
+        channel_id: &str,
+        ack_ids: &[u64],
+    ) -> Result<HashMap<u64, bool>, ApiError> {
+        let mut state = self.state.lock().expect("poisoned lock");
+        let channel = state
+            .channels
+            .get_mut(channel_id)
+            .ok_or(ApiError::InvalidDataChannel)?;
+        channel.last_used = Instant::now();
+
+        let statuses = ack_ids
+            .iter()
+            .map(|&ack_id| {
+                let acknowledged = channel.statuses.get(&ack_id).copied().unwrap_or(false);
+                if acknowledged {
+                    channel.statuses.remove(&ack_id);
+                }
+                (ack_id, acknowledged)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let acknowledged = statuses.values().filter(|&&status| status).count() as u64;
+        state.pending_acks -= acknowledged;
+
+        Ok(statuses)
+    }
+
+    /// Releases an ack id whose request failed, which the client then sends again with a new id.
+    pub fn release_ack_id(&self, channel_id: &str, ack_id: u64) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        let removed = state
+            .channels
+            .get_mut(channel_id)
+            .map_or(false, |channel| channel.statuses.remove(&ack_id).is_some());
+        if removed {
+            state.pending_acks -= 1;
+        }
+    }
+
+    fn resolve(&self, channel_id: &str, ack_id: u64, delivered: bool) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        let removed = match state.channels.get_mut(channel_id) {
+            Some(channel) if delivered => {
+                if let Some(status) = channel.statuses.get_mut(&ack_id) {
+                    *status = true;
+                }
+                false
+            }
+            // Events which weren't delivered are never acknowledged, so clients eventually
+            // send them again.
+            Some(channel) => channel.statuses.remove(&ack_id).is_some(),
+            None => false,
+        };
+        if removed {
+            state.pending_acks -= 1;
+        }
+    }
+
+    fn remove_idle_channels(&self, max_idle_time: Duration) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        let mut removed = 0;
+        state.channels.retain(|_, channel| {
+            let idle = channel.last_used.elapsed() >= max_idle_time;
+            if idle {
+                removed += channel.statuses.len() as u64;
+            }
+            !idle
+        });
+        state.pending_acks -= removed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{BatchNotifier, Event, EventStatus};
+
+    async fn resolved(idx_ack: &Arc<IndexerAcknowledgement>, status: EventStatus) -> u64 {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let ack_id = idx_ack.get_ack_id("channel", receiver).unwrap();
+
+        let event = Event::from("event").with_batch_notifier(&batch);
+        drop(batch);
+        event.metadata().update_status(status);
+        drop(event);
+        // Let the ack id resolve.
+        time::sleep(Duration::from_millis(50)).await;
+
+        ack_id
+    }
+
+    #[tokio::test]
+    async fn acknowledges_delivered_events() {
+        let idx_ack = IndexerAcknowledgement::new(HecAcknowledgementsConfig::default());
+
+        let delivered = resolved(&idx_ack, EventStatus::Delivered).await;
+        let errored = resolved(&idx_ack, EventStatus::Errored).await;
+        assert_eq!((delivered, errored), (0, 1));
+
+        let statuses = idx_ack
+            .get_acks_status("channel", &[delivered, errored, 2])
+            .unwrap();
+        assert_eq!(
+            statuses,
+            vec![(delivered, true), (errored, false), (2, false)]
+                .into_iter()
+                .collect()
+        );
+
+        // Acknowledged ids are only reported once.
+        let statuses = idx_ack.get_acks_status("channel", &[delivered]).unwrap();
+        assert_eq!(statuses.get(&delivered), Some(&false));
+
+        assert!(matches!(
+            idx_ack.get_acks_status("other", &[0]),
+            Err(ApiError::InvalidDataChannel)
+        ));
+    }
+
+    #[tokio::test]
+    async fn limits_pending_acks() {
+        let idx_ack = IndexerAcknowledgement::new(HecAcknowledgementsConfig {
+            max_pending_acks_per_channel: 1,
+            ..Default::default()
+        });
+
+        let ack_id = resolved(&idx_ack, EventStatus::Delivered).await;
+        let (_batch, receiver) = BatchNotifier::new_with_receiver();
+        assert!(matches!(
+            idx_ack.get_ack_id("channel", receiver),
+            Err(ApiError::ServiceUnavailable)
+        ));
+
+        // Querying the acknowledged id frees its slot.
+        idx_ack.get_acks_status("channel", &[ack_id]).unwrap();
+        let (_batch, receiver) = BatchNotifier::new_with_receiver();
+        assert_eq!(idx_ack.get_ack_id("channel", receiver).unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn releases_ack_ids() {
+        let idx_ack = IndexerAcknowledgement::new(HecAcknowledgementsConfig {
+            max_pending_acks_per_channel: 1,
+            ..Default::default()
+        });
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let ack_id = idx_ack.get_ack_id("channel", receiver).unwrap();
+        idx_ack.release_ack_id("channel", ack_id);
+        assert_eq!(idx_ack.state.lock().unwrap().pending_acks, 0);
+
+        // Resolving a released id doesn't acknowledge it.
+        drop(batch);
+        time::sleep(Duration::from_millis(50)).await;
+        let statuses = idx_ack.get_acks_status("channel", &[ack_id]).unwrap();
+        assert_eq!(statuses.get(&ack_id), Some(&false));
+
+        let (_batch, receiver) = BatchNotifier::new_with_receiver();
+        assert_eq!(idx_ack.get_ack_id("channel", receiver).unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn removes_idle_channels() {
+        let idx_ack = IndexerAcknowledgement::new(HecAcknowledgementsConfig::default());
+        resolved(&idx_ack, EventStatus::Delivered).await;
+
+        idx_ack.remove_idle_channels(Duration::from_secs(0));
+        assert!(matches!(
+            idx_ack.get_acks_status("channel", &[0]),
+            Err(ApiError::InvalidDataChannel)
+        ));
+        assert_eq!(idx_ack.state.lock().unwrap().pending_acks, 0);
+    }
+}
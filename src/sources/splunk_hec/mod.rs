@@ -1,31 +1,36 @@
+use self::acknowledgements::{
+    HecAckStatusRequest, HecAckStatusResponse, HecAcknowledgementsConfig, IndexerAcknowledgement,
+};
 use crate::{
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, BatchStatusReceiver, Event, LogEvent, Value},
     internal_events::{
         EventsReceived, HttpBytesReceived, SplunkHecRequestBodyInvalidError, SplunkHecRequestError,
         SplunkHecRequestReceived,
     },
+    sources::util::{content_encoding, decode},
     tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, TimeZone, Utc};
-use flate2::read::MultiGzDecoder;
-use futures::{stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
-use http::StatusCode;
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use http::{HeaderMap, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::{de::Read as JsonRead, json, Deserializer, Value as JsonValue};
 use snafu::Snafu;
 use std::{
     collections::HashMap,
     future,
-    io::Read,
     net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
 };
 use vector_core::ByteSizeOf;
 
 use warp::{filters::BoxedFilter, path, reject::Rejection, reply::Response, Filter, Reply};
 
+mod acknowledgements;
+
 // Event fields unique to splunk_hec source
 pub const CHANNEL: &str = "splunk_channel";
 pub const INDEX: &str = "splunk_index";
@@ -44,6 +49,10 @@ pub struct SplunkConfig {
     /// A list of tokens to accept. Omit this to accept any token
     valid_tokens: Option<Vec<String>>,
     tls: Option<TlsConfig>,
+    /// Maximum size of a request body after decompression
+    max_decompressed_bytes: Option<usize>,
+    /// Limits of the indexer acknowledgements, given when `acknowledgements` are enabled
+    indexer_acknowledgements: HecAcknowledgementsConfig,
 }
 
 inventory::submit! {
@@ -69,6 +78,8 @@ impl Default for SplunkConfig {
             token: None,
             valid_tokens: None,
             tls: None,
+            max_decompressed_bytes: None,
+            indexer_acknowledgements: HecAcknowledgementsConfig::default(),
         }
     }
 }
@@ -82,10 +93,14 @@ fn default_socket_address() -> SocketAddr {
 impl SourceConfig for SplunkConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
-        let source = SplunkSource::new(self, tls.http_protocol_name());
+        let idx_ack = cx
+            .acknowledgements
+            .then(|| IndexerAcknowledgement::new(self.indexer_acknowledgements.clone()));
+        let source = SplunkSource::new(self, tls.http_protocol_name(), idx_ack);
 
         let event_service = source.event_service(cx.out.clone());
         let raw_service = source.raw_service(cx.out);
+        let ack_service = source.ack_service();
         let health_service = source.health_service();
         let options = SplunkSource::options();
 
@@ -103,6 +118,8 @@ impl SourceConfig for SplunkConfig {
                 event_service
                     .or(raw_service)
                     .unify()
+                    .or(ack_service)
+                    .unify()
                     .or(health_service)
                     .unify()
                     .or(options)
@@ -143,10 +160,16 @@ impl SourceConfig for SplunkConfig {
 struct SplunkSource {
     valid_credentials: Vec<String>,
     protocol: &'static str,
+    max_decompressed_bytes: Option<usize>,
+    idx_ack: Option<Arc<IndexerAcknowledgement>>,
 }
 
 impl SplunkSource {
-    fn new(config: &SplunkConfig, protocol: &'static str) -> Self {
+    fn new(
+        config: &SplunkConfig,
+        protocol: &'static str,
+        idx_ack: Option<Arc<IndexerAcknowledgement>>,
+    ) -> Self {
         let valid_tokens = config
             .valid_tokens
             .iter()
@@ -157,26 +180,22 @@ impl SplunkSource {
                 .map(|token| format!("Splunk {}", token))
                 .collect(),
             protocol,
+            max_decompressed_bytes: config.max_decompressed_bytes,
+            idx_ack,
         }
     }
 
     fn event_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
-        let splunk_channel_query_param = warp::query::<HashMap<String, String>>()
-            .map(|qs: HashMap<String, String>| qs.get("channel").map(|v| v.to_owned()));
-        let splunk_channel_header = warp::header::optional::<String>("x-splunk-request-channel");
-
-        let splunk_channel = splunk_channel_header
-            .and(splunk_channel_query_param)
-            .map(|header: Option<String>, query_param| header.or(query_param));
-
         let protocol = self.protocol;
+        let max_decompressed_bytes = self.max_decompressed_bytes;
+        let idx_ack = self.idx_ack.clone();
         warp::post()
             .and(path!("event").or(path!("event" / "1.0")))
             .and(self.authorization())
-            .and(splunk_channel)
+            .and(Self::channel())
             .and(warp::addr::remote())
             .and(warp::header::optional::<String>("X-Forwarded-For"))
-            .and(self.gzip())
+            .and(Self::content_encoding())
             .and(warp::body::bytes())
             .and(warp::path::full())
             .and_then(
@@ -185,38 +204,52 @@ impl SplunkSource {
                       channel: Option<String>,
                       remote: Option<SocketAddr>,
                       xff: Option<String>,
-                      gzip: bool,
+                      encoding: Option<String>,
                       body: Bytes,
                       path: warp::path::FullPath| {
                     let mut out = out
                         .clone()
                         .sink_map_err(|_| Rejection::from(ApiError::ServerShutdown));
+                    let idx_ack = idx_ack.clone();
                     emit!(&HttpBytesReceived {
                         byte_size: body.len(),
                         http_path: path.as_str(),
                         protocol,
                     });
                     async move {
-                        let reader: Box<dyn Read + Send> = if gzip {
-                            Box::new(MultiGzDecoder::new(body.reader()))
-                        } else {
-                            Box::new(body.reader())
+                        // Acknowledgements are tracked per channel.
+                        let ack_channel = match (&idx_ack, &channel) {
+                            (Some(_), None) => return Err(ApiError::MissingChannel.into()),
+                            (Some(_), Some(channel)) => Some(channel.clone()),
+                            (None, _) => None,
                         };
+                        let (batch, receiver) =
+                            BatchNotifier::maybe_new_with_receiver(ack_channel.is_some());
+                        let ack_id = ack_id(&idx_ack, &ack_channel, receiver)?;
 
-                        let events = stream::iter(EventIterator::new(
-                            Deserializer::from_reader(reader).into_iter::<JsonValue>(),
-                            channel,
-                            remote,
-                            xff,
-                        ));
+                        let res = async {
+                            let body = decode_body(&encoding, body, max_decompressed_bytes)?;
+
+                            let events = stream::iter(EventIterator::new(
+                                Deserializer::from_reader(body.reader()).into_iter::<JsonValue>(),
+                                channel,
+                                remote,
+                                xff,
+                                batch,
+                            ));
 
-                        // `fn send_all` can be used once https://github.com/rust-lang/futures-rs/issues/2402
-                        // is resolved.
-                        let res = events.forward(&mut out).await;
+                            // `fn send_all` can be used once https://github.com/rust-lang/futures-rs/issues/2402
+                            // is resolved.
+                            let res = events.forward(&mut out).await;
 
-                        out.flush().await?;
+                            out.flush().await?;
 
-                        res
+                            res
+                        }
+                        .await;
+
+                        release_ack_id_on_error(&idx_ack, &ack_channel, ack_id, &res);
+                        res.map(|_| ack_id)
                     }
                 },
             )
@@ -225,26 +258,16 @@ impl SplunkSource {
     }
 
     fn raw_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
-        let splunk_channel_query_param = warp::query::<HashMap<String, String>>()
-            .map(|qs: HashMap<String, String>| qs.get("channel").map(|v| v.to_owned()));
-        let splunk_channel_header = warp::header::optional::<String>("x-splunk-request-channel");
-
-        let splunk_channel = splunk_channel_header
-            .and(splunk_channel_query_param)
-            .and_then(|header: Option<String>, query_param| async move {
-                header
-                    .or(query_param)
-                    .ok_or_else(|| Rejection::from(ApiError::MissingChannel))
-            });
-
         let protocol = self.protocol;
+        let max_decompressed_bytes = self.max_decompressed_bytes;
+        let idx_ack = self.idx_ack.clone();
         warp::post()
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
-            .and(splunk_channel)
+            .and(Self::required_channel())
             .and(warp::addr::remote())
             .and(warp::header::optional::<String>("X-Forwarded-For"))
-            .and(self.gzip())
+            .and(Self::content_encoding())
             .and(warp::body::bytes())
             .and(warp::path::full())
             .and_then(
@@ -253,23 +276,39 @@ impl SplunkSource {
                       channel: String,
                       remote: Option<SocketAddr>,
                       xff: Option<String>,
-                      gzip: bool,
+                      encoding: Option<String>,
                       body: Bytes,
                       path: warp::path::FullPath| {
                     let out = out.clone();
+                    let idx_ack = idx_ack.clone();
                     emit!(&HttpBytesReceived {
                         byte_size: body.len(),
                         http_path: path.as_str(),
                         protocol,
                     });
                     async move {
-                        let event = future::ready(raw_event(body, gzip, channel, remote, xff));
-                        futures::stream::once(event)
+                        let ack_channel = idx_ack.as_ref().map(|_| channel.clone());
+                        let (batch, receiver) =
+                            BatchNotifier::maybe_new_with_receiver(idx_ack.is_some());
+                        let ack_id = ack_id(&idx_ack, &ack_channel, receiver)?;
+
+                        let event = future::ready(raw_event(
+                            body,
+                            &encoding,
+                            max_decompressed_bytes,
+                            channel,
+                            remote,
+                            xff,
+                            batch,
+                        ));
+                        let res = futures::stream::once(event)
                             .forward(
                                 out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
                             )
-                            .map_ok(|_| ())
-                            .await
+                            .await;
+
+                        release_ack_id_on_error(&idx_ack, &ack_channel, ack_id, &res);
+                        res.map(|_| ack_id)
                     }
                 },
             )
@@ -277,6 +316,28 @@ impl SplunkSource {
             .boxed()
     }
 
+    fn ack_service(&self) -> BoxedFilter<(Response,)> {
+        let idx_ack = self.idx_ack.clone();
+        warp::post()
+            .and(path!("ack"))
+            .and(self.authorization())
+            .and(Self::required_channel())
+            .and(warp::body::bytes())
+            .and_then(move |_, channel: String, body: Bytes| {
+                let idx_ack = idx_ack.clone();
+                async move {
+                    let idx_ack =
+                        idx_ack.ok_or_else(|| Rejection::from(ApiError::AckIsDisabled))?;
+                    let request = serde_json::from_slice::<HecAckStatusRequest>(&body)
+                        .map_err(|_| Rejection::from(ApiError::InvalidAckQuery))?;
+
+                    let acks = idx_ack.get_acks_status(&channel, &request.acks)?;
+                    Ok::<_, Rejection>(response_json(StatusCode::OK, HecAckStatusResponse { acks }))
+                }
+            })
+            .boxed()
+    }
+
     fn health_service(&self) -> BoxedFilter<(Response,)> {
         let valid_credentials = self.valid_credentials.clone();
         let authorize =
@@ -306,7 +367,8 @@ impl SplunkSource {
                 path!("event")
                     .or(path!("event" / "1.0"))
                     .or(path!("raw" / "1.0"))
-                    .or(path!("raw")),
+                    .or(path!("raw"))
+                    .or(path!("ack")),
             )
             .map(|_| warp::reply::with_header(warp::reply(), "Allow", "POST").into_response());
 
@@ -335,18 +397,47 @@ impl SplunkSource {
             .boxed()
     }
 
-    /// Is body encoded with gzip
-    fn gzip(&self) -> BoxedFilter<(bool,)> {
-        warp::header::optional::<String>("Content-Encoding")
-            .and_then(|encoding: Option<String>| async move {
-                match encoding {
-                    Some(s) if s.as_bytes() == b"gzip" => Ok(true),
-                    Some(_) => Err(Rejection::from(ApiError::UnsupportedEncoding)),
-                    None => Ok(false),
-                }
+    /// Channel of the request, from either the header or the query parameter
+    fn channel() -> BoxedFilter<(Option<String>,)> {
+        let splunk_channel_query_param = warp::query::<HashMap<String, String>>()
+            .map(|qs: HashMap<String, String>| qs.get("channel").map(|v| v.to_owned()));
+        let splunk_channel_header = warp::header::optional::<String>("x-splunk-request-channel");
+
+        splunk_channel_header
+            .and(splunk_channel_query_param)
+            .map(|header: Option<String>, query_param| header.or(query_param))
+            .boxed()
+    }
+
+    fn required_channel() -> BoxedFilter<(String,)> {
+        Self::channel()
+            .and_then(|channel: Option<String>| async move {
+                channel.ok_or_else(|| Rejection::from(ApiError::MissingChannel))
             })
             .boxed()
     }
+
+    /// Encodings applied to the body, in order
+    fn content_encoding() -> BoxedFilter<(Option<String>,)> {
+        warp::header::headers_cloned()
+            .map(|headers: HeaderMap| content_encoding(&headers))
+            .boxed()
+    }
+}
+
+/// Decodes the body according to its `Content-Encoding`
+fn decode_body(
+    encoding: &Option<String>,
+    body: Bytes,
+    max_decompressed_bytes: Option<usize>,
+) -> Result<Bytes, Rejection> {
+    decode(encoding, body, max_decompressed_bytes).map_err(|error| {
+        Rejection::from(match error.status_code() {
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => ApiError::UnsupportedEncoding,
+            StatusCode::PAYLOAD_TOO_LARGE => ApiError::PayloadTooLarge,
+            _ => ApiError::InvalidDataFormat { event: 0 },
+        })
+    })
 }
 /// Constructs one or more events from json-s coming from reader.
 /// If errors, it's done with input.
@@ -357,6 +448,8 @@ struct EventIterator<'de, R: JsonRead<'de>> {
     events: usize,
     /// Optional channel from headers
     channel: Option<Value>,
+    /// Notifier of the request's batch, when acknowledging it
+    batch: Option<Arc<BatchNotifier>>,
     /// Default time
     time: Time,
     /// Remaining extracted default values
@@ -369,11 +462,13 @@ impl<'de, R: JsonRead<'de>> EventIterator<'de, R> {
         channel: Option<String>,
         remote: Option<SocketAddr>,
         remote_addr: Option<String>,
+        batch: Option<Arc<BatchNotifier>>,
     ) -> Self {
         EventIterator {
             deserializer,
             events: 0,
             channel: channel.map(Value::from),
+            batch,
             time: Time::Now(Utc::now()),
             extractors: [
                 // Extract the host field with the given priority:
@@ -489,6 +584,10 @@ impl<'de, R: JsonRead<'de>> EventIterator<'de, R> {
             de.extract(log, &mut json);
         }
 
+        if let Some(batch) = &self.batch {
+            event.add_batch_notifier(Arc::clone(batch));
+        }
+
         emit!(&EventsReceived {
             count: 1,
             byte_size: event.size_of(),
@@ -609,24 +708,20 @@ enum Time {
 /// Creates event from raw request
 fn raw_event(
     bytes: Bytes,
-    gzip: bool,
+    encoding: &Option<String>,
+    max_decompressed_bytes: Option<usize>,
     channel: String,
     remote: Option<SocketAddr>,
     xff: Option<String>,
+    batch: Option<Arc<BatchNotifier>>,
 ) -> Result<Event, Rejection> {
-    // Process gzip
-    let message: Value = if gzip {
-        let mut data = Vec::new();
-        match MultiGzDecoder::new(bytes.reader()).read_to_end(&mut data) {
-            Ok(0) => return Err(ApiError::NoData.into()),
-            Ok(_) => Value::from(Bytes::from(data)),
-            Err(error) => {
-                emit!(&SplunkHecRequestBodyInvalidError { error });
-                return Err(ApiError::InvalidDataFormat { event: 0 }.into());
-            }
-        }
-    } else {
-        bytes.into()
+    // Process encoding
+    let message: Value = match encoding {
+        Some(_) => match decode_body(encoding, bytes, max_decompressed_bytes)? {
+            data if data.is_empty() => return Err(ApiError::NoData.into()),
+            data => data.into(),
+        },
+        None => bytes.into(),
     };
 
     // Construct event
@@ -656,6 +751,10 @@ fn raw_event(
         .as_mut_log()
         .try_insert(log_schema().source_type_key(), Bytes::from("splunk_hec"));
 
+    if let Some(batch) = batch {
+        event.add_batch_notifier(batch);
+    }
+
     emit!(&EventsReceived {
         count: 1,
         byte_size: event.size_of(),
@@ -669,6 +768,7 @@ pub(crate) enum ApiError {
     MissingAuthorization,
     InvalidAuthorization,
     UnsupportedEncoding,
+    PayloadTooLarge,
     MissingChannel,
     NoData,
     InvalidDataFormat { event: usize },
@@ -676,6 +776,10 @@ pub(crate) enum ApiError {
     EmptyEventField { event: usize },
     MissingEventField { event: usize },
     BadRequest,
+    ServiceUnavailable,
+    AckIsDisabled,
+    InvalidDataChannel,
+    InvalidAckQuery,
 }
 
 impl warp::reject::Reject for ApiError {}
@@ -703,13 +807,57 @@ mod splunk_response {
             json_to_bytes(json!({"text":"Server is shutting down","code":9}));
         pub static ref UNSUPPORTED_MEDIA_TYPE: Bytes =
             json_to_bytes(json!({"text":"unsupported content encoding"}));
+        pub static ref PAYLOAD_TOO_LARGE: Bytes =
+            json_to_bytes(json!({"text":"decompressed payload too large"}));
         pub static ref NO_CHANNEL: Bytes =
             json_to_bytes(json!({"text":"Data channel is missing","code":10}));
+        pub static ref INVALID_CHANNEL: Bytes =
+            json_to_bytes(json!({"text":"Invalid data channel","code":11}));
+        pub static ref SERVER_BUSY: Bytes =
+            json_to_bytes(json!({"text":"Server is busy","code":9}));
+        pub static ref ACK_IS_DISABLED: Bytes =
+            json_to_bytes(json!({"text":"ACK is disabled","code":14}));
+        pub static ref INVALID_DATA_FORMAT: Bytes =
+            json_to_bytes(json!({"text":"Invalid data format","code":6}));
     }
 }
 
-fn finish_ok(_: ()) -> Response {
-    response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref())
+/// Creates the ack id of a request's events, if acknowledging them
+fn ack_id(
+    idx_ack: &Option<Arc<IndexerAcknowledgement>>,
+    channel: &Option<String>,
+    receiver: Option<BatchStatusReceiver>,
+) -> Result<Option<u64>, Rejection> {
+    match (idx_ack, channel, receiver) {
+        (Some(idx_ack), Some(channel), Some(receiver)) => idx_ack
+            .get_ack_id(channel, receiver)
+            .map(Some)
+            .map_err(Rejection::from),
+        _ => Ok(None),
+    }
+}
+
+/// Releases the ack id of a request whose events couldn't be forwarded
+fn release_ack_id_on_error<T>(
+    idx_ack: &Option<Arc<IndexerAcknowledgement>>,
+    channel: &Option<String>,
+    ack_id: Option<u64>,
+    result: &Result<T, Rejection>,
+) {
+    if let (Err(_), Some(idx_ack), Some(channel), Some(ack_id)) = (result, idx_ack, channel, ack_id)
+    {
+        idx_ack.release_ack_id(channel, ack_id);
+    }
+}
+
+fn finish_ok(ack_id: Option<u64>) -> Response {
+    match ack_id {
+        Some(ack_id) => response_json(
+            StatusCode::OK,
+            json!({"text":"Success","code":0,"ackId":ack_id}),
+        ),
+        None => response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref()),
+    }
 }
 
 async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
@@ -728,6 +876,10 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 StatusCode::UNSUPPORTED_MEDIA_TYPE,
                 splunk_response::UNSUPPORTED_MEDIA_TYPE.as_ref(),
             ),
+            ApiError::PayloadTooLarge => response_json(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                splunk_response::PAYLOAD_TOO_LARGE.as_ref(),
+            ),
             ApiError::MissingChannel => response_json(
                 StatusCode::BAD_REQUEST,
                 splunk_response::NO_CHANNEL.as_ref(),
@@ -747,6 +899,22 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 event_error("Event field is required", 12, event)
             }
             ApiError::BadRequest => empty_response(StatusCode::BAD_REQUEST),
+            ApiError::ServiceUnavailable => response_json(
+                StatusCode::SERVICE_UNAVAILABLE,
+                splunk_response::SERVER_BUSY.as_ref(),
+            ),
+            ApiError::AckIsDisabled => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::ACK_IS_DISABLED.as_ref(),
+            ),
+            ApiError::InvalidDataChannel => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::INVALID_CHANNEL.as_ref(),
+            ),
+            ApiError::InvalidAckQuery => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::INVALID_DATA_FORMAT.as_ref(),
+            ),
         },))
     } else {
         Err(rejection)
@@ -791,9 +959,12 @@ mod tests {
     use super::{parse_timestamp, SplunkConfig};
     use crate::{
         config::{log_schema, SinkConfig, SinkContext, SourceConfig, SourceContext},
-        event::Event,
+        event::{BatchNotifier, BatchStatus, Event},
         sinks::{
-            splunk_hec::logs::{Encoding, HecSinkLogsConfig},
+            splunk_hec::{
+                acknowledgements::HecClientAcknowledgementsConfig,
+                logs::{Encoding, HecSinkLogsConfig},
+            },
             util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
             Healthcheck, VectorSink,
         },
@@ -806,6 +977,7 @@ mod tests {
     };
     use chrono::{TimeZone, Utc};
     use futures::{channel::mpsc, stream, StreamExt};
+    use serde_json::{json, Value as JsonValue};
     use std::{future::ready, net::SocketAddr};
 
     #[test]
@@ -836,6 +1008,8 @@ mod tests {
                 token,
                 valid_tokens,
                 tls: None,
+                max_decompressed_bytes: None,
+                indexer_acknowledgements: Default::default(),
             }
             .build(SourceContext::new_test(sender))
             .await
@@ -852,6 +1026,17 @@ mod tests {
         encoding: impl Into<EncodingConfig<Encoding>>,
         compression: Compression,
     ) -> (VectorSink, Healthcheck) {
+        sink_config(address, encoding, compression)
+            .build(SinkContext::new_test())
+            .await
+            .unwrap()
+    }
+
+    fn sink_config(
+        address: SocketAddr,
+        encoding: impl Into<EncodingConfig<Encoding>>,
+        compression: Compression,
+    ) -> HecSinkLogsConfig {
         HecSinkLogsConfig {
             token: TOKEN.to_owned(),
             endpoint: format!("http://{}", address),
@@ -865,10 +1050,8 @@ mod tests {
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             tls: None,
+            acknowledgements: Default::default(),
         }
-        .build(SinkContext::new_test())
-        .await
-        .unwrap()
     }
 
     async fn start(
@@ -1088,6 +1271,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn raw_zstd() {
+        let message = "raw_zstd";
+        let (source, address) = source().await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/services/collector/raw", address))
+            .header("x-splunk-request-channel", "channel")
+            .header("Content-Encoding", "zstd")
+            .body(zstd::stream::encode_all(message.as_bytes(), 0).unwrap())
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(200, status.as_u16());
+
+        let event = collect_n(source, 1).await.remove(0);
+        assert_eq!(event.as_log()[log_schema().message_key()], message.into());
+    }
+
+    #[tokio::test]
+    async fn raw_exceeding_max_decompressed_bytes() {
+        components::init_test();
+        let (sender, _recv) = Pipeline::new_test();
+        let address = next_addr();
+        tokio::spawn(async move {
+            SplunkConfig {
+                max_decompressed_bytes: Some(16),
+                ..SplunkConfig::on(address)
+            }
+            .build(SourceContext::new_test(sender))
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/services/collector/raw", address))
+            .header("x-splunk-request-channel", "channel")
+            .header("Content-Encoding", "zstd")
+            .body(zstd::stream::encode_all(&[b'a'; 1024][..], 0).unwrap())
+            .send()
+            .await
+            .unwrap()
+            .status();
+
+        assert_eq!(413, status.as_u16());
+    }
+
     #[tokio::test]
     async fn channel_header() {
         let message = "raw";
@@ -1341,6 +1575,160 @@ mod tests {
         assert_eq!(events[2].as_log()[&super::SOURCE], "secondary".into());
     }
 
+    async fn acknowledging_source() -> (mpsc::Receiver<Event>, SocketAddr) {
+        components::init_test();
+        let (sender, recv) = Pipeline::new_test();
+        let address = next_addr();
+        let mut cx = SourceContext::new_test(sender);
+        cx.acknowledgements = true;
+        tokio::spawn(async move {
+            SplunkConfig {
+                token: Some(TOKEN.to_owned()),
+                ..SplunkConfig::on(address)
+            }
+            .build(cx)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn post_json(
+        address: SocketAddr,
+        api: &str,
+        channel: Option<&str>,
+        body: &str,
+    ) -> (u16, JsonValue) {
+        let mut request = reqwest::Client::new()
+            .post(&format!("http://{}/{}", address, api))
+            .header("Authorization", format!("Splunk {}", TOKEN));
+        if let Some(channel) = channel {
+            request = request.header("x-splunk-request-channel", channel);
+        }
+
+        let response = request.body(body.to_owned()).send().await.unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn ack_is_disabled() {
+        let (_source, address) = source().await;
+
+        let (status, body) = post_json(
+            address,
+            "services/collector/ack",
+            Some("guid"),
+            r#"{"acks":[0]}"#,
+        )
+        .await;
+        assert_eq!(400, status);
+        assert_eq!(body["code"], 14);
+    }
+
+    #[tokio::test]
+    async fn acknowledges_delivered_events() {
+        let (mut source, address) = acknowledging_source().await;
+
+        let (status, body) = post_json(
+            address,
+            "services/collector/event",
+            Some("guid"),
+            r#"{"event":"first"}"#,
+        )
+        .await;
+        assert_eq!(200, status);
+        assert_eq!(body["ackId"], 0);
+
+        let (status, body) =
+            post_json(address, "services/collector/raw", Some("guid"), "second").await;
+        assert_eq!(200, status);
+        assert_eq!(body["ackId"], 1);
+
+        let (status, body) = post_json(
+            address,
+            "services/collector/ack",
+            Some("guid"),
+            r#"{"acks":[0,1]}"#,
+        )
+        .await;
+        assert_eq!(200, status);
+        assert_eq!(body, json!({"acks":{"0":false,"1":false}}));
+
+        // Delivering the events acknowledges them.
+        drop(source.next().await.unwrap());
+        drop(source.next().await.unwrap());
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let (_, body) = post_json(
+            address,
+            "services/collector/ack",
+            Some("guid"),
+            r#"{"acks":[0,1]}"#,
+        )
+        .await;
+        assert_eq!(body, json!({"acks":{"0":true,"1":true}}));
+
+        let (status, body) = post_json(
+            address,
+            "services/collector/ack",
+            Some("other"),
+            r#"{"acks":[0]}"#,
+        )
+        .await;
+        assert_eq!(400, status);
+        assert_eq!(body["code"], 11);
+    }
+
+    #[tokio::test]
+    async fn acknowledgements_require_channel() {
+        let (_source, address) = acknowledging_source().await;
+
+        let (status, body) = post_json(
+            address,
+            "services/collector/event",
+            None,
+            r#"{"event":"first"}"#,
+        )
+        .await;
+        assert_eq!(400, status);
+        assert_eq!(body["code"], 10);
+    }
+
+    #[tokio::test]
+    async fn sink_waits_for_acknowledgements() {
+        let (mut source, address) = acknowledging_source().await;
+        let (sink, _) = HecSinkLogsConfig {
+            acknowledgements: HecClientAcknowledgementsConfig {
+                query_interval_secs: 1,
+                ..Default::default()
+            },
+            ..sink_config(address, Encoding::Text, Compression::None)
+        }
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from("acknowledged").with_batch_notifier(&batch);
+        drop(batch);
+        tokio::spawn(async move {
+            sink.run(stream::once(ready(event))).await.unwrap();
+        });
+
+        let event = source.next().await.unwrap();
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "acknowledged".into()
+        );
+        drop(event);
+
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
     #[test]
     fn parse_timestamps() {
         let cases = vec![
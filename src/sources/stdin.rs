@@ -1,6 +1,7 @@
 use crate::{
     codecs::{DecodingConfig, FramingConfig, ParserConfig},
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceDescription},
+    event::Event,
     internal_events::StdinEventsReceived,
     serde::{default_decoding, default_framing_stream_based},
     shutdown::ShutdownSignal,
@@ -57,7 +58,7 @@ impl SourceConfig for StdinConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding.output_type()
     }
 
     fn source_type(&self) -> &'static str {
@@ -129,13 +130,13 @@ where
                         let now = Utc::now();
 
                         for mut event in events {
-                            let log = event.as_mut_log();
+                            if let Event::Log(ref mut log) = event {
+                                log.try_insert(log_schema().source_type_key(), Bytes::from("stdin"));
+                                log.try_insert(log_schema().timestamp_key(), now);
 
-                            log.try_insert(log_schema().source_type_key(), Bytes::from("stdin"));
-                            log.try_insert(log_schema().timestamp_key(), now);
-
-                            if let Some(hostname) = &hostname {
-                                log.try_insert(&host_key, hostname.clone());
+                                if let Some(hostname) = &hostname {
+                                    log.try_insert(&host_key, hostname.clone());
+                                }
                             }
 
                             yield event;
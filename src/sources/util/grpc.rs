@@ -0,0 +1,177 @@
+use super::decode;
+use crate::tls::MaybeTlsIncomingStream;
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::future::BoxFuture;
+use http::{header::CONTENT_LENGTH, Request, Response, StatusCode};
+use hyper::Body;
+use std::{
+    net::SocketAddr,
+    task::{Context, Poll},
+};
+use tokio::net::TcpStream;
+use tonic::{
+    body::BoxBody,
+    transport::{server::Connected, Certificate, NamedService},
+    Status,
+};
+use tower::Service;
+use vector_core::event::{BatchStatus, BatchStatusReceiver};
+
+#[derive(Clone)]
+pub struct MaybeTlsConnectInfo {
+    pub remote_addr: SocketAddr,
+    pub peer_certs: Option<Vec<Certificate>>,
+}
+
+impl Connected for MaybeTlsIncomingStream<TcpStream> {
+    type ConnectInfo = MaybeTlsConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        MaybeTlsConnectInfo {
+            remote_addr: self.peer_addr(),
+            peer_certs: self
+                .ssl_stream()
+                .and_then(|s| s.ssl().peer_cert_chain())
+                .map(|s| {
+                    s.into_iter()
+                        .filter_map(|c| c.to_pem().ok())
+                        .map(Certificate::from_pem)
+                        .collect()
+                }),
+        }
+    }
+}
+
+pub async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Failed => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
+
+/// The header naming the compression applied to the messages of a gRPC request.
+const GRPC_ENCODING: &str = "grpc-encoding";
+
+// One byte for the compression flag plus four bytes for the length.
+const GRPC_HEADER_SIZE: usize = 5;
+
+/// Decompresses the messages of requests sent with a `grpc-encoding` before
+/// handing them to the wrapped gRPC service, so that any of the encodings
+/// supported by the HTTP sources can be used.
+#[derive(Clone)]
+pub struct DecompressionService<S> {
+    inner: S,
+    max_decompressed_bytes: Option<usize>,
+}
+
+impl<S> DecompressionService<S> {
+    pub const fn new(inner: S, max_decompressed_bytes: Option<usize>) -> Self {
+        Self {
+            inner,
+            max_decompressed_bytes,
+        }
+    }
+}
+
+impl<S: NamedService> NamedService for DecompressionService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<Request<Body>> for DecompressionService<S>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let encoding = request
+            .headers()
+            .get(GRPC_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .filter(|encoding| *encoding != "identity")
+            .map(String::from);
+        if encoding.is_none() {
+            return Box::pin(self.inner.call(request));
+        }
+
+        // The clone is ready to be called, as `poll_ready` has been called on it.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let max_decompressed_bytes = self.max_decompressed_bytes;
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let body = match hyper::body::to_bytes(body).await {
+                Ok(body) => body,
+                Err(error) => return Ok(Status::internal(error.to_string()).to_http()),
+            };
+            let body = match decompress_messages(&encoding, body, max_decompressed_bytes) {
+                Ok(body) => body,
+                Err(status) => return Ok(status.to_http()),
+            };
+
+            parts.headers.remove(GRPC_ENCODING);
+            parts.headers.remove(CONTENT_LENGTH);
+            inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+        })
+    }
+}
+
+/// Rewrites the length-prefixed messages of a gRPC request body, decompressing
+/// the ones flagged as compressed.
+fn decompress_messages(
+    encoding: &Option<String>,
+    mut body: Bytes,
+    max_decompressed_bytes: Option<usize>,
+) -> Result<Bytes, Status> {
+    let mut decompressed = BytesMut::with_capacity(body.len());
+    while !body.is_empty() {
+        if body.len() < GRPC_HEADER_SIZE {
+            return Err(Status::invalid_argument("Truncated message"));
+        }
+        let compressed = body[0] == 1;
+        let len = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+        if body.len() - GRPC_HEADER_SIZE < len {
+            return Err(Status::invalid_argument("Truncated message"));
+        }
+
+        let mut message = body
+            .split_to(GRPC_HEADER_SIZE + len)
+            .split_off(GRPC_HEADER_SIZE);
+        if compressed {
+            message =
+                decode(encoding, message, max_decompressed_bytes).map_err(|error| {
+                    match error.status_code() {
+                        StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                            Status::unimplemented("Unsupported message encoding")
+                        }
+                        StatusCode::PAYLOAD_TOO_LARGE => {
+                            Status::resource_exhausted("Decompressed message too large")
+                        }
+                        _ => Status::invalid_argument("Failed decompressing message"),
+                    }
+                })?;
+        }
+
+        decompressed.put_u8(0);
+        decompressed.put_u32(message.len() as u32);
+        decompressed.put(message);
+    }
+
+    Ok(decompressed.freeze())
+}
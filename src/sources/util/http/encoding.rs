@@ -4,31 +4,65 @@ use bytes::{Buf, Bytes};
 use flate2::read::{MultiGzDecoder, ZlibDecoder};
 use snap::raw::Decoder as SnappyDecoder;
 use std::io::Read;
-use warp::http::StatusCode;
+use warp::http::{header::CONTENT_ENCODING, HeaderMap, StatusCode};
 
-pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
+/// Collects the encodings of all `Content-Encoding` headers, in the order in
+/// which they have been applied.
+pub fn content_encoding(headers: &HeaderMap) -> Option<String> {
+    let encodings = headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>();
+
+    (!encodings.is_empty()).then(|| encodings.join(","))
+}
+
+/// Decodes `body` according to the encodings listed in `header`, starting with
+/// the one applied last.
+///
+/// None of the intermediate nor the final bodies may exceed
+/// `max_decompressed_bytes`, which guards against decompression bombs.
+pub fn decode(
+    header: &Option<String>,
+    mut body: Bytes,
+    max_decompressed_bytes: Option<usize>,
+) -> Result<Bytes, ErrorMessage> {
     if let Some(encodings) = header {
         for encoding in encodings.rsplit(',').map(str::trim) {
             body = match encoding {
                 "identity" => body,
-                "gzip" => {
-                    let mut decoded = Vec::new();
-                    MultiGzDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                "deflate" => {
-                    let mut decoded = Vec::new();
-                    ZlibDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
+                "gzip" | "x-gzip" => read_limited(
+                    encoding,
+                    MultiGzDecoder::new(body.reader()),
+                    max_decompressed_bytes,
+                )?,
+                "deflate" | "x-deflate" => read_limited(
+                    encoding,
+                    ZlibDecoder::new(body.reader()),
+                    max_decompressed_bytes,
+                )?,
+                "zstd" => read_limited(
+                    encoding,
+                    zstd::stream::read::Decoder::new(body.reader())
+                        .map_err(|error| handle_decode_error(encoding, error))?,
+                    max_decompressed_bytes,
+                )?,
+                // Raw snappy, as used by Prometheus remote write.
+                "snappy" => {
+                    let len = snap::raw::decompress_len(&body)
                         .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
+                    check_limit(len, max_decompressed_bytes)?;
+                    SnappyDecoder::new()
+                        .decompress_vec(&body)
+                        .map_err(|error| handle_decode_error(encoding, error))?
+                        .into()
                 }
-                "snappy" => SnappyDecoder::new()
-                    .decompress_vec(&body)
-                    .map_err(|error| handle_decode_error(encoding, error))?
-                    .into(),
+                "x-snappy-framed" => read_limited(
+                    encoding,
+                    snap::read::FrameDecoder::new(body.reader()),
+                    max_decompressed_bytes,
+                )?,
                 encoding => {
                     return Err(ErrorMessage::new(
                         StatusCode::UNSUPPORTED_MEDIA_TYPE,
@@ -42,6 +76,34 @@ pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMe
     Ok(body)
 }
 
+fn read_limited(
+    encoding: &str,
+    mut reader: impl Read,
+    max_decompressed_bytes: Option<usize>,
+) -> Result<Bytes, ErrorMessage> {
+    let mut decoded = Vec::new();
+    match max_decompressed_bytes {
+        // Reading a single byte past the limit is enough to tell that it has
+        // been exceeded.
+        Some(max) => reader.take(max as u64 + 1).read_to_end(&mut decoded),
+        None => reader.read_to_end(&mut decoded),
+    }
+    .map_err(|error| handle_decode_error(encoding, error))?;
+
+    check_limit(decoded.len(), max_decompressed_bytes)?;
+    Ok(decoded.into())
+}
+
+fn check_limit(len: usize, max_decompressed_bytes: Option<usize>) -> Result<(), ErrorMessage> {
+    match max_decompressed_bytes {
+        Some(max) if len > max => Err(ErrorMessage::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("Decompressed payload exceeds the limit of {} bytes.", max),
+        )),
+        _ => Ok(()),
+    }
+}
+
 fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMessage {
     emit!(&HttpDecompressError {
         encoding,
@@ -52,3 +114,72 @@ fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMe
         format!("Failed decompressing payload with {} decoder.", encoding),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zstd(data: &[u8]) -> Vec<u8> {
+        zstd::stream::encode_all(data, 0).unwrap()
+    }
+
+    #[test]
+    fn decodes_zstd() {
+        let body = decode(&Some("zstd".into()), zstd(b"hello").into(), None).unwrap();
+        assert_eq!(body, "hello");
+    }
+
+    #[test]
+    fn decodes_snappy() {
+        let compressed = snap::raw::Encoder::new().compress_vec(b"hello").unwrap();
+        let body = decode(&Some("snappy".into()), compressed.into(), None).unwrap();
+        assert_eq!(body, "hello");
+    }
+
+    #[test]
+    fn decodes_chains_in_reverse_order() {
+        let compressed = zstd(&gzip(b"hello"));
+        let body = decode(&Some("gzip, zstd".into()), compressed.into(), None).unwrap();
+        assert_eq!(body, "hello");
+    }
+
+    #[test]
+    fn collects_encodings_from_all_headers() {
+        let mut headers = HeaderMap::new();
+        headers.append(CONTENT_ENCODING, "gzip".parse().unwrap());
+        headers.append(CONTENT_ENCODING, "zstd".parse().unwrap());
+
+        assert_eq!(content_encoding(&headers), Some("gzip,zstd".into()));
+        assert_eq!(content_encoding(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn rejects_unsupported_encodings() {
+        let error = decode(&Some("br".into()), Bytes::from("hello"), None).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn limits_decompressed_size() {
+        let compressed = Bytes::from(zstd(&[0; 1024]));
+
+        assert!(decode(&Some("zstd".into()), compressed.clone(), Some(1024)).is_ok());
+        let error = decode(&Some("zstd".into()), compressed, Some(1023)).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn limits_snappy_before_decompressing() {
+        let compressed = snap::raw::Encoder::new().compress_vec(&[0; 1024]).unwrap();
+        let error = decode(&Some("snappy".into()), compressed.into(), Some(10)).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
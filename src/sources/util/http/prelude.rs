@@ -1,6 +1,6 @@
 use super::{
     auth::{HttpSourceAuth, HttpSourceAuthConfig},
-    encoding::decode,
+    encoding::{content_encoding, decode},
     error::ErrorMessage,
 };
 use crate::{
@@ -32,6 +32,15 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// Upper bound for the size of request bodies after decompression.
+    fn max_decompressed_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Resolves what building the events of the decompressed `body` requires,
+    /// such as schemas from a schema registry, before `build_events` is called.
+    async fn resolve(&self, _body: &[u8]) {}
+
     fn run(
         self,
         address: SocketAddr,
@@ -70,14 +79,12 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 .untuple_one()
                 .and(warp::path::full())
                 .and(warp::header::optional::<String>("authorization"))
-                .and(warp::header::optional::<String>("content-encoding"))
                 .and(warp::header::headers_cloned())
                 .and(warp::body::bytes())
                 .and(warp::query::<HashMap<String, String>>())
                 .and_then(
                     move |path: FullPath,
                           auth_header,
-                          encoding_header,
                           headers: HeaderMap,
                           body: Bytes,
                           query_parameters: HashMap<String, String>| {
@@ -89,23 +96,38 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                             protocol,
                         });
 
-                        let events = auth
-                            .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
-                            .and_then(|body| {
-                                self.build_events(body, headers, query_parameters, path.as_str())
-                            })
+                        let encoding_header = content_encoding(&headers);
+                        let body = auth.is_valid(&auth_header).and_then(|()| {
+                            decode(&encoding_header, body, self.max_decompressed_bytes())
+                        });
+
+                        let source = self.clone();
+                        let out = out.clone();
+                        async move {
+                            let events = match body {
+                                Ok(body) => {
+                                    source.resolve(&body).await;
+                                    source.build_events(
+                                        body,
+                                        headers,
+                                        query_parameters,
+                                        path.as_str(),
+                                    )
+                                }
+                                Err(error) => Err(error),
+                            }
                             .map(|events| {
                                 emit!(&HttpEventsReceived {
                                     count: events.len(),
                                     byte_size: events.size_of(),
-                                    http_path,
+                                    http_path: path.as_str(),
                                     protocol,
                                 });
                                 events
                             });
 
-                        handle_request(events, acknowledgements, out.clone())
+                            handle_request(events, acknowledgements, out).await
+                        }
                     },
                 )
                 .with(warp::trace(move |_info| span.clone()));
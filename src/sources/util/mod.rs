@@ -5,6 +5,8 @@ mod encoding_config;
 pub mod finalizer;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
+#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+pub mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
     feature = "sources-utils-http-encoding",
@@ -30,17 +32,17 @@ mod unix_stream;
 pub use self::body_decoding::Encoding;
 #[cfg(feature = "sources-utils-http-query")]
 pub use self::http::add_query_parameters;
-#[cfg(any(
-    feature = "sources-prometheus",
-    feature = "sources-utils-http-encoding"
-))]
-pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
 pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
+#[cfg(any(
+    feature = "sources-prometheus",
+    feature = "sources-utils-http-encoding"
+))]
+pub use self::http::{content_encoding, decode};
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
@@ -1,6 +1,6 @@
 use crate::{
     config::Resource,
-    event::Event,
+    event::{BatchNotifier, BatchStatus, Event},
     internal_events::{
         ConnectionOpen, OpenGauge, TcpBytesReceived, TcpSendAckError, TcpSocketConnectionError,
     },
@@ -11,7 +11,7 @@ use crate::{
     Pipeline,
 };
 use bytes::Bytes;
-use futures::{future::BoxFuture, FutureExt, Sink, SinkExt, StreamExt};
+use futures::{future::BoxFuture, stream, FutureExt, Sink, SinkExt, StreamExt};
 use listenfd::ListenFd;
 use pin_project::pin_project;
 use serde::{de, Deserialize, Deserializer, Serialize};
@@ -19,7 +19,7 @@ use smallvec::SmallVec;
 use socket2::SockRef;
 use std::net::{IpAddr, SocketAddr};
 use std::task::{Context, Poll};
-use std::{fmt, io, mem::drop, pin::Pin, time::Duration};
+use std::{fmt, io, mem::drop, pin::Pin, sync::Arc, time::Duration};
 use tokio::{
     io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf},
     net::{TcpListener, TcpStream},
@@ -28,6 +28,10 @@ use tokio::{
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing_futures::Instrument;
 
+/// The maximum number of frames read at once from a connection, which are sent and acknowledged
+/// together.
+const MAX_FRAMES_PER_CHUNK: usize = 1024;
+
 async fn make_listener(
     addr: SocketListenAddr,
     mut listenfd: ListenFd,
@@ -87,6 +91,7 @@ where
         tls: MaybeTlsSettings,
         receive_buffer_bytes: Option<usize>,
         shutdown_signal: ShutdownSignal,
+        acknowledgements: bool,
         out: Pipeline,
     ) -> crate::Result<crate::sources::Source> {
         let out = out.sink_map_err(|error| error!(message = "Error sending event.", %error));
@@ -165,6 +170,7 @@ where
                                 source,
                                 tripwire,
                                 peer_addr.ip(),
+                                acknowledgements,
                                 out,
                             );
 
@@ -188,6 +194,7 @@ async fn handle_stream<T>(
     source: T,
     mut tripwire: BoxFuture<'static, ()>,
     peer_addr: IpAddr,
+    acknowledgements: bool,
     mut out: impl Sink<Event> + Send + 'static + Unpin,
 ) where
     <<T as TcpSource>::Decoder as tokio_util::codec::Decoder>::Item: std::marker::Send,
@@ -218,7 +225,7 @@ async fn handle_stream<T>(
     }
 
     let socket = TcpSocketWrapper::new(socket, peer_addr);
-    let mut reader = FramedRead::new(socket, source.decoder());
+    let mut reader = FramedRead::new(socket, source.decoder()).ready_chunks(MAX_FRAMES_PER_CHUNK);
     let host = Bytes::from(peer_addr.to_string());
 
     loop {
@@ -228,7 +235,7 @@ async fn handle_stream<T>(
                 debug!("Start graceful shutdown.");
                 // Close our write part of TCP socket to signal the other side
                 // that it should stop writing and close the channel.
-                let socket = reader.get_ref().get_ref();
+                let socket = reader.get_ref().get_ref().get_ref();
                 if let Some(stream) = socket.get_ref() {
                     let socket = SockRef::from(stream);
                     if let Err(error) = socket.shutdown(std::net::Shutdown::Write) {
@@ -241,37 +248,70 @@ async fn handle_stream<T>(
                 }
             },
             res = reader.next() => {
-                match res {
-                    Some(Ok((item, byte_size))) => {
-                        let ack = source.build_ack(&item);
-                        let mut events = item.into();
-                        source.handle_events(&mut events, host.clone(), byte_size);
-                        for event in events {
-                            match out.send(event).await {
-                                Ok(_) => {
-                                    let stream = reader.get_mut();
-                                    if let Err(error) = stream.write_all(&ack).await {
-                                        emit!(&TcpSendAckError{ error });
-                                        break;
-                                    }
-                                }
-                                Err(_) => {
-                                    warn!("Failed to send event.");
-                                    break;
-                                }
+                let frames = match res {
+                    Some(frames) => frames,
+                    None => {
+                        debug!("Connection closed.");
+                        break
+                    },
+                };
+
+                let mut events = Vec::with_capacity(frames.len());
+                let mut ack = Vec::new();
+                let mut stop = false;
+                for frame in frames {
+                    match frame {
+                        Ok((item, byte_size)) => {
+                            ack.extend_from_slice(&source.build_ack(&item));
+                            let mut item_events: SmallVec<[Event; 1]> = item.into();
+                            source.handle_events(&mut item_events, host.clone(), byte_size);
+                            events.extend(item_events);
+                        }
+                        Err(error) => {
+                            if !<<T as TcpSource>::Error as TcpError>::can_continue(&error) {
+                                warn!(message = "Failed to read data from TCP source.", %error);
+                                stop = true;
+                                break;
                             }
                         }
                     }
-                    Some(Err(error)) => {
-                        if !<<T as TcpSource>::Error as TcpError>::can_continue(&error) {
-                            warn!(message = "Failed to read data from TCP source.", %error);
+                }
+
+                let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+                if let Some(batch) = batch {
+                    for event in &mut events {
+                        event.add_batch_notifier(Arc::clone(&batch));
+                    }
+                }
+
+                if out.send_all(&mut stream::iter(events).map(Ok)).await.is_err() {
+                    warn!("Failed to send event.");
+                    break;
+                }
+
+                // With acknowledgements, the frames are only acknowledged once their events
+                // were delivered, so the client sends them again otherwise. Shutting down
+                // doesn't wait for them though.
+                if let Some(receiver) = receiver {
+                    tokio::select! {
+                        status = receiver => if status != BatchStatus::Delivered {
+                            warn!("Events were not delivered, closing the connection without acknowledging them.");
                             break;
-                        }
+                        },
+                        _ = &mut tripwire => break,
                     }
-                    None => {
-                        debug!("Connection closed.");
-                        break
-                    },
+                }
+
+                if !ack.is_empty() {
+                    let stream = reader.get_mut().get_mut();
+                    if let Err(error) = stream.write_all(&ack).await {
+                        emit!(&TcpSendAckError{ error });
+                        break;
+                    }
+                }
+
+                if stop {
+                    break;
                 }
             }
             else => break,
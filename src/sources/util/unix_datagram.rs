@@ -76,6 +76,7 @@ async fn listen(
                 let received_from: Option<Bytes> =
                     path.map(|p| p.to_string_lossy().into_owned().into());
 
+                decoder.resolve(&payload).await;
                 let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
 
                 loop {
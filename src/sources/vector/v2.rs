@@ -3,8 +3,11 @@ use crate::{
     config::{DataType, GenerateConfig, Resource},
     proto::vector as proto,
     shutdown::ShutdownSignalToken,
-    sources::Source,
-    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsConfig},
+    sources::{
+        util::grpc::{handle_batch_status, DecompressionService},
+        Source,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
 
@@ -12,12 +15,8 @@ use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::net::TcpStream;
-use tonic::{
-    transport::{server::Connected, Certificate, Server},
-    Request, Response, Status,
-};
-use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event};
+use tonic::{transport::Server, Request, Response, Status};
+use vector_core::event::{BatchNotifier, Event};
 
 #[derive(Debug, Clone)]
 pub struct Service {
@@ -70,19 +69,6 @@ impl proto::Service for Service {
     }
 }
 
-async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
-    let status = match receiver {
-        Some(receiver) => receiver.await,
-        None => BatchStatus::Delivered,
-    };
-
-    match status {
-        BatchStatus::Errored => Err(Status::internal("Delivery error")),
-        BatchStatus::Failed => Err(Status::data_loss("Delivery failed")),
-        BatchStatus::Delivered => Ok(()),
-    }
-}
-
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
@@ -91,6 +77,8 @@ pub struct VectorConfig {
     pub shutdown_timeout_secs: u64,
     #[serde(default)]
     tls: Option<TlsConfig>,
+    #[serde(default)]
+    max_decompressed_bytes: Option<usize>,
 }
 
 const fn default_shutdown_timeout_secs() -> u64 {
@@ -103,6 +91,7 @@ impl GenerateConfig for VectorConfig {
             address: "0.0.0.0:6000".parse().unwrap(),
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             tls: None,
+            max_decompressed_bytes: None,
         })
         .unwrap()
     }
@@ -112,9 +101,10 @@ impl VectorConfig {
     pub(super) async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
         let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
 
-        let source = run(self.address, tls_settings, cx).map_err(|error| {
-            error!(message = "Source future failed.", %error);
-        });
+        let source =
+            run(self.address, tls_settings, self.max_decompressed_bytes, cx).map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            });
 
         Ok(Box::pin(source))
     }
@@ -135,14 +125,18 @@ impl VectorConfig {
 async fn run(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
+    max_decompressed_bytes: Option<usize>,
     cx: SourceContext,
 ) -> crate::Result<()> {
     let _span = crate::trace::current_span();
 
-    let service = proto::Server::new(Service {
-        pipeline: cx.out,
-        acknowledgements: cx.acknowledgements,
-    });
+    let service = DecompressionService::new(
+        proto::Server::new(Service {
+            pipeline: cx.out,
+            acknowledgements: cx.acknowledgements,
+        }),
+        max_decompressed_bytes,
+    );
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
 
     let listener = tls_settings.bind(&address).await?;
@@ -158,27 +152,113 @@ async fn run(
     Ok(())
 }
 
-#[derive(Clone)]
-pub struct MaybeTlsConnectInfo {
-    pub remote_addr: SocketAddr,
-    pub peer_certs: Option<Vec<Certificate>>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::proto::EventWrapper,
+        test_util::{collect_n, next_addr, wait_for_tcp},
+    };
+    use bytes::{BufMut, Bytes, BytesMut};
+    use futures::channel::mpsc;
+    use hyper::body::HttpBody;
+    use prost::Message;
+
+    async fn source(max_decompressed_bytes: Option<usize>) -> (mpsc::Receiver<Event>, SocketAddr) {
+        let (sender, recv) = Pipeline::new_test();
+        let address = next_addr();
+        tokio::spawn(async move {
+            VectorConfig {
+                address,
+                shutdown_timeout_secs: default_shutdown_timeout_secs(),
+                tls: None,
+                max_decompressed_bytes,
+            }
+            .build(SourceContext::new_test(sender))
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
 
-impl Connected for MaybeTlsIncomingStream<TcpStream> {
-    type ConnectInfo = MaybeTlsConnectInfo;
-
-    fn connect_info(&self) -> Self::ConnectInfo {
-        MaybeTlsConnectInfo {
-            remote_addr: self.peer_addr(),
-            peer_certs: self
-                .ssl_stream()
-                .and_then(|s| s.ssl().peer_cert_chain())
-                .map(|s| {
-                    s.into_iter()
-                        .filter_map(|c| c.to_pem().ok())
-                        .map(Certificate::from_pem)
-                        .collect()
-                }),
+    fn request(message: &str) -> proto::PushEventsRequest {
+        proto::PushEventsRequest {
+            events: vec![EventWrapper::from(Event::from(message))],
         }
     }
+
+    /// Sends a single message compressed with zstd, framed as by gRPC clients
+    /// supporting `grpc-encoding`, returning the `grpc-status` of the response.
+    async fn send_zstd(address: SocketAddr, request: proto::PushEventsRequest) -> String {
+        let message = zstd::stream::encode_all(&request.encode_to_vec()[..], 0).unwrap();
+        let mut body = BytesMut::new();
+        body.put_u8(1);
+        body.put_u32(message.len() as u32);
+        body.put(&message[..]);
+
+        let request = hyper::Request::post(format!("http://{}/vector.Vector/PushEvents", address))
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .header("grpc-encoding", "zstd")
+            .body(hyper::Body::from(Bytes::from(body)))
+            .unwrap();
+        let mut response = hyper::Client::builder()
+            .http2_only(true)
+            .build_http()
+            .request(request)
+            .await
+            .unwrap();
+
+        // Errors are sent as trailers-only responses, otherwise the status
+        // follows the response message.
+        let headers = match response.headers().get("grpc-status") {
+            Some(_) => response.headers().clone(),
+            None => {
+                let _ = hyper::body::to_bytes(response.body_mut()).await.unwrap();
+                response.trailers().await.unwrap().unwrap()
+            }
+        };
+        headers["grpc-status"].to_str().unwrap().to_owned()
+    }
+
+    #[tokio::test]
+    async fn receives_events() {
+        let (recv, address) = source(None).await;
+
+        let mut client = proto::Client::connect(format!("http://{}", address))
+            .await
+            .unwrap();
+        client.push_events(request("hello")).await.unwrap();
+
+        let events = collect_n(recv, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "hello".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn decompresses_messages() {
+        let (recv, address) = source(None).await;
+
+        assert_eq!(send_zstd(address, request("hello")).await, "0");
+
+        let events = collect_n(recv, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "hello".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn limits_decompressed_size() {
+        let (_recv, address) = source(Some(16)).await;
+
+        let status = send_zstd(address, request(&"a".repeat(1024))).await;
+        assert_eq!(status, (tonic::Code::ResourceExhausted as i32).to_string());
+    }
 }
@@ -2,13 +2,13 @@ use crate::{
     config,
     signal::{SignalRx, SignalTo},
 };
-use tokio_stream::StreamExt;
+use tokio_stream::{Stream, StreamExt};
 use url::Url;
 use vector_api_client::{connect_subscription_client, gql::TapSubscriptionExt, Client};
 
 /// CLI command func for issuing 'tap' queries, and communicating with a local/remote
 /// Vector API server via HTTP/WebSockets.
-pub async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode::ExitCode {
+pub async fn cmd(opts: &super::Opts, signal_rx: SignalRx) -> exitcode::ExitCode {
     // Use the provided URL as the Vector GraphQL API server, or default to the local port
     // provided by the API config. This will work despite `api` and `api-client` being distinct
     // features; the config is available even if `api` is disabled.
@@ -39,17 +39,87 @@ pub async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode::ExitC
         }
     };
 
-    // Issue the 'tap' request, printing to stdout.
-    let res = subscription_client.output_events_by_component_id_patterns_subscription(
-        opts.component_id_patterns.clone(),
-        opts.format,
-        opts.limit as i64,
-        opts.interval as i64,
-    );
+    // Issue the 'tap' request, printing to stdout. Each result is mapped to the encoded events
+    // it contains, or to the errors returned by the server.
+    if opts.invocations {
+        let res = subscription_client.transform_invocations_by_component_id_patterns_subscription(
+            opts.component_id_patterns.clone(),
+            opts.format,
+            opts.limit as i64,
+            opts.interval as i64,
+            opts.filter.clone(),
+            opts.sample as i64,
+        );
 
-    tokio::pin! {
-        let stream = res.stream();
-    };
+        // Each received event is followed by the events the transform emitted in return.
+        let stream = res
+            .stream()
+            .filter_map(|res| res)
+            .map(|res| match res.errors {
+                Some(errors) => Err(errors.into_iter().map(|error| error.message).collect()),
+                None => Ok(res
+                    .data
+                    .map(|d| {
+                        d.transform_invocations_by_component_id_patterns
+                            .iter()
+                            .filter_map(|ev| ev.as_invocation())
+                            .flat_map(|invocation| {
+                                invocation
+                                    .input
+                                    .iter()
+                                    .map(|log_event| log_event.string.clone())
+                                    .chain(
+                                        invocation
+                                            .outputs
+                                            .iter()
+                                            .map(|log_event| log_event.string.clone()),
+                                    )
+                                    .collect::<Vec<_>>()
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()),
+            });
+
+        print_events(stream, signal_rx).await
+    } else {
+        let res = subscription_client.output_events_by_component_id_patterns_subscription(
+            opts.component_id_patterns.clone(),
+            (!opts.inputs_of.is_empty()).then(|| opts.inputs_of.clone()),
+            opts.format,
+            opts.limit as i64,
+            opts.interval as i64,
+            opts.filter.clone(),
+            opts.sample as i64,
+        );
+
+        let stream = res
+            .stream()
+            .filter_map(|res| res)
+            .map(|res| match res.errors {
+                Some(errors) => Err(errors.into_iter().map(|error| error.message).collect()),
+                None => Ok(res
+                    .data
+                    .map(|d| {
+                        d.output_events_by_component_id_patterns
+                            .iter()
+                            .filter_map(|ev| ev.as_log())
+                            .map(|log_event| log_event.string.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default()),
+            });
+
+        print_events(stream, signal_rx).await
+    }
+}
+
+/// Prints the encoded events of each result to stdout, until a shutdown signal is received.
+async fn print_events(
+    stream: impl Stream<Item = Result<Vec<String>, Vec<String>>>,
+    mut signal_rx: SignalRx,
+) -> exitcode::ExitCode {
+    tokio::pin!(stream);
 
     // Loop over the returned results, printing out log events.
     // NOTE: This will currently ignore notifications. A later `--verbose` option is planned
@@ -59,11 +129,18 @@ pub async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode::ExitC
         tokio::select! {
             biased;
             Some(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => break,
-            Some(Some(res)) = stream.next() => {
-                if let Some(d) = res.data {
-                    for log_event in d.output_events_by_component_id_patterns.iter().filter_map(|ev| ev.as_log()) {
-                        println!("{}", log_event.string);
+            Some(res) = stream.next() => match res {
+                Ok(events) => {
+                    for event in events {
+                        println!("{}", event);
+                    }
+                }
+                // Errors, such as an invalid filter, end the subscription.
+                Err(errors) => {
+                    for error in errors {
+                        eprintln!("{}", error);
                     }
+                    return exitcode::DATAERR;
                 }
             }
         }
@@ -25,6 +25,24 @@ pub struct Opts {
     #[structopt(default_value = "json", possible_values = &["json", "yaml"], short = "f", long)]
     format: TapEncodingFormat,
 
+    /// VRL condition evaluated by Vector against events, only those for which it's true are sent
+    #[structopt(long)]
+    filter: Option<String>,
+
+    /// Send only one in every N events passing the filter, sampled by Vector
+    #[structopt(default_value = "1", long)]
+    sample: u32,
+
+    /// Components IDs whose received events are also observed (comma-separated; accepts glob
+    /// patterns)
+    #[structopt(long, use_delimiter(true))]
+    inputs_of: Vec<String>,
+
+    /// Observe the invocations of the transforms matching the component ID patterns instead,
+    /// printing each received event followed by the events emitted in return
+    #[structopt(long, conflicts_with = "inputs-of")]
+    invocations: bool,
+
     /// Components IDs to observe (comma-separated; accepts glob patterns)
     #[structopt(default_value = "*", use_delimiter(true))]
     component_id_patterns: Vec<String>,
@@ -1,5 +1,6 @@
 use super::{
     fanout::{self, Fanout},
+    invocations::{Invocation, InvocationTap},
     task::{Task, TaskOutput},
     BuiltBuffer, ConfigDiff,
 };
@@ -8,13 +9,14 @@ use crate::{
     config::{
         ComponentKey, DataType, OutputId, ProxyConfig, SinkContext, SourceContext, TransformContext,
     },
+    dropped::{self, DroppedSender, DROPPED_OUTPUT},
     event::Event,
     internal_events::{EventsReceived, EventsSent},
     shutdown::SourceShutdownCoordinator,
-    transforms::Transform,
+    transforms::{Transform, TransformOutputsBuf},
     Pipeline,
 };
-use futures::{stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{future, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
 use lazy_static::lazy_static;
 use std::pin::Pin;
 use std::{
@@ -48,7 +50,10 @@ pub async fn load_enrichment_tables<'a>(
             let indexes = if !diff.enrichment_tables.contains_new(name) {
                 // If this is an existing enrichment table, we need to store the indexes to reapply
                 // them again post load.
-                Some(ENRICHMENT_TABLES.index_fields(&table_name))
+                Some((
+                    ENRICHMENT_TABLES.index_fields(&table_name),
+                    ENRICHMENT_TABLES.field_indexes(&table_name),
+                ))
             } else {
                 None
             };
@@ -61,7 +66,7 @@ pub async fn load_enrichment_tables<'a>(
                 }
             };
 
-            if let Some(indexes) = indexes {
+            if let Some((indexes, field_indexes)) = indexes {
                 for (case, index) in indexes {
                     match table
                         .add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())
@@ -78,6 +83,15 @@ pub async fn load_enrichment_tables<'a>(
                         }
                     }
                 }
+
+                for (field, kind) in field_indexes {
+                    if let Err(error) = table.add_field_index(&field, kind) {
+                        error!(message = "Unable to add index to reloaded enrichment table.",
+                                table = ?name.to_string(),
+                                %error);
+                        continue 'tables;
+                    }
+                }
             }
 
             enrichment_tables.insert(table_name, table);
@@ -98,6 +112,7 @@ pub struct Pieces {
     pub shutdown_coordinator: SourceShutdownCoordinator,
     pub detach_triggers: HashMap<ComponentKey, Trigger>,
     pub enrichment_tables: enrichment::TableRegistry,
+    pub invocation_taps: HashMap<ComponentKey, InvocationTap>,
 }
 
 /// Builds only the new pieces, and doesn't check their topology.
@@ -113,6 +128,7 @@ pub async fn build_pieces(
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
+    let mut invocation_taps = HashMap::new();
 
     let mut errors = vec![];
 
@@ -198,6 +214,7 @@ pub async fn build_pieces(
         let typetag = transform.inner.transform_type();
 
         let mut named_outputs = transform.inner.named_outputs();
+        let has_primary_output = transform.inner.has_primary_output();
 
         let input_type = transform.inner.input_type();
         let transform = match transform.inner.build(&context).await {
@@ -222,6 +239,8 @@ pub async fn build_pieces(
         let task = match transform {
             Transform::Function(mut t) => {
                 let (output, control) = Fanout::new();
+                let invocations = InvocationTap::default();
+                invocation_taps.insert(key.clone(), invocations.clone());
 
                 let transform = input_rx
                     .filter(move |event| ready(filter_event_type(event, input_type)))
@@ -237,7 +256,16 @@ pub async fn build_pieces(
                         let mut buf = Vec::with_capacity(4); // also an arbitrary,
                                                              // smallish constant
                         for v in events {
-                            t.transform(&mut buf, v);
+                            if invocations.is_active() {
+                                let input = v.clone();
+                                t.transform(&mut buf, v);
+                                invocations.send(Invocation {
+                                    input,
+                                    outputs: buf.clone(),
+                                });
+                            } else {
+                                t.transform(&mut buf, v);
+                            }
                             output.append(&mut buf);
                         }
                         emit!(&EventsSent {
@@ -261,6 +289,15 @@ pub async fn build_pieces(
                 let (mut output, control) = Fanout::new();
                 let (mut errors_output, errors_control) = Fanout::new();
 
+                // TODO: actually drive fanout creation from transform output declaration instead
+                // of relying on the one fallible function pattern we currently have
+                assert_eq!(1, named_outputs.len());
+                let errors_port = named_outputs.remove(0);
+                let reroutes_dropped = errors_port == DROPPED_OUTPUT;
+                let component_key = key.clone();
+                let invocations = InvocationTap::default();
+                invocation_taps.insert(key.clone(), invocations.clone());
+
                 let transform = async move {
                     while let Some(event) = input_rx.next().await {
                         if !filter_event_type(&event, input_type) {
@@ -274,7 +311,14 @@ pub async fn build_pieces(
                         let mut buf = Vec::with_capacity(1);
                         let mut err_buf = Vec::with_capacity(1);
 
+                        let input = invocations.is_active().then(|| event.clone());
                         t.transform(&mut buf, &mut err_buf, event);
+                        if let Some(input) = input {
+                            invocations.send(Invocation {
+                                input,
+                                outputs: buf.iter().chain(err_buf.iter()).cloned().collect(),
+                            });
+                        }
                         // TODO: account for error outputs separately?
                         emit!(&EventsSent {
                             count: buf.len() + err_buf.len(),
@@ -285,7 +329,15 @@ pub async fn build_pieces(
                             output.feed(event).await.expect("unit error");
                         }
                         output.flush().await.expect("unit error");
-                        for event in err_buf {
+                        for mut event in err_buf {
+                            if reroutes_dropped {
+                                dropped::annotate_component(
+                                    &mut event,
+                                    &component_key,
+                                    typetag,
+                                    "transform",
+                                );
+                            }
                             errors_output.feed(event).await.expect("unit error");
                         }
                         errors_output.flush().await.expect("unit error");
@@ -297,13 +349,73 @@ pub async fn build_pieces(
                 .boxed();
 
                 outputs.insert(OutputId::from(key), control);
-                // TODO: actually drive fanout creation from transform output declaration instead
-                // of relying on the one fallible function pattern we currently have
-                assert_eq!(1, named_outputs.len());
-                outputs.insert(
-                    OutputId::from((key, named_outputs.remove(0))),
-                    errors_control,
-                );
+                outputs.insert(OutputId::from((key, errors_port)), errors_control);
+
+                Task::new(key.clone(), typetag, transform)
+            }
+            Transform::Synchronous(mut t) => {
+                let (mut output, control) = Fanout::new();
+                if has_primary_output {
+                    outputs.insert(OutputId::from(key), control);
+                }
+                let invocations = InvocationTap::default();
+                invocation_taps.insert(key.clone(), invocations.clone());
+
+                let mut named_fanouts = Vec::with_capacity(named_outputs.len());
+                for name in &named_outputs {
+                    let (output, control) = Fanout::new();
+                    outputs.insert(OutputId::from((key, name.clone())), control);
+                    named_fanouts.push((name.clone(), output));
+                }
+
+                let transform = async move {
+                    let mut buf = TransformOutputsBuf::new_with_capacity(named_outputs, 1);
+
+                    while let Some(event) = input_rx.next().await {
+                        if !filter_event_type(&event, input_type) {
+                            continue;
+                        }
+                        emit!(&EventsReceived {
+                            count: 1,
+                            byte_size: event.size_of(),
+                        });
+
+                        let input = invocations.is_active().then(|| event.clone());
+                        t.transform(event, &mut buf);
+                        emit!(&EventsSent {
+                            count: buf.len(),
+                            byte_size: buf.size_of(),
+                        });
+
+                        let primary = buf.take_primary();
+                        let mut invocation_outputs = input.as_ref().map(|_| primary.clone());
+                        for event in primary {
+                            output.feed(event).await.expect("unit error");
+                        }
+                        output.flush().await.expect("unit error");
+                        for (name, output) in named_fanouts.iter_mut() {
+                            let events = buf.take_named(name);
+                            if events.is_empty() {
+                                continue;
+                            }
+                            if let Some(invocation_outputs) = invocation_outputs.as_mut() {
+                                invocation_outputs.extend(events.iter().cloned());
+                            }
+                            for event in events {
+                                output.feed(event).await.expect("unit error");
+                            }
+                            output.flush().await.expect("unit error");
+                        }
+
+                        if let (Some(input), Some(outputs)) = (input, invocation_outputs) {
+                            invocations.send(Invocation { input, outputs });
+                        }
+                    }
+
+                    debug!("Finished.");
+                    Ok(TaskOutput::Transform)
+                }
+                .boxed();
 
                 Task::new(key.clone(), typetag, transform)
             }
@@ -364,6 +476,8 @@ pub async fn build_pieces(
                 buffers::BufferConfig::Memory { .. } => "memory",
                 #[cfg(feature = "disk-buffer")]
                 buffers::BufferConfig::Disk { .. } => "disk",
+                #[cfg(feature = "disk-buffer-v2")]
+                buffers::BufferConfig::DiskV2 { .. } => "disk_v2",
             };
             let buffer_span = error_span!(
                 "sink",
@@ -384,11 +498,19 @@ pub async fn build_pieces(
             }
         };
 
+        let (dropped, dropped_rx) = if sink.reroute_dropped() {
+            let (dropped, dropped_rx) = DroppedSender::new();
+            (Some(dropped), Some(dropped_rx))
+        } else {
+            (None, None)
+        };
+
         let cx = SinkContext {
             acker: acker.clone(),
             healthcheck,
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
+            dropped: dropped.clone(),
         };
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -399,6 +521,20 @@ pub async fn build_pieces(
             Ok(built) => built,
         };
 
+        let dropped_pump = dropped_rx.map(|dropped_rx| {
+            let (output, control) = Fanout::new();
+            outputs.insert(OutputId::from((key, DROPPED_OUTPUT.to_owned())), control);
+
+            let component_key = key.clone();
+            dropped_rx
+                .map(move |mut event| {
+                    dropped::annotate_component(&mut event, &component_key, typetag, "sink");
+                    Ok(event)
+                })
+                .forward(output)
+                .map(|_| ())
+        });
+
         let (trigger, tripwire) = Tripwire::new();
 
         let sink = async move {
@@ -416,19 +552,32 @@ pub async fn build_pieces(
 
             let mut rx = crate::utilization::wrap(rx);
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .inspect(|event| {
-                        emit!(&EventsReceived {
-                            count: 1,
-                            byte_size: event.size_of(),
+            let run = sink
+                .run(
+                    rx.by_ref()
+                        .filter(|event| ready(filter_event_type(event, input_type)))
+                        .inspect(|event| {
+                            emit!(&EventsReceived {
+                                count: 1,
+                                byte_size: event.size_of(),
+                            })
                         })
-                    })
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+                        .take_until_if(tripwire),
+                )
+                .map(|result| {
+                    // Ends the `dropped` output once the events dropped so
+                    // far have been forwarded, even if the sink leaks a sender.
+                    if let Some(dropped) = &dropped {
+                        dropped.close();
+                    }
+                    result
+                });
+            let result = match dropped_pump {
+                Some(dropped_pump) => future::join(run, dropped_pump).await.0,
+                None => run.await,
+            };
+
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -507,6 +656,7 @@ pub async fn build_pieces(
             shutdown_coordinator,
             detach_triggers,
             enrichment_tables: enrichment_tables.clone(),
+            invocation_taps,
         };
 
         Ok(pieces)
@@ -520,5 +670,6 @@ const fn filter_event_type(event: &Event, data_type: DataType) -> bool {
         DataType::Any => true,
         DataType::Log => matches!(event, Event::Log(_)),
         DataType::Metric => matches!(event, Event::Metric(_)),
+        DataType::Trace => matches!(event, Event::Trace(_)),
     }
 }
@@ -0,0 +1,111 @@
+//! Observation of individual transform invocations, pairing the event a transform was given
+//! with the events it emitted in return.
+
+use crate::event::Event;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::sync::mpsc;
+
+/// A single transform invocation.
+#[derive(Debug, Clone)]
+pub struct Invocation {
+    /// The event given to the transform.
+    pub input: Event,
+    /// The events emitted by the transform, on any of its outputs.
+    pub outputs: Vec<Event>,
+}
+
+#[derive(Default)]
+struct Subscribers {
+    /// Number of subscribers, readable without taking the lock.
+    count: AtomicUsize,
+    next_id: AtomicU64,
+    senders: Mutex<HashMap<u64, mpsc::Sender<Invocation>>>,
+}
+
+/// Relays the invocations of a transform to subscribers. Checking for subscribers is a single
+/// atomic load, so it can sit in the transform's hot path.
+#[derive(Clone, Default)]
+pub struct InvocationTap {
+    subscribers: Arc<Subscribers>,
+}
+
+impl InvocationTap {
+    /// Whether anyone is subscribed, in which case invocations should be recorded.
+    pub fn is_active(&self) -> bool {
+        self.subscribers.count.load(Ordering::Relaxed) > 0
+    }
+
+    /// Sends an invocation to every subscriber. Subscribers which can't keep up miss it, as
+    /// the transform isn't held back for them.
+    pub fn send(&self, invocation: Invocation) {
+        let senders = self.subscribers.senders.lock().expect("poisoned lock");
+        for sender in senders.values() {
+            let _ = sender.try_send(invocation.clone());
+        }
+    }
+
+    /// Subscribes to the invocations, until the returned subscription is dropped.
+    pub fn subscribe(&self, sender: mpsc::Sender<Invocation>) -> InvocationSubscription {
+        let id = self.subscribers.next_id.fetch_add(1, Ordering::Relaxed);
+        self.subscribers
+            .senders
+            .lock()
+            .expect("poisoned lock")
+            .insert(id, sender);
+        self.subscribers.count.fetch_add(1, Ordering::Relaxed);
+
+        InvocationSubscription {
+            subscribers: Arc::clone(&self.subscribers),
+            id,
+        }
+    }
+}
+
+/// Unsubscribes from the invocations of a transform when dropped.
+pub struct InvocationSubscription {
+    subscribers: Arc<Subscribers>,
+    id: u64,
+}
+
+impl Drop for InvocationSubscription {
+    fn drop(&mut self) {
+        if let Ok(mut senders) = self.subscribers.senders.lock() {
+            if senders.remove(&self.id).is_some() {
+                self.subscribers.count.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn relays_invocations_to_subscribers() {
+        let tap = InvocationTap::default();
+        assert!(!tap.is_active());
+
+        let (tx, mut rx) = mpsc::channel(10);
+        let subscription = tap.subscribe(tx);
+        assert!(tap.is_active());
+
+        tap.send(Invocation {
+            input: Event::from("in"),
+            outputs: vec![Event::from("out"), Event::from("other")],
+        });
+        let invocation = rx.recv().await.unwrap();
+        assert_eq!(invocation.input, Event::from("in"));
+        assert_eq!(invocation.outputs.len(), 2);
+
+        drop(subscription);
+        assert!(!tap.is_active());
+        assert!(rx.recv().await.is_none());
+    }
+}
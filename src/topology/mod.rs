@@ -8,6 +8,7 @@
 
 pub mod builder;
 pub mod fanout;
+pub mod invocations;
 mod running;
 mod task;
 
@@ -43,11 +44,18 @@ type BuiltBuffer = (
 
 type Outputs = HashMap<OutputId, fanout::ControlChannel>;
 
-// Watcher types for topology changes. These are currently specific to receiving
-// `Outputs`. This could be expanded in the future to send an enum of types if,
-// for example, this included a new 'Inputs' type.
-type WatchTx = watch::Sender<Outputs>;
-pub type WatchRx = watch::Receiver<Outputs>;
+/// The parts of a running topology that can be tapped into: the outputs of every component,
+/// the outputs each component takes its inputs from, and the invocations of transforms.
+#[derive(Clone, Default)]
+pub struct TapResource {
+    pub outputs: Outputs,
+    pub inputs: HashMap<ComponentKey, Vec<OutputId>>,
+    pub invocations: HashMap<ComponentKey, invocations::InvocationTap>,
+}
+
+// Watcher types for topology changes, sending the latest `TapResource`.
+type WatchTx = watch::Sender<TapResource>;
+pub type WatchRx = watch::Receiver<TapResource>;
 
 pub async fn start_validated(
     config: Config,
@@ -1,8 +1,8 @@
 use crate::topology::builder;
 use crate::topology::fanout::{ControlChannel, ControlMessage};
 use crate::topology::{
-    build_or_log_errors, handle_errors, retain, take_healthchecks, BuiltBuffer, Outputs,
-    TaskHandle, WatchRx, WatchTx,
+    build_or_log_errors, handle_errors, invocations::InvocationTap, retain, take_healthchecks,
+    BuiltBuffer, TapResource, TaskHandle, WatchRx, WatchTx,
 };
 use crate::{
     buffers,
@@ -26,7 +26,9 @@ use tracing::Instrument;
 #[allow(dead_code)]
 pub struct RunningTopology {
     inputs: HashMap<ComponentKey, buffers::BufferInputCloner<Event>>,
+    input_ids: HashMap<ComponentKey, Vec<OutputId>>,
     outputs: HashMap<OutputId, ControlChannel>,
+    invocation_taps: HashMap<ComponentKey, InvocationTap>,
     source_tasks: HashMap<ComponentKey, TaskHandle>,
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
@@ -40,14 +42,16 @@ impl RunningTopology {
     pub fn new(config: Config, abort_tx: mpsc::UnboundedSender<()>) -> Self {
         Self {
             inputs: HashMap::new(),
+            input_ids: HashMap::new(),
             outputs: HashMap::new(),
+            invocation_taps: HashMap::new(),
             config,
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
-            watch: watch::channel(HashMap::new()),
+            watch: watch::channel(TapResource::default()),
         }
     }
 
@@ -377,6 +381,7 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             info!(message = "Removing sink.", key = %key);
             self.remove_inputs(key).await;
+            self.remove_outputs(key);
         }
 
         // Detach changed sinks
@@ -442,6 +447,14 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks only have outputs if they reroute dropped events, and these
+        // can be used as inputs by transforms too.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         for key in &diff.transforms.to_change {
             self.replace_inputs(key, new_pieces, diff).await;
         }
@@ -459,16 +472,18 @@ impl RunningTopology {
             self.setup_inputs(key, new_pieces).await;
         }
 
+        self.invocation_taps
+            .extend(new_pieces.invocation_taps.drain());
+
         // Broadcast changes to subscribers.
         if !self.watch.0.is_closed() {
             self.watch
                 .0
-                .send(
-                    self.outputs
-                        .iter()
-                        .map(|item| (item.0.clone(), item.1.clone()))
-                        .collect::<HashMap<_, _>>(),
-                )
+                .send(TapResource {
+                    outputs: self.outputs.clone(),
+                    inputs: self.input_ids.clone(),
+                    invocations: self.invocation_taps.clone(),
+                })
                 .expect("Couldn't broadcast config changes.");
         }
     }
@@ -570,10 +585,12 @@ impl RunningTopology {
 
     fn remove_outputs(&mut self, key: &ComponentKey) {
         self.outputs.retain(|id, _output| &id.component != key);
+        self.invocation_taps.remove(key);
     }
 
     async fn remove_inputs(&mut self, key: &ComponentKey) {
         self.inputs.remove(key);
+        self.input_ids.remove(key);
         self.detach_triggers.remove(key);
 
         let sink_inputs = self.config.sinks.get(key).map(|s| &s.inputs);
@@ -628,17 +645,18 @@ impl RunningTopology {
     async fn setup_inputs(&mut self, key: &ComponentKey, new_pieces: &mut builder::Pieces) {
         let (tx, inputs) = new_pieces.inputs.remove(key).unwrap();
 
-        for input in inputs {
+        for input in &inputs {
             // This can only fail if we are disconnected, which is a valid situation.
             let _ = self
                 .outputs
-                .get_mut(&input)
+                .get_mut(input)
                 .expect("unknown output")
                 .send(ControlMessage::Add(key.clone(), tx.get()))
                 .await;
         }
 
         self.inputs.insert(key.clone(), tx);
+        self.input_ids.insert(key.clone(), inputs);
         new_pieces
             .detach_triggers
             .remove(key)
@@ -675,6 +693,7 @@ impl RunningTopology {
                 .sources
                 .changed_and_added()
                 .chain(diff.transforms.changed_and_added())
+                .chain(diff.sinks.changed_and_added())
                 .any(|key| key == &input.component)
             {
                 inputs_to_add.insert(input);
@@ -711,6 +730,7 @@ impl RunningTopology {
         }
 
         self.inputs.insert(key.clone(), tx);
+        self.input_ids.insert(key.clone(), inputs);
         new_pieces
             .detach_triggers
             .remove(key)
@@ -719,6 +739,7 @@ impl RunningTopology {
 
     async fn detach_inputs(&mut self, key: &ComponentKey) {
         self.inputs.remove(key);
+        self.input_ids.remove(key);
         self.detach_triggers.remove(key);
 
         let sink_inputs = self.config.sinks.get(key).map(|s| &s.inputs);
@@ -742,10 +763,10 @@ impl RunningTopology {
         &self.config
     }
 
-    /// Subscribe to topology changes. This will receive an `Outputs` currently, but may be
-    /// expanded in the future to accommodate `Inputs`. This is used by the 'tap' API to observe
-    /// config changes, and re-wire tap sinks.
-    pub fn watch(&self) -> watch::Receiver<Outputs> {
+    /// Subscribe to topology changes. This will receive a `TapResource` describing the
+    /// component outputs, inputs and transform invocations. This is used by the 'tap' API to
+    /// observe config changes, and re-wire tap sinks.
+    pub fn watch(&self) -> watch::Receiver<TapResource> {
         self.watch.1.clone()
     }
 }
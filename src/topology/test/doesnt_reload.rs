@@ -15,7 +15,9 @@ async fn topology_doesnt_reload_new_data_dir() {
         &["in"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
     old_config.global.data_dir = Some(Path::new("/asdf").to_path_buf());
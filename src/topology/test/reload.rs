@@ -23,7 +23,9 @@ async fn topology_reuse_old_port() {
         &[&"in1"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
 
@@ -34,7 +36,9 @@ async fn topology_reuse_old_port() {
         &[&"in2"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
 
@@ -57,7 +61,9 @@ async fn topology_rebuild_old() {
         &[&"in1"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
 
@@ -68,7 +74,9 @@ async fn topology_rebuild_old() {
         &[&"in1"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
 
@@ -93,7 +101,9 @@ async fn topology_old() {
         &[&"in1"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
 
@@ -243,6 +253,7 @@ async fn topology_disk_buffer_conflict() {
     old_config.sinks["out"].buffer = BufferConfig::Disk {
         max_size: 1024,
         when_full: WhenFull::Block,
+        overflow: None,
     };
 
     let mut new_config = old_config.clone();
@@ -254,6 +265,7 @@ async fn topology_disk_buffer_conflict() {
     new_config.sinks["out"].buffer = BufferConfig::Disk {
         max_size: 2048,
         when_full: WhenFull::Block,
+        overflow: None,
     };
 
     reload_sink_test(
@@ -16,7 +16,9 @@ async fn sources_finished() {
         &["in"],
         ConsoleSinkConfig {
             target: Target::Stdout,
-            encoding: Encoding::Text.into(),
+            encoding: Some(Encoding::Text.into()),
+            framing: None,
+            serializer: None,
         },
     );
 
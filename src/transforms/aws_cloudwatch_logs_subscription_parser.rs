@@ -4,9 +4,10 @@ use crate::{
         log_schema, DataType, GenerateConfig, TransformConfig, TransformContext,
         TransformDescription,
     },
+    dropped,
     event::Event,
     internal_events::AwsCloudwatchLogsSubscriptionParserFailedParse,
-    transforms::FunctionTransform,
+    transforms::{FallibleFunctionTransform, FunctionTransform},
 };
 use serde::{Deserialize, Serialize};
 use shared::aws_cloudwatch_logs_subscription::{
@@ -19,6 +20,7 @@ use std::iter;
 #[derivative(Default)]
 pub struct AwsCloudwatchLogsSubscriptionParserConfig {
     pub field: Option<String>,
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -29,9 +31,16 @@ inventory::submit! {
 #[typetag::serde(name = "aws_cloudwatch_logs_subscription_parser")]
 impl TransformConfig for AwsCloudwatchLogsSubscriptionParserConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(
-            AwsCloudwatchLogsSubscriptionParser::from(self.clone()),
-        ))
+        let parser = AwsCloudwatchLogsSubscriptionParser::from(self.clone());
+        Ok(if self.reroute_dropped {
+            Transform::fallible_function(parser)
+        } else {
+            Transform::function(parser)
+        })
+    }
+
+    fn named_outputs(&self) -> Vec<String> {
+        dropped::named_outputs(self.reroute_dropped)
     }
 
     fn input_type(&self) -> DataType {
@@ -49,13 +58,18 @@ impl TransformConfig for AwsCloudwatchLogsSubscriptionParserConfig {
 
 impl GenerateConfig for AwsCloudwatchLogsSubscriptionParserConfig {
     fn generate_config() -> toml::Value {
-        toml::Value::try_from(Self { field: None }).unwrap()
+        toml::Value::try_from(Self {
+            field: None,
+            reroute_dropped: false,
+        })
+        .unwrap()
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct AwsCloudwatchLogsSubscriptionParser {
     field: String,
+    reroute_dropped: bool,
 }
 
 impl From<AwsCloudwatchLogsSubscriptionParserConfig> for AwsCloudwatchLogsSubscriptionParser {
@@ -66,30 +80,45 @@ impl From<AwsCloudwatchLogsSubscriptionParserConfig> for AwsCloudwatchLogsSubscr
             field: config
                 .field
                 .unwrap_or_else(|| log_schema().message_key().to_string()),
+            reroute_dropped: config.reroute_dropped,
         }
     }
 }
 
 impl FunctionTransform for AwsCloudwatchLogsSubscriptionParser {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        let log = event.as_log();
+        // Events are only pushed to the errors if they are rerouted, in which
+        // case the transform is built as a fallible one.
+        FallibleFunctionTransform::transform(self, output, &mut Vec::new(), event)
+    }
+}
 
-        let message = log
-            .get(&self.field)
-            .map(|s| s.as_bytes())
-            .and_then(|to_parse| {
-                serde_json::from_slice::<AwsCloudWatchLogsSubscriptionMessage>(&to_parse)
-                    .map_err(|error| {
-                        emit!(&AwsCloudwatchLogsSubscriptionParserFailedParse { error })
-                    })
-                    .ok()
-            });
+impl FallibleFunctionTransform for AwsCloudwatchLogsSubscriptionParser {
+    fn transform(&mut self, output: &mut Vec<Event>, errors: &mut Vec<Event>, mut event: Event) {
+        let log = event.as_log();
 
-        let events = message
-            .map(|m| subscription_event_to_events(&event, m))
-            .unwrap_or_else(|| Box::new(iter::empty()));
+        let message =
+            log.get(&self.field)
+                .map(|s| s.as_bytes())
+                .ok_or_else(|| format!("Field {:?} does not exist.", self.field))
+                .and_then(|to_parse| {
+                    serde_json::from_slice::<AwsCloudWatchLogsSubscriptionMessage>(&to_parse)
+                        .map_err(|error| {
+                            let message = error.to_string();
+                            emit!(&AwsCloudwatchLogsSubscriptionParserFailedParse { error });
+                            message
+                        })
+                });
 
-        output.extend(events);
+        match message {
+            Ok(message) => output.extend(subscription_event_to_events(&event, message)),
+            Err(error) => {
+                if self.reroute_dropped {
+                    dropped::annotate(&mut event, "parse_failed", error);
+                    errors.push(event);
+                }
+            }
+        }
     }
 }
 
@@ -142,6 +171,7 @@ mod test {
         let mut parser =
             AwsCloudwatchLogsSubscriptionParser::from(AwsCloudwatchLogsSubscriptionParserConfig {
                 field: None,
+                reroute_dropped: false,
             });
 
         let mut event = Event::from(
@@ -175,7 +205,7 @@ mod test {
 
         let mut output: Vec<Event> = Vec::new();
 
-        parser.transform(&mut output, event);
+        FunctionTransform::transform(&mut parser, &mut output, event);
 
         shared::assert_event_data_eq!(
             output,
@@ -211,6 +241,7 @@ mod test {
         let mut parser =
             AwsCloudwatchLogsSubscriptionParser::from(AwsCloudwatchLogsSubscriptionParserConfig {
                 field: None,
+                reroute_dropped: false,
             });
 
         let event = Event::from(
@@ -234,7 +265,7 @@ mod test {
 
         let mut output: Vec<Event> = Vec::new();
 
-        parser.transform(&mut output, event);
+        FunctionTransform::transform(&mut parser, &mut output, event);
 
         assert_eq!(output, vec![]);
     }
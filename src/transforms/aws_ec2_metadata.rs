@@ -208,6 +208,11 @@ impl Ec2MetadataTransform {
                         metric.insert_tag(k.clone(), String::from_utf8_lossy(v).to_string());
                     });
                 }
+                Event::Trace(ref mut trace) => {
+                    state.iter().for_each(|(k, v)| {
+                        trace.insert(k.clone(), v.clone());
+                    });
+                }
             }
         }
 
@@ -1,8 +1,9 @@
 use crate::{
     config::{log_schema, DataType, TransformConfig, TransformContext, TransformDescription},
+    dropped,
     event::Event,
     internal_events::{JsonParserFailedParse, JsonParserTargetExists},
-    transforms::{FunctionTransform, Transform},
+    transforms::{FallibleFunctionTransform, FunctionTransform, Transform},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -17,6 +18,7 @@ pub struct JsonParserConfig {
     pub drop_field: bool,
     pub target_field: Option<String>,
     pub overwrite_target: Option<bool>,
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -29,7 +31,16 @@ impl_generate_config_from_default!(JsonParserConfig);
 #[typetag::serde(name = "json_parser")]
 impl TransformConfig for JsonParserConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(JsonParser::from(self.clone())))
+        let parser = JsonParser::from(self.clone());
+        Ok(if self.reroute_dropped {
+            Transform::fallible_function(parser)
+        } else {
+            Transform::function(parser)
+        })
+    }
+
+    fn named_outputs(&self) -> Vec<String> {
+        dropped::named_outputs(self.reroute_dropped)
     }
 
     fn input_type(&self) -> DataType {
@@ -52,6 +63,7 @@ pub struct JsonParser {
     drop_field: bool,
     target_field: Option<String>,
     overwrite_target: bool,
+    reroute_dropped: bool,
 }
 
 impl From<JsonParserConfig> for JsonParser {
@@ -66,39 +78,49 @@ impl From<JsonParserConfig> for JsonParser {
             drop_field: config.drop_field,
             target_field: config.target_field,
             overwrite_target: config.overwrite_target.unwrap_or(false),
+            reroute_dropped: config.reroute_dropped,
         }
     }
 }
 
 impl FunctionTransform for JsonParser {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        // Events are only pushed to the errors if they are rerouted, in which
+        // case the transform is built as a fallible one.
+        FallibleFunctionTransform::transform(self, output, &mut Vec::new(), event)
+    }
+}
+
+impl FallibleFunctionTransform for JsonParser {
+    fn transform(&mut self, output: &mut Vec<Event>, errors: &mut Vec<Event>, mut event: Event) {
         let log = event.as_mut_log();
         let value = log.get(&self.field);
 
         let parsed = value
+            .ok_or_else(|| format!("Field {:?} does not exist.", self.field))
             .and_then(|value| {
                 let to_parse = value.as_bytes();
-                serde_json::from_slice::<Value>(to_parse.as_ref())
-                    .map_err(|error| {
-                        emit!(&JsonParserFailedParse {
-                            field: &self.field,
-                            value: value.to_string_lossy().as_str(),
-                            error,
-                            drop_invalid: self.drop_invalid,
-                        })
-                    })
-                    .ok()
+                serde_json::from_slice::<Value>(to_parse.as_ref()).map_err(|error| {
+                    let message = error.to_string();
+                    emit!(&JsonParserFailedParse {
+                        field: &self.field,
+                        value: value.to_string_lossy().as_str(),
+                        error,
+                        drop_invalid: self.drop_invalid,
+                    });
+                    message
+                })
             })
             .and_then(|value| {
                 if let Value::Object(object) = value {
-                    Some(object)
+                    Ok(object)
                 } else {
-                    None
+                    Err("Parsed value is not an object.".to_owned())
                 }
             });
 
-        if let Some(object) = parsed {
-            match self.target_field {
+        match parsed {
+            Ok(object) => match self.target_field {
                 Some(ref target_field) => {
                     let contains_target = log.contains(&target_field);
 
@@ -121,9 +143,15 @@ impl FunctionTransform for JsonParser {
                         log.insert_flat(key, value);
                     }
                 }
+            },
+            Err(error) if self.drop_invalid => {
+                if self.reroute_dropped {
+                    dropped::annotate(&mut event, "parse_failed", error);
+                    errors.push(event);
+                }
+                return;
             }
-        } else if self.drop_invalid {
-            return;
+            Err(_) => {}
         }
 
         output.push(event);
@@ -385,6 +413,33 @@ mod test {
         assert!(transform_one(&mut parser, event).is_none());
     }
 
+    #[test]
+    fn json_parser_reroute_dropped() {
+        let mut parser = JsonParser::from(JsonParserConfig {
+            drop_invalid: true,
+            reroute_dropped: true,
+            ..Default::default()
+        });
+
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        for message in &[r#"{"greeting": "hello"}"#, r#"{"greeting": "#] {
+            FallibleFunctionTransform::transform(
+                &mut parser,
+                &mut output,
+                &mut errors,
+                Event::from(*message),
+            );
+        }
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(errors.len(), 1);
+        let log = errors[0].as_log();
+        assert_eq!(log[log_schema().message_key()], r#"{"greeting": "#.into());
+        assert_eq!(log["metadata.dropped.reason"], "parse_failed".into());
+        assert!(log.contains("metadata.dropped.message"));
+    }
+
     #[test]
     fn json_parser_chained() {
         let mut parser1 = JsonParser::from(JsonParserConfig {
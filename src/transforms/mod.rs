@@ -66,7 +66,8 @@ pub mod tag_cardinality_limit;
 pub mod tokenizer;
 
 pub use vector_core::transform::{
-    FallibleFunctionTransform, FunctionTransform, TaskTransform, Transform,
+    FallibleFunctionTransform, FunctionTransform, SyncTransform, TaskTransform, Transform,
+    TransformOutputsBuf,
 };
 
 #[derive(Debug, Snafu)]
@@ -1,11 +1,12 @@
 use crate::{
     config::{DataType, TransformConfig, TransformContext, TransformDescription},
+    dropped,
     event::{Event, Value},
     internal_events::{
         RegexParserConversionFailed, RegexParserFailedMatch, RegexParserMissingField,
         RegexParserTargetExists,
     },
-    transforms::{FunctionTransform, Transform},
+    transforms::{FallibleFunctionTransform, FunctionTransform, Transform},
     types::{parse_check_conversion_map, Conversion},
 };
 use bytes::Bytes;
@@ -35,6 +36,7 @@ pub struct RegexParserConfig {
     pub types: HashMap<String, String>,
     #[serde(default)]
     pub timezone: Option<TimeZone>,
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -50,6 +52,10 @@ impl TransformConfig for RegexParserConfig {
         RegexParser::build(self, context.globals.timezone)
     }
 
+    fn named_outputs(&self) -> Vec<String> {
+        dropped::named_outputs(self.reroute_dropped)
+    }
+
     fn input_type(&self) -> DataType {
         DataType::Log
     }
@@ -72,6 +78,7 @@ pub struct RegexParser {
     drop_failed: bool,
     target_field: Option<String>,
     overwrite_target: bool,
+    reroute_dropped: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -190,7 +197,7 @@ impl RegexParser {
         let types =
             parse_check_conversion_map(&config.types, names, config.timezone.unwrap_or(timezone))?;
 
-        Ok(Transform::function(RegexParser::new(
+        let parser = RegexParser::new(
             regexset,
             patterns,
             field,
@@ -199,7 +206,13 @@ impl RegexParser {
             config.target_field.clone(),
             config.overwrite_target,
             types,
-        )))
+            config.reroute_dropped,
+        );
+        Ok(if config.reroute_dropped {
+            Transform::fallible_function(parser)
+        } else {
+            Transform::function(parser)
+        })
     }
 
     pub fn new(
@@ -211,6 +224,7 @@ impl RegexParser {
         target_field: Option<String>,
         overwrite_target: bool,
         types: HashMap<String, Conversion>,
+        reroute_dropped: bool,
     ) -> Self {
         // Build a buffer of the regex capture locations and names to avoid
         // repeated allocations.
@@ -235,12 +249,37 @@ impl RegexParser {
             drop_failed,
             target_field,
             overwrite_target,
+            reroute_dropped,
+        }
+    }
+
+    /// Hands an event that didn't match on to the output, or drops it.
+    fn failed(
+        &self,
+        output: &mut Vec<Event>,
+        errors: &mut Vec<Event>,
+        mut event: Event,
+        error: &str,
+    ) {
+        if !self.drop_failed {
+            output.push(event);
+        } else if self.reroute_dropped {
+            dropped::annotate(&mut event, "parse_failed", error);
+            errors.push(event);
         }
     }
 }
 
 impl FunctionTransform for RegexParser {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        // Events are only pushed to the errors if they are rerouted, in which
+        // case the transform is built as a fallible one.
+        FallibleFunctionTransform::transform(self, output, &mut Vec::new(), event)
+    }
+}
+
+impl FallibleFunctionTransform for RegexParser {
+    fn transform(&mut self, output: &mut Vec<Event>, errors: &mut Vec<Event>, mut event: Event) {
         let log = event.as_mut_log();
         let value = log.get(&self.field).map(|s| s.as_bytes());
 
@@ -250,9 +289,7 @@ impl FunctionTransform for RegexParser {
                 Some(id) => id,
                 None => {
                     emit!(&RegexParserFailedMatch { value });
-                    if !self.drop_failed {
-                        output.push(event);
-                    };
+                    self.failed(output, errors, event, "Value didn't match any pattern.");
                     return;
                 }
             };
@@ -290,12 +327,11 @@ impl FunctionTransform for RegexParser {
                 output.push(event);
                 return;
             }
+            self.failed(output, errors, event, "Value didn't match the pattern.");
         } else {
             emit!(&RegexParserMissingField { field: &self.field });
-        }
-
-        if !self.drop_failed {
-            output.push(event);
+            let error = format!("Field {:?} does not exist.", self.field);
+            self.failed(output, errors, event, &error);
         }
     }
 }
@@ -491,6 +527,31 @@ mod tests {
         assert!(log.is_none());
     }
 
+    #[tokio::test]
+    async fn reroutes_dropped_event_if_no_match() {
+        let config = toml::from_str::<RegexParserConfig>(
+            r#"
+                patterns = ['something']
+                drop_failed = true
+                reroute_dropped = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.named_outputs(), vec!["dropped".to_owned()]);
+        let mut parser = config.build(&TransformContext::default()).await.unwrap();
+        let parser = parser.as_fallible_function();
+
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        parser.transform(&mut output, &mut errors, Event::from("asdf1234"));
+
+        assert!(output.is_empty());
+        assert_eq!(errors.len(), 1);
+        let log = errors[0].as_log();
+        assert_eq!(log["message"], "asdf1234".into());
+        assert_eq!(log["metadata.dropped.reason"], "parse_failed".into());
+    }
+
     #[tokio::test]
     async fn handles_valid_optional_capture() {
         let log = do_transform("1234", r#"['(?P<status>\d+)?']"#, "")
@@ -1,8 +1,9 @@
 use crate::{
     config::{DataType, TransformConfig, TransformContext, TransformDescription},
+    dropped,
     event::{Event, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
-    transforms::{FunctionTransform, Transform},
+    transforms::{FallibleFunctionTransform, FunctionTransform, Transform},
     Result,
 };
 
@@ -26,6 +27,11 @@ pub struct RemapConfig {
     pub drop_on_error: bool,
     #[serde(default = "crate::serde::default_true")]
     pub drop_on_abort: bool,
+    /// Sends the events dropped through `drop_on_error` and `drop_on_abort`
+    /// to the `dropped` output instead.
+    pub reroute_dropped: bool,
+    /// The directories searched, in order, for the files the program imports.
+    pub import_paths: Vec<PathBuf>,
 }
 
 inventory::submit! {
@@ -38,7 +44,16 @@ impl_generate_config_from_default!(RemapConfig);
 #[typetag::serde(name = "remap")]
 impl TransformConfig for RemapConfig {
     async fn build(&self, context: &TransformContext) -> Result<Transform> {
-        Remap::new(self.clone(), &context.enrichment_tables).map(Transform::function)
+        let remap = Remap::new(self.clone(), &context.enrichment_tables)?;
+        Ok(if self.reroute_dropped {
+            Transform::fallible_function(remap)
+        } else {
+            Transform::function(remap)
+        })
+    }
+
+    fn named_outputs(&self) -> Vec<String> {
+        dropped::named_outputs(self.reroute_dropped)
     }
 
     fn input_type(&self) -> DataType {
@@ -61,6 +76,7 @@ pub struct Remap {
     timezone: TimeZone,
     drop_on_error: bool,
     drop_on_abort: bool,
+    reroute_dropped: bool,
 }
 
 impl Remap {
@@ -86,12 +102,12 @@ impl Remap {
         let mut functions = vrl_stdlib::all();
         functions.append(&mut enrichment::vrl_functions());
 
-        let program = vrl::compile(
-            &source,
-            &functions,
-            Some(Box::new(enrichment_tables.clone())),
-        )
-        .map_err(|diagnostics| Formatter::new(&source, diagnostics).colored().to_string())?;
+        let mut state = vrl::state::Compiler::new();
+        state.set_external_context(Some(Box::new(enrichment_tables.clone())));
+        state.set_import_paths(config.import_paths);
+
+        let program = vrl::compile_with_state(&source, &functions, &mut state)
+            .map_err(|diagnostics| Formatter::new(&source, diagnostics).colored().to_string())?;
 
         Ok(Remap {
             program,
@@ -99,6 +115,7 @@ impl Remap {
             timezone: config.timezone,
             drop_on_error: config.drop_on_error,
             drop_on_abort: config.drop_on_abort,
+            reroute_dropped: config.reroute_dropped,
         })
     }
 
@@ -116,12 +133,13 @@ impl Clone for Remap {
             timezone: self.timezone,
             drop_on_error: self.drop_on_error,
             drop_on_abort: self.drop_on_abort,
+            reroute_dropped: self.reroute_dropped,
         }
     }
 }
 
-impl FunctionTransform for Remap {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+impl FallibleFunctionTransform for Remap {
+    fn transform(&mut self, output: &mut Vec<Event>, errors: &mut Vec<Event>, event: Event) {
         // If a program can fail or abort at runtime, we need to clone the
         // original event and keep it around, to allow us to discard any
         // mutations made to the event while the VRL program runs, before it
@@ -129,15 +147,17 @@ impl FunctionTransform for Remap {
         //
         // The `drop_on_{error, abort}` transform config allows operators to
         // ignore events if their failed/aborted, in which case we can skip the
-        // cloning, since any mutations made by VRL will be ignored regardless.
+        // cloning, since any mutations made by VRL will be ignored regardless,
+        // unless the dropped events are rerouted.
         #[allow(clippy::if_same_then_else)]
-        let original_event = if !self.drop_on_error && self.program.can_fail() {
-            Some(event.clone())
-        } else if !self.drop_on_abort && self.program.can_abort() {
-            Some(event.clone())
-        } else {
-            None
-        };
+        let original_event =
+            if (!self.drop_on_error || self.reroute_dropped) && self.program.can_fail() {
+                Some(event.clone())
+            } else if (!self.drop_on_abort || self.reroute_dropped) && self.program.can_abort() {
+                Some(event.clone())
+            } else {
+                None
+            };
 
         let mut target: VrlTarget = event.into();
 
@@ -152,13 +172,17 @@ impl FunctionTransform for Remap {
                     output.push(event)
                 }
             }
-            Err(Terminate::Abort(_)) => {
+            Err(Terminate::Abort(error)) => {
                 emit!(&RemapMappingAbort {
                     event_dropped: self.drop_on_abort,
                 });
 
                 if !self.drop_on_abort {
                     output.push(original_event.expect("event will be set"))
+                } else if self.reroute_dropped {
+                    let mut event = original_event.expect("event will be set");
+                    dropped::annotate(&mut event, "abort", error);
+                    errors.push(event)
                 }
             }
             Err(Terminate::Error(error)) => {
@@ -169,12 +193,24 @@ impl FunctionTransform for Remap {
 
                 if !self.drop_on_error {
                     output.push(original_event.expect("event will be set"))
+                } else if self.reroute_dropped {
+                    let mut event = original_event.expect("event will be set");
+                    dropped::annotate(&mut event, "error", error);
+                    errors.push(event)
                 }
             }
         }
     }
 }
 
+impl FunctionTransform for Remap {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        // Events are only pushed to the errors if they are rerouted, in which
+        // case the transform is built as a fallible one.
+        FallibleFunctionTransform::transform(self, output, &mut Vec::new(), event)
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum BuildError {
     #[snafu(display("must provide exactly one of `source` or `file` configuration"))]
@@ -251,6 +287,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
         assert!(tform.runtime().is_empty());
@@ -300,6 +338,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
@@ -312,6 +352,43 @@ mod tests {
         assert_eq!(result.metadata(), &metadata);
     }
 
+    #[test]
+    fn check_remap_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("normalize.vrl"),
+            indoc! {r#"
+                fn normalize_level(level) {
+                    downcase(string(level) ?? "info")
+                }
+            "#},
+        )
+        .unwrap();
+
+        let event = {
+            let mut event = LogEvent::from("normalize me");
+            event.insert("level", "WARN");
+            Event::from(event)
+        };
+
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                    import "normalize.vrl"
+
+                    .level = normalize_level(.level)
+                "#}
+                .to_string(),
+            ),
+            import_paths: vec![dir.path().to_owned()],
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf, &Default::default()).unwrap();
+
+        let result = transform_one(&mut tform, event).unwrap();
+        assert_eq!(get_field_string(&result, "level"), "warn");
+    }
+
     #[test]
     fn check_remap_emits_multiple() {
         let event = {
@@ -335,11 +412,13 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
         let mut result = vec![];
-        tform.transform(&mut result, event);
+        FunctionTransform::transform(&mut tform, &mut result, event);
 
         assert_eq!(get_field_string(&result[0], "message"), "foo");
         assert_eq!(get_field_string(&result[1], "message"), "bar");
@@ -365,6 +444,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
@@ -393,12 +474,83 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
         assert!(transform_one(&mut tform, event).is_none())
     }
 
+    #[test]
+    fn check_remap_error_reroute() {
+        let event = {
+            let mut event = Event::from("augment me");
+            event.as_mut_log().insert("bar", "is a string");
+            event
+        };
+
+        let conf = RemapConfig {
+            source: Some(formatdoc! {r#"
+                .foo = "foo"
+                .not_an_int = int!(.bar)
+            "#}),
+            drop_on_error: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        assert_eq!(conf.named_outputs(), vec!["dropped".to_owned()]);
+        let mut tform = Remap::new(conf, &Default::default()).unwrap();
+
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        FallibleFunctionTransform::transform(&mut tform, &mut output, &mut errors, event);
+
+        assert!(output.is_empty());
+        assert_eq!(errors.len(), 1);
+        let log = errors[0].as_log();
+        assert_eq!(log.get("bar"), Some(&Value::from("is a string")));
+        assert!(log.get("foo").is_none());
+        assert_eq!(
+            log.get("metadata.dropped.reason"),
+            Some(&Value::from("error"))
+        );
+        assert!(log
+            .get("metadata.dropped.message")
+            .unwrap()
+            .to_string_lossy()
+            .contains("function call error"));
+    }
+
+    #[test]
+    fn check_remap_abort_reroute() {
+        let conf = RemapConfig {
+            source: Some(".foo = \"foo\"\nabort".to_owned()),
+            drop_on_abort: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf, &Default::default()).unwrap();
+
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        FallibleFunctionTransform::transform(
+            &mut tform,
+            &mut output,
+            &mut errors,
+            Event::from("augment me"),
+        );
+
+        assert!(output.is_empty());
+        assert_eq!(errors.len(), 1);
+        let log = errors[0].as_log();
+        assert!(log.get("foo").is_none());
+        assert_eq!(
+            log.get("metadata.dropped.reason"),
+            Some(&Value::from("abort"))
+        );
+    }
+
     #[test]
     fn check_remap_error_infallible() {
         let event = {
@@ -416,6 +568,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
@@ -444,6 +598,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
@@ -472,6 +628,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: true,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
@@ -499,6 +657,8 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
+            import_paths: Vec::new(),
         };
         let mut tform = Remap::new(conf, &Default::default()).unwrap();
 
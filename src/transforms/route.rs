@@ -1,65 +1,46 @@
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{
-        DataType, ExpandType, GenerateConfig, TransformConfig, TransformContext,
-        TransformDescription,
-    },
+    config::{DataType, GenerateConfig, TransformConfig, TransformContext, TransformDescription},
     event::Event,
-    internal_events::RouteEventDiscarded,
-    transforms::{FunctionTransform, Transform},
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-//------------------------------------------------------------------------------
+/// The output receiving the events that didn't match any route.
+pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LaneConfig {
-    condition: AnyCondition,
-}
-
-#[async_trait::async_trait]
-#[typetag::serde(name = "lane")]
-impl TransformConfig for LaneConfig {
-    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Lane::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
-    }
-
-    fn input_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn output_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn transform_type(&self) -> &'static str {
-        "lane"
-    }
-}
+//------------------------------------------------------------------------------
 
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct Lane {
+pub struct Route {
     #[derivative(Debug = "ignore")]
-    condition: Box<dyn Condition>,
+    conditions: Vec<(String, Box<dyn Condition>)>,
 }
 
-impl Lane {
-    pub fn new(condition: Box<dyn Condition>) -> Self {
-        Self { condition }
+impl Route {
+    pub fn new(config: &RouteConfig, context: &TransformContext) -> crate::Result<Self> {
+        let mut conditions = Vec::with_capacity(config.route.len());
+        for (output_name, condition) in config.route.iter() {
+            let condition = condition.build(&context.enrichment_tables)?;
+            conditions.push((output_name.clone(), condition));
+        }
+        Ok(Self { conditions })
     }
 }
 
-impl FunctionTransform for Lane {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        if self.condition.check(&event) {
-            output.push(event);
-        } else {
-            emit!(&RouteEventDiscarded);
+impl SyncTransform for Route {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut matched = false;
+        for (output_name, condition) in &self.conditions {
+            if condition.check(&event) {
+                output.push_named(output_name, event.clone());
+                matched = true;
+            }
+        }
+        if !matched {
+            output.push_named(UNMATCHED_ROUTE, event);
         }
     }
 }
@@ -94,29 +75,26 @@ impl GenerateConfig for RouteConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "route")]
 impl TransformConfig for RouteConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Err("this transform must be expanded".into())
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if self.route.is_empty() {
+            return Err("must specify at least one route".into());
+        }
+        if self.route.contains_key(UNMATCHED_ROUTE) {
+            return Err(format!("the route id `{}` is reserved", UNMATCHED_ROUTE).into());
+        }
+        Ok(Transform::synchronous(Route::new(self, context)?))
     }
 
-    fn expand(
-        &mut self,
-    ) -> crate::Result<Option<(IndexMap<String, Box<dyn TransformConfig>>, ExpandType)>> {
-        let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
-
-        while let Some((k, v)) = self.route.pop() {
-            if map
-                .insert(k.clone(), Box::new(LaneConfig { condition: v }))
-                .is_some()
-            {
-                return Err("duplicate route id".into());
-            }
-        }
+    fn named_outputs(&self) -> Vec<String> {
+        self.route
+            .keys()
+            .cloned()
+            .chain(std::iter::once(UNMATCHED_ROUTE.to_owned()))
+            .collect()
+    }
 
-        if !map.is_empty() {
-            Ok(Some((map, ExpandType::Parallel)))
-        } else {
-            Err("must specify at least one lane".into())
-        }
+    fn has_primary_output(&self) -> bool {
+        false
     }
 
     fn input_type(&self) -> DataType {
@@ -143,10 +121,12 @@ impl TransformConfig for RouteCompatConfig {
         self.0.build(context).await
     }
 
-    fn expand(
-        &mut self,
-    ) -> crate::Result<Option<(IndexMap<String, Box<dyn TransformConfig>>, ExpandType)>> {
-        self.0.expand()
+    fn named_outputs(&self) -> Vec<String> {
+        self.0.named_outputs()
+    }
+
+    fn has_primary_output(&self) -> bool {
+        self.0.has_primary_output()
     }
 
     fn input_type(&self) -> DataType {
@@ -185,37 +165,89 @@ mod test {
     }
 
     #[test]
-    fn can_serialize_remap() {
+    fn can_serialize_check_fields() {
         // We need to serialize the config to check if a config has
         // changed when reloading.
-        let config = LaneConfig {
-            condition: AnyCondition::String("foo".to_string()),
-        };
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            lanes.first.type = "check_fields"
+            lanes.first."message.eq" = "foo"
+        "#,
+        )
+        .unwrap();
 
         assert_eq!(
             serde_json::to_string(&config).unwrap(),
-            r#"{"condition":"foo"}"#
+            r#"{"route":{"first":{"type":"check_fields","message.eq":"foo"}}}"#
         );
     }
 
     #[test]
-    fn can_serialize_check_fields() {
-        // We need to serialize the config to check if a config has
-        // changed when reloading.
+    fn named_outputs_include_unmatched() {
         let config = toml::from_str::<RouteConfig>(
             r#"
-            lanes.first.type = "check_fields"
-            lanes.first."message.eq" = "foo"
+            route.first.type = "check_fields"
+            route.first."message.eq" = "foo"
+            route.second.type = "check_fields"
+            route.second."message.eq" = "bar"
         "#,
         )
-        .unwrap()
-        .expand()
-        .unwrap()
         .unwrap();
 
         assert_eq!(
-            serde_json::to_string(&config).unwrap(),
-            r#"[{"first":{"type":"lane","condition":{"type":"check_fields","message.eq":"foo"}}},"Parallel"]"#
+            config.named_outputs(),
+            vec![
+                "first".to_owned(),
+                "second".to_owned(),
+                "_unmatched".to_owned()
+            ]
         );
     }
+
+    #[tokio::test]
+    async fn routes_to_all_matching_outputs() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.foo.type = "check_fields"
+            route.foo."message.contains" = "foo"
+            route.bar.type = "check_fields"
+            route.bar."message.contains" = "bar"
+        "#,
+        )
+        .unwrap();
+        let mut transform = config.build(&Default::default()).await.unwrap();
+        let transform = transform.as_synchronous();
+
+        for message in &["foo", "foobar", "baz"] {
+            let mut outputs = TransformOutputsBuf::new_with_capacity(config.named_outputs(), 1);
+            transform.transform(Event::from(*message), &mut outputs);
+
+            let mut routed = config
+                .named_outputs()
+                .into_iter()
+                .filter(|name| !outputs.take_named(name).is_empty())
+                .collect::<Vec<_>>();
+            routed.sort();
+            let expected: &[&str] = match *message {
+                "foo" => &["foo"],
+                "foobar" => &["bar", "foo"],
+                _ => &["_unmatched"],
+            };
+            assert_eq!(routed, expected);
+            assert!(outputs.take_primary().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn unmatched_route_id_is_reserved() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route._unmatched.type = "check_fields"
+            route._unmatched."message.eq" = "foo"
+        "#,
+        )
+        .unwrap();
+
+        assert!(config.build(&Default::default()).await.is_err());
+    }
 }
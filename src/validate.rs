@@ -384,6 +384,8 @@ impl Formatter {
     }
 
     fn print(&mut self, print: impl AsRef<str>) {
+        // Errors and warnings can quote parts of the config, secrets included.
+        let print = config::secret::redact(print.as_ref());
         let width = print
             .as_ref()
             .lines()
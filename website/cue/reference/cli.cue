@@ -298,7 +298,15 @@ cli: {
 				Observe log events from topology components.
 				"""
 
-			flags: _default_flags
+			flags: _default_flags & {
+				"invocations": {
+					description: """
+						Observe the invocations of the transforms matching the component ID
+						patterns instead, printing each received event followed by the events
+						emitted in return
+						"""
+				}
+			}
 
 			options: {
 				"interval": {
@@ -328,6 +336,25 @@ cli: {
 						yaml: "Output events as YAML"
 					}
 				}
+				"filter": {
+					description: """
+						VRL condition evaluated by Vector against events, only those for which
+						it's true are sent
+						"""
+					type: "string"
+				}
+				"sample": {
+					description: "Send only one in every N events passing the filter, sampled by Vector"
+					type:        "integer"
+					default:     1
+				}
+				"inputs-of": {
+					description: """
+						Components IDs whose received events are also observed (comma-separated;
+						accepts glob patterns)
+						"""
+					type: "string"
+				}
 			}
 
 			args: {
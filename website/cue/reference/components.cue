@@ -194,6 +194,10 @@ components: {
 				enabled: bool | string
 			}
 
+			// `codecs` notes if a component supports the `framing` and
+			// `serializer` options.
+			codecs?: #FeaturesCodecs
+
 			// `healtcheck` notes if a component offers a healthcheck on boot.
 			healthcheck: {
 				enabled: bool
@@ -79,12 +79,22 @@ components: sinks: [Name=string]: {
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"disk_v2\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
 							}
 						}
+						overflow: {
+							common:        false
+							description:   "The buffer that events are written to while this one is full, such as a disk buffer taking over from a memory buffer during sink outages. Takes the same options as `buffer` itself. A disk buffer can't overflow into a disk buffer of the same type."
+							required:      true
+							relevant_when: "when_full = \"overflow\""
+							type: object: {
+								examples: [{type: "disk_v2", max_size: 104900000}]
+								options: {}
+							}
+						}
 						type: {
 							common:      true
 							description: "The buffer's type and storage mechanism."
@@ -100,6 +110,11 @@ components: sinks: [Name=string]: {
 									WARNING: This may stall the sink if disk performance isn't on par with the throughput.
 									For comparison, AWS gp2 volumes are usually too slow for common cases.
 									"""
+									disk_v2: """
+									Stores the sink's buffer on disk in a series of segment files, without relying on LevelDB.
+									Records are checksummed, so that records torn by a crash are dropped on startup, and
+									acknowledged events are freed up right away.
+									"""
 								}
 								syntax: "literal"
 							}
@@ -113,6 +128,7 @@ components: sinks: [Name=string]: {
 								enum: {
 									block:       "Applies back pressure when the buffer is full. This prevents data loss, but will cause data to pile up on the edge."
 									drop_newest: "Drops new data as it's received. This data is lost. This should be used when performance is the highest priority."
+									overflow:    "Writes new data to the `overflow` buffer until this one has room again. Events are not guaranteed to be delivered in order while both buffers hold data."
 								}
 								syntax: "literal"
 							}
@@ -143,6 +159,15 @@ components: sinks: [Name=string]: {
 							if list.Contains(features.send.compression.algorithms, "gzip") {
 								gzip: "[Gzip](\(urls.gzip)) standard DEFLATE compression."
 							}
+							if list.Contains(features.send.compression.algorithms, "zstd") {
+								zstd: "[Zstandard](\(urls.zstd)) compression."
+							}
+							if list.Contains(features.send.compression.algorithms, "snappy") {
+								snappy: "[Snappy](\(urls.snappy)) compression, in its framing format."
+							}
+							if list.Contains(features.send.compression.algorithms, "lz4") {
+								lz4: "[LZ4](\(urls.lz4)) compression, in its frame format."
+							}
 						}
 						syntax: "literal"
 					}
@@ -159,7 +184,12 @@ components: sinks: [Name=string]: {
 					type: object: options: {
 						if features.send.encoding.codec.enabled {
 							codec: {
-								description: "The encoding codec used to serialize the events before outputting."
+								if features.codecs == _|_ {
+									description: "The encoding codec used to serialize the events before outputting."
+								}
+								if features.codecs != _|_ {
+									description: "The encoding codec used to serialize the events before outputting. Replaced by `serializer` when it is configured."
+								}
 								required:    true
 								type: string: {
 									examples: features.send.encoding.codec.enum
@@ -244,6 +274,95 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		if features.codecs != _|_ {
+			if features.codecs.enabled {
+				framing: {
+					common:      false
+					description: "Configures in which way the serialized events are wrapped into byte frames. Requires `serializer` to be configured."
+					required:    false
+					type: object: options: {
+						method: {
+							description: "The framing method."
+							required:    false
+							common:      true
+							type: string: {
+								default: features.codecs.default_framing
+								enum: {
+									bytes:               "Byte frames are sent as-is, without any delimiter."
+									character_delimited: "Byte frames which are delimited by a chosen character."
+									length_delimited:    "Byte frames whose length is encoded in a header."
+									newline_delimited:   "Byte frames which are delimited by a newline character."
+									octet_counting:      "Byte frames according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
+								}
+								syntax: "literal"
+							}
+						}
+						character_delimited: {
+							description:   "Options for `character_delimited` framing."
+							required:      true
+							relevant_when: "method = `character_delimited`"
+							type: object: options: {
+								delimiter: {
+									description: "The character used to separate frames."
+									required:    true
+									type: string: {
+										examples: ["\n", "\t"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+				serializer: {
+					common:      false
+					description: "Configures how events are serialized into bytes. Replaces `encoding.codec`, while the other `encoding` options still apply, in which case `encoding` is optional."
+					required:    false
+					type: object: options: {
+						codec: {
+							description: "The serializer to use."
+							required:    true
+							type: string: {
+								enum: {
+									csv:         "Serializes the fields of a log event as a [CSV](\(urls.rfc_4180)) row."
+									json:        "Serializes an event as [JSON](\(urls.json))."
+									logfmt:      "Serializes the fields of a log event as [logfmt](\(urls.logfmt))."
+									native:      "Serializes an event in Vector's native protobuf representation, as used between Vector instances."
+									raw_message: "Serializes the message field of a log event as-is."
+								}
+								syntax: "literal"
+							}
+						}
+						csv: {
+							description:   "Options for the `csv` serializer."
+							required:      true
+							relevant_when: "codec = `csv`"
+							type: object: options: {
+								fields: {
+									description: "The fields to write, in the order of the columns."
+									required:    true
+									type: array: items: type: string: {
+										examples: ["timestamp", "host", "message"]
+										syntax: "field_path"
+									}
+								}
+								delimiter: {
+									description: "The character that separates the columns."
+									required:    false
+									common:      false
+									type: string: {
+										default: ","
+										examples: [";", "\t"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		if features.healthcheck != _|_ {
 			if features.healthcheck.enabled {
 				healthcheck: {
@@ -265,6 +384,22 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send the events that fail to be encoded through the `serializer` to the
+				`<component_id>.dropped` output, annotated with `metadata.dropped` fields describing the
+				failure, instead of dropping them.
+
+				Only the `aws_s3`, `console`, `file`, `http`, `kafka` and `socket` sinks support this
+				option, when their `serializer` is set. Enabling it for other sinks, or without a
+				`serializer`, is rejected when loading the configuration. Events are discarded, and
+				counted in the `events_discarded_total` metric, while the `dropped` output is full.
+				"""
+			type: bool: default: false
+		}
+
 		if features.send != _|_ {
 			if features.send.proxy != _|_ {
 				if features.send.proxy.enabled {
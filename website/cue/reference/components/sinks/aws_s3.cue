@@ -15,6 +15,10 @@ components: sinks: aws_s3: components._aws & {
 	features: {
 		buffer: enabled:      true
 		healthcheck: enabled: true
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		send: {
 			batch: {
 				enabled:      true
@@ -25,7 +29,7 @@ components: sinks: aws_s3: components._aws & {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -15,6 +15,10 @@ components: sinks: console: {
 	features: {
 		buffer: enabled:      false
 		healthcheck: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		send: {
 			compression: enabled: false
 			encoding: {
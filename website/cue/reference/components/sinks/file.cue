@@ -16,11 +16,15 @@ components: sinks: file: {
 	features: {
 		buffer: enabled:      false
 		healthcheck: enabled: true
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		send: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -88,6 +92,16 @@ components: sinks: file: {
 				to create and write to files in the specified directories.
 				"""
 		}
+		compression_formats: {
+			title: "Compression Formats"
+			body: """
+				Files are compressed as a stream, hence `snappy` files are
+				written in the Snappy [framing format](\(urls.snappy_framing_format))
+				and `lz4` files in the LZ4 frame format, like the objects
+				and request bodies of the other sinks supporting these
+				algorithms. The raw Snappy format can't be streamed.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -0,0 +1,184 @@
+package metadata
+
+components: sinks: fluent: {
+	title: "Fluent"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1048576
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: enabled:           false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.fluent
+
+				interface: {
+					socket: {
+						api: {
+							title: "Fluent"
+							url:   urls.fluent
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the Fluentd or Fluent Bit server to connect to."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1:24224"]
+				syntax: "literal"
+			}
+		}
+		tag: {
+			description: "The tag of the events. Events are batched by tag."
+			required:    true
+			type: string: {
+				examples: ["vector", "vector.{{ host }}"]
+				syntax: "template"
+			}
+		}
+		compression: {
+			description: "The compression of the entries of each message."
+			common:      false
+			required:    false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "The entries aren't compressed."
+					gzip: "The entries are compressed with gzip, as in `CompressedPackedForward` messages."
+				}
+				syntax: "literal"
+			}
+		}
+		require_ack_response: {
+			description: "Whether to wait for the server to acknowledge each message, sent with a `chunk` option, before marking its events as delivered. Unacknowledged messages are sent again on a new connection. Messages are sent one at a time, each only once the previous one was acknowledged, so larger batches are needed to keep up with high volumes."
+			common:      true
+			required:    false
+			type: bool: default: false
+		}
+		ack_response_timeout_secs: {
+			description:   "The time to wait for the acknowledgement of a message before sending it again."
+			common:        false
+			required:      false
+			relevant_when: "require_ack_response = true"
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		auth: {
+			description: "Shared key authentication, as configured in the `security` section of Fluentd's `in_forward` plugin."
+			common:      false
+			required:    false
+			type: object: options: {
+				shared_key: {
+					description: "The key shared with the server."
+					required:    true
+					type: string: {
+						examples: ["${FLUENT_SHARED_KEY}"]
+						syntax: "literal"
+					}
+				}
+				self_hostname: {
+					description: "The hostname sent to the server. Defaults to the hostname of the machine."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector.example.com"]
+						syntax: "literal"
+					}
+				}
+				username: {
+					description: "The username, when the server requires user authentication."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector"]
+						syntax: "literal"
+					}
+				}
+				password: {
+					description: "The password, when the server requires user authentication."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["${FLUENT_PASSWORD}"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		packed_forward: {
+			title: "PackedForward messages"
+			body: """
+				Events are batched by their rendered `tag` and sent as `PackedForward` messages of the
+				[Forward protocol](\(urls.fluent)). The timestamp of each event is sent as the time of its
+				entry, with nanosecond precision, and its other fields as the record. Timestamps before 1970
+				or after 2106, out of the range of the `EventTime` extension, are sent as integer seconds.
+				"""
+		}
+	}
+}
@@ -15,6 +15,10 @@ components: sinks: kafka: {
 	features: {
 		buffer: enabled:      true
 		healthcheck: enabled: true
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
 		send: {
 			batch: {
 				enabled:      true
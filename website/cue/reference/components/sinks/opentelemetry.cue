@@ -0,0 +1,142 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs and metrics to OpenTelemetry collectors and other receivers of the
+		OpenTelemetry protocol (OTLP), using either gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry protocol"
+							url:   urls.opentelemetry_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          false
+		}
+	}
+
+	configuration: {
+		endpoint: {
+			description: """
+				The endpoint of the OTLP receiver. With the `http` protocol, logs and metrics are sent
+				to the `/v1/logs` and `/v1/metrics` paths below this endpoint. When the endpoint has no
+				scheme, it defaults to `https` if TLS is enabled and to `http` otherwise.
+				"""
+			required: true
+			warnings: []
+			type: string: {
+				examples: ["http://127.0.0.1:4317", "https://otel-collector.example.com:4318"]
+				syntax: "literal"
+			}
+		}
+		protocol: {
+			description: "The transport of the OTLP requests."
+			common:      true
+			required:    false
+			warnings: []
+			type: string: {
+				default: "grpc"
+				enum: {
+					grpc: "Export with gRPC, to the `LogsService` and `MetricsService` services."
+					http: "Export protobuf encoded requests with HTTP POST requests."
+				}
+				syntax: "literal"
+			}
+		}
+	}
+
+	how_it_works: {
+		signals: {
+			title: "Logs and metrics"
+			body: """
+				Events are batched per signal, so that logs and metrics are exported, and retried, with
+				separate requests. Log fields are mapped back to OTLP log records following the
+				output of the `opentelemetry` source: the `message` field is sent as the body, and the
+				`attributes`, `resources` and `scope` fields as the attributes, resource and
+				instrumentation scope of the record. Other fields are sent as attributes.
+				"""
+		}
+		unsupported_metrics: {
+			title: "Unsupported metrics"
+			body: """
+				Distribution and set metrics have no OTLP representation, so they are dropped, as are
+				trace events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
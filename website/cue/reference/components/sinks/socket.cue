@@ -15,6 +15,10 @@ components: sinks: socket: {
 	features: {
 		buffer: enabled:      true
 		healthcheck: enabled: true
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		send: {
 			compression: enabled: false
 			encoding: {
@@ -83,6 +83,43 @@ components: sinks: splunk_hec_logs: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Options of the indexer acknowledgements, with which Splunk confirms that events were indexed."
+			required:    false
+			warnings: []
+			type: object: {
+				options: {
+					indexer_acknowledgements_enabled: {
+						common:      false
+						description: "Whether to wait for the indexer to acknowledge events before marking them as delivered. Only responses carrying an `ackId`, sent when the token has indexer acknowledgements enabled, are waited for."
+						required:    false
+						warnings: []
+						type: bool: default: true
+					}
+					query_interval_secs: {
+						common:      false
+						description: "The interval between queries of the pending acknowledgements."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 10
+							unit:    "seconds"
+						}
+					}
+					retry_limit: {
+						common:      false
+						description: "The number of queries after which events not acknowledged yet are considered errored."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 30
+							unit:    null
+						}
+					}
+				}
+			}
+		}
 		endpoint: {
 			description: "The base URL of the Splunk instance."
 			required:    true
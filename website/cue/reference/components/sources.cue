@@ -169,13 +169,141 @@ components: sources: [Name=string]: {
 							type: string: {
 								default: "bytes"
 								enum: {
-									bytes:  "Events containing the byte frame as-is."
-									json:   "Events being parsed from a JSON string."
-									syslog: "Events being parsed from a Syslog message."
+									avro:     "Events being parsed from an Avro datum, optionally in the Confluent wire format."
+									bytes:    "Events containing the byte frame as-is."
+									csv:      "Events being parsed from CSV records, with the columns stored in the configured `csv.headers` fields."
+									gelf:     "Events being parsed from a GELF message, possibly compressed or split in chunks, in which case the `bytes` framing is required."
+									json:     "Events being parsed from a JSON string."
+									logfmt:   "Events being parsed from a logfmt line."
+									msgpack:  "Events being parsed from a MessagePack map."
+									native:   "Events being parsed from Vector's native protobuf representation, which may be metrics as well as logs."
+									protobuf: "Events being parsed from a Protobuf message, optionally in the Confluent wire format."
+									syslog:   "Events being parsed from a Syslog message."
 								}
 								syntax: "literal"
 							}
 						}
+						gelf: {
+							description:   "Options for the `gelf` codec. Compressed and chunked GELF messages are binary, and require `framing.method = \"bytes\"` so that each message or datagram is parsed as a whole, since a character delimited framing would split them. Uncompressed GELF messages sent over TCP are delimited by null bytes instead, which `framing.method = \"character_delimited\"` with `framing.character_delimited.delimiter = \"\\u0000\"` handles."
+							required:      false
+							common:        false
+							relevant_when: "codec = `gelf`"
+							type: object: options: {
+								max_decompressed_bytes: {
+									description: "The maximum size of a message once decompressed. Larger messages are rejected."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 8388608
+										unit:    "bytes"
+									}
+								}
+								max_pending_messages: {
+									description: "The maximum number of chunked messages buffered until all of their chunks were received. The oldest ones are dropped over it."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 1000
+										unit:    null
+									}
+								}
+							}
+						}
+						csv: {
+							description:   "Options for the `csv` codec."
+							required:      true
+							relevant_when: "codec = `csv`"
+							type: object: options: {
+								headers: {
+									description: "The fields the columns are stored in, in order."
+									required:    true
+									type: array: items: type: string: {
+										examples: ["timestamp", "level", "message"]
+										syntax: "literal"
+									}
+								}
+								delimiter: {
+									description: "The character that separates the columns."
+									required:    false
+									common:      false
+									type: string: {
+										default: ","
+										examples: ["\t"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+						schema_registry: {
+							description:   "The Confluent schema registry to fetch schemas from. Messages are then expected in the Confluent wire format, prefixed with the id of their schema."
+							required:      false
+							common:        false
+							relevant_when: "codec = `avro` or `protobuf`"
+							type: object: options: {
+								url: {
+									description: "The URL of the schema registry."
+									required:    true
+									type: string: {
+										examples: ["http://localhost:8081"]
+										syntax: "literal"
+									}
+								}
+								auth: {
+									description: "The authentication used with the schema registry, as the `auth` option of the `http` sink."
+									required:    false
+									common:      false
+									type: object: {}
+								}
+								tls: {
+									description: "The TLS options used to connect to the schema registry."
+									required:    false
+									common:      false
+									type: object: {}
+								}
+								timeout_secs: {
+									description: "The time after which requests to the schema registry fail. Schemas which couldn't be fetched aren't fetched again for a delay, doubled on each consecutive failure up to 5 minutes, and messages using them fail to parse meanwhile."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 10
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+						schema_path: {
+							description:   "The path of a file holding the JSON schema of the Avro datums. Required unless `schema_registry` is set."
+							required:      false
+							common:        false
+							relevant_when: "codec = `avro`"
+							type: string: {
+								default: null
+								examples: ["/etc/vector/event.avsc"]
+								syntax: "literal"
+							}
+						}
+						descriptor_set_path: {
+							description:   "The path of a file holding a `FileDescriptorSet` describing the Protobuf messages, as written by `protoc --descriptor_set_out`. Required unless `schema_registry` is set."
+							required:      false
+							common:        false
+							relevant_when: "codec = `protobuf`"
+							type: string: {
+								default: null
+								examples: ["/etc/vector/events.desc"]
+								syntax: "literal"
+							}
+						}
+						message_type: {
+							description:   "The fully qualified name of the type of the Protobuf messages. Required along with `descriptor_set_path`."
+							required:      false
+							common:        false
+							relevant_when: "codec = `protobuf`"
+							type: string: {
+								default: null
+								examples: ["my.package.Event"]
+								syntax: "literal"
+							}
+						}
 					}
 				}
 			}
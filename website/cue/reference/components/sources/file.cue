@@ -305,19 +305,28 @@ components: sources: file: {
 			title: "Compressed Files"
 			body: """
 				Vector will transparently detect files which have been compressed
-				using Gzip and decompress them for reading. This detection process
-				looks for the unique sequence of bytes in the Gzip header and does
-				not rely on the compressed files adhering to any kind of naming
-				convention.
+				using Gzip or Zstandard and decompress them for reading. This
+				detection process looks for the unique sequence of bytes in the
+				Gzip and Zstandard headers and does not rely on the compressed
+				files adhering to any kind of naming convention.
+
+				Compressed files are fingerprinted and checkpointed on their
+				decompressed content. A file compressed by log rotation, such as
+				`app.log.1` becoming `app.log.1.gz`, is therefore recognized as the
+				file it was before, and Vector continues reading it where it left
+				off.
 
 				One caveat with reading compressed files is that Vector is not able
-				to efficiently seek into them. Rather than implement a
-				potentially-expensive full scan as a seek mechanism, Vector
-				currently will not attempt to make further reads from a file for
-				which it has already stored a checkpoint in a previous run. For
-				this reason, users should take care to allow Vector to fully
-				process any compressed files before shutting the process down or moving the
-				files to another location on disk.
+				to efficiently seek into them. When resuming a compressed file from
+				its checkpoint, Vector decompresses and skips over the content read
+				before. Compressed files read from their end, with
+				`read_from = "end"` or `ignore_checkpoints`, are considered complete
+				and are not decompressed at all, so their content is not read on
+				later runs either.
+
+				Checkpoints of compressed files written by earlier versions of
+				Vector, which fingerprinted them on their compressed bytes, are
+				migrated to the decompressed fingerprint on startup.
 				"""
 		}
 
@@ -60,6 +60,7 @@ components: sources: fluent: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		address: {
 			description: "The address to listen for TCP connections on."
 			required:    true
@@ -198,10 +199,10 @@ components: sources: fluent: {
 		acking: {
 			title: "Acknowledgement support"
 			body:  """
-				The `fluent` source currently does not support the acknowledgement parts of the Fluent protocol and so
-				the `require_ack_response` option forward output plugins for Fluent and Fluent Bit cannot be used.
-
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7533).
+				Messages sent with a `chunk` option, as done by the `require_ack_response` option of the forward output
+				plugins for Fluent and Fluent Bit, are acknowledged with an `ack` response once they're processed. With
+				`acknowledgements` enabled, they're only acknowledged once their events were delivered by the sinks, and
+				the connection is closed without acknowledging them otherwise, so the client sends them again.
 				"""
 		}
 	}
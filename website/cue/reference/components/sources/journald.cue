@@ -49,9 +49,10 @@ components: sources: journald: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		batch_size: {
 			common:      false
-			description: "The systemd journal is read in batches, and a checkpoint is set at the end of each batch. This option limits the size of the batch."
+			description: "The systemd journal is read in batches, and a checkpoint is set at the end of each batch, or once all of its events were delivered with `acknowledgements` enabled. This option limits the size of the batch."
 			required:    false
 			warnings: []
 			type: uint: {
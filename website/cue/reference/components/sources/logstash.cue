@@ -60,6 +60,7 @@ components: sources: logstash: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		address: {
 			description: "The address to listen for TCP connections on."
 			required:    true
@@ -306,9 +307,10 @@ components: sources: logstash: {
 		acking: {
 			title: "Acknowledgement support"
 			body: """
-				Currently, this source will acknowledge events to the sender once the event has been sent to the next
-				component in the topology. In the future, this source will utilize Vector's support for end-to-end
-				acknowledgements.
+				By default, this source acknowledges events to the sender once they have been sent to the next
+				component in the topology. With `acknowledgements` enabled, events are only acknowledged once they
+				were delivered by the sinks, and the connection is closed without acknowledging them otherwise, so
+				the sender sends them again.
 				"""
 		}
 	}
@@ -0,0 +1,261 @@
+package metadata
+
+components: sources: opentelemetry: {
+	_grpc_port: 4317
+	_http_port: 4318
+
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs and metrics from OpenTelemetry collectors and SDKs over the
+		OpenTelemetry protocol (OTLP), using either gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _grpc_port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			// TLS is configured separately for the gRPC and HTTP servers.
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		grpc: {
+			description: "Configuration of the gRPC server, receiving OTLP requests on the `LogsService` and `MetricsService` services."
+			required:    true
+			warnings: []
+			type: object: options: {
+				address: {
+					description: """
+						The address to listen for gRPC connections on. It _must_ include a port.
+						"""
+					required: true
+					warnings: []
+					type: string: {
+						examples: ["0.0.0.0:\(_grpc_port)"]
+						syntax: "literal"
+					}
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+		http: {
+			description: "Configuration of the HTTP server, receiving protobuf encoded OTLP requests on the `/v1/logs` and `/v1/metrics` paths."
+			required:    true
+			warnings: []
+			type: object: options: {
+				address: {
+					description: """
+						The address to listen for HTTP connections on. It _must_ include a port.
+						"""
+					required: true
+					warnings: []
+					type: string: {
+						examples: ["0.0.0.0:\(_http_port)"]
+						syntax: "literal"
+					}
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+	}
+
+	output: {
+		logs: record: {
+			description: "An OTLP log record."
+			fields: {
+				attributes: {
+					description: "The attributes of the log record."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"http.method": "GET"}]
+						options: {}
+					}
+				}
+				dropped_attributes_count: {
+					description: "The number of attributes dropped by the sender, when not zero."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [3]
+						unit: null
+					}
+				}
+				flags: {
+					description: "The trace flags of the log record, when not zero."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [1]
+						unit: null
+					}
+				}
+				message: {
+					description: "The body of the log record."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["Request handled"]
+						syntax: "literal"
+					}
+				}
+				observed_timestamp: {
+					description: "The time the log record was observed by the sender."
+					required:    false
+					common:      false
+					type: timestamp: {}
+				}
+				resources: {
+					description: "The attributes of the resource the log record comes from."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"service.name": "checkout"}]
+						options: {}
+					}
+				}
+				scope: {
+					description: "The instrumentation scope of the log record, with its `name`, `version` and `attributes`."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"name": "io.opentelemetry.contrib.mongodb", "version": "1.0.0"}]
+						options: {}
+					}
+				}
+				severity_number: {
+					description: "The numerical severity of the log record."
+					required:    false
+					common:      true
+					type: uint: {
+						default: null
+						examples: [9]
+						unit: null
+					}
+				}
+				severity_text: {
+					description: "The severity of the log record, as known to its source."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["INFO"]
+						syntax: "literal"
+					}
+				}
+				span_id: {
+					description: "The hex encoded identifier of the span the log record belongs to."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["e457b5a2e4d86bd1"]
+						syntax: "literal"
+					}
+				}
+				timestamp: {
+					description: "The time of the log record, or the time it was received when not set by the sender."
+					required:    true
+					type: timestamp: {}
+				}
+				trace_id: {
+					description: "The hex encoded identifier of the trace the log record belongs to."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["4bf92f3577b34da6a3ce929d0e0e4736"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+	}
+
+	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body: """
+				Monotonic sums are received as counters, and other sums and gauges as gauges. Cumulative
+				data points are received as absolute metrics and delta data points as incremental
+				metrics. The attributes of the resource, the instrumentation scope, and each data point
+				are received as tags.
+				"""
+		}
+		transport: {
+			title: "Transport"
+			body: """
+				The gRPC server listens on the `grpc.address` and the HTTP server on the `http.address`.
+				The HTTP server only accepts protobuf encoded requests, sent with the
+				`application/x-protobuf` content type.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
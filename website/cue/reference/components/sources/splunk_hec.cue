@@ -72,6 +72,63 @@ components: sources: splunk_hec: {
 				syntax:  "literal"
 			}
 		}
+		indexer_acknowledgements: {
+			common:      false
+			description: "Limits of the indexer acknowledgements, which are given to clients when `acknowledgements` are enabled."
+			required:    false
+			warnings: []
+			type: object: {
+				options: {
+					ack_idle_cleanup: {
+						common:      false
+						description: "Whether to remove channels, along with their pending acknowledgements, once they're idle for `max_idle_time_secs`."
+						required:    false
+						warnings: ["Without it, channels are never removed, so new channels are rejected once `max_number_of_ack_channels` were opened."]
+						type: bool: default: true
+					}
+					max_idle_time_secs: {
+						common:      false
+						description: "The time after which a channel without any request is idle."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 300
+							unit:    "seconds"
+						}
+					}
+					max_number_of_ack_channels: {
+						common:      false
+						description: "The maximum number of channels clients can open. Requests on new channels are rejected with a `503` once it's reached."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 1000000
+							unit:    null
+						}
+					}
+					max_pending_acks: {
+						common:      false
+						description: "The maximum number of pending acknowledgements, over all channels. Requests are rejected with a `503` once it's reached."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 10000000
+							unit:    null
+						}
+					}
+					max_pending_acks_per_channel: {
+						common:      false
+						description: "The maximum number of pending acknowledgements of a single channel. Requests on the channel are rejected with a `503` once it's reached."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 1000000
+							unit:    null
+						}
+					}
+				}
+			}
+		}
 		token: {
 			common:      true
 			description: "If supplied, incoming requests must supply this token in the `Authorization` header, just as a client would if it was communicating with the Splunk HEC endpoint directly. If _not_ supplied, the `Authorization` header will be ignored and requests will not be authenticated."
@@ -111,6 +168,20 @@ components: sources: splunk_hec: {
 		}
 	}
 
+	how_it_works: {
+		indexer_acknowledgements: {
+			title: "Indexer Acknowledgements"
+			body: """
+				When `acknowledgements` are enabled, requests to the `event` and `raw` endpoints must be sent on a
+				channel, and their responses carry an `ackId`. It's acknowledged once all of the request's events
+				were delivered by the sinks, which clients query on the `/services/collector/ack` endpoint, as with
+				the [Splunk indexer acknowledgement protocol](\(urls.splunk_hec_indexer_acknowledgements)).
+				Acknowledged ids are only reported once. Requests whose events failed to be delivered are never
+				acknowledged, so clients send them again.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
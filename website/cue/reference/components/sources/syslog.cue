@@ -47,6 +47,7 @@ components: sources: syslog: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		address: {
 			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
 			relevant_when: "mode = `tcp` or `udp`"
@@ -233,6 +234,17 @@ components: sources: syslog: {
 	]
 
 	how_it_works: {
+		acking: {
+			title: "Acknowledgement support"
+			body: """
+				`acknowledgements` are only supported in the `tcp` mode, and are ignored in the `udp` and
+				`unix` modes. Syslog has no application level acknowledgements, so Vector can't tell the
+				sender that events were delivered. Instead, it stops reading from the connection until the
+				events read from it were delivered by the sinks, and closes the connection if they weren't,
+				so that the sender can notice the failure and send them again.
+				"""
+		}
+
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
@@ -60,6 +60,16 @@ components: transforms: aws_cloudwatch_logs_subscription_parser: {
 				syntax:  "literal"
 			}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Instead of dropping the events that fail to be parsed, send them to the
+				`<component_id>.dropped` output, annotated with `metadata.dropped` fields describing the
+				failure.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
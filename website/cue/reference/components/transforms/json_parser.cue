@@ -62,6 +62,16 @@ components: transforms: json_parser: {
 			warnings: []
 			type: bool: {}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Instead of dropping the events with invalid JSON through `drop_invalid`, send them to the
+				`<component_id>.dropped` output, annotated with `metadata.dropped` fields describing the
+				failure.
+				"""
+			type: bool: default: false
+		}
 		field: {
 			common:      true
 			description: "The log field to decode as JSON. Must be a `string` value type."
@@ -56,6 +56,16 @@ components: transforms: regex_parser: {
 			warnings: []
 			type: bool: default: false
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Instead of dropping the events that fail to be parsed through `drop_failed`, send them to the
+				`<component_id>.dropped` output, annotated with `metadata.dropped` fields describing the
+				failure.
+				"""
+			type: bool: default: false
+		}
 		drop_field: {
 			common:      true
 			description: "If the specified `field` should be dropped (removed) after parsing."
@@ -101,6 +101,33 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: true
 		}
+		import_paths: {
+			common:      false
+			description: """
+				The directories searched, in order, for the files imported by the program through
+				[`import`](\(urls.vrl_expressions)#import) expressions.
+
+				If a relative path is provided, its root is the current working directory.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/etc/vector/vrl"]
+					syntax: "literal"
+				}
+			}
+		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Instead of dropping the events that fail or abort through `drop_on_error` and `drop_on_abort`,
+				send them to the `<component_id>.dropped` output, annotated with `metadata.dropped` fields
+				describing the failure.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
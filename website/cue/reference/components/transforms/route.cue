@@ -42,6 +42,8 @@ components: transforms: route: {
 			description: """
 				A table of route identifiers to logical conditions representing the filter of the route. Each route
 				can then be referenced as an input by other components with the name `<transform_name>.<route_id>`.
+				Events that don't match any of the routes are sent to the `<transform_name>._unmatched` output,
+				so the `_unmatched` route identifier is reserved.
 				"""
 			required: true
 			warnings: []
@@ -127,7 +129,19 @@ components: transforms: route: {
 		},
 	]
 
-	telemetry: metrics: {
-		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	how_it_works: {
+		unmatched_events: {
+			title: "Unmatched events"
+			body: """
+				The conditions of all routes are evaluated once per event, and the event is sent to
+				every route it matches. Events matching none of the routes are sent to the implicit
+				`<transform_name>._unmatched` output, which can be consumed like any other route, e.g.
+				to archive or inspect them. Those events are discarded if no component consumes that
+				output.
+
+				The transform has no output of its own: components consume one of its routes, so using
+				`<transform_name>` alone as an input is an error.
+				"""
+		}
 	}
 }
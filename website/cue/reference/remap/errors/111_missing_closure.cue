@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "111": {
+	title:       "Missing closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#function-call) fails to pass a closure to a
+		function that requires one.
+		"""
+	resolution: """
+		Add a closure to the function call, as documented by the function.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				map_keys(.)
+				"""#
+			diff: #"""
+				-map_keys(.)
+				+map_keys(.) -> |key| { downcase(key) }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,26 @@
+package metadata
+
+remap: errors: "120": {
+	title:       "Closure variable arity mismatch"
+	description: """
+		A closure passed to a [function call expression](\(urls.vrl_expressions)#function-call) defines
+		a different number of variables than the function provides.
+		"""
+	resolution: """
+		Define exactly the variables documented by the function. Prefix a variable with an underscore
+		(`_`) if it's unused.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				. = filter(.) -> |value| { value != null }
+				"""#
+			diff: #"""
+				-. = filter(.) -> |value| { value != null }
+				+. = filter(.) -> |_key, value| { value != null }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "122": {
+	title:       "Closure return type mismatch"
+	description: """
+		The block of a closure passed to a [function call expression](\(urls.vrl_expressions)#function-call)
+		resolves to a different type than the function expects.
+		"""
+	resolution: """
+		Make sure the last expression of the closure block resolves to the type documented by the function.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				.tags = filter(array!(.tags)) -> |_index, value| { value }
+				"""#
+			diff: #"""
+				-.tags = filter(array!(.tags)) -> |_index, value| { value }
+				+.tags = filter(array!(.tags)) -> |_index, value| { value != null }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,26 @@
+package metadata
+
+remap: errors: "130": {
+	title:       "Function already defined"
+	description: """
+		A [function definition expression](\(urls.vrl_expressions)#function_definition) defines a function with
+		the name of a built-in function, or of a function defined earlier in the program or in one of the files it
+		imports.
+		"""
+	resolution: """
+		Rename the function.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				fn upcase(value) { value }
+				"""#
+			diff: #"""
+				-fn upcase(value) { value }
+				+fn identity(value) { value }
+				"""#
+		},
+	]
+}
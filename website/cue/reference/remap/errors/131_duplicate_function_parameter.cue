@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "131": {
+	title:       "Duplicate function parameter"
+	description: """
+		A [function definition expression](\(urls.vrl_expressions)#function_definition) defines the same parameter
+		more than once.
+		"""
+	resolution: """
+		Rename or remove the duplicate parameter.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				fn sum(value, value) { value + value }
+				"""#
+			diff: #"""
+				-fn sum(value, value) { value + value }
+				+fn sum(lhs, rhs) { lhs + rhs }
+				"""#
+		},
+	]
+}
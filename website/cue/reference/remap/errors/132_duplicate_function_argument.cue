@@ -0,0 +1,26 @@
+package metadata
+
+remap: errors: "132": {
+	title:       "Duplicate function argument"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#function_call) invokes a function defined by the
+		program, passing the same argument more than once.
+		"""
+	resolution: """
+		Remove the duplicate argument.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				fn sum(lhs, rhs) { lhs + rhs }
+				sum!(1, lhs: 2)
+				"""#
+			diff: #"""
+				-sum!(1, lhs: 2)
+				+sum!(1, rhs: 2)
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "140": {
+	title:       "Unresolved import"
+	description: """
+		An [import expression](\(urls.vrl_expressions)#import) imports a file that doesn't exist in any of the
+		configured import paths.
+		"""
+	resolution: """
+		Fix the path of the imported file, or add the directory containing it to the import paths.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				import "normalise.vrl"
+				"""#
+			diff: #"""
+				-import "normalise.vrl"
+				+import "normalize.vrl"
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "141": {
+	title:       "Unreadable import"
+	description: """
+		An [import expression](\(urls.vrl_expressions)#import) imports a file that can't be read, for example
+		because of its permissions, or because it isn't valid UTF-8.
+		"""
+	resolution: """
+		Make sure the imported file can be read by Vector, and only contains valid UTF-8.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				import "binary.vrl"
+				"""#
+			diff: #"""
+				-import "binary.vrl"
+				+import "normalize.vrl"
+				"""#
+		},
+	]
+}
@@ -0,0 +1,30 @@
+package metadata
+
+remap: errors: "142": {
+	title:       "Cyclic import"
+	description: """
+		An [import expression](\(urls.vrl_expressions)#import) imports a file that is already being imported,
+		because two or more files import each other.
+		"""
+	resolution: """
+		Move the functions shared between the files into a separate file that both of them import.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				# a.vrl
+				import "b.vrl"
+
+				# b.vrl
+				import "a.vrl"
+				"""#
+			diff: #"""
+				 # b.vrl
+				-import "a.vrl"
+				+import "shared.vrl"
+				"""#
+		},
+	]
+}
@@ -0,0 +1,29 @@
+package metadata
+
+remap: errors: "143": {
+	title:       "Unexpected expression in imported file"
+	description: """
+		A file imported by an [import expression](\(urls.vrl_expressions)#import) contains an expression other
+		than a function definition or an import.
+		"""
+	resolution: """
+		Move the expression into a function, and call that function from the importing program.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				# normalize.vrl
+				.level = downcase(string(.level) ?? "info")
+				"""#
+			diff: #"""
+				 # normalize.vrl
+				-.level = downcase(string(.level) ?? "info")
+				+fn normalize() {
+				+	.level = downcase(string(.level) ?? "info")
+				+}
+				"""#
+		},
+	]
+}
@@ -15,7 +15,7 @@ remap: expressions: function_call: {
 
 	grammar: {
 		source: """
-			function ~ abort? ~ "(" ~ arguments? ~ ")"
+			function ~ abort? ~ "(" ~ arguments? ~ ")" ~ closure?
 			"""
 		definitions: {
 			function: {
@@ -95,6 +95,24 @@ remap: expressions: function_call: {
 					}
 				}
 			}
+			closure: {
+				description: """
+					`closure` is a block of expressions passed to functions that iterate over a collection, such
+					as [`map_values`](\(urls.vrl_functions)#map_values). It's preceded by an arrow (`->`) and
+					defines the variables set by the function between pipes (`|`):
+
+					```coffee
+					map_values(.) -> |value| { upcase!(value) }
+					```
+
+					The block is resolved once for every element of the collection, with the variables holding
+					the current element. Variables defined by the closure are only available within its block,
+					while variables assigned within the block remain available after the function call.
+
+					Closures can only be passed to functions documented to accept them. They can't be assigned to
+					variables or called on their own, which guarantees that a program still runs to completion.
+					"""
+			}
 		}
 	}
 
@@ -120,5 +138,14 @@ remap: expressions: function_call: {
 				"""#
 			return: ["hello", "world!"]
 		},
+		{
+			title: "Function invocation with a closure"
+			source: #"""
+				map_keys({ "foo": 1 }) -> |key| { upcase(key) }
+				"""#
+			return: {
+				"FOO": 1
+			}
+		},
 	]
 }
@@ -0,0 +1,77 @@
+package metadata
+
+remap: expressions: function_definition: {
+	title:       "Function definition"
+	description: """
+		A _function definition_ expression defines a named function within the program, which can then be
+		invoked by [function call expressions](\(urls.vrl_expressions)#function_call) the same way as the
+		built-in [VRL functions](\(urls.vrl_functions)).
+		"""
+	return: """
+		Doesn't return a value. Calling the function returns the value of the last expression of its block.
+		"""
+
+	grammar: {
+		source: """
+			"fn" ~ function ~ "(" ~ parameters? ~ ")" ~ block
+			"""
+		definitions: {
+			function: {
+				description: """
+					`function` represents the name of the function. It can't be the name of a built-in function,
+					or of a function defined earlier.
+					"""
+
+				characteristics: {
+					definition_order: {
+						title: "Definition order"
+						description: """
+							Functions can only be called after they have been defined. A function therefore can't
+							call itself, which guarantees that a program still runs to completion.
+							"""
+					}
+				}
+			}
+			parameters: {
+				description: """
+					The `parameters` are the comma-delimited names of the function arguments. Within the block,
+					each parameter is a variable holding the value of the argument it's named after. Arguments can
+					be passed by position or by name, and all of them are required.
+					"""
+			}
+			block: {
+				description: """
+					The `block` is resolved each time the function is called. It only has access to the function
+					parameters and the variables it assigns itself, but can read and modify the event. Variables
+					assigned within the block aren't available outside of it.
+
+					If any of the expressions of the block can fail, so does the function, and the error must be
+					handled at each call:
+
+					```coffee
+					fn parse(value) {
+						parse_json(string!(value))
+					}
+
+					.parsed = parse!(.message)
+					```
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Function definition"
+			input: log: level: "WARNING"
+			source: #"""
+				fn normalize_level(level) {
+					downcase(string(level) ?? "info")
+				}
+
+				.level = normalize_level(.level)
+				"""#
+			output: log: level: "warning"
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: expressions: import: {
+	title:       "Import"
+	description: """
+		An _import_ expression makes the [functions defined](\(urls.vrl_expressions)#function_definition) in
+		another VRL file available to the program, allowing them to be shared between programs.
+		"""
+	return: """
+		Doesn't return a value.
+		"""
+
+	grammar: {
+		source: """
+			"import" ~ path
+			"""
+		definitions: {
+			path: {
+				description: """
+					The `path` is a string literal holding the path of the imported file. Relative paths are looked
+					up in each of the configured import paths, in order, such as the `import_paths` option of the
+					[`remap` transform](\(urls.vector_remap_transform)). Absolute paths are used as-is.
+
+					Imported files can only contain function definitions and imports of other files. Importing a
+					file more than once has no effect, while files importing each other are rejected.
+					"""
+
+				characteristics: {
+					compile_time: {
+						title: "Compile-time resolution"
+						description: """
+							Imports are resolved when the program is compiled. Errors within an imported file are
+							reported for that file.
+							"""
+					}
+				}
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Import shared functions"
+			input: log: level: "WARNING"
+			source: #"""
+				import "normalize.vrl"
+
+				.level = normalize_level(.level)
+				"""#
+			output: log: level: "warning"
+			skip_test: true
+		},
+	]
+}
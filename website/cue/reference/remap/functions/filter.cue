@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: filter: {
+	category: "Enumerate"
+	description: #"""
+		Filters the elements of the `value` object or array.
+
+		The function takes a closure defining two variables, holding the key (or index) and value of
+		each element in turn. Only elements for which the closure block resolves to `true` are kept.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to filter."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Drop null values"
+			source: #"""
+				filter({ "a": 1, "b": null, "c": 3 }) -> |_key, value| { !is_null(value) }
+				"""#
+			return: {
+				"a": 1
+				"c": 3
+			}
+		},
+		{
+			title: "Keep the first elements of an array"
+			source: #"""
+				filter([1, 2, 3, 4]) -> |index, _value| { index < 2 }
+				"""#
+			return: [1, 2]
+		},
+	]
+}
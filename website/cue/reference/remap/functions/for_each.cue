@@ -0,0 +1,37 @@
+package metadata
+
+remap: functions: for_each: {
+	category: "Enumerate"
+	description: #"""
+		Iterates over the elements of the `value` object or array.
+
+		The function takes a closure defining two variables, holding the key (or index) and value of
+		each element in turn. Variables assigned within the closure block remain available after the
+		function call, which allows accumulating values.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to iterate over."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["null"]
+	}
+
+	examples: [
+		{
+			title: "Sum the values of an object"
+			source: #"""
+				total = 0
+				for_each({ "a": 1, "b": 2 }) -> |_key, value| { total = total + value }
+				total
+				"""#
+			return: 3
+		},
+	]
+}
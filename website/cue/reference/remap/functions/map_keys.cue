@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: map_keys: {
+	category: "Enumerate"
+	description: #"""
+		Maps the keys within the `value` object.
+
+		The function takes a closure defining a single variable, which holds each key in turn. The key
+		is replaced by the string the closure block resolves to. If multiple keys are mapped to the same
+		key, the last value wins.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to map the keys of."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "recursive"
+			description: "Whether to also map the keys of any nested objects, including those within arrays."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["object"]
+	}
+
+	examples: [
+		{
+			title: "Upcase keys"
+			source: #"""
+				map_keys({ "foo": 1, "bar": 2 }) -> |key| { upcase(key) }
+				"""#
+			return: {
+				"FOO": 1
+				"BAR": 2
+			}
+		},
+		{
+			title: "Normalize nested keys"
+			source: #"""
+				map_keys({ "Parent": { "Child": true } }, recursive: true) -> |key| { downcase(key) }
+				"""#
+			return: {
+				"parent": {
+					"child": true
+				}
+			}
+		},
+	]
+}
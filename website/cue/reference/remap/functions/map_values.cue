@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: map_values: {
+	category: "Enumerate"
+	description: #"""
+		Maps the values within the `value` object or array.
+
+		The function takes a closure defining a single variable, which holds each value in turn. The
+		value is replaced by the value the closure block resolves to. Keys and indices are left
+		untouched.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to map the values of."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Multiply each value of an object"
+			source: #"""
+				map_values({ "a": 1, "b": 2 }) -> |value| { value * 10 }
+				"""#
+			return: {
+				"a": 10
+				"b": 20
+			}
+		},
+		{
+			title: "Upcase each element of an array"
+			source: #"""
+				map_values(["foo", "bar"]) -> |value| { upcase(value) }
+				"""#
+			return: ["FOO", "BAR"]
+		},
+	]
+}